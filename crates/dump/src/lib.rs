@@ -6,11 +6,14 @@ use meilisearch_types::error::ResponseError;
 use meilisearch_types::keys::Key;
 use meilisearch_types::milli::update::IndexDocumentsMethod;
 use meilisearch_types::settings::Unchecked;
-use meilisearch_types::tasks::{Details, IndexSwap, KindWithContent, Status, Task, TaskId};
+use meilisearch_types::tasks::{
+    Details, IndexAlias, IndexSwap, KindWithContent, Status, Task, TaskId,
+};
 use meilisearch_types::InstanceUid;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
+use uuid::Uuid;
 
 mod error;
 mod reader;
@@ -89,6 +92,9 @@ pub struct TaskDump {
         default
     )]
     pub finished_at: Option<OffsetDateTime>,
+    // The tasks dumped before the key uids were recorded don't have one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_uid: Option<Uuid>,
 }
 
 // A `Kind` specific version made for the dump. If modified you may break the dump.
@@ -128,6 +134,13 @@ pub enum KindDump {
     IndexSwap {
         swaps: Vec<IndexSwap>,
     },
+    IndexClone {
+        new_index_uid: String,
+    },
+    IndexExport,
+    IndexImport {
+        dump_uid: String,
+    },
     TaskCancelation {
         query: String,
         tasks: RoaringBitmap,
@@ -144,6 +157,9 @@ pub enum KindDump {
     UpgradeDatabase {
         from: (u32, u32, u32),
     },
+    IndexAliasUpdate {
+        aliases: Vec<IndexAlias>,
+    },
 }
 
 impl From<Task> for TaskDump {
@@ -160,6 +176,7 @@ impl From<Task> for TaskDump {
             enqueued_at: task.enqueued_at,
             started_at: task.started_at,
             finished_at: task.finished_at,
+            key_uid: task.key_uid,
         }
     }
 }
@@ -203,6 +220,11 @@ impl From<KindWithContent> for KindDump {
                 KindDump::IndexUpdate { primary_key }
             }
             KindWithContent::IndexSwap { swaps } => KindDump::IndexSwap { swaps },
+            KindWithContent::IndexClone { new_index_uid, .. } => {
+                KindDump::IndexClone { new_index_uid }
+            }
+            KindWithContent::IndexExport { .. } => KindDump::IndexExport,
+            KindWithContent::IndexImport { dump_uid, .. } => KindDump::IndexImport { dump_uid },
             KindWithContent::TaskCancelation { query, tasks } => {
                 KindDump::TaskCancelation { query, tasks }
             }
@@ -216,18 +238,21 @@ impl From<KindWithContent> for KindDump {
             KindWithContent::UpgradeDatabase { from: version } => {
                 KindDump::UpgradeDatabase { from: version }
             }
+            KindWithContent::IndexAliasUpdate { aliases } => KindDump::IndexAliasUpdate { aliases },
         }
     }
 }
 
 #[cfg(test)]
 pub(crate) mod test {
+    use std::collections::BTreeMap;
     use std::fs::File;
     use std::io::Seek;
     use std::str::FromStr;
 
     use big_s::S;
     use maplit::{btreemap, btreeset};
+    use meilisearch_types::audit_log::{AuditLog, AuditLogKeyUid, AuditLogOutcome};
     use meilisearch_types::batches::{Batch, BatchEnqueuedAt, BatchStats};
     use meilisearch_types::facet_values_sort::FacetValuesSort;
     use meilisearch_types::features::{Network, Remote, RuntimeTogglableFeatures};
@@ -305,6 +330,8 @@ pub(crate) mod test {
             localized_attributes: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            ngram_attributes: Setting::NotSet,
+            query_rules: Setting::NotSet,
             _kind: std::marker::PhantomData,
         };
         settings.check()
@@ -324,6 +351,7 @@ pub(crate) mod test {
                 status: maplit::btreemap! { Status::Succeeded => 1 },
                 types: maplit::btreemap! { Kind::DocumentAdditionOrUpdate => 1 },
                 index_uids: maplit::btreemap! { "doggo".to_string() => 1 },
+                key_uids: Default::default(),
                 progress_trace: Default::default(),
                 write_channel_congestion: None,
                 internal_database_sizes: Default::default(),
@@ -361,6 +389,7 @@ pub(crate) mod test {
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
                     started_at: Some(datetime!(2022-11-20 0:00 UTC)),
                     finished_at: Some(datetime!(2022-11-21 0:00 UTC)),
+                    key_uid: None,
                 },
                 None,
             ),
@@ -385,6 +414,7 @@ pub(crate) mod test {
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
                     started_at: None,
                     finished_at: None,
+                    key_uid: Some(Uuid::from_str("9f8a34da-b6b2-42f0-939b-dbd4c3448655").unwrap()),
                 },
                 Some(vec![
                     json!({ "id": 4, "race": "leonberg" }).as_object().unwrap().clone(),
@@ -404,6 +434,7 @@ pub(crate) mod test {
                     enqueued_at: datetime!(2022-11-15 0:00 UTC),
                     started_at: None,
                     finished_at: None,
+                    key_uid: None,
                 },
                 None,
             ),
@@ -419,6 +450,11 @@ pub(crate) mod test {
                 actions: vec![Action::DocumentsAll],
                 indexes: vec![IndexUidPattern::from_str("doggos").unwrap()],
                 expires_at: Some(datetime!(4130-03-14 12:21 UTC)),
+                rate_limits: None,
+                allowed_ips: None,
+                allowed_origins: None,
+                salt: None,
+                previous_secret: None,
                 created_at: datetime!(1960-11-15 0:00 UTC),
                 updated_at: datetime!(2022-11-10 0:00 UTC),
             },
@@ -429,6 +465,11 @@ pub(crate) mod test {
                 actions: vec![Action::All],
                 indexes: vec![IndexUidPattern::all()],
                 expires_at: None,
+                rate_limits: None,
+                allowed_ips: None,
+                allowed_origins: None,
+                salt: None,
+                previous_secret: None,
                 created_at: datetime!(0000-01-01 00:01 UTC),
                 updated_at: datetime!(1964-05-04 17:25 UTC),
             },
@@ -439,12 +480,48 @@ pub(crate) mod test {
                 actions: vec![],
                 indexes: vec![],
                 expires_at: None,
+                rate_limits: None,
+                allowed_ips: None,
+                allowed_origins: None,
+                salt: None,
+                previous_secret: None,
                 created_at: datetime!(400-02-29 0:00 UTC),
                 updated_at: datetime!(1024-02-29 0:00 UTC),
             },
         ]
     }
 
+    pub fn create_test_audit_logs() -> Vec<AuditLog> {
+        vec![
+            AuditLog {
+                uid: 0,
+                timestamp: datetime!(2022-11-10 0:00 UTC),
+                key_uid: AuditLogKeyUid::Key(
+                    Uuid::from_str("9f8a34da-b6b2-42f0-939b-dbd4c3448655").unwrap(),
+                ),
+                key_name: Some(S("doggos_key")),
+                action: Action::DocumentsAdd,
+                index_uid: Some(S("doggos")),
+                method: S("POST"),
+                route: S("/indexes/doggos/documents"),
+                status_code: 202,
+                outcome: AuditLogOutcome::Succeeded,
+            },
+            AuditLog {
+                uid: 1,
+                timestamp: datetime!(2022-11-10 0:01 UTC),
+                key_uid: AuditLogKeyUid::MasterKey,
+                key_name: None,
+                action: Action::KeysDelete,
+                index_uid: None,
+                method: S("DELETE"),
+                route: S("/keys/fb80b58b-0a34-412f-8ba7-1ce868f8ac5c"),
+                status_code: 404,
+                outcome: AuditLogOutcome::Failed,
+            },
+        ]
+    }
+
     pub fn create_test_dump() -> File {
         let instance_uid = create_test_instance_uid();
         let dump = DumpWriter::new(Some(instance_uid)).unwrap();
@@ -492,6 +569,13 @@ pub(crate) mod test {
         }
         keys.flush().unwrap();
 
+        // ========== audit logs
+        let mut audit_logs = dump.create_audit_logs().unwrap();
+        for log in &create_test_audit_logs() {
+            audit_logs.push_audit_log(log).unwrap();
+        }
+        audit_logs.flush().unwrap();
+
         // ========== experimental features
         let features = create_test_features();
 
@@ -501,6 +585,10 @@ pub(crate) mod test {
         let network = create_test_network();
         dump.create_network(network).unwrap();
 
+        // ========== index aliases
+        let aliases = create_test_index_aliases();
+        dump.create_index_aliases(&aliases).unwrap();
+
         // create the dump
         let mut file = tempfile::tempfile().unwrap();
         dump.persist_to(&mut file).unwrap();
@@ -520,6 +608,10 @@ pub(crate) mod test {
         }
     }
 
+    fn create_test_index_aliases() -> BTreeMap<String, String> {
+        btreemap! { "dogs".to_string() => "doggos".to_string() }
+    }
+
     #[test]
     fn test_creating_and_read_dump() {
         let mut file = create_test_dump();
@@ -565,6 +657,10 @@ pub(crate) mod test {
             assert_eq!(key.unwrap(), expected);
         }
 
+        // ==== checking the audit logs
+        let audit_logs: Vec<_> = dump.audit_logs().unwrap().map(|log| log.unwrap()).collect();
+        assert_eq!(audit_logs, create_test_audit_logs());
+
         // ==== checking the features
         let expected = create_test_features();
         assert_eq!(dump.features().unwrap().unwrap(), expected);
@@ -572,5 +668,9 @@ pub(crate) mod test {
         // ==== checking the network
         let expected = create_test_network();
         assert_eq!(&expected, dump.network().unwrap().unwrap());
+
+        // ==== checking the index aliases
+        let expected = create_test_index_aliases();
+        assert_eq!(&expected, dump.index_aliases().unwrap().unwrap());
     }
 }
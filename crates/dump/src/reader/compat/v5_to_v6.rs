@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::str::FromStr;
 
 use super::v4_to_v5::{CompatIndexV4ToV5, CompatV4ToV5};
@@ -159,6 +160,7 @@ impl CompatV5ToV6 {
                     enqueued_at: task_view.enqueued_at,
                     started_at: task_view.started_at,
                     finished_at: task_view.finished_at,
+                    key_uid: None,
                 };
 
                 (task, content_file)
@@ -187,6 +189,11 @@ impl CompatV5ToV6 {
                     })
                     .collect(),
                 expires_at: key.expires_at,
+                rate_limits: None,
+                allowed_ips: None,
+                allowed_origins: None,
+                salt: None,
+                previous_secret: None,
                 created_at: key.created_at,
                 updated_at: key.updated_at,
             })
@@ -200,6 +207,10 @@ impl CompatV5ToV6 {
     pub fn network(&self) -> Result<Option<&v6::Network>> {
         Ok(None)
     }
+
+    pub fn index_aliases(&self) -> Result<Option<&BTreeMap<String, String>>> {
+        Ok(None)
+    }
 }
 
 pub enum CompatIndexV5ToV6 {
@@ -398,6 +409,8 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
             search_cutoff_ms: v6::Setting::NotSet,
             facet_search: v6::Setting::NotSet,
             prefix_search: v6::Setting::NotSet,
+            ngram_attributes: v6::Setting::NotSet,
+            query_rules: v6::Setting::NotSet,
             _kind: std::marker::PhantomData,
         }
     }
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{BufReader, Read};
 
@@ -116,6 +117,13 @@ impl DumpReader {
         }
     }
 
+    pub fn audit_logs(&mut self) -> Result<Box<dyn Iterator<Item = Result<v6::AuditLog>> + '_>> {
+        match self {
+            DumpReader::Current(current) => Ok(current.audit_logs()),
+            DumpReader::Compat(_compat) => Ok(Box::new(std::iter::empty())),
+        }
+    }
+
     pub fn features(&self) -> Result<Option<v6::RuntimeTogglableFeatures>> {
         match self {
             DumpReader::Current(current) => Ok(current.features()),
@@ -129,6 +137,13 @@ impl DumpReader {
             DumpReader::Compat(compat) => compat.network(),
         }
     }
+
+    pub fn index_aliases(&self) -> Result<Option<&BTreeMap<String, String>>> {
+        match self {
+            DumpReader::Current(current) => Ok(current.index_aliases()),
+            DumpReader::Compat(compat) => compat.index_aliases(),
+        }
+    }
 }
 
 impl From<V6Reader> for DumpReader {
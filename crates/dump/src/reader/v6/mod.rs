@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs::{self, File};
 use std::io::{BufRead, BufReader, ErrorKind};
 use std::path::Path;
@@ -21,6 +22,7 @@ pub type Unchecked = meilisearch_types::settings::Unchecked;
 pub type Task = crate::TaskDump;
 pub type Batch = meilisearch_types::batches::Batch;
 pub type Key = meilisearch_types::keys::Key;
+pub type AuditLog = meilisearch_types::audit_log::AuditLog;
 pub type RuntimeTogglableFeatures = meilisearch_types::features::RuntimeTogglableFeatures;
 pub type Network = meilisearch_types::features::Network;
 
@@ -55,8 +57,10 @@ pub struct V6Reader {
     tasks: BufReader<File>,
     batches: Option<BufReader<File>>,
     keys: BufReader<File>,
+    audit_logs: Option<BufReader<File>>,
     features: Option<RuntimeTogglableFeatures>,
     network: Option<Network>,
+    index_aliases: Option<BTreeMap<String, String>>,
 }
 
 impl V6Reader {
@@ -91,6 +95,13 @@ impl V6Reader {
             Err(e) => return Err(e.into()),
         };
 
+        let audit_logs = match File::open(dump.path().join("audit-logs.jsonl")) {
+            Ok(file) => Some(BufReader::new(file)),
+            // The audit log was introduced in the v1.15, anything prior to that won't have one
+            Err(err) if err.kind() == ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+
         let network_file = match fs::read(dump.path().join("network.json")) {
             Ok(network_file) => Some(network_file),
             Err(error) => match error.kind() {
@@ -108,14 +119,26 @@ impl V6Reader {
             None
         };
 
+        let index_aliases = match fs::read(dump.path().join("index-aliases.json")) {
+            Ok(aliases_file) => Some(serde_json::from_reader(&*aliases_file)?),
+            // The aliases were introduced in the v1.15, anything prior to that won't have aliases
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                debug!("`index-aliases.json` not found in dump");
+                None
+            }
+            Err(error) => return Err(error.into()),
+        };
+
         Ok(V6Reader {
             metadata: serde_json::from_reader(&*meta_file)?,
             instance_uid,
             tasks: BufReader::new(File::open(dump.path().join("tasks").join("queue.jsonl"))?),
             batches,
             keys: BufReader::new(File::open(dump.path().join("keys.jsonl"))?),
+            audit_logs,
             features,
             network,
+            index_aliases,
             dump,
         })
     }
@@ -192,6 +215,15 @@ impl V6Reader {
         )
     }
 
+    pub fn audit_logs(&mut self) -> Box<dyn Iterator<Item = Result<AuditLog>> + '_> {
+        match self.audit_logs.as_mut() {
+            Some(audit_logs) => Box::new(
+                audit_logs.lines().map(|line| -> Result<_> { Ok(serde_json::from_str(&line?)?) }),
+            ),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+
     pub fn features(&self) -> Option<RuntimeTogglableFeatures> {
         self.features
     }
@@ -199,6 +231,10 @@ impl V6Reader {
     pub fn network(&self) -> Option<&Network> {
         self.network.as_ref()
     }
+
+    pub fn index_aliases(&self) -> Option<&BTreeMap<String, String>> {
+        self.index_aliases.as_ref()
+    }
 }
 
 pub struct UpdateFile {
@@ -1,9 +1,11 @@
+use std::collections::BTreeMap;
 use std::fs::{self, File};
 use std::io::{BufWriter, Write};
 use std::path::PathBuf;
 
 use flate2::write::GzEncoder;
 use flate2::Compression;
+use meilisearch_types::audit_log::AuditLog;
 use meilisearch_types::batches::Batch;
 use meilisearch_types::features::{Network, RuntimeTogglableFeatures};
 use meilisearch_types::keys::Key;
@@ -51,6 +53,10 @@ impl DumpWriter {
         KeyWriter::new(self.dir.path().to_path_buf())
     }
 
+    pub fn create_audit_logs(&self) -> Result<AuditLogWriter> {
+        AuditLogWriter::new(self.dir.path().to_path_buf())
+    }
+
     pub fn create_tasks_queue(&self) -> Result<TaskWriter> {
         TaskWriter::new(self.dir.path().join("tasks"))
     }
@@ -70,6 +76,13 @@ impl DumpWriter {
         Ok(std::fs::write(self.dir.path().join("network.json"), serde_json::to_string(&network)?)?)
     }
 
+    pub fn create_index_aliases(&self, aliases: &BTreeMap<String, String>) -> Result<()> {
+        Ok(std::fs::write(
+            self.dir.path().join("index-aliases.json"),
+            serde_json::to_string(aliases)?,
+        )?)
+    }
+
     pub fn persist_to(self, mut writer: impl Write) -> Result<()> {
         let gz_encoder = GzEncoder::new(&mut writer, Compression::default());
         let mut tar_encoder = tar::Builder::new(gz_encoder);
@@ -104,6 +117,28 @@ impl KeyWriter {
     }
 }
 
+pub struct AuditLogWriter {
+    audit_logs: BufWriter<File>,
+}
+
+impl AuditLogWriter {
+    pub(crate) fn new(path: PathBuf) -> Result<Self> {
+        let audit_logs = File::create(path.join("audit-logs.jsonl"))?;
+        Ok(AuditLogWriter { audit_logs: BufWriter::new(audit_logs) })
+    }
+
+    pub fn push_audit_log(&mut self, log: &AuditLog) -> Result<()> {
+        serde_json::to_writer(&mut self.audit_logs, &log)?;
+        self.audit_logs.write_all(b"\n")?;
+        Ok(())
+    }
+
+    pub fn flush(mut self) -> Result<()> {
+        self.audit_logs.flush()?;
+        Ok(())
+    }
+}
+
 pub struct TaskWriter {
     queue: BufWriter<File>,
     update_files: PathBuf,
@@ -238,8 +273,8 @@ pub(crate) mod test {
     use super::*;
     use crate::reader::Document;
     use crate::test::{
-        create_test_api_keys, create_test_batches, create_test_documents, create_test_dump,
-        create_test_instance_uid, create_test_settings, create_test_tasks,
+        create_test_api_keys, create_test_audit_logs, create_test_batches, create_test_documents,
+        create_test_dump, create_test_instance_uid, create_test_settings, create_test_tasks,
     };
 
     fn create_directory_hierarchy(dir: &Path) -> String {
@@ -327,7 +362,9 @@ pub(crate) mod test {
         │    ├---- update_files/
         │    │    └---- 1.jsonl
         │    └---- queue.jsonl
+        ├---- audit-logs.jsonl
         ├---- experimental-features.json
+        ├---- index-aliases.json
         ├---- instance_uid.uuid
         ├---- keys.jsonl
         ├---- metadata.json
@@ -400,5 +437,11 @@ pub(crate) mod test {
         for (key, expected) in keys.lines().zip(create_test_api_keys()) {
             assert_eq!(serde_json::from_str::<Key>(key).unwrap(), expected);
         }
+
+        // ==== checking the audit logs
+        let audit_logs = fs::read_to_string(dump_path.join("audit-logs.jsonl")).unwrap();
+        for (log, expected) in audit_logs.lines().zip(create_test_audit_logs()) {
+            assert_eq!(serde_json::from_str::<AuditLog>(log).unwrap(), expected);
+        }
     }
 }
@@ -0,0 +1,410 @@
+//! Encryption at rest of the files written by Meilisearch, e.g. the update files, dumps and snapshots.
+//!
+//! An encrypted file starts with a header containing a random salt, from which the key of the file
+//! is derived with HKDF-SHA256. Its content is then split in chunks of [`CHUNK_SIZE`] bytes, each
+//! sealed with AES-256-GCM following the STREAM construction: the nonce of a chunk contains its
+//! position and whether it is the last one, so chunks can't be reordered, removed or truncated
+//! without the decryption failing.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read, Seek, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use memmap2::{Mmap, MmapMut};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::digest::{digest, SHA256};
+use ring::hkdf::{Salt, HKDF_SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::{Error, Result};
+
+/// The bytes every encrypted file starts with.
+const MAGIC: &[u8; 8] = b"MEILIENC";
+const FORMAT_VERSION: u8 = 1;
+const KEY_ID_LEN: usize = 8;
+const SALT_LEN: usize = 32;
+const TAG_LEN: usize = 16;
+/// The size of the header following the magic bytes.
+const HEADER_LEN: usize = 1 + KEY_ID_LEN + SALT_LEN;
+/// The number of bytes of plaintext in each encrypted chunk, except the last one.
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+/// A 256-bit key used to encrypt the files at rest, written as 64 hexadecimal characters.
+#[derive(Clone, PartialEq, Eq)]
+pub struct EncryptionKey([u8; 32]);
+
+impl EncryptionKey {
+    /// Reads the key from a file, ignoring the whitespace around it.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        content.parse()
+    }
+
+    /// Identifies the key without revealing it, so we can tell which key encrypted a file.
+    fn id(&self) -> [u8; KEY_ID_LEN] {
+        let hash = digest(&SHA256, &self.0);
+        let mut id = [0; KEY_ID_LEN];
+        id.copy_from_slice(&hash.as_ref()[..KEY_ID_LEN]);
+        id
+    }
+
+    /// Derives the key of a single file from its salt.
+    fn file_key(&self, salt: &[u8; SALT_LEN]) -> io::Result<LessSafeKey> {
+        let prk = Salt::new(HKDF_SHA256, salt).extract(&self.0);
+        let okm = prk
+            .expand(&[b"meilisearch file encryption"], &AES_256_GCM)
+            .map_err(|_| io::Error::other("Could not derive the key of the file"))?;
+        Ok(LessSafeKey::new(UnboundKey::from(okm)))
+    }
+}
+
+impl FromStr for EncryptionKey {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.len() != 64 || !s.is_ascii() {
+            return Err(Error::InvalidEncryptionKey);
+        }
+        let mut key = [0; 32];
+        for (byte, hex) in key.iter_mut().zip(s.as_bytes().chunks(2)) {
+            let hex = std::str::from_utf8(hex).map_err(|_| Error::InvalidEncryptionKey)?;
+            *byte = u8::from_str_radix(hex, 16).map_err(|_| Error::InvalidEncryptionKey)?;
+        }
+        Ok(EncryptionKey(key))
+    }
+}
+
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("EncryptionKey").field(&"[redacted]").finish()
+    }
+}
+
+fn chunk_nonce(counter: u32, last: bool) -> Nonce {
+    let mut nonce = [0; NONCE_LEN];
+    nonce[NONCE_LEN - 5..NONCE_LEN - 1].copy_from_slice(&counter.to_be_bytes());
+    nonce[NONCE_LEN - 1] = last as u8;
+    Nonce::assume_unique_for_key(nonce)
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Encrypts everything written to it before writing it to the inner writer.
+///
+/// [`EncryptWriter::finish`] must be called once everything has been written,
+/// otherwise the last chunk is lost and the file can't be decrypted.
+pub struct EncryptWriter<W: Write> {
+    writer: W,
+    key: LessSafeKey,
+    buffer: Vec<u8>,
+    counter: u32,
+}
+
+impl<W: Write> EncryptWriter<W> {
+    pub fn new(mut writer: W, key: &EncryptionKey) -> io::Result<Self> {
+        let mut salt = [0; SALT_LEN];
+        SystemRandom::new()
+            .fill(&mut salt)
+            .map_err(|_| io::Error::other("Could not generate the salt of the file"))?;
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[FORMAT_VERSION])?;
+        writer.write_all(&key.id())?;
+        writer.write_all(&salt)?;
+
+        let key = key.file_key(&salt)?;
+        Ok(EncryptWriter {
+            writer,
+            key,
+            buffer: Vec::with_capacity(CHUNK_SIZE + TAG_LEN),
+            counter: 0,
+        })
+    }
+
+    fn seal_chunk(&mut self, last: bool) -> io::Result<()> {
+        self.key
+            .seal_in_place_append_tag(
+                chunk_nonce(self.counter, last),
+                Aad::empty(),
+                &mut self.buffer,
+            )
+            .map_err(|_| io::Error::other("Could not encrypt the file"))?;
+        self.writer.write_all(&self.buffer)?;
+        self.buffer.clear();
+        self.counter = self
+            .counter
+            .checked_add(1)
+            .ok_or_else(|| io::Error::other("File too large to be encrypted"))?;
+        Ok(())
+    }
+
+    /// Returns the inner writer, to take the chunks already encrypted when it is a buffer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Encrypts the last chunk and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.seal_chunk(true)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write> Write for EncryptWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // a full chunk is only sealed once we know it isn't the last one.
+        if self.buffer.len() == CHUNK_SIZE && !buf.is_empty() {
+            self.seal_chunk(false)?;
+        }
+        let len = buf.len().min(CHUNK_SIZE - self.buffer.len());
+        self.buffer.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Decrypts the content of a file written by an [`EncryptWriter`].
+pub struct DecryptReader<R: Read> {
+    reader: R,
+    key: LessSafeKey,
+    chunk: Vec<u8>,
+    pos: usize,
+    counter: u32,
+    peeked: Option<u8>,
+    done: bool,
+}
+
+impl<R: Read> DecryptReader<R> {
+    pub fn new(mut reader: R, key: &EncryptionKey) -> io::Result<Self> {
+        let mut magic = [0; MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("The file is not encrypted."));
+        }
+        Self::after_magic(reader, key)
+    }
+
+    fn after_magic(mut reader: R, key: &EncryptionKey) -> io::Result<Self> {
+        let mut header = [0; HEADER_LEN];
+        reader.read_exact(&mut header)?;
+        let (version, rest) = header.split_at(1);
+        let (key_id, salt) = rest.split_at(KEY_ID_LEN);
+        if version[0] != FORMAT_VERSION {
+            return Err(invalid_data("The file has been encrypted with an unknown format."));
+        }
+        if key_id != key.id() {
+            return Err(invalid_data("The file has been encrypted with another key."));
+        }
+
+        let key = key.file_key(salt.try_into().unwrap())?;
+        Ok(DecryptReader {
+            reader,
+            key,
+            chunk: Vec::with_capacity(CHUNK_SIZE + TAG_LEN + 1),
+            pos: 0,
+            counter: 0,
+            peeked: None,
+            done: false,
+        })
+    }
+
+    fn open_next_chunk(&mut self) -> io::Result<()> {
+        self.chunk.clear();
+        self.pos = 0;
+        self.chunk.extend(self.peeked.take());
+
+        // we read one more byte than a chunk to know if it is the last one.
+        let limit = CHUNK_SIZE + TAG_LEN + 1;
+        (&mut self.reader).take((limit - self.chunk.len()) as u64).read_to_end(&mut self.chunk)?;
+        let last = self.chunk.len() < limit;
+        if !last {
+            self.peeked = self.chunk.pop();
+        }
+
+        let len = self
+            .key
+            .open_in_place(chunk_nonce(self.counter, last), Aad::empty(), &mut self.chunk)
+            .map_err(|_| invalid_data("The encrypted file is corrupted or truncated."))?
+            .len();
+        self.chunk.truncate(len);
+        self.counter = self
+            .counter
+            .checked_add(1)
+            .ok_or_else(|| invalid_data("The encrypted file is too large."))?;
+        self.done = last;
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DecryptReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if self.pos < self.chunk.len() || buf.is_empty() {
+                let len = buf.len().min(self.chunk.len() - self.pos);
+                buf[..len].copy_from_slice(&self.chunk[self.pos..self.pos + len]);
+                self.pos += len;
+                return Ok(len);
+            }
+            if self.done {
+                return Ok(0);
+            }
+            self.open_next_chunk()?;
+        }
+    }
+}
+
+/// Returns a reader over the plaintext content of `reader`.
+///
+/// The content is decrypted when it starts with the header of an encrypted file, and read as-is
+/// otherwise, so the files written before the encryption was enabled can still be read.
+pub fn decrypted_reader<'a, R: Read + Send + 'a>(
+    mut reader: R,
+    key: Option<&EncryptionKey>,
+) -> io::Result<Box<dyn Read + Send + 'a>> {
+    let mut prefix = Vec::with_capacity(MAGIC.len());
+    (&mut reader).take(MAGIC.len() as u64).read_to_end(&mut prefix)?;
+    if prefix != MAGIC {
+        return Ok(Box::new(io::Cursor::new(prefix).chain(reader)));
+    }
+
+    match key {
+        Some(key) => Ok(Box::new(DecryptReader::after_magic(reader, key)?)),
+        None => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "The file is encrypted but no encryption key has been provided.",
+        )),
+    }
+}
+
+/// Maps the plaintext content of `file` in memory.
+///
+/// A plaintext file is mapped as-is, while an encrypted file is decrypted in anonymous memory,
+/// so its plaintext is never written on disk.
+pub fn decrypted_mmap(mut file: File, key: Option<&EncryptionKey>) -> io::Result<Mmap> {
+    if !is_encrypted(&mut file)? {
+        return unsafe { Mmap::map(&file) };
+    }
+
+    let len = plaintext_len(file.metadata()?.len())?;
+    file.rewind()?;
+    let mut reader = decrypted_reader(file, key)?;
+    let mut mmap = MmapMut::map_anon(len as usize)?;
+    reader.read_exact(&mut mmap)?;
+    mmap.make_read_only()
+}
+
+/// Returns the size of the plaintext of an encrypted file of `len` bytes.
+pub(crate) fn plaintext_len(len: u64) -> io::Result<u64> {
+    let body = len
+        .checked_sub((MAGIC.len() + HEADER_LEN) as u64)
+        .ok_or_else(|| invalid_data("The encrypted file is truncated."))?;
+    let chunks = body.div_ceil((CHUNK_SIZE + TAG_LEN) as u64).max(1);
+    body.checked_sub(chunks * TAG_LEN as u64)
+        .ok_or_else(|| invalid_data("The encrypted file is truncated."))
+}
+
+/// Returns wether the content of `reader` starts with the header of an encrypted file.
+pub fn is_encrypted(reader: impl Read) -> io::Result<bool> {
+    let mut prefix = Vec::with_capacity(MAGIC.len());
+    reader.take(MAGIC.len() as u64).read_to_end(&mut prefix)?;
+    Ok(prefix == MAGIC)
+}
+
+/// Returns whether the content of `reader` is encrypted with `key`, or in plaintext if there is no key.
+pub fn is_encrypted_with(reader: impl Read, key: Option<&EncryptionKey>) -> io::Result<bool> {
+    let mut prefix = Vec::with_capacity(MAGIC.len() + HEADER_LEN);
+    reader.take((MAGIC.len() + HEADER_LEN) as u64).read_to_end(&mut prefix)?;
+    let key_id = match prefix.strip_prefix(MAGIC) {
+        Some(header) if header.len() == HEADER_LEN => Some(&header[1..1 + KEY_ID_LEN]),
+        _ => None,
+    };
+    Ok(match (key_id, key) {
+        (None, None) => true,
+        (Some(key_id), Some(key)) => key_id == key.id(),
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn key(byte: char) -> EncryptionKey {
+        byte.to_string().repeat(64).parse().unwrap()
+    }
+
+    fn encrypt(content: &[u8], key: &EncryptionKey) -> Vec<u8> {
+        let mut writer = EncryptWriter::new(Vec::new(), key).unwrap();
+        // write in odd sizes to cross the chunk boundaries
+        for part in content.chunks(7919) {
+            writer.write_all(part).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let key = key('a');
+        for len in [0, 1, CHUNK_SIZE - 1, CHUNK_SIZE, CHUNK_SIZE + 1, 3 * CHUNK_SIZE] {
+            let content: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let encrypted = encrypt(&content, &key);
+            assert!(is_encrypted(&encrypted[..]).unwrap());
+            assert_eq!(plaintext_len(encrypted.len() as u64).unwrap(), len as u64);
+
+            let mut decrypted = Vec::new();
+            decrypted_reader(&encrypted[..], Some(&key))
+                .unwrap()
+                .read_to_end(&mut decrypted)
+                .unwrap();
+            assert!(decrypted == content, "length {len}");
+        }
+    }
+
+    #[test]
+    fn plaintext_is_read_as_is() {
+        let mut content = Vec::new();
+        decrypted_reader(&b"{\"id\": 1}"[..], None).unwrap().read_to_end(&mut content).unwrap();
+        assert_eq!(content, b"{\"id\": 1}");
+        assert!(!is_encrypted(&b"MEILI"[..]).unwrap());
+    }
+
+    #[test]
+    fn tampering_is_detected() {
+        let key = key('b');
+        let content = vec![42; 2 * CHUNK_SIZE + 12];
+        let encrypted = encrypt(&content, &key);
+        let read = |bytes: &[u8], key: Option<&EncryptionKey>| {
+            let mut content = Vec::new();
+            decrypted_reader(bytes, key)?.read_to_end(&mut content).map(|_| content)
+        };
+
+        assert!(read(&encrypted, None).is_err());
+        assert!(read(&encrypted, Some(&super::test::key('c'))).is_err());
+
+        let mut flipped = encrypted.clone();
+        *flipped.last_mut().unwrap() ^= 1;
+        assert!(read(&flipped, Some(&key)).is_err());
+
+        // truncated at a chunk boundary
+        let truncated = &encrypted[..MAGIC.len() + HEADER_LEN + 2 * (CHUNK_SIZE + TAG_LEN)];
+        assert!(read(truncated, Some(&key)).is_err());
+    }
+
+    #[test]
+    fn invalid_keys() {
+        let (not_hex, too_short, not_ascii) = ("g".repeat(64), "a".repeat(63), "é".repeat(32));
+        for invalid in ["", "abc", &not_hex, &too_short, &not_ascii] {
+            assert!(EncryptionKey::from_str(invalid).is_err(), "{invalid}");
+        }
+        assert_eq!(key('A'), key('a'));
+    }
+}
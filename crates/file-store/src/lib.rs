@@ -1,8 +1,15 @@
+pub mod encryption;
+
 use std::fs::File as StdFile;
-use std::io::Write;
+use std::io::{Read, Seek, Write};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
+use encryption::{
+    decrypted_mmap, decrypted_reader, is_encrypted, is_encrypted_with, plaintext_len,
+    EncryptWriter, EncryptionKey,
+};
+use memmap2::Mmap;
 use tempfile::NamedTempFile;
 use uuid::Uuid;
 
@@ -12,6 +19,10 @@ const UPDATE_FILES_PATH: &str = "updates/updates_files";
 pub enum Error {
     #[error("Could not parse file name as utf-8")]
     CouldNotParseFileNameAsUtf8,
+    #[error("The encryption key must be made of 64 hexadecimal characters.")]
+    InvalidEncryptionKey,
+    #[error("The update file of {size} bytes can't be decrypted in memory as it exceeds the maximum indexing memory of {limit} bytes.")]
+    UpdateFileTooLarge { size: u64, limit: u64 },
     #[error(transparent)]
     IoError(#[from] std::io::Error),
     #[error(transparent)]
@@ -25,13 +36,35 @@ pub type Result<T> = std::result::Result<T, Error>;
 #[derive(Clone, Debug)]
 pub struct FileStore {
     path: PathBuf,
+    encryption_key: Option<EncryptionKey>,
+    decryption_limit: Option<u64>,
 }
 
 impl FileStore {
     pub fn new(path: impl AsRef<Path>) -> Result<FileStore> {
+        Self::new_with_encryption(path, None)
+    }
+
+    /// Opens a file store whose update files are encrypted with the key, if any.
+    ///
+    /// The update files written before the encryption was enabled can still be read.
+    pub fn new_with_encryption(
+        path: impl AsRef<Path>,
+        encryption_key: Option<EncryptionKey>,
+    ) -> Result<FileStore> {
         let path = path.as_ref().to_path_buf();
         std::fs::create_dir_all(&path)?;
-        Ok(FileStore { path })
+        Ok(FileStore { path, encryption_key, decryption_limit: None })
+    }
+
+    /// Limits the size of the encrypted update files, which are entirely decrypted in memory
+    /// to be indexed. The larger ones can't be persisted nor decrypted.
+    pub fn with_decryption_limit(self, decryption_limit: Option<u64>) -> Self {
+        FileStore { decryption_limit, ..self }
+    }
+
+    pub fn encryption_key(&self) -> Option<&EncryptionKey> {
+        self.encryption_key.as_ref()
     }
 }
 
@@ -39,10 +72,8 @@ impl FileStore {
     /// Creates a new temporary update file.
     /// A call to `persist` is needed to persist the file in the database.
     pub fn new_update(&self) -> Result<(Uuid, File)> {
-        let file = NamedTempFile::new_in(&self.path)?;
         let uuid = Uuid::new_v4();
-        let path = self.path.join(uuid.to_string());
-        let update_file = File { file: Some(file), path };
+        let update_file = self.new_update_file(uuid)?;
 
         Ok((uuid, update_file))
     }
@@ -50,14 +81,24 @@ impl FileStore {
     /// Creates a new temporary update file with the given Uuid.
     /// A call to `persist` is needed to persist the file in the database.
     pub fn new_update_with_uuid(&self, uuid: u128) -> Result<(Uuid, File)> {
-        let file = NamedTempFile::new_in(&self.path)?;
         let uuid = Uuid::from_u128(uuid);
-        let path = self.path.join(uuid.to_string());
-        let update_file = File { file: Some(file), path };
+        let update_file = self.new_update_file(uuid)?;
 
         Ok((uuid, update_file))
     }
 
+    /// The content is encrypted as it is written if the store has an encryption key,
+    /// so the plaintext never reaches the disk.
+    fn new_update_file(&self, uuid: Uuid) -> Result<File> {
+        let file = NamedTempFile::new_in(&self.path)?;
+        let path = self.path.join(uuid.to_string());
+        let writer = match &self.encryption_key {
+            Some(key) => UpdateWriter::Encrypted(Box::new(EncryptWriter::new(file, key)?)),
+            None => UpdateWriter::Plain(file),
+        };
+        Ok(File { path, file: Some(writer), written: 0, decryption_limit: self.decryption_limit })
+    }
+
     /// Returns the file corresponding to the requested uuid.
     pub fn get_update(&self, uuid: Uuid) -> Result<StdFile> {
         let path = self.get_update_path(uuid);
@@ -71,6 +112,53 @@ impl FileStore {
         Ok(file)
     }
 
+    /// Returns a reader over the decrypted content of the file corresponding to the requested uuid.
+    pub fn get_update_reader(&self, uuid: Uuid) -> Result<Box<dyn Read + Send>> {
+        let file = self.get_update(uuid)?;
+        Ok(decrypted_reader(file, self.encryption_key.as_ref())?)
+    }
+
+    /// Maps the decrypted content of the file corresponding to the requested uuid in memory.
+    ///
+    /// An encrypted file is decrypted in anonymous memory, its plaintext is never written on disk.
+    /// The indexer borrows the content of all the update files of a batch until it is processed,
+    /// so the batches must be limited in size to bound the memory used, see [`Self::compute_size`],
+    /// and a single file larger than the decryption limit is rejected.
+    pub fn get_update_mmap(&self, uuid: Uuid) -> Result<Mmap> {
+        let mut file = self.get_update(uuid)?;
+        if let Some(limit) = self.decryption_limit {
+            if is_encrypted(&mut file)? {
+                let size = plaintext_len(file.metadata()?.len())?;
+                if size > limit {
+                    return Err(Error::UpdateFileTooLarge { size, limit });
+                }
+            }
+            file.rewind()?;
+        }
+
+        Ok(decrypted_mmap(file, self.encryption_key.as_ref())?)
+    }
+
+    /// Rewrites the file corresponding to the requested uuid with the encryption key of the store,
+    /// or in plaintext if it doesn't have one. Returns `false` if the file already was.
+    ///
+    /// The file is currently encrypted with `previous_key`, or in plaintext. The new version is
+    /// written next to it and then renamed over it, so an interrupted rotation can be resumed.
+    pub fn reencrypt(&self, uuid: Uuid, previous_key: Option<&EncryptionKey>) -> Result<bool> {
+        let mut file = self.get_update(uuid)?;
+        if is_encrypted_with(&mut file, self.encryption_key.as_ref())? {
+            return Ok(false);
+        }
+        file.rewind()?;
+
+        let mut reader = decrypted_reader(file, previous_key)?;
+        let (_, mut file) = self.new_update_with_uuid(uuid.as_u128())?;
+        std::io::copy(&mut reader, &mut file)?;
+        drop(reader);
+        file.persist()?;
+        Ok(true)
+    }
+
     /// Returns the path that correspond to this uuid, the path could not exists.
     pub fn get_update_path(&self, uuid: Uuid) -> PathBuf {
         self.path.join(uuid.to_string())
@@ -95,9 +183,15 @@ impl FileStore {
         Ok(total)
     }
 
-    /// Compute the size of one update
+    /// Compute the size of one update, the size of its plaintext if it is encrypted.
     pub fn compute_size(&self, uuid: Uuid) -> Result<u64> {
-        Ok(self.get_update(uuid)?.metadata()?.len())
+        let mut file = self.get_update(uuid)?;
+        let len = file.metadata()?.len();
+        if is_encrypted(&mut file)? {
+            Ok(plaintext_len(len)?)
+        } else {
+            Ok(len)
+        }
     }
 
     pub fn delete(&self, uuid: Uuid) -> Result<()> {
@@ -132,25 +226,47 @@ impl FileStore {
 
 pub struct File {
     path: PathBuf,
-    file: Option<NamedTempFile>,
+    file: Option<UpdateWriter>,
+    written: u64,
+    decryption_limit: Option<u64>,
+}
+
+enum UpdateWriter {
+    Plain(NamedTempFile),
+    Encrypted(Box<EncryptWriter<NamedTempFile>>),
 }
 
 impl File {
     pub fn from_parts(path: PathBuf, file: Option<NamedTempFile>) -> Self {
-        Self { path, file }
+        Self { path, file: file.map(UpdateWriter::Plain), written: 0, decryption_limit: None }
     }
 
-    pub fn into_parts(self) -> (PathBuf, Option<NamedTempFile>) {
-        (self.path, self.file)
+    /// Returns the path and the temporary file of a plaintext update file, or the update file
+    /// itself if it is encrypted, as its temporary file only contains the encrypted content.
+    pub fn into_parts(self) -> std::result::Result<(PathBuf, Option<NamedTempFile>), Self> {
+        match self.file {
+            Some(UpdateWriter::Encrypted(_)) => Err(self),
+            Some(UpdateWriter::Plain(file)) => Ok((self.path, Some(file))),
+            None => Ok((self.path, None)),
+        }
     }
 
     pub fn dry_file() -> Result<Self> {
-        Ok(Self { path: PathBuf::new(), file: None })
+        Ok(Self { path: PathBuf::new(), file: None, written: 0, decryption_limit: None })
     }
 
     pub fn persist(self) -> Result<()> {
-        if let Some(file) = self.file {
-            file.persist(&self.path)?;
+        match self.file {
+            Some(UpdateWriter::Plain(file)) => {
+                file.persist(&self.path)?;
+            }
+            Some(UpdateWriter::Encrypted(writer)) => {
+                if let Some(limit) = self.decryption_limit.filter(|limit| self.written > *limit) {
+                    return Err(Error::UpdateFileTooLarge { size: self.written, limit });
+                }
+                writer.finish()?.persist(&self.path)?;
+            }
+            None => (),
         }
         Ok(())
     }
@@ -158,18 +274,22 @@ impl File {
 
 impl Write for File {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        if let Some(file) = self.file.as_mut() {
-            file.write(buf)
-        } else {
-            Ok(buf.len())
+        match self.file.as_mut() {
+            Some(UpdateWriter::Plain(file)) => file.write(buf),
+            Some(UpdateWriter::Encrypted(writer)) => {
+                let written = writer.write(buf)?;
+                self.written += written as u64;
+                Ok(written)
+            }
+            None => Ok(buf.len()),
         }
     }
 
     fn flush(&mut self) -> std::io::Result<()> {
-        if let Some(file) = self.file.as_mut() {
-            file.flush()
-        } else {
-            Ok(())
+        match self.file.as_mut() {
+            Some(UpdateWriter::Plain(file)) => file.flush(),
+            Some(UpdateWriter::Encrypted(writer)) => writer.flush(),
+            None => Ok(()),
         }
     }
 }
@@ -203,4 +323,84 @@ mod test {
         expected.sort();
         assert_eq!(all_uuids, expected);
     }
+
+    #[test]
+    fn encrypted_update_files() {
+        let dir = TempDir::new().unwrap();
+        let key: EncryptionKey = "ab".repeat(32).parse().unwrap();
+        let fs = FileStore::new_with_encryption(dir.path(), Some(key.clone())).unwrap();
+        let (uuid, mut file) = fs.new_update().unwrap();
+        file.write_all(b"{\"id\": 1}").unwrap();
+        file.persist().unwrap();
+
+        // nothing is readable on disk, not even in the temporary file before it is persisted
+        let (_, mut pending) = fs.new_update().unwrap();
+        pending.write_all(&vec![b'i'; encryption::CHUNK_SIZE + 1]).unwrap();
+        let temporary = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .find(|path| *path != fs.get_update_path(uuid))
+            .unwrap();
+        let temporary = std::fs::read(temporary).unwrap();
+        assert!(temporary.len() > encryption::CHUNK_SIZE);
+        assert!(!temporary.windows(16).any(|w| w == [b'i'; 16]));
+        drop(pending);
+
+        let raw = std::fs::read(fs.get_update_path(uuid)).unwrap();
+        assert!(!raw.windows(2).any(|w| w == b"id"));
+        assert_eq!(&fs.get_update_mmap(uuid).unwrap()[..], b"{\"id\": 1}");
+        let mut content = String::new();
+        fs.get_update_reader(uuid).unwrap().read_to_string(&mut content).unwrap();
+        assert_eq!(content, "{\"id\": 1}");
+
+        // the files written before the encryption was enabled are still readable
+        let plain = FileStore::new(dir.path()).unwrap();
+        let (plain_uuid, mut file) = plain.new_update().unwrap();
+        file.write_all(b"{\"id\": 2}").unwrap();
+        file.persist().unwrap();
+        assert_eq!(&fs.get_update_mmap(plain_uuid).unwrap()[..], b"{\"id\": 2}");
+        assert!(plain.get_update_reader(uuid).is_err());
+
+        // and can be encrypted, or decrypted, afterward
+        assert!(fs.reencrypt(plain_uuid, None).unwrap());
+        assert!(plain.get_update_mmap(plain_uuid).is_err());
+        assert_eq!(fs.compute_size(plain_uuid).unwrap(), 9);
+        assert!(plain.reencrypt(uuid, Some(&key)).unwrap());
+        assert_eq!(&plain.get_update_mmap(uuid).unwrap()[..], b"{\"id\": 1}");
+
+        // an interrupted rotation can be resumed, the files already rotated are skipped
+        assert!(!plain.reencrypt(uuid, Some(&key)).unwrap());
+        assert!(!fs.reencrypt(plain_uuid, None).unwrap());
+        assert_eq!(&fs.get_update_mmap(plain_uuid).unwrap()[..], b"{\"id\": 2}");
+    }
+
+    #[test]
+    fn encrypted_update_files_larger_than_the_decryption_limit() {
+        let dir = TempDir::new().unwrap();
+        let key: EncryptionKey = "ab".repeat(32).parse().unwrap();
+        let fs = FileStore::new_with_encryption(dir.path(), Some(key.clone())).unwrap();
+        let (uuid, mut file) = fs.new_update().unwrap();
+        file.write_all(b"{\"id\": 1}").unwrap();
+        file.persist().unwrap();
+
+        let limited = fs.clone().with_decryption_limit(Some(8));
+        assert!(matches!(
+            limited.get_update_mmap(uuid),
+            Err(Error::UpdateFileTooLarge { size: 9, limit: 8 })
+        ));
+        let (_, mut file) = limited.new_update().unwrap();
+        file.write_all(b"{\"id\": 1}").unwrap();
+        assert!(matches!(file.persist(), Err(Error::UpdateFileTooLarge { size: 9, limit: 8 })));
+
+        // the plaintext files are mapped, not decrypted, in memory
+        let plain = FileStore::new(dir.path()).unwrap().with_decryption_limit(Some(8));
+        let (plain_uuid, mut file) = plain.new_update().unwrap();
+        file.write_all(b"{\"id\": 2}").unwrap();
+        file.persist().unwrap();
+        assert_eq!(&limited.get_update_mmap(plain_uuid).unwrap()[..], b"{\"id\": 2}");
+        assert_eq!(
+            &fs.with_decryption_limit(Some(9)).get_update_mmap(uuid).unwrap()[..],
+            b"{\"id\": 1}"
+        );
+    }
 }
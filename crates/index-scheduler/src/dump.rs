@@ -20,6 +20,7 @@ pub struct Dump<'a> {
     indexes: HashMap<String, RoaringBitmap>,
     statuses: HashMap<Status, RoaringBitmap>,
     kinds: HashMap<Kind, RoaringBitmap>,
+    key_tasks: HashMap<Uuid, RoaringBitmap>,
 
     batch_indexes: HashMap<String, RoaringBitmap>,
     batch_statuses: HashMap<Status, RoaringBitmap>,
@@ -38,6 +39,7 @@ impl<'a> Dump<'a> {
             indexes: HashMap::new(),
             statuses: HashMap::new(),
             kinds: HashMap::new(),
+            key_tasks: HashMap::new(),
             batch_indexes: HashMap::new(),
             batch_statuses: HashMap::new(),
             batch_kinds: HashMap::new(),
@@ -146,6 +148,7 @@ impl<'a> Dump<'a> {
             canceled_by: task.canceled_by,
             details: task.details,
             status: task.status,
+            key_uid: task.key_uid,
             kind: match task.kind {
                 KindDump::DocumentImport {
                     primary_key,
@@ -201,6 +204,17 @@ impl<'a> Dump<'a> {
                     primary_key,
                 },
                 KindDump::IndexSwap { swaps } => KindWithContent::IndexSwap { swaps },
+                KindDump::IndexClone { new_index_uid } => KindWithContent::IndexClone {
+                    index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                    new_index_uid,
+                },
+                KindDump::IndexExport => KindWithContent::IndexExport {
+                    index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                },
+                KindDump::IndexImport { dump_uid } => KindWithContent::IndexImport {
+                    index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                    dump_uid,
+                },
                 KindDump::TaskCancelation { query, tasks } => {
                     KindWithContent::TaskCancelation { query, tasks }
                 }
@@ -212,6 +226,9 @@ impl<'a> Dump<'a> {
                 }
                 KindDump::SnapshotCreation => KindWithContent::SnapshotCreation,
                 KindDump::UpgradeDatabase { from } => KindWithContent::UpgradeDatabase { from },
+                KindDump::IndexAliasUpdate { aliases } => {
+                    KindWithContent::IndexAliasUpdate { aliases }
+                }
             },
         };
 
@@ -262,6 +279,9 @@ impl<'a> Dump<'a> {
 
         self.statuses.entry(task.status).or_default().insert(task.uid);
         self.kinds.entry(task.kind.as_kind()).or_default().insert(task.uid);
+        if let Some(key_uid) = task.key_uid {
+            self.key_tasks.entry(key_uid).or_default().insert(task.uid);
+        }
 
         Ok(task)
     }
@@ -285,6 +305,9 @@ impl<'a> Dump<'a> {
         for (kind, bitmap) in self.kinds {
             self.index_scheduler.queue.tasks.put_kind(&mut self.wtxn, kind, &bitmap)?;
         }
+        for (key_uid, bitmap) in self.key_tasks {
+            self.index_scheduler.queue.tasks.key_tasks.put(&mut self.wtxn, &key_uid, &bitmap)?;
+        }
 
         for (index, bitmap) in self.batch_indexes {
             self.index_scheduler.queue.batches.index_tasks.put(&mut self.wtxn, &index, &bitmap)?;
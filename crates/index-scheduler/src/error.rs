@@ -74,8 +74,20 @@ pub enum Error {
         .0.iter().map(|s| format!("`{}`", s)).collect::<Vec<_>>().join(", ")
     )]
     SwapIndexesNotFound(Vec<String>),
+    #[error("Alias `{0}` not found.")]
+    IndexAliasNotFound(String),
+    #[error("Cannot use `{0}` as an alias because an index with this uid already exists.")]
+    IndexAliasIsAnIndex(String),
+    #[error("Cannot create the index `{0}` because an alias with this name already exists.")]
+    IndexUidIsAnAlias(String),
+    #[error("Aliases must be declared only once per request. `{0}` was specified several times.")]
+    IndexAliasDuplicateFound(String),
     #[error("Corrupted dump.")]
     CorruptedDump,
+    #[error("Dump `{0}` not found.")]
+    DumpNotFound(String),
+    #[error("Dump `{dump_uid}` cannot be imported in an index because it contains {nb_indexes} indexes. Only dumps containing exactly one index can be imported.")]
+    IndexImportInvalidDump { dump_uid: String, nb_indexes: usize },
     #[error(
         "Task `{field}` `{date}` is invalid. It should follow the YYYY-MM-DD or RFC 3339 date-time format."
     )]
@@ -141,6 +153,8 @@ pub enum Error {
     #[error(transparent)]
     FeatureNotEnabled(#[from] FeatureNotEnabledError),
 
+    #[error(transparent)]
+    S3(#[from] crate::s3::Error),
     #[error(transparent)]
     Anyhow(#[from] anyhow::Error),
 
@@ -199,7 +213,13 @@ impl Error {
             | Error::SwapIndexNotFound(_)
             | Error::NoSpaceLeftInTaskQueue
             | Error::SwapIndexesNotFound(_)
+            | Error::IndexAliasNotFound(_)
+            | Error::IndexAliasIsAnIndex(_)
+            | Error::IndexUidIsAnAlias(_)
+            | Error::IndexAliasDuplicateFound(_)
             | Error::CorruptedDump
+            | Error::DumpNotFound(_)
+            | Error::IndexImportInvalidDump { .. }
             | Error::InvalidTaskDate { .. }
             | Error::InvalidTaskUid { .. }
             | Error::InvalidBatchUid { .. }
@@ -221,6 +241,7 @@ impl Error {
             | Error::IoError(_)
             | Error::Persist(_)
             | Error::FeatureNotEnabled(_)
+            | Error::S3(_)
             | Error::Anyhow(_) => true,
             Error::CreateBatch(_)
             | Error::CorruptedTaskQueue
@@ -265,6 +286,12 @@ impl ErrorCode for Error {
             Error::SwapDuplicateIndexFound(_) => Code::InvalidSwapDuplicateIndexFound,
             Error::SwapIndexNotFound(_) => Code::IndexNotFound,
             Error::SwapIndexesNotFound(_) => Code::IndexNotFound,
+            Error::IndexAliasNotFound(_) => Code::IndexAliasNotFound,
+            Error::IndexAliasIsAnIndex(_) => Code::IndexAliasConflict,
+            Error::IndexUidIsAnAlias(_) => Code::IndexAliasConflict,
+            Error::IndexAliasDuplicateFound(_) => Code::InvalidIndexAliasDuplicateFound,
+            Error::DumpNotFound(_) => Code::DumpNotFound,
+            Error::IndexImportInvalidDump { .. } => Code::InvalidIndexImportDumpUid,
             Error::InvalidTaskDate { field, .. } => (*field).into(),
             Error::InvalidTaskUid { .. } => Code::InvalidTaskUids,
             Error::InvalidBatchUid { .. } => Code::InvalidBatchUids,
@@ -290,6 +317,7 @@ impl ErrorCode for Error {
             Error::FeatureNotEnabled(_) => Code::FeatureNotEnabled,
 
             // Irrecoverable errors
+            Error::S3(_) => Code::Internal,
             Error::Anyhow(_) => Code::Internal,
             Error::CorruptedTaskQueue => Code::Internal,
             Error::CorruptedDump => Code::Internal,
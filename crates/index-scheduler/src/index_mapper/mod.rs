@@ -1,10 +1,11 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use std::{fs, thread};
 
 use meilisearch_types::heed::types::{SerdeJson, Str};
-use meilisearch_types::heed::{Database, Env, RoTxn, RwTxn, WithoutTls};
+use meilisearch_types::heed::{CompactionOption, Database, Env, RoTxn, RwTxn, WithoutTls};
 use meilisearch_types::milli;
 use meilisearch_types::milli::database_stats::DatabaseStats;
 use meilisearch_types::milli::index::RollbackOutcome;
@@ -23,11 +24,12 @@ use crate::{Error, IndexBudget, IndexSchedulerOptions, Result};
 mod index_map;
 
 /// The number of database used by index mapper
-const NUMBER_OF_DATABASES: u32 = 2;
+const NUMBER_OF_DATABASES: u32 = 3;
 /// Database const names for the `IndexMapper`.
 mod db_name {
     pub const INDEX_MAPPING: &str = "index-mapping";
     pub const INDEX_STATS: &str = "index-stats";
+    pub const INDEX_ALIASES: &str = "index-aliases";
 }
 
 /// Structure managing meilisearch's indexes.
@@ -37,6 +39,7 @@ mod db_name {
 /// 2. Opening indexes and storing references to these opened indexes
 /// 3. Accessing indexes through their uuid
 /// 4. Mapping a user-defined name to each index uuid.
+/// 5. Mapping aliases to the name of the index they point to.
 ///
 /// # Implementation notes
 ///
@@ -67,6 +70,12 @@ pub struct IndexMapper {
     /// Using an UUID forces to use the index_mapping table to recover the index behind a name, ensuring
     /// consistency wrt index swapping.
     pub(crate) index_stats: Database<UuidCodec, SerdeJson<IndexStats>>,
+    /// Map an alias with the name of the index it resolves to.
+    ///
+    /// Aliases point to index names rather than UUIDs: swapping the index an alias points to
+    /// makes the alias serve the other index, exactly like the name does. The requests made
+    /// through an alias are authorized against the name it resolves to, never the alias itself.
+    pub(crate) index_aliases: Database<Str, Str>,
 
     /// Path to the folder where the LMDB environments of each index are.
     base_path: PathBuf,
@@ -174,6 +183,7 @@ impl IndexMapper {
             index_map: Arc::new(RwLock::new(IndexMap::new(budget.index_count))),
             index_mapping: env.create_database(wtxn, Some(db_name::INDEX_MAPPING))?,
             index_stats: env.create_database(wtxn, Some(db_name::INDEX_STATS))?,
+            index_aliases: env.create_database(wtxn, Some(db_name::INDEX_ALIASES))?,
             base_path: options.indexes_path.clone(),
             index_base_map_size: budget.map_size,
             index_growth_amount: options.index_growth_amount,
@@ -196,6 +206,9 @@ impl IndexMapper {
                 Ok(index)
             }
             Err(Error::IndexNotFound(_)) => {
+                if self.index_aliases.get(&wtxn, name)?.is_some() {
+                    return Err(Error::IndexUidIsAnAlias(name.to_string()));
+                }
                 let uuid = Uuid::new_v4();
                 self.index_mapping.put(&mut wtxn, name, &uuid)?;
 
@@ -232,6 +245,67 @@ impl IndexMapper {
         }
     }
 
+    /// Creates a new index named `new_name` with a compacted copy of the environment of the `name` index.
+    ///
+    /// The documents, settings and embeddings are copied as-is, nothing is reindexed.
+    /// The creation and update dates of the source index are kept.
+    pub fn clone_index(&self, mut wtxn: RwTxn, name: &str, new_name: &str) -> Result<Index> {
+        if self.index_exists(&wtxn, new_name)? {
+            return Err(Error::IndexAlreadyExists(new_name.to_string()));
+        }
+        if self.index_aliases.get(&wtxn, new_name)?.is_some() {
+            return Err(Error::IndexUidIsAnAlias(new_name.to_string()));
+        }
+        let source = self.index(&wtxn, name)?;
+
+        let uuid = Uuid::new_v4();
+        let index_path = self.base_path.join(uuid.to_string());
+        fs::create_dir_all(&index_path)?;
+
+        // LMDB opens its own read transaction on the source index to copy it.
+        // No other write can happen in the meantime as we are the ones processing the tasks.
+        let copy = source
+            .copy_to_path(index_path.join("data.mdb"), CompactionOption::Enabled)
+            .map_err(|e| Error::from_milli(e, Some(name.to_string())));
+        if let Err(e) = copy {
+            // Do not leave a half-copied environment behind us.
+            let _ = fs::remove_dir_all(&index_path);
+            return Err(e);
+        }
+        self.index_mapping.put(&mut wtxn, new_name, &uuid)?;
+
+        let index = self
+            .index_map
+            .write()
+            .unwrap()
+            .create(
+                &uuid,
+                &index_path,
+                None,
+                self.enable_mdb_writemap,
+                self.index_base_map_size,
+                false,
+            )
+            .map_err(|e| Error::from_milli(e, Some(uuid.to_string())));
+        let index = match index {
+            Ok(index) => index,
+            Err(e) => {
+                // The mapping is dropped with the transaction but the copy is on disk.
+                let _ = fs::remove_dir_all(&index_path);
+                return Err(e);
+            }
+        };
+        let index_rtxn = index.read_txn()?;
+        let stats = crate::index_mapper::IndexStats::new(&index, &index_rtxn)
+            .map_err(|e| Error::from_milli(e, Some(new_name.to_string())))?;
+        self.store_stats_of(&mut wtxn, new_name, &stats)?;
+        drop(index_rtxn);
+
+        wtxn.commit()?;
+
+        Ok(index)
+    }
+
     /// Removes the index from the mapping table and the in-memory index map
     /// but keeps the associated tasks.
     pub fn delete_index(&self, mut wtxn: RwTxn, name: &str) -> Result<()> {
@@ -246,6 +320,20 @@ impl IndexMapper {
         // Once we retrieved the UUID of the index we remove it from the mapping table.
         assert!(self.index_mapping.delete(&mut wtxn, name)?);
 
+        // The aliases pointing to this index would be left dangling, we remove them as well.
+        let dangling_aliases: Vec<String> = self
+            .index_aliases
+            .iter(&wtxn)?
+            .filter_map(|ret| match ret {
+                Ok((alias, index_uid)) if index_uid == name => Some(Ok(alias.to_string())),
+                Ok(_) => None,
+                Err(e) => Some(Err(e)),
+            })
+            .collect::<std::result::Result<_, _>>()?;
+        for alias in dangling_aliases {
+            self.index_aliases.delete(&mut wtxn, &alias)?;
+        }
+
         wtxn.commit()?;
 
         let mut tries = 0;
@@ -526,6 +614,43 @@ impl IndexMapper {
         Ok(())
     }
 
+    /// Return the name of the index the alias points to, if `name` is an alias.
+    pub fn resolve_alias(&self, rtxn: &RoTxn, name: &str) -> Result<Option<String>> {
+        Ok(self.index_aliases.get(rtxn, name)?.map(|index_uid| index_uid.to_string()))
+    }
+
+    /// Create or repoint an alias to an existing index.
+    pub fn put_alias(&self, wtxn: &mut RwTxn, alias: &str, index_uid: &str) -> Result<()> {
+        if self.index_exists(wtxn, alias)? {
+            return Err(Error::IndexAliasIsAnIndex(alias.to_string()));
+        }
+        if !self.index_exists(wtxn, index_uid)? {
+            return Err(Error::IndexNotFound(index_uid.to_string()));
+        }
+        self.index_aliases.put(wtxn, alias, index_uid)?;
+        Ok(())
+    }
+
+    /// Remove an alias, returns an error if the alias doesn't exist.
+    pub fn delete_alias(&self, wtxn: &mut RwTxn, alias: &str) -> Result<()> {
+        if self.index_aliases.delete(wtxn, alias)? {
+            Ok(())
+        } else {
+            Err(Error::IndexAliasNotFound(alias.to_string()))
+        }
+    }
+
+    /// Return all the aliases along with the name of the index they point to.
+    pub fn aliases(&self, rtxn: &RoTxn) -> Result<BTreeMap<String, String>> {
+        self.index_aliases
+            .iter(rtxn)?
+            .map(|res| {
+                res.map_err(Error::from)
+                    .map(|(alias, index_uid)| (alias.to_string(), index_uid.to_string()))
+            })
+            .collect()
+    }
+
     /// The stats of an index.
     ///
     /// If available in the cache, they are directly returned.
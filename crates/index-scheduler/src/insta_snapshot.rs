@@ -211,12 +211,16 @@ pub fn snapshot_task(task: &Task) -> String {
         details,
         status,
         kind,
+        key_uid,
     } = task;
     snap.push('{');
     snap.push_str(&format!("uid: {uid}, "));
     if let Some(batch_uid) = batch_uid {
         snap.push_str(&format!("batch_uid: {batch_uid}, "));
     }
+    if let Some(key_uid) = key_uid {
+        snap.push_str(&format!("key_uid: {key_uid}, "));
+    }
     snap.push_str(&format!("status: {status}, "));
     if let Some(canceled_by) = canceled_by {
         snap.push_str(&format!("canceled_by: {canceled_by}, "));
@@ -291,6 +295,18 @@ fn snapshot_details(d: &Details) -> String {
         Details::UpgradeDatabase { from, to } => {
             format!("{{ from: {from:?}, to: {to:?} }}")
         }
+        Details::IndexAliasUpdate { aliases } => {
+            format!("{{ aliases: {aliases:?} }}")
+        }
+        Details::IndexClone { new_index_uid } => {
+            format!("{{ new_index_uid: {new_index_uid:?} }}")
+        }
+        Details::IndexExport { dump_uid } => {
+            format!("{{ dump_uid: {dump_uid:?} }}")
+        }
+        Details::IndexImport { dump_uid, indexed_documents } => {
+            format!("{{ dump_uid: {dump_uid:?}, indexed_documents: {indexed_documents:?} }}")
+        }
     }
 }
 
@@ -27,6 +27,7 @@ mod insta_snapshot;
 mod lru;
 mod processing;
 mod queue;
+pub mod s3;
 mod scheduler;
 #[cfg(test)]
 mod test_utils;
@@ -48,6 +49,7 @@ use std::time::Duration;
 use dump::Dump;
 pub use error::Error;
 pub use features::RoFeatures;
+use file_store::encryption::EncryptionKey;
 use flate2::bufread::GzEncoder;
 use flate2::Compression;
 use meilisearch_types::batches::Batch;
@@ -55,6 +57,7 @@ use meilisearch_types::features::{InstanceTogglableFeatures, Network, RuntimeTog
 use meilisearch_types::heed::byteorder::BE;
 use meilisearch_types::heed::types::I128;
 use meilisearch_types::heed::{self, Env, RoTxn, WithoutTls};
+use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::milli::index::IndexEmbeddingConfig;
 use meilisearch_types::milli::update::IndexerConfig;
 use meilisearch_types::milli::vector::{Embedder, EmbedderOptions, EmbeddingConfigs};
@@ -67,6 +70,7 @@ use queue::Queue;
 use roaring::RoaringBitmap;
 use scheduler::Scheduler;
 use time::OffsetDateTime;
+use uuid::Uuid;
 use versioning::Versioning;
 
 use crate::index_mapper::IndexMapper;
@@ -131,6 +135,12 @@ pub struct IndexSchedulerOptions {
     ///
     /// 0 disables the cache.
     pub embedding_cache_cap: usize,
+    /// Whether the snapshots only contain the indexes updated since the previous snapshot.
+    pub incremental_snapshots: bool,
+    /// The object storage on which the snapshots and dumps are uploaded, if any.
+    pub s3_options: Option<s3::S3Options>,
+    /// The key used to encrypt the update files, dumps and snapshots, if any.
+    pub encryption_key: Option<EncryptionKey>,
 }
 
 /// Structure which holds meilisearch's indexes and schedules the tasks
@@ -456,6 +466,34 @@ impl IndexScheduler {
         self.index_mapper.index(&rtxn, name)
     }
 
+    /// Return the uid of the index the alias points to, or the provided uid if it isn't an alias.
+    ///
+    /// The routes working on the content of an index call this function so that an alias
+    /// can be used in place of the index uid.
+    pub fn resolve_index_alias(&self, index_uid: IndexUid) -> Result<IndexUid> {
+        let rtxn = self.env.read_txn()?;
+        match self.index_mapper.resolve_alias(&rtxn, &index_uid)? {
+            Some(target) => Ok(IndexUid::new_unchecked(target)),
+            None => Ok(index_uid),
+        }
+    }
+
+    /// Return all the index aliases along with the uid of the index they point to.
+    pub fn index_aliases(&self) -> Result<BTreeMap<String, String>> {
+        let rtxn = self.env.read_txn()?;
+        self.index_mapper.aliases(&rtxn)
+    }
+
+    /// Register the index aliases coming from a dump, the indexes must have been imported beforehand.
+    pub fn put_index_aliases(&self, aliases: &BTreeMap<String, String>) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        for (alias, index_uid) in aliases {
+            self.index_mapper.put_alias(&mut wtxn, alias, index_uid)?;
+        }
+        wtxn.commit()?;
+        Ok(())
+    }
+
     /// Return the boolean referring if index exists.
     pub fn index_exists(&self, name: &str) -> Result<bool> {
         let rtxn = self.env.read_txn()?;
@@ -637,6 +675,19 @@ impl IndexScheduler {
         kind: KindWithContent,
         task_id: Option<TaskId>,
         dry_run: bool,
+    ) -> Result<Task> {
+        self.register_with_key(kind, task_id, None, dry_run)
+    }
+
+    /// Register a new task in the scheduler on behalf of the API key that enqueued it.
+    ///
+    /// The key is `None` for the tasks enqueued with the master key or by Meilisearch itself.
+    pub fn register_with_key(
+        &self,
+        kind: KindWithContent,
+        task_id: Option<TaskId>,
+        key_uid: Option<Uuid>,
+        dry_run: bool,
     ) -> Result<Task> {
         // if the task doesn't delete or cancel anything and 40% of the task queue is full, we must refuse to enqueue the incoming task
         if !matches!(&kind, KindWithContent::TaskDeletion { tasks, .. } | KindWithContent::TaskCancelation { tasks, .. } if !tasks.is_empty())
@@ -647,7 +698,7 @@ impl IndexScheduler {
         }
 
         let mut wtxn = self.env.write_txn()?;
-        let task = self.queue.register(&mut wtxn, &kind, task_id, dry_run)?;
+        let task = self.queue.register(&mut wtxn, &kind, task_id, key_uid, dry_run)?;
 
         // If the registered task is a task cancelation
         // we inform the processing tasks to stop (if necessary).
@@ -96,6 +96,7 @@ make_enum_progress! {
         SnapshotTheIndexes,
         SnapshotTheApiKeys,
         CreateTheTarball,
+        UploadTheSnapshot,
     }
 }
 
@@ -108,6 +109,7 @@ make_enum_progress! {
         DumpTheIndexes,
         DumpTheExperimentalFeatures,
         CompressTheDump,
+        UploadTheDump,
     }
 }
 
@@ -144,6 +146,33 @@ make_enum_progress! {
     }
 }
 
+make_enum_progress! {
+    pub enum UpdatingTheAliases {
+        UpdatingTheAliases,
+    }
+}
+
+make_enum_progress! {
+    pub enum CloneIndexProgress {
+        CloningTheIndex,
+    }
+}
+
+make_enum_progress! {
+    pub enum IndexExportProgress {
+        DumpTheIndex,
+        CompressTheDump,
+    }
+}
+
+make_enum_progress! {
+    pub enum IndexImportProgress {
+        OpenTheDump,
+        ImportTheSettings,
+        ImportTheDocuments,
+    }
+}
+
 make_enum_progress! {
     pub enum DocumentOperationProgress {
         RetrievingConfig,
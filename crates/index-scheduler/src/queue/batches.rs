@@ -310,6 +310,7 @@ impl Queue {
             statuses,
             types,
             index_uids,
+            key_uids,
             canceled_by,
             before_enqueued_at,
             after_enqueued_at,
@@ -427,6 +428,28 @@ impl Queue {
             batches &= &index_batches;
         }
 
+        // There is no database for this query, we must retrieve the batches containing the tasks of the keys
+        if let Some(key_uids) = &key_uids {
+            let mut key_tasks = RoaringBitmap::new();
+            for key_uid in key_uids {
+                key_tasks |= self.tasks.key_tasks(rtxn, *key_uid)?;
+            }
+
+            let mut key_batches = RoaringBitmap::new();
+            for batch_uid in &batches {
+                let is_key_batch = match processing.batch.as_ref() {
+                    Some(batch) if batch.uid == batch_uid => {
+                        !processing.processing.is_disjoint(&key_tasks)
+                    }
+                    _ => !self.tasks_in_batch(rtxn, batch_uid)?.is_disjoint(&key_tasks),
+                };
+                if is_key_batch {
+                    key_batches.insert(batch_uid);
+                }
+            }
+            batches &= &key_batches;
+        }
+
         // For the started_at filter, we need to treat the part of the batches that are processing from the part of the
         // batches that are not processing. The non-processing ones are filtered normally while the processing ones
         // are entirely removed unless the in-memory startedAt variable falls within the date filter.
@@ -520,6 +543,12 @@ impl Queue {
         filters: &meilisearch_auth::AuthFilter,
         processing: &ProcessingTasks,
     ) -> Result<(RoaringBitmap, u64)> {
+        // A key restricted to its own tasks must not see, nor count, the other batches.
+        let query = &match filters.tasks_owner() {
+            Some(key_uid) => query.clone().with_key_uid(key_uid),
+            None => query.clone(),
+        };
+
         // compute all batches matching the filter by ignoring the limits, to find the number of batches matching
         // the filter.
         // As this causes us to compute the filter twice it is slightly inefficient, but doing it this way spares
@@ -8,9 +8,10 @@ mod tasks_test;
 mod test;
 
 use std::collections::BTreeMap;
-use std::fs::File as StdFile;
+use std::io::Read;
 use std::time::Duration;
 
+use file_store::encryption::EncryptionKey;
 use file_store::FileStore;
 use meilisearch_types::batches::BatchId;
 use meilisearch_types::heed::{Database, Env, RoTxn, RwTxn, WithoutTls};
@@ -25,7 +26,8 @@ pub(crate) use self::batches::BatchQueue;
 pub(crate) use self::tasks::TaskQueue;
 use crate::processing::ProcessingTasks;
 use crate::utils::{
-    check_index_swap_validity, filter_out_references_to_newer_tasks, ProcessingBatch,
+    check_index_alias_validity, check_index_swap_validity, filter_out_references_to_newer_tasks,
+    ProcessingBatch,
 };
 use crate::{Error, IndexSchedulerOptions, Result, TaskId};
 
@@ -66,6 +68,8 @@ pub struct Query {
     pub types: Option<Vec<Kind>>,
     /// The allowed [index ids](meilisearch_types::tasks::Task::index_uid) of the matched tasks
     pub index_uids: Option<Vec<String>>,
+    /// The uids of the [API keys](meilisearch_types::tasks::Task::key_uid) that enqueued the matched tasks
+    pub key_uids: Option<Vec<Uuid>>,
     /// The [task ids](`meilisearch_types::tasks::Task::uid`) of the [`TaskCancelation`](meilisearch_types::tasks::Task::Kind::TaskCancelation) tasks
     /// that canceled the matched tasks.
     pub canceled_by: Option<Vec<TaskId>>,
@@ -98,6 +102,7 @@ impl Query {
                 statuses: None,
                 types: None,
                 index_uids: None,
+                key_uids: None,
                 canceled_by: None,
                 before_enqueued_at: None,
                 after_enqueued_at: None,
@@ -116,6 +121,15 @@ impl Query {
         Self { index_uids: Some(index_vec), ..self }
     }
 
+    /// Restricts the query to the tasks enqueued by the given API key.
+    pub fn with_key_uid(self, key_uid: Uuid) -> Self {
+        let key_uids = match self.key_uids {
+            Some(key_uids) => key_uids.into_iter().filter(|uid| *uid == key_uid).collect(),
+            None => vec![key_uid],
+        };
+        Self { key_uids: Some(key_uids), ..self }
+    }
+
     // Removes the `from` and `limit` restrictions from the query.
     // Useful to get the total number of tasks matching a filter.
     pub fn without_limits(self) -> Self {
@@ -163,7 +177,11 @@ impl Queue {
     ) -> Result<Self> {
         // allow unreachable_code to get rids of the warning in the case of a test build.
         Ok(Self {
-            file_store: FileStore::new(&options.update_file_path)?,
+            file_store: FileStore::new_with_encryption(
+                &options.update_file_path,
+                options.encryption_key.clone(),
+            )?
+            .with_decryption_limit(options.indexer_config.max_memory.map(|max| max as u64)),
             batch_to_tasks_mapping: env
                 .create_database(wtxn, Some(db_name::BATCH_TO_TASKS_MAPPING))?,
             tasks: TaskQueue::new(env, wtxn)?,
@@ -217,9 +235,9 @@ impl Queue {
         }
     }
 
-    /// Open and returns the task's content File.
-    pub fn update_file(&self, uuid: Uuid) -> file_store::Result<StdFile> {
-        self.file_store.get_update(uuid)
+    /// Open and returns a reader over the decrypted content of the task's content File.
+    pub fn update_file(&self, uuid: Uuid) -> file_store::Result<Box<dyn Read + Send>> {
+        self.file_store.get_update_reader(uuid)
     }
 
     /// Delete a file from the index scheduler.
@@ -247,6 +265,11 @@ impl Queue {
         Ok(self.file_store.new_update_with_uuid(uuid)?)
     }
 
+    /// The key the update files are encrypted with, if any.
+    pub fn update_files_encryption_key(&self) -> Option<&EncryptionKey> {
+        self.file_store.encryption_key()
+    }
+
     /// The size on disk taken by all the updates files contained in the `IndexScheduler`, in bytes.
     pub fn compute_update_file_size(&self) -> Result<u64> {
         Ok(self.file_store.compute_total_size()?)
@@ -257,6 +280,7 @@ impl Queue {
         wtxn: &mut RwTxn,
         kind: &KindWithContent,
         task_id: Option<TaskId>,
+        key_uid: Option<Uuid>,
         dry_run: bool,
     ) -> Result<Task> {
         let next_task_id = self.tasks.next_task_id(wtxn)?;
@@ -279,6 +303,7 @@ impl Queue {
             details: kind.default_details(),
             status: Status::Enqueued,
             kind: kind.clone(),
+            key_uid,
         };
         // For deletion and cancelation tasks, we want to make extra sure that they
         // don't attempt to delete/cancel tasks that are newer than themselves.
@@ -286,6 +311,8 @@ impl Queue {
         // If the register task is an index swap task, verify that it is well-formed
         // (that it does not contain duplicate indexes).
         check_index_swap_validity(&task)?;
+        // Same thing for the index alias tasks.
+        check_index_alias_validity(&task)?;
 
         // At this point the task is going to be registered and no further checks will be done
         if dry_run {
@@ -342,6 +369,7 @@ impl Queue {
                 tasks: to_delete,
             },
             None,
+            None,
             false,
         )?;
 
@@ -6,16 +6,18 @@ use meilisearch_types::milli::{CboRoaringBitmapCodec, RoaringBitmapCodec, BEU32}
 use meilisearch_types::tasks::{Kind, Status, Task};
 use roaring::{MultiOps, RoaringBitmap};
 use time::OffsetDateTime;
+use uuid::Uuid;
 
 use super::{Query, Queue};
 use crate::processing::ProcessingTasks;
 use crate::utils::{
     self, insert_task_datetime, keep_ids_within_datetimes, map_bound, remove_task_datetime,
 };
+use crate::uuid_codec::UuidCodec;
 use crate::{Error, Result, TaskId, BEI128};
 
 /// The number of database used by the task queue
-const NUMBER_OF_DATABASES: u32 = 8;
+const NUMBER_OF_DATABASES: u32 = 9;
 /// Database const names for the `IndexScheduler`.
 mod db_name {
     pub const ALL_TASKS: &str = "all-tasks";
@@ -23,6 +25,7 @@ mod db_name {
     pub const STATUS: &str = "status";
     pub const KIND: &str = "kind";
     pub const INDEX_TASKS: &str = "index-tasks";
+    pub const KEY_TASKS: &str = "key-tasks";
     pub const CANCELED_BY: &str = "canceled_by";
     pub const ENQUEUED_AT: &str = "enqueued-at";
     pub const STARTED_AT: &str = "started-at";
@@ -40,6 +43,8 @@ pub struct TaskQueue {
     pub(crate) kind: Database<SerdeBincode<Kind>, RoaringBitmapCodec>,
     /// Store the tasks associated to an index.
     pub(crate) index_tasks: Database<Str, RoaringBitmapCodec>,
+    /// Store the tasks enqueued by an API key.
+    pub(crate) key_tasks: Database<UuidCodec, RoaringBitmapCodec>,
     /// Store the tasks that were canceled by a task uid
     pub(crate) canceled_by: Database<BEU32, RoaringBitmapCodec>,
     /// Store the task ids of tasks which were enqueued at a specific date
@@ -57,6 +62,7 @@ impl TaskQueue {
             status: self.status,
             kind: self.kind,
             index_tasks: self.index_tasks,
+            key_tasks: self.key_tasks,
             canceled_by: self.canceled_by,
             enqueued_at: self.enqueued_at,
             started_at: self.started_at,
@@ -74,6 +80,7 @@ impl TaskQueue {
             status: env.create_database(wtxn, Some(db_name::STATUS))?,
             kind: env.create_database(wtxn, Some(db_name::KIND))?,
             index_tasks: env.create_database(wtxn, Some(db_name::INDEX_TASKS))?,
+            key_tasks: env.create_database(wtxn, Some(db_name::KEY_TASKS))?,
             canceled_by: env.create_database(wtxn, Some(db_name::CANCELED_BY))?,
             enqueued_at: env.create_database(wtxn, Some(db_name::ENQUEUED_AT))?,
             started_at: env.create_database(wtxn, Some(db_name::STARTED_AT))?,
@@ -183,6 +190,28 @@ impl TaskQueue {
         Ok(())
     }
 
+    /// Returns the whole set of tasks enqueued by this API key.
+    pub(crate) fn key_tasks(&self, rtxn: &RoTxn, key_uid: Uuid) -> Result<RoaringBitmap> {
+        Ok(self.key_tasks.get(rtxn, &key_uid)?.unwrap_or_default())
+    }
+
+    pub(crate) fn update_key(
+        &self,
+        wtxn: &mut RwTxn,
+        key_uid: Uuid,
+        f: impl Fn(&mut RoaringBitmap),
+    ) -> Result<()> {
+        let mut tasks = self.key_tasks(wtxn, key_uid)?;
+        f(&mut tasks);
+        if tasks.is_empty() {
+            self.key_tasks.delete(wtxn, &key_uid)?;
+        } else {
+            self.key_tasks.put(wtxn, &key_uid, &tasks)?;
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn get_status(&self, rtxn: &RoTxn, status: Status) -> Result<RoaringBitmap> {
         Ok(self.status.get(rtxn, &status)?.unwrap_or_default())
     }
@@ -259,6 +288,12 @@ impl TaskQueue {
             })?;
         }
 
+        if let Some(key_uid) = task.key_uid {
+            self.update_key(wtxn, key_uid, |bitmap| {
+                bitmap.insert(task.uid);
+            })?;
+        }
+
         self.update_status(wtxn, Status::Enqueued, |bitmap| {
             bitmap.insert(task.uid);
         })?;
@@ -292,6 +327,7 @@ impl Queue {
             statuses,
             types,
             index_uids,
+            key_uids,
             canceled_by,
             before_enqueued_at,
             after_enqueued_at,
@@ -379,6 +415,14 @@ impl Queue {
             tasks &= &index_tasks;
         }
 
+        if let Some(key_uids) = key_uids {
+            let mut key_tasks = RoaringBitmap::new();
+            for key_uid in key_uids {
+                key_tasks |= self.tasks.key_tasks(rtxn, *key_uid)?;
+            }
+            tasks &= &key_tasks;
+        }
+
         // For the started_at filter, we need to treat the part of the tasks that are processing from the part of the
         // tasks that are not processing. The non-processing ones are filtered normally while the processing ones
         // are entirely removed unless the in-memory startedAt variable falls within the date filter.
@@ -462,6 +506,12 @@ impl Queue {
         filters: &meilisearch_auth::AuthFilter,
         processing_tasks: &ProcessingTasks,
     ) -> Result<(RoaringBitmap, u64)> {
+        // A key restricted to its own tasks must not see, nor count, the other tasks.
+        let query = &match filters.tasks_owner() {
+            Some(key_uid) => query.clone().with_key_uid(key_uid),
+            None => query.clone(),
+        };
+
         // compute all tasks matching the filter by ignoring the limits, to find the number of tasks matching
         // the filter.
         // As this causes us to compute the filter twice it is slightly inefficient, but doing it this way spares
@@ -0,0 +1,384 @@
+//! A minimal client for the S3 API, used to upload the snapshots and dumps to an
+//! object storage and to download them back when importing.
+//!
+//! Only the few requests we need are implemented and signed with AWS Signature V4.
+//! The bucket is always addressed in path-style (`{bucket_url}/{bucket}/{key}`), which
+//! is supported by AWS as well as the S3-compatible storages like MinIO.
+
+use std::io::{self, Read, Write};
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use time::macros::format_description;
+use time::OffsetDateTime;
+
+/// S3 refuses the parts of a multipart upload smaller than 5MiB, except for the last one.
+pub const MIN_MULTIPART_PART_SIZE: u64 = 5 * 1024 * 1024;
+pub const DEFAULT_MULTIPART_PART_SIZE: u64 = 64 * 1024 * 1024;
+
+const SNAPSHOTS_PREFIX: &str = "snapshots/";
+const DUMPS_PREFIX: &str = "dumps/";
+const EMPTY_PAYLOAD_SHA256: &str =
+    "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("The S3 object `{0}` does not exist.")]
+    ObjectNotFound(String),
+    #[error("The S3 server answered with a `{status}` status: {body}")]
+    Response { status: u16, body: String },
+    #[error("Could not reach the S3 server: {0}")]
+    Transport(String),
+    #[error("Unexpected answer from the S3 server: {0}")]
+    InvalidResponse(String),
+    #[error("`{0}` is not a valid S3 URL. It must be formatted as `s3://bucket/key`.")]
+    InvalidUrl(String),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+impl From<ureq::Error> for Error {
+    fn from(error: ureq::Error) -> Self {
+        match error {
+            ureq::Error::Status(status, response) => {
+                let body = response.into_string().unwrap_or_default();
+                Error::Response { status, body }
+            }
+            ureq::Error::Transport(transport) => Error::Transport(transport.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct S3Options {
+    /// The URL of the S3 API, e.g. `https://s3.eu-west-3.amazonaws.com` or `http://localhost:9000`.
+    pub bucket_url: String,
+    /// The region of the bucket, used to sign the requests.
+    pub bucket_region: String,
+    /// The bucket in which the snapshots and dumps are uploaded.
+    pub bucket_name: String,
+    pub access_key: String,
+    pub secret_key: String,
+    /// The prefix of every key written in the bucket.
+    pub prefix: String,
+    /// The size, in bytes, of the parts sent in a multipart upload.
+    pub multipart_part_size: u64,
+    /// The number of snapshots and of dumps to keep in the bucket, the older ones are deleted.
+    ///
+    /// `None` keeps all of them.
+    pub retention: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct S3Client {
+    options: S3Options,
+    host: String,
+}
+
+impl S3Client {
+    pub fn new(mut options: S3Options) -> Self {
+        options.bucket_url = options.bucket_url.trim_end_matches('/').to_string();
+        if !options.prefix.is_empty() && !options.prefix.ends_with('/') {
+            options.prefix.push('/');
+        }
+        options.multipart_part_size = options.multipart_part_size.max(MIN_MULTIPART_PART_SIZE);
+        let host = options.bucket_url.split_once("://").map_or(&*options.bucket_url, |(_, s)| s);
+        let host = host.split('/').next().unwrap_or_default().to_string();
+        S3Client { options, host }
+    }
+
+    /// Returns a client targeting another bucket with the same credentials.
+    pub fn with_bucket(&self, bucket_name: &str) -> Self {
+        let mut client = self.clone();
+        client.options.bucket_name = bucket_name.to_string();
+        client
+    }
+
+    /// Uploads a snapshot in the `snapshots/` directory of the bucket and
+    /// deletes the ones exceeding the retention.
+    pub fn upload_snapshot(&self, name: &str, reader: impl Read) -> Result<(), Error> {
+        self.upload_with_retention(SNAPSHOTS_PREFIX, name, reader)
+    }
+
+    /// Uploads a dump in the `dumps/` directory of the bucket and
+    /// deletes the ones exceeding the retention.
+    pub fn upload_dump(&self, name: &str, reader: impl Read) -> Result<(), Error> {
+        self.upload_with_retention(DUMPS_PREFIX, name, reader)
+    }
+
+    fn upload_with_retention(
+        &self,
+        directory: &str,
+        name: &str,
+        reader: impl Read,
+    ) -> Result<(), Error> {
+        let directory = format!("{}{directory}", self.options.prefix);
+        self.upload(&format!("{directory}{name}"), reader)?;
+
+        if let Some(retention) = self.options.retention {
+            // The names of the snapshots and dumps end with their creation date,
+            // so the lexicographic order of the keys is also the chronological one.
+            let mut keys = self.list(&directory)?;
+            keys.sort_unstable();
+            let outdated = keys.len().saturating_sub(retention);
+            for key in &keys[..outdated] {
+                self.delete(key)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Streams the content of the reader to the bucket with a multipart upload,
+    /// only keeping a single part in memory at a time.
+    pub fn upload(&self, key: &str, mut reader: impl Read) -> Result<(), Error> {
+        let response = self.request("POST", key, &[("uploads", "")], &[])?;
+        let body = response.into_string()?;
+        let upload_id = xml_tag_values(&body, "UploadId")
+            .first()
+            .ok_or_else(|| Error::InvalidResponse(format!("missing upload id in `{body}`")))?
+            .to_string();
+
+        let mut upload_parts = || -> Result<Vec<String>, Error> {
+            let mut etags = Vec::new();
+            let mut buffer = Vec::new();
+            loop {
+                buffer.clear();
+                let read = (&mut reader)
+                    .take(self.options.multipart_part_size)
+                    .read_to_end(&mut buffer)?;
+                // An empty object is still made of one empty part.
+                if read == 0 && !etags.is_empty() {
+                    break;
+                }
+                let part_number = (etags.len() + 1).to_string();
+                let query =
+                    [("partNumber", part_number.as_str()), ("uploadId", upload_id.as_str())];
+                let response = self.request("PUT", key, &query, &buffer)?;
+                let etag = response.header("ETag").ok_or_else(|| {
+                    Error::InvalidResponse(format!("missing ETag for part {part_number}"))
+                })?;
+                etags.push(etag.to_string());
+                if (read as u64) < self.options.multipart_part_size {
+                    break;
+                }
+            }
+            Ok(etags)
+        };
+
+        let etags = match upload_parts() {
+            Ok(etags) => etags,
+            Err(e) => {
+                // The parts already sent are billed as long as the upload isn't aborted.
+                if let Err(abort_error) =
+                    self.request("DELETE", key, &[("uploadId", upload_id.as_str())], &[])
+                {
+                    tracing::warn!("Could not abort the upload of `{key}`: {abort_error}");
+                }
+                return Err(e);
+            }
+        };
+
+        let mut body = String::from("<CompleteMultipartUpload>");
+        for (i, etag) in etags.iter().enumerate() {
+            body.push_str(&format!(
+                "<Part><PartNumber>{}</PartNumber><ETag>{etag}</ETag></Part>",
+                i + 1
+            ));
+        }
+        body.push_str("</CompleteMultipartUpload>");
+        let response =
+            self.request("POST", key, &[("uploadId", upload_id.as_str())], body.as_bytes())?;
+        // S3 can answer with a 200 status and an error in the body.
+        let body = response.into_string()?;
+        if body.contains("<Error>") {
+            return Err(Error::InvalidResponse(body));
+        }
+
+        Ok(())
+    }
+
+    /// Writes the content of the object into the writer.
+    pub fn download(&self, key: &str, mut writer: impl Write) -> Result<u64, Error> {
+        let response = match self.request("GET", key, &[], &[]) {
+            Err(Error::Response { status: 404, .. }) => {
+                return Err(Error::ObjectNotFound(key.to_string()))
+            }
+            result => result?,
+        };
+        Ok(io::copy(&mut response.into_reader(), &mut writer)?)
+    }
+
+    /// Lists the keys of all the objects starting with the prefix.
+    pub fn list(&self, prefix: &str) -> Result<Vec<String>, Error> {
+        let mut keys = Vec::new();
+        let mut continuation_token: Option<String> = None;
+        loop {
+            let mut query = vec![("list-type", "2"), ("prefix", prefix)];
+            if let Some(token) = &continuation_token {
+                query.push(("continuation-token", token.as_str()));
+            }
+            let response = self.request("GET", "", &query, &[])?;
+            let body = response.into_string()?;
+            keys.extend(xml_tag_values(&body, "Key").into_iter().map(xml_unescape));
+
+            continuation_token =
+                xml_tag_values(&body, "NextContinuationToken").first().map(|s| xml_unescape(s));
+            let truncated = xml_tag_values(&body, "IsTruncated").first() == Some(&"true");
+            if !truncated || continuation_token.is_none() {
+                break;
+            }
+        }
+        Ok(keys)
+    }
+
+    pub fn delete(&self, key: &str) -> Result<(), Error> {
+        self.request("DELETE", key, &[], &[])?;
+        Ok(())
+    }
+
+    /// Sends a request signed with AWS Signature V4 on an object of the bucket,
+    /// or on the bucket itself when the key is empty.
+    fn request(
+        &self,
+        method: &str,
+        key: &str,
+        query: &[(&str, &str)],
+        body: &[u8],
+    ) -> Result<ureq::Response, Error> {
+        let S3Options { bucket_url, bucket_region, bucket_name, access_key, secret_key, .. } =
+            &self.options;
+
+        let now = OffsetDateTime::now_utc();
+        let amz_date =
+            now.format(format_description!("[year][month][day]T[hour][minute][second]Z")).unwrap();
+        let date = &amz_date[..8];
+        let payload_hash = if body.is_empty() {
+            EMPTY_PAYLOAD_SHA256.to_string()
+        } else {
+            hex::encode(Sha256::digest(body))
+        };
+
+        let path = format!("/{}/{}", uri_encode(bucket_name, true), uri_encode(key, false));
+        let mut query: Vec<_> =
+            query.iter().map(|(k, v)| (uri_encode(k, true), uri_encode(v, true))).collect();
+        query.sort_unstable();
+        let query = query.iter().map(|(k, v)| format!("{k}={v}")).collect::<Vec<_>>().join("&");
+
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "{method}\n{path}\n{query}\nhost:{}\nx-amz-content-sha256:{payload_hash}\nx-amz-date:{amz_date}\n\n{signed_headers}\n{payload_hash}",
+            self.host
+        );
+        let scope = format!("{date}/{bucket_region}/s3/aws4_request");
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+        let signing_key = [date, bucket_region, "s3", "aws4_request"]
+            .iter()
+            .fold(format!("AWS4{secret_key}").into_bytes(), |key, data| hmac_sha256(&key, data));
+        let signature = hex::encode(hmac_sha256(&signing_key, &string_to_sign));
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={access_key}/{scope}, SignedHeaders={signed_headers}, Signature={signature}"
+        );
+
+        let url = if query.is_empty() {
+            format!("{bucket_url}{path}")
+        } else {
+            format!("{bucket_url}{path}?{query}")
+        };
+        let response = ureq::request(method, &url)
+            .timeout(Duration::from_secs(300))
+            .set("x-amz-content-sha256", &payload_hash)
+            .set("x-amz-date", &amz_date)
+            .set("Authorization", &authorization)
+            .send_bytes(body)?;
+        Ok(response)
+    }
+}
+
+/// Splits an `s3://bucket/key` URL in its bucket and key.
+///
+/// Returns `Ok(None)` if the path is not an S3 URL.
+pub fn parse_url(url: &str) -> Result<Option<(&str, &str)>, Error> {
+    let Some(rest) = url.strip_prefix("s3://") else { return Ok(None) };
+    match rest.split_once('/') {
+        Some((bucket, key)) if !bucket.is_empty() && !key.is_empty() => Ok(Some((bucket, key))),
+        _ => Err(Error::InvalidUrl(url.to_string())),
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC can take a key of any size");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Percent-encodes everything but the unreserved characters, as required by the signature.
+fn uri_encode(s: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Returns the content of all the `<tag>...</tag>` of a flat XML document.
+fn xml_tag_values<'a>(xml: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    xml.split(open.as_str())
+        .skip(1)
+        .filter_map(|s| s.split_once(close.as_str()))
+        .map(|(value, _)| value)
+        .collect()
+}
+
+fn xml_unescape(s: &str) -> String {
+    s.replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_s3_url() {
+        assert_eq!(
+            parse_url("s3://bucket/dumps/a.dump").unwrap(),
+            Some(("bucket", "dumps/a.dump"))
+        );
+        assert_eq!(parse_url("dumps/a.dump").unwrap(), None);
+        assert!(parse_url("s3://bucket").is_err());
+        assert!(parse_url("s3:///a.dump").is_err());
+    }
+
+    #[test]
+    fn encode_keys() {
+        assert_eq!(
+            uri_encode("snapshots/data.ms-1 2.snapshot", false),
+            "snapshots/data.ms-1%202.snapshot"
+        );
+        assert_eq!(uri_encode("snapshots/", true), "snapshots%2F");
+    }
+
+    #[test]
+    fn parse_list_objects() {
+        let xml = "<ListBucketResult><IsTruncated>false</IsTruncated><Contents><Key>dumps/a&amp;b.dump</Key></Contents><Contents><Key>dumps/c.dump</Key></Contents></ListBucketResult>";
+        let keys: Vec<_> = xml_tag_values(xml, "Key").into_iter().map(xml_unescape).collect();
+        assert_eq!(keys, ["dumps/a&b.dump", "dumps/c.dump"]);
+        assert_eq!(xml_tag_values(xml, "IsTruncated"), ["false"]);
+    }
+}
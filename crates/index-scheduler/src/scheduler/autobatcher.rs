@@ -25,6 +25,10 @@ enum AutobatchKind {
     IndexDeletion,
     IndexUpdate,
     IndexSwap,
+    IndexAliasUpdate,
+    IndexClone,
+    IndexExport,
+    IndexImport,
 }
 
 impl AutobatchKind {
@@ -68,6 +72,10 @@ impl From<KindWithContent> for AutobatchKind {
             KindWithContent::IndexCreation { .. } => AutobatchKind::IndexCreation,
             KindWithContent::IndexUpdate { .. } => AutobatchKind::IndexUpdate,
             KindWithContent::IndexSwap { .. } => AutobatchKind::IndexSwap,
+            KindWithContent::IndexAliasUpdate { .. } => AutobatchKind::IndexAliasUpdate,
+            KindWithContent::IndexClone { .. } => AutobatchKind::IndexClone,
+            KindWithContent::IndexExport { .. } => AutobatchKind::IndexExport,
+            KindWithContent::IndexImport { .. } => AutobatchKind::IndexImport,
             KindWithContent::TaskCancelation { .. }
             | KindWithContent::TaskDeletion { .. }
             | KindWithContent::DumpCreation { .. }
@@ -117,6 +125,18 @@ pub enum BatchKind {
     IndexSwap {
         id: TaskId,
     },
+    IndexAliasUpdate {
+        id: TaskId,
+    },
+    IndexClone {
+        id: TaskId,
+    },
+    IndexExport {
+        id: TaskId,
+    },
+    IndexImport {
+        id: TaskId,
+    },
 }
 
 impl BatchKind {
@@ -182,6 +202,34 @@ impl BatchKind {
                 )),
                 false,
             ),
+            K::IndexAliasUpdate => (
+                Break((
+                    BatchKind::IndexAliasUpdate { id: task_id },
+                    BatchStopReason::TaskCannotBeBatched { kind, id: task_id },
+                )),
+                false,
+            ),
+            K::IndexClone => (
+                Break((
+                    BatchKind::IndexClone { id: task_id },
+                    BatchStopReason::TaskCannotBeBatched { kind, id: task_id },
+                )),
+                false,
+            ),
+            K::IndexExport => (
+                Break((
+                    BatchKind::IndexExport { id: task_id },
+                    BatchStopReason::TaskCannotBeBatched { kind, id: task_id },
+                )),
+                false,
+            ),
+            K::IndexImport => (
+                Break((
+                    BatchKind::IndexImport { id: task_id },
+                    BatchStopReason::TaskCannotBeBatched { kind, id: task_id },
+                )),
+                false,
+            ),
             K::DocumentClear => (Continue(BatchKind::DocumentClear { ids: vec![task_id] }), false),
             K::DocumentImport { allow_index_creation, primary_key: pk }
                 if primary_key.is_none() || pk.is_none() || primary_key == pk.as_deref() =>
@@ -287,7 +335,7 @@ impl BatchKind {
 
         match (self, autobatch_kind) {
             // We don't batch any of these operations
-            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap | K::DocumentEdition) => Break((this, BatchStopReason::TaskCannotBeBatched { kind, id })),
+            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap | K::IndexAliasUpdate | K::IndexClone | K::IndexExport | K::IndexImport | K::DocumentEdition) => Break((this, BatchStopReason::TaskCannotBeBatched { kind, id })),
             // We must not batch tasks that don't have the same index creation rights if the index doesn't already exists.
             (this, kind) if !index_already_exists && this.allow_index_creation() == Some(false) && kind.allow_index_creation() == Some(true) => {
                 Break((this, BatchStopReason::IndexCreationMismatch { id }))
@@ -482,6 +530,10 @@ impl BatchKind {
                 | BatchKind::IndexDeletion { .. }
                 | BatchKind::IndexUpdate { .. }
                 | BatchKind::IndexSwap { .. }
+                | BatchKind::IndexAliasUpdate { .. }
+                | BatchKind::IndexClone { .. }
+                | BatchKind::IndexExport { .. }
+                | BatchKind::IndexImport { .. }
                 | BatchKind::DocumentEdition { .. },
                 _,
             ) => {
@@ -47,6 +47,23 @@ pub(crate) enum Batch {
     IndexSwap {
         task: Task,
     },
+    IndexAliasUpdate {
+        task: Task,
+    },
+    IndexClone {
+        index_uid: String,
+        new_index_uid: String,
+        task: Task,
+    },
+    IndexExport {
+        index_uid: String,
+        task: Task,
+    },
+    IndexImport {
+        index_uid: String,
+        dump_uid: String,
+        task: Task,
+    },
     UpgradeDatabase {
         tasks: Vec<Task>,
     },
@@ -103,7 +120,10 @@ impl Batch {
             Batch::TaskCancelation { task, .. }
             | Batch::Dump(task)
             | Batch::IndexCreation { task, .. }
-            | Batch::IndexUpdate { task, .. } => {
+            | Batch::IndexUpdate { task, .. }
+            | Batch::IndexClone { task, .. }
+            | Batch::IndexExport { task, .. }
+            | Batch::IndexImport { task, .. } => {
                 RoaringBitmap::from_sorted_iter(std::iter::once(task.uid)).unwrap()
             }
             Batch::SnapshotCreation(tasks)
@@ -128,7 +148,7 @@ impl Batch {
                     ..
                 } => RoaringBitmap::from_iter(tasks.iter().chain(other).map(|task| task.uid)),
             },
-            Batch::IndexSwap { task } => {
+            Batch::IndexSwap { task } | Batch::IndexAliasUpdate { task } => {
                 RoaringBitmap::from_sorted_iter(std::iter::once(task.uid)).unwrap()
             }
         }
@@ -143,11 +163,15 @@ impl Batch {
             | SnapshotCreation(_)
             | Dump(_)
             | UpgradeDatabase { .. }
-            | IndexSwap { .. } => None,
+            | IndexSwap { .. }
+            | IndexAliasUpdate { .. } => None,
             IndexOperation { op, .. } => Some(op.index_uid()),
             IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
-            | IndexDeletion { index_uid, .. } => Some(index_uid),
+            | IndexDeletion { index_uid, .. }
+            | IndexClone { index_uid, .. }
+            | IndexExport { index_uid, .. }
+            | IndexImport { index_uid, .. } => Some(index_uid),
         }
     }
 }
@@ -167,6 +191,10 @@ impl fmt::Display for Batch {
             Batch::IndexUpdate { .. } => f.write_str("IndexUpdate")?,
             Batch::IndexDeletion { .. } => f.write_str("IndexDeletion")?,
             Batch::IndexSwap { .. } => f.write_str("IndexSwap")?,
+            Batch::IndexAliasUpdate { .. } => f.write_str("IndexAliasUpdate")?,
+            Batch::IndexClone { .. } => f.write_str("IndexClone")?,
+            Batch::IndexExport { .. } => f.write_str("IndexExport")?,
+            Batch::IndexImport { .. } => f.write_str("IndexImport")?,
             Batch::UpgradeDatabase { .. } => f.write_str("UpgradeDatabase")?,
         };
         match index_uid {
@@ -419,6 +447,40 @@ impl IndexScheduler {
                 current_batch.processing(Some(&mut task));
                 Ok(Some(Batch::IndexSwap { task }))
             }
+            BatchKind::IndexAliasUpdate { id } => {
+                let mut task =
+                    self.queue.tasks.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                current_batch.processing(Some(&mut task));
+                Ok(Some(Batch::IndexAliasUpdate { task }))
+            }
+            BatchKind::IndexClone { id } => {
+                let mut task =
+                    self.queue.tasks.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                current_batch.processing(Some(&mut task));
+                let (index_uid, new_index_uid) = match &task.kind {
+                    KindWithContent::IndexClone { index_uid, new_index_uid } => {
+                        (index_uid.clone(), new_index_uid.clone())
+                    }
+                    _ => unreachable!(),
+                };
+                Ok(Some(Batch::IndexClone { index_uid, new_index_uid, task }))
+            }
+            BatchKind::IndexExport { id } => {
+                let mut task =
+                    self.queue.tasks.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                current_batch.processing(Some(&mut task));
+                Ok(Some(Batch::IndexExport { index_uid, task }))
+            }
+            BatchKind::IndexImport { id } => {
+                let mut task =
+                    self.queue.tasks.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                current_batch.processing(Some(&mut task));
+                let dump_uid = match &task.kind {
+                    KindWithContent::IndexImport { dump_uid, .. } => dump_uid.clone(),
+                    _ => unreachable!(),
+                };
+                Ok(Some(Batch::IndexImport { index_uid, dump_uid, task }))
+            }
         }
     }
 
@@ -528,20 +590,27 @@ impl IndexScheduler {
         let mut task =
             self.queue.tasks.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
 
-        // If the task is not associated with any index, verify that it is an index swap and
-        // create the batch directly. Otherwise, get the index name associated with the task
-        // and use the autobatcher to batch the enqueued tasks associated with it
+        // If the task is not associated with any index, verify that it is an empty index swap or
+        // alias update and create the batch directly. Otherwise, get the index name associated
+        // with the task and use the autobatcher to batch the enqueued tasks associated with it
 
         let index_name = if let Some(&index_name) = task.indexes().first() {
             index_name
         } else {
-            assert!(matches!(&task.kind, KindWithContent::IndexSwap { swaps } if swaps.is_empty()));
+            let is_alias_update = matches!(&task.kind, KindWithContent::IndexAliasUpdate { aliases } if aliases.is_empty());
+            assert!(
+                is_alias_update
+                    || matches!(&task.kind, KindWithContent::IndexSwap { swaps } if swaps.is_empty())
+            );
+            let kind = task.kind.as_kind();
             current_batch.processing(Some(&mut task));
-            current_batch.reason(BatchStopReason::TaskCannotBeBatched {
-                kind: Kind::IndexSwap,
-                id: task.uid,
-            });
-            return Ok(Some((Batch::IndexSwap { task }, current_batch)));
+            current_batch.reason(BatchStopReason::TaskCannotBeBatched { kind, id: task.uid });
+            let batch = if is_alias_update {
+                Batch::IndexAliasUpdate { task }
+            } else {
+                Batch::IndexSwap { task }
+            };
+            return Ok(Some((batch, current_batch)));
         };
 
         let index_already_exists = self.index_mapper.exists(rtxn, index_name)?;
@@ -4,6 +4,7 @@ mod autobatcher_test;
 mod create_batch;
 mod process_batch;
 mod process_dump_creation;
+mod process_index_dump;
 mod process_index_operation;
 mod process_snapshot_creation;
 mod process_upgrade;
@@ -21,6 +22,7 @@ use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
 
 use convert_case::{Case, Casing as _};
+use file_store::encryption::EncryptionKey;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::heed::{Env, WithoutTls};
 use meilisearch_types::milli;
@@ -32,6 +34,7 @@ use roaring::RoaringBitmap;
 use synchronoise::SignalEvent;
 
 use crate::processing::{AtomicTaskStep, BatchProgress};
+use crate::s3::S3Client;
 use crate::{Error, IndexScheduler, IndexSchedulerOptions, Result, TickOutcome};
 
 #[derive(Default, Clone, Debug)]
@@ -83,6 +86,15 @@ pub struct Scheduler {
     ///
     /// 0 disables the cache.
     pub(crate) embedding_cache_cap: usize,
+
+    /// Whether the snapshots only contain the indexes updated since the previous snapshot.
+    pub(crate) incremental_snapshots: bool,
+
+    /// The object storage on which the snapshots and dumps are uploaded, if any.
+    pub(crate) s3: Option<S3Client>,
+
+    /// The key used to encrypt the dumps and snapshots, if any.
+    pub(crate) encryption_key: Option<EncryptionKey>,
 }
 
 impl Scheduler {
@@ -98,6 +110,9 @@ impl Scheduler {
             auth_env: self.auth_env.clone(),
             version_file_path: self.version_file_path.clone(),
             embedding_cache_cap: self.embedding_cache_cap,
+            incremental_snapshots: self.incremental_snapshots,
+            s3: self.s3.clone(),
+            encryption_key: self.encryption_key.clone(),
         }
     }
 
@@ -114,6 +129,9 @@ impl Scheduler {
             auth_env,
             version_file_path: options.version_file_path.clone(),
             embedding_cache_cap: options.embedding_cache_cap,
+            incremental_snapshots: options.incremental_snapshots,
+            s3: options.s3_options.clone().map(S3Client::new),
+            encryption_key: options.encryption_key.clone(),
         }
     }
 }
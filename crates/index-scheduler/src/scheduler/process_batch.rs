@@ -6,16 +6,18 @@ use meilisearch_types::batches::{BatchEnqueuedAt, BatchId};
 use meilisearch_types::heed::{RoTxn, RwTxn};
 use meilisearch_types::milli::progress::{Progress, VariableNameStep};
 use meilisearch_types::milli::{self, ChannelCongestion};
-use meilisearch_types::tasks::{Details, IndexSwap, Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{
+    Details, IndexAlias, IndexSwap, Kind, KindWithContent, Status, Task,
+};
 use meilisearch_types::versioning::{VERSION_MAJOR, VERSION_MINOR, VERSION_PATCH};
 use milli::update::Settings as MilliSettings;
 use roaring::RoaringBitmap;
 
 use super::create_batch::Batch;
 use crate::processing::{
-    AtomicBatchStep, AtomicTaskStep, CreateIndexProgress, DeleteIndexProgress, FinalizingIndexStep,
-    InnerSwappingTwoIndexes, SwappingTheIndexes, TaskCancelationProgress, TaskDeletionProgress,
-    UpdateIndexProgress,
+    AtomicBatchStep, AtomicTaskStep, CloneIndexProgress, CreateIndexProgress, DeleteIndexProgress,
+    FinalizingIndexStep, InnerSwappingTwoIndexes, SwappingTheIndexes, TaskCancelationProgress,
+    TaskDeletionProgress, UpdateIndexProgress, UpdatingTheAliases,
 };
 use crate::utils::{
     self, remove_n_tasks_datetime_earlier_than, remove_task_datetime, swap_index_uid_in_task,
@@ -361,6 +363,46 @@ impl IndexScheduler {
                 task.status = Status::Succeeded;
                 Ok((vec![task], ProcessBatchInfo::default()))
             }
+            Batch::IndexAliasUpdate { mut task } => {
+                progress.update_progress(UpdatingTheAliases::UpdatingTheAliases);
+
+                let mut wtxn = self.env.write_txn()?;
+                let aliases = if let KindWithContent::IndexAliasUpdate { aliases } = &task.kind {
+                    aliases
+                } else {
+                    unreachable!()
+                };
+                // All the aliases are updated in the same transaction, so a single
+                // failure leaves every alias pointing to its previous index.
+                for IndexAlias { alias, index_uid } in aliases {
+                    match index_uid {
+                        Some(index_uid) => {
+                            self.index_mapper.put_alias(&mut wtxn, alias, index_uid)?
+                        }
+                        None => self.index_mapper.delete_alias(&mut wtxn, alias)?,
+                    }
+                }
+                wtxn.commit()?;
+                task.status = Status::Succeeded;
+                Ok((vec![task], ProcessBatchInfo::default()))
+            }
+            Batch::IndexClone { index_uid, new_index_uid, mut task } => {
+                progress.update_progress(CloneIndexProgress::CloningTheIndex);
+
+                // The write transaction is directly owned and committed inside.
+                let wtxn = self.env.write_txn()?;
+                self.index_mapper.clone_index(wtxn, &index_uid, &new_index_uid)?;
+
+                task.status = Status::Succeeded;
+                task.details = Some(Details::IndexClone { new_index_uid });
+                Ok((vec![task], ProcessBatchInfo::default()))
+            }
+            Batch::IndexExport { index_uid, task } => self
+                .process_index_export(progress, index_uid, task)
+                .map(|tasks| (tasks, ProcessBatchInfo::default())),
+            Batch::IndexImport { index_uid, dump_uid, task } => self
+                .process_index_import(progress, index_uid, dump_uid, task)
+                .map(|tasks| (tasks, ProcessBatchInfo::default())),
             Batch::UpgradeDatabase { mut tasks } => {
                 let KindWithContent::UpgradeDatabase { from } = tasks.last().unwrap().kind else {
                     unreachable!();
@@ -478,6 +520,7 @@ impl IndexScheduler {
         let mut affected_indexes = HashSet::new();
         let mut affected_statuses = HashSet::new();
         let mut affected_kinds = HashSet::new();
+        let mut affected_keys = HashSet::new();
         let mut affected_canceled_by = RoaringBitmap::new();
         // The tasks that have been removed *per batches*.
         let mut affected_batches: HashMap<BatchId, RoaringBitmap> = HashMap::new();
@@ -491,6 +534,7 @@ impl IndexScheduler {
             affected_indexes.extend(task.indexes().into_iter().map(|x| x.to_owned()));
             affected_statuses.insert(task.status);
             affected_kinds.insert(task.kind.as_kind());
+            affected_keys.extend(task.key_uid);
             // Note: don't delete the persisted task data since
             // we can only delete succeeded, failed, and canceled tasks.
             // In each of those cases, the persisted data is supposed to
@@ -528,7 +572,10 @@ impl IndexScheduler {
 
         progress.update_progress(TaskDeletionProgress::DeletingTasksMetadata);
         let (atomic_progress, task_progress) = AtomicTaskStep::new(
-            (affected_indexes.len() + affected_statuses.len() + affected_kinds.len()) as u32,
+            (affected_indexes.len()
+                + affected_statuses.len()
+                + affected_kinds.len()
+                + affected_keys.len()) as u32,
         );
         progress.update_progress(task_progress);
         for index in affected_indexes.iter() {
@@ -546,6 +593,11 @@ impl IndexScheduler {
             atomic_progress.fetch_add(1, Ordering::Relaxed);
         }
 
+        for key_uid in affected_keys.iter() {
+            self.queue.tasks.update_key(wtxn, *key_uid, |bitmap| *bitmap -= &to_delete_tasks)?;
+            atomic_progress.fetch_add(1, Ordering::Relaxed);
+        }
+
         progress.update_progress(TaskDeletionProgress::DeletingTasks);
         let (atomic_progress, task_progress) = AtomicTaskStep::new(to_delete_tasks.len() as u32);
         progress.update_progress(task_progress);
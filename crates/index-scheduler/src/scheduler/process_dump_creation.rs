@@ -1,13 +1,15 @@
 use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::BufWriter;
+use std::path::Path;
 use std::sync::atomic::Ordering;
 
 use dump::IndexMetadata;
+use file_store::encryption::EncryptWriter;
 use meilisearch_types::milli::constants::RESERVED_VECTORS_FIELD_NAME;
 use meilisearch_types::milli::progress::{Progress, VariableNameStep};
 use meilisearch_types::milli::vector::parsed_vectors::{ExplicitVectors, VectorOrArrayOfVectors};
-use meilisearch_types::milli::{self};
+use meilisearch_types::milli::{self, Index};
 use meilisearch_types::tasks::{Details, KindWithContent, Status, Task};
 use time::macros::format_description;
 use time::OffsetDateTime;
@@ -41,6 +43,23 @@ impl IndexScheduler {
         }
         dump_keys.flush()?;
 
+        // the audit log lives next to the keys, in the auth environment
+        let auth_rtxn = self.scheduler.auth_env.read_txn()?;
+        let mut dump_audit_logs = dump.create_audit_logs()?;
+        if let Some(audit_logs) =
+            meilisearch_auth::open_audit_log_database(&self.scheduler.auth_env, &auth_rtxn)?
+        {
+            for ret in audit_logs.iter(&auth_rtxn)? {
+                if self.scheduler.must_stop_processing.get() {
+                    return Err(Error::AbortedTask);
+                }
+                let (_, log) = ret?;
+                dump_audit_logs.push_audit_log(&log)?;
+            }
+        }
+        dump_audit_logs.flush()?;
+        drop(auth_rtxn);
+
         let rtxn = self.env.read_txn()?;
 
         // 2. dump the tasks
@@ -87,7 +106,7 @@ impl IndexScheduler {
                     return Err(Error::AbortedTask);
                 }
                 if status == Status::Enqueued {
-                    let content_file = self.queue.file_store.get_update(content_file)?;
+                    let content_file = self.queue.file_store.get_update_reader(content_file)?;
 
                     for document in
                         serde_json::de::Deserializer::from_reader(content_file).into_iter()
@@ -150,105 +169,7 @@ impl IndexScheduler {
             ));
             count += 1;
 
-            let rtxn = index.read_txn()?;
-            let metadata = IndexMetadata {
-                uid: uid.to_owned(),
-                primary_key: index.primary_key(&rtxn)?.map(String::from),
-                created_at: index
-                    .created_at(&rtxn)
-                    .map_err(|e| Error::from_milli(e, Some(uid.to_string())))?,
-                updated_at: index
-                    .updated_at(&rtxn)
-                    .map_err(|e| Error::from_milli(e, Some(uid.to_string())))?,
-            };
-            let mut index_dumper = dump.create_index(uid, &metadata)?;
-
-            let fields_ids_map = index.fields_ids_map(&rtxn)?;
-            let all_fields: Vec<_> = fields_ids_map.iter().map(|(id, _)| id).collect();
-            let embedding_configs = index
-                .embedding_configs(&rtxn)
-                .map_err(|e| Error::from_milli(e, Some(uid.to_string())))?;
-
-            let nb_documents = index
-                .number_of_documents(&rtxn)
-                .map_err(|e| Error::from_milli(e, Some(uid.to_string())))?
-                as u32;
-            let (atomic, update_document_progress) = AtomicDocumentStep::new(nb_documents);
-            progress.update_progress(update_document_progress);
-            let documents = index
-                .all_documents(&rtxn)
-                .map_err(|e| Error::from_milli(e, Some(uid.to_string())))?;
-            // 4.1. Dump the documents
-            for ret in documents {
-                if self.scheduler.must_stop_processing.get() {
-                    return Err(Error::AbortedTask);
-                }
-
-                let (id, doc) = ret.map_err(|e| Error::from_milli(e, Some(uid.to_string())))?;
-
-                let mut document = milli::obkv_to_json(&all_fields, &fields_ids_map, doc)
-                    .map_err(|e| Error::from_milli(e, Some(uid.to_string())))?;
-
-                'inject_vectors: {
-                    let embeddings = index
-                        .embeddings(&rtxn, id)
-                        .map_err(|e| Error::from_milli(e, Some(uid.to_string())))?;
-
-                    if embeddings.is_empty() {
-                        break 'inject_vectors;
-                    }
-
-                    let vectors = document
-                        .entry(RESERVED_VECTORS_FIELD_NAME.to_owned())
-                        .or_insert(serde_json::Value::Object(Default::default()));
-
-                    let serde_json::Value::Object(vectors) = vectors else {
-                        let user_err =
-                            milli::Error::UserError(milli::UserError::InvalidVectorsMapType {
-                                document_id: {
-                                    if let Ok(Some(Ok(index))) = index
-                                        .external_id_of(&rtxn, std::iter::once(id))
-                                        .map(|it| it.into_iter().next())
-                                    {
-                                        index
-                                    } else {
-                                        format!("internal docid={id}")
-                                    }
-                                },
-                                value: vectors.clone(),
-                            });
-
-                        return Err(Error::from_milli(user_err, Some(uid.to_string())));
-                    };
-
-                    for (embedder_name, embeddings) in embeddings {
-                        let user_provided = embedding_configs
-                            .iter()
-                            .find(|conf| conf.name == embedder_name)
-                            .is_some_and(|conf| conf.user_provided.contains(id));
-                        let embeddings = ExplicitVectors {
-                            embeddings: Some(VectorOrArrayOfVectors::from_array_of_vectors(
-                                embeddings,
-                            )),
-                            regenerate: !user_provided,
-                        };
-                        vectors.insert(embedder_name, serde_json::to_value(embeddings).unwrap());
-                    }
-                }
-
-                index_dumper.push_document(&document)?;
-                atomic.fetch_add(1, Ordering::Relaxed);
-            }
-
-            // 4.2. Dump the settings
-            let settings = meilisearch_types::settings::settings(
-                index,
-                &rtxn,
-                meilisearch_types::settings::SecretPolicy::RevealSecrets,
-            )
-            .map_err(|e| Error::from_milli(e, Some(uid.to_string())))?;
-            index_dumper.settings(&settings)?;
-            Ok(())
+            self.dump_index(&progress, &dump, uid, index)
         })?;
 
         // 5. Dump experimental feature settings
@@ -257,22 +178,154 @@ impl IndexScheduler {
         dump.create_experimental_features(features)?;
         let network = self.network();
         dump.create_network(network)?;
+        let aliases = self.index_mapper.aliases(&rtxn)?;
+        dump.create_index_aliases(&aliases)?;
 
-        let dump_uid = started_at.format(format_description!(
-                    "[year repr:full][month repr:numerical][day padding:zero]-[hour padding:zero][minute padding:zero][second padding:zero][subsecond digits:3]"
-                )).unwrap();
+        let dump_uid = dump_uid_from_date(started_at);
 
         if self.scheduler.must_stop_processing.get() {
             return Err(Error::AbortedTask);
         }
         progress.update_progress(DumpCreationProgress::CompressTheDump);
         let path = self.scheduler.dumps_path.join(format!("{}.dump", dump_uid));
-        let file = File::create(path)?;
-        dump.persist_to(BufWriter::new(file))?;
+        self.persist_dump(dump, &path)?;
+
+        if let Some(s3) = &self.scheduler.s3 {
+            progress.update_progress(DumpCreationProgress::UploadTheDump);
+            s3.upload_dump(&format!("{}.dump", dump_uid), File::open(&path)?)?;
+        }
 
         // if we reached this step we can tell the scheduler we succeeded to dump ourselves.
         task.status = Status::Succeeded;
         task.details = Some(Details::Dump { dump_uid: Some(dump_uid) });
         Ok(vec![task])
     }
+
+    /// Compress the dump to `path`, encrypting it when an encryption key has been provided.
+    pub(super) fn persist_dump(&self, dump: dump::DumpWriter, path: &Path) -> Result<()> {
+        let file = BufWriter::new(File::create(path)?);
+        match &self.scheduler.encryption_key {
+            Some(key) => {
+                let mut writer = EncryptWriter::new(file, key)?;
+                dump.persist_to(&mut writer)?;
+                writer.finish()?;
+            }
+            None => dump.persist_to(file)?,
+        }
+        Ok(())
+    }
+
+    /// Write the documents, along with their embeddings, and the settings of an index into the dump.
+    pub(super) fn dump_index(
+        &self,
+        progress: &Progress,
+        dump: &dump::DumpWriter,
+        uid: &str,
+        index: &Index,
+    ) -> Result<()> {
+        let rtxn = index.read_txn()?;
+        let metadata = IndexMetadata {
+            uid: uid.to_owned(),
+            primary_key: index.primary_key(&rtxn)?.map(String::from),
+            created_at: index
+                .created_at(&rtxn)
+                .map_err(|e| Error::from_milli(e, Some(uid.to_string())))?,
+            updated_at: index
+                .updated_at(&rtxn)
+                .map_err(|e| Error::from_milli(e, Some(uid.to_string())))?,
+        };
+        let mut index_dumper = dump.create_index(uid, &metadata)?;
+
+        let fields_ids_map = index.fields_ids_map(&rtxn)?;
+        let all_fields: Vec<_> = fields_ids_map.iter().map(|(id, _)| id).collect();
+        let embedding_configs = index
+            .embedding_configs(&rtxn)
+            .map_err(|e| Error::from_milli(e, Some(uid.to_string())))?;
+
+        let nb_documents = index
+            .number_of_documents(&rtxn)
+            .map_err(|e| Error::from_milli(e, Some(uid.to_string())))?
+            as u32;
+        let (atomic, update_document_progress) = AtomicDocumentStep::new(nb_documents);
+        progress.update_progress(update_document_progress);
+        let documents =
+            index.all_documents(&rtxn).map_err(|e| Error::from_milli(e, Some(uid.to_string())))?;
+        // 1. Dump the documents
+        for ret in documents {
+            if self.scheduler.must_stop_processing.get() {
+                return Err(Error::AbortedTask);
+            }
+
+            let (id, doc) = ret.map_err(|e| Error::from_milli(e, Some(uid.to_string())))?;
+
+            let mut document = milli::obkv_to_json(&all_fields, &fields_ids_map, doc)
+                .map_err(|e| Error::from_milli(e, Some(uid.to_string())))?;
+
+            'inject_vectors: {
+                let embeddings = index
+                    .embeddings(&rtxn, id)
+                    .map_err(|e| Error::from_milli(e, Some(uid.to_string())))?;
+
+                if embeddings.is_empty() {
+                    break 'inject_vectors;
+                }
+
+                let vectors = document
+                    .entry(RESERVED_VECTORS_FIELD_NAME.to_owned())
+                    .or_insert(serde_json::Value::Object(Default::default()));
+
+                let serde_json::Value::Object(vectors) = vectors else {
+                    let user_err =
+                        milli::Error::UserError(milli::UserError::InvalidVectorsMapType {
+                            document_id: {
+                                if let Ok(Some(Ok(index))) = index
+                                    .external_id_of(&rtxn, std::iter::once(id))
+                                    .map(|it| it.into_iter().next())
+                                {
+                                    index
+                                } else {
+                                    format!("internal docid={id}")
+                                }
+                            },
+                            value: vectors.clone(),
+                        });
+
+                    return Err(Error::from_milli(user_err, Some(uid.to_string())));
+                };
+
+                for (embedder_name, embeddings) in embeddings {
+                    let user_provided = embedding_configs
+                        .iter()
+                        .find(|conf| conf.name == embedder_name)
+                        .is_some_and(|conf| conf.user_provided.contains(id));
+                    let embeddings = ExplicitVectors {
+                        embeddings: Some(VectorOrArrayOfVectors::from_array_of_vectors(embeddings)),
+                        regenerate: !user_provided,
+                    };
+                    vectors.insert(embedder_name, serde_json::to_value(embeddings).unwrap());
+                }
+            }
+
+            index_dumper.push_document(&document)?;
+            atomic.fetch_add(1, Ordering::Relaxed);
+        }
+
+        // 2. Dump the settings
+        let settings = meilisearch_types::settings::settings(
+            index,
+            &rtxn,
+            meilisearch_types::settings::SecretPolicy::RevealSecrets,
+        )
+        .map_err(|e| Error::from_milli(e, Some(uid.to_string())))?;
+        index_dumper.settings(&settings)?;
+        Ok(())
+    }
+}
+
+/// Generate the identifier of a dump, which is also the name of its file, from its creation date.
+pub(super) fn dump_uid_from_date(date: OffsetDateTime) -> String {
+    date.format(format_description!(
+        "[year repr:full][month repr:numerical][day padding:zero]-[hour padding:zero][minute padding:zero][second padding:zero][subsecond digits:3]"
+    ))
+    .unwrap()
 }
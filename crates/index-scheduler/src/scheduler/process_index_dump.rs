@@ -0,0 +1,208 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind};
+
+use file_store::encryption::decrypted_reader;
+use meilisearch_types::milli::documents::{DocumentsBatchBuilder, DocumentsBatchReader};
+use meilisearch_types::milli::progress::Progress;
+use meilisearch_types::milli::update::{
+    IndexDocuments, IndexDocumentsConfig, IndexDocumentsMethod, Settings as MilliSettings,
+};
+use meilisearch_types::milli::{self};
+use meilisearch_types::settings::apply_settings_to_builder;
+use meilisearch_types::tasks::{Details, Status, Task};
+use time::OffsetDateTime;
+
+use super::process_dump_creation::dump_uid_from_date;
+use crate::processing::{IndexExportProgress, IndexImportProgress};
+use crate::{Error, IndexScheduler, Result};
+
+impl IndexScheduler {
+    /// Write a dump containing only the given index, its documents, embeddings and settings.
+    ///
+    /// The dump doesn't contain any API key or task and can be imported in another
+    /// instance with an `indexImport` task.
+    pub(super) fn process_index_export(
+        &self,
+        progress: Progress,
+        index_uid: String,
+        mut task: Task,
+    ) -> Result<Vec<Task>> {
+        progress.update_progress(IndexExportProgress::DumpTheIndex);
+        let started_at = OffsetDateTime::now_utc();
+        let index = {
+            let rtxn = self.env.read_txn()?;
+            self.index_mapper.index(&rtxn, &index_uid)?
+        };
+
+        let dump = dump::DumpWriter::new(None)?;
+        // The keys and the task queue are empty but must be there for the dump to be readable.
+        dump.create_keys()?.flush()?;
+        dump.create_tasks_queue()?.flush()?;
+        dump.create_batches_queue()?.flush()?;
+
+        self.dump_index(&progress, &dump, &index_uid, &index)?;
+
+        if self.scheduler.must_stop_processing.get() {
+            return Err(Error::AbortedTask);
+        }
+        progress.update_progress(IndexExportProgress::CompressTheDump);
+        let dump_uid = format!("{index_uid}-{}", dump_uid_from_date(started_at));
+        let path = self.scheduler.dumps_path.join(format!("{}.dump", dump_uid));
+        self.persist_dump(dump, &path)?;
+
+        task.status = Status::Succeeded;
+        task.details = Some(Details::IndexExport { dump_uid: Some(dump_uid) });
+        Ok(vec![task])
+    }
+
+    /// Import the only index of a dump of the dumps directory into `index_uid`.
+    ///
+    /// The index is created if it doesn't exist. Otherwise the settings of the dump are
+    /// applied on top of the existing ones and its documents replace the ones sharing
+    /// the same id.
+    pub(super) fn process_index_import(
+        &self,
+        progress: Progress,
+        index_uid: String,
+        dump_uid: String,
+        mut task: Task,
+    ) -> Result<Vec<Task>> {
+        progress.update_progress(IndexImportProgress::OpenTheDump);
+        let path = self.scheduler.dumps_path.join(format!("{}.dump", dump_uid));
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Err(Error::DumpNotFound(dump_uid)),
+            Err(e) => return Err(e.into()),
+        };
+        let file = decrypted_reader(file, self.scheduler.encryption_key.as_ref())?;
+        let dump_reader = dump::DumpReader::open(file)?;
+        let mut indexes = dump_reader.indexes()?.collect::<std::result::Result<Vec<_>, _>>()?;
+        let mut index_reader = match indexes.pop() {
+            Some(index_reader) if indexes.is_empty() => index_reader,
+            _ => {
+                return Err(Error::IndexImportInvalidDump {
+                    dump_uid,
+                    nb_indexes: indexes.len() + 1,
+                })
+            }
+        };
+        let metadata = index_reader.metadata().clone();
+
+        // The dates of the dumped index are only used if the index doesn't already exist.
+        let wtxn = self.env.write_txn()?;
+        let index_existed = self.index_mapper.index_exists(&wtxn, &index_uid)?;
+        let date = Some((metadata.created_at, metadata.updated_at));
+        let index = self.index_mapper.create_index(wtxn, &index_uid, date)?;
+
+        let import = || -> Result<_> {
+            let indexer_config = self.index_mapper.indexer_config();
+            let must_stop_processing = self.scheduler.must_stop_processing.clone();
+            let mut index_wtxn = index.write_txn()?;
+
+            // 1. Import the primary key and the settings
+            progress.update_progress(IndexImportProgress::ImportTheSettings);
+            let settings = index_reader.settings()?;
+            let mut builder = MilliSettings::new(&mut index_wtxn, &index, indexer_config);
+            if let Some(primary_key) = metadata.primary_key {
+                builder.set_primary_key(primary_key);
+            }
+            apply_settings_to_builder(&settings, &mut builder);
+            builder
+                .execute(
+                    |indexing_step| tracing::debug!(update = ?indexing_step),
+                    || must_stop_processing.get(),
+                )
+                .map_err(|e| Error::from_milli(e, Some(index_uid.clone())))?;
+
+            // 2. Import the documents, the embeddings are part of their `_vectors` field
+            progress.update_progress(IndexImportProgress::ImportTheDocuments);
+            let file = tempfile::tempfile()?;
+            let mut builder = DocumentsBatchBuilder::new(BufWriter::new(file));
+            for document in index_reader.documents()? {
+                if self.scheduler.must_stop_processing.get() {
+                    return Err(Error::AbortedTask);
+                }
+                builder.append_json_object(&document?)?;
+            }
+            // This flush the content of the batch builder.
+            let file = builder.into_inner()?.into_inner().map_err(|e| e.into_error())?;
+            let reader = DocumentsBatchReader::from_reader(BufReader::new(file))
+                .map_err(|e| Error::from_milli(e.into(), Some(index_uid.clone())))?;
+
+            let embedder_configs = index
+                .embedding_configs(&index_wtxn)
+                .map_err(|e| Error::from_milli(e, Some(index_uid.clone())))?;
+            let embedders = self.embedders(index_uid.clone(), embedder_configs)?;
+
+            let builder = IndexDocuments::new(
+                &mut index_wtxn,
+                &index,
+                indexer_config,
+                IndexDocumentsConfig {
+                    update_method: IndexDocumentsMethod::ReplaceDocuments,
+                    ..Default::default()
+                },
+                |indexing_step| tracing::trace!(update = ?indexing_step),
+                || must_stop_processing.get(),
+            )
+            .map_err(|e| Error::from_milli(e, Some(index_uid.clone())))?
+            .with_embedders(embedders);
+            let (builder, user_result) = builder
+                .add_documents(reader)
+                .map_err(|e| Error::from_milli(e, Some(index_uid.clone())))?;
+            user_result.map_err(|e| {
+                Error::from_milli(milli::Error::UserError(e), Some(index_uid.clone()))
+            })?;
+            let result =
+                builder.execute().map_err(|e| Error::from_milli(e, Some(index_uid.clone())))?;
+            index_wtxn.commit()?;
+            Ok(result)
+        };
+
+        let result = match import() {
+            Ok(result) => result,
+            Err(e) => {
+                // Do not leave an empty index behind a failed or cancelled import.
+                if !index_existed {
+                    drop(index);
+                    let wtxn = self.env.write_txn()?;
+                    if let Err(error) = self.index_mapper.delete_index(wtxn, &index_uid) {
+                        tracing::error!(
+                            error = &error as &dyn std::error::Error,
+                            "Could not delete the index of the failed import"
+                        );
+                    }
+                }
+                return Err(e);
+            }
+        };
+
+        // if the import processed successfully, we're going to store the new
+        // stats of the index. Since the task has already been processed and
+        // this is a non-critical operation. If it fails, we should not fail
+        // the entire batch.
+        let res = || -> Result<()> {
+            let mut wtxn = self.env.write_txn()?;
+            let index_rtxn = index.read_txn()?;
+            let stats = crate::index_mapper::IndexStats::new(&index, &index_rtxn)
+                .map_err(|e| Error::from_milli(e, Some(index_uid.clone())))?;
+            self.index_mapper.store_stats_of(&mut wtxn, &index_uid, &stats)?;
+            wtxn.commit()?;
+            Ok(())
+        }();
+
+        if let Err(e) = res {
+            tracing::error!(
+                error = &e as &dyn std::error::Error,
+                "Could not write the stats of the index"
+            );
+        }
+
+        task.status = Status::Succeeded;
+        task.details = Some(Details::IndexImport {
+            dump_uid,
+            indexed_documents: Some(result.indexed_documents),
+        });
+        Ok(vec![task])
+    }
+}
@@ -71,8 +71,7 @@ impl IndexScheduler {
                     match operation {
                         DocumentOperation::Replace(content_uuid)
                         | DocumentOperation::Update(content_uuid) => {
-                            let content_file = self.queue.file_store.get_update(*content_uuid)?;
-                            let mmap = unsafe { memmap2::Mmap::map(&content_file)? };
+                            let mmap = self.queue.file_store.get_update_mmap(*content_uuid)?;
                             content_files.push(mmap);
                         }
                         _ => (),
@@ -4,9 +4,12 @@ use std::sync::atomic::Ordering;
 
 use meilisearch_types::heed::CompactionOption;
 use meilisearch_types::milli::progress::{Progress, VariableNameStep};
+use meilisearch_types::snapshot::{IndexSnapshot, SnapshotManifest, SNAPSHOT_MANIFEST_FILE_NAME};
 use meilisearch_types::tasks::{Status, Task};
 use meilisearch_types::{compression, VERSION_FILE_NAME};
+use time::OffsetDateTime;
 
+use super::process_dump_creation::dump_uid_from_date;
 use crate::processing::{AtomicUpdateFileStep, SnapshotCreationProgress};
 use crate::{Error, IndexScheduler, Result};
 
@@ -18,9 +21,31 @@ impl IndexScheduler {
     ) -> Result<Vec<Task>> {
         progress.update_progress(SnapshotCreationProgress::StartTheSnapshotCreation);
 
-        fs::create_dir_all(&self.scheduler.snapshots_path)?;
+        let snapshots_path = &self.scheduler.snapshots_path;
+        fs::create_dir_all(snapshots_path)?;
         let temp_snapshot_dir = tempfile::tempdir()?;
 
+        // Find the original name of the database
+        // TODO find a better way to get this path
+        let mut base_path = self.env.path().to_owned();
+        base_path.pop();
+        let db_name = base_path.file_name().and_then(OsStr::to_str).unwrap_or("data.ms");
+
+        // When the snapshots are incremental, each snapshot gets a unique name and only
+        // contains the indexes updated since the previous one. A copy of the manifest of
+        // the latest snapshot is kept next to it to know which indexes changed.
+        // The unique name is also the one used when uploading to the object storage.
+        let manifest_path = snapshots_path.join(format!("{db_name}.manifest.json"));
+        let snapshot_name =
+            format!("{db_name}-{}.snapshot", dump_uid_from_date(OffsetDateTime::now_utc()));
+        let (previous_manifest, mut manifest) = if self.scheduler.incremental_snapshots {
+            // If the previous manifest can't be read we fall back on a full snapshot.
+            let previous = SnapshotManifest::from_path(&manifest_path).unwrap_or_default();
+            (previous, Some(SnapshotManifest::default()))
+        } else {
+            (SnapshotManifest::default(), None)
+        };
+
         // 1. Snapshot the version file.
         let dst = temp_snapshot_dir.path().join(VERSION_FILE_NAME);
         fs::copy(&self.scheduler.version_file_path, dst)?;
@@ -77,6 +102,30 @@ impl IndexScheduler {
                 name, i as u32, nb_indexes,
             ));
             let index = self.index_mapper.index(&rtxn, name)?;
+
+            if let Some(manifest) = manifest.as_mut() {
+                let index_rtxn = index.read_txn()?;
+                let updated_at = index
+                    .updated_at(&index_rtxn)
+                    .map_err(|e| Error::from_milli(e, Some(name.to_string())))?;
+                drop(index_rtxn);
+
+                match previous_manifest.indexes.get(&uuid) {
+                    // The index didn't change and the snapshot containing it is still there
+                    Some(previous)
+                        if previous.updated_at == updated_at
+                            && snapshots_path.join(&previous.snapshot).exists() =>
+                    {
+                        manifest.indexes.insert(uuid, previous.clone());
+                        continue;
+                    }
+                    _ => {
+                        let snapshot = snapshot_name.clone();
+                        manifest.indexes.insert(uuid, IndexSnapshot { updated_at, snapshot });
+                    }
+                }
+            }
+
             let dst = temp_snapshot_dir.path().join("indexes").join(uuid.to_string());
             fs::create_dir_all(&dst)?;
             index
@@ -92,21 +141,27 @@ impl IndexScheduler {
         fs::create_dir_all(&dst)?;
         self.scheduler.auth_env.copy_to_path(dst.join("data.mdb"), CompactionOption::Disabled)?;
 
+        if let Some(manifest) = &manifest {
+            manifest.persist(temp_snapshot_dir.path().join(SNAPSHOT_MANIFEST_FILE_NAME))?;
+        }
+
         // 5. Copy and tarball the flat snapshot
         progress.update_progress(SnapshotCreationProgress::CreateTheTarball);
-        // 5.1 Find the original name of the database
-        // TODO find a better way to get this path
-        let mut base_path = self.env.path().to_owned();
-        base_path.pop();
-        let db_name = base_path.file_name().and_then(OsStr::to_str).unwrap_or("data.ms");
-
-        // 5.2 Tarball the content of the snapshot in a tempfile with a .snapshot extension
-        let snapshot_path = self.scheduler.snapshots_path.join(format!("{}.snapshot", db_name));
-        let temp_snapshot_file = tempfile::NamedTempFile::new_in(&self.scheduler.snapshots_path)?;
-        compression::to_tar_gz(temp_snapshot_dir.path(), temp_snapshot_file.path())?;
-        let file = temp_snapshot_file.persist(snapshot_path)?;
-
-        // 5.3 Change the permission to make the snapshot readonly
+        // 5.1 Tarball the content of the snapshot in a tempfile with a .snapshot extension
+        let latest_snapshot_path = snapshots_path.join(format!("{}.snapshot", db_name));
+        let snapshot_path = match manifest {
+            Some(_) => snapshots_path.join(&snapshot_name),
+            None => latest_snapshot_path.clone(),
+        };
+        let temp_snapshot_file = tempfile::NamedTempFile::new_in(snapshots_path)?;
+        compression::to_tar_gz(
+            temp_snapshot_dir.path(),
+            temp_snapshot_file.path(),
+            self.scheduler.encryption_key.as_ref(),
+        )?;
+        let file = temp_snapshot_file.persist(&snapshot_path)?;
+
+        // 5.2 Change the permission to make the snapshot readonly
         let mut permissions = file.metadata()?.permissions();
         permissions.set_readonly(true);
         #[cfg(unix)]
@@ -119,6 +174,41 @@ impl IndexScheduler {
 
         file.set_permissions(permissions)?;
 
+        // 5.3 Expose the latest incremental snapshot under the usual name and get
+        //     rid of the snapshots that aren't part of its chain anymore.
+        if let Some(manifest) = &manifest {
+            match fs::remove_file(&latest_snapshot_path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => (),
+            }
+            if fs::hard_link(&snapshot_path, &latest_snapshot_path).is_err() {
+                fs::copy(&snapshot_path, &latest_snapshot_path)?;
+            }
+            manifest.persist(&manifest_path)?;
+
+            let prefix = format!("{db_name}-");
+            for entry in fs::read_dir(snapshots_path)? {
+                let file_name = entry?.file_name();
+                let Some(file_name) = file_name.to_str() else { continue };
+                let part_of_chain = file_name == snapshot_name
+                    || manifest.indexes.values().any(|index| index.snapshot == file_name);
+                if file_name.starts_with(&prefix)
+                    && file_name.ends_with(".snapshot")
+                    && !part_of_chain
+                {
+                    if let Err(e) = fs::remove_file(snapshots_path.join(file_name)) {
+                        tracing::warn!("Could not delete the outdated snapshot `{file_name}`: {e}");
+                    }
+                }
+            }
+        }
+
+        // 6. Upload the snapshot to the object storage
+        if let Some(s3) = &self.scheduler.s3 {
+            progress.update_progress(SnapshotCreationProgress::UploadTheSnapshot);
+            s3.upload_snapshot(&snapshot_name, fs::File::open(&snapshot_path)?)?;
+        }
+
         for task in &mut tasks {
             task.status = Status::Succeeded;
         }
@@ -8,7 +8,7 @@ use meilisearch_types::heed::{Database, RoTxn, RwTxn};
 use meilisearch_types::milli::CboRoaringBitmapCodec;
 use meilisearch_types::task_view::DetailsView;
 use meilisearch_types::tasks::{
-    BatchStopReason, Details, IndexSwap, Kind, KindWithContent, Status,
+    BatchStopReason, Details, IndexAlias, IndexSwap, Kind, KindWithContent, Status,
 };
 use roaring::RoaringBitmap;
 use time::OffsetDateTime;
@@ -76,6 +76,9 @@ impl ProcessingBatch {
             if let Some(index_uid) = task.index_uid() {
                 *self.stats.index_uids.entry(index_uid.to_string()).or_default() += 1;
             }
+            if let Some(key_uid) = task.key_uid {
+                *self.stats.key_uids.entry(key_uid.to_string()).or_default() += 1;
+            }
             if let Some(ref details) = task.details {
                 self.details.accumulate(&DetailsView::from(details.clone()));
             }
@@ -138,6 +141,9 @@ impl ProcessingBatch {
         if let Some(index_uid) = task.index_uid() {
             *self.stats.index_uids.entry(index_uid.to_string()).or_default() += 1;
         }
+        if let Some(key_uid) = task.key_uid {
+            *self.stats.key_uids.entry(key_uid.to_string()).or_default() += 1;
+        }
     }
 
     pub fn to_batch(&self) -> Batch {
@@ -260,6 +266,12 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
         K::IndexDeletion { index_uid } => index_uids.push(index_uid),
         K::IndexCreation { index_uid, .. } => index_uids.push(index_uid),
         K::IndexUpdate { index_uid, .. } => index_uids.push(index_uid),
+        K::IndexExport { index_uid } => index_uids.push(index_uid),
+        K::IndexImport { index_uid, .. } => index_uids.push(index_uid),
+        K::IndexClone { index_uid, new_index_uid } => {
+            index_uids.push(index_uid);
+            index_uids.push(new_index_uid);
+        }
         K::IndexSwap { swaps } => {
             for IndexSwap { indexes: (lhs, rhs) } in swaps.iter_mut() {
                 if lhs == swap.0 || lhs == swap.1 {
@@ -270,21 +282,39 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
                 }
             }
         }
+        K::IndexAliasUpdate { aliases } => {
+            for IndexAlias { alias: _, index_uid } in aliases.iter_mut() {
+                if let Some(index_uid) = index_uid {
+                    index_uids.push(index_uid);
+                }
+            }
+        }
         K::TaskCancelation { .. }
         | K::TaskDeletion { .. }
         | K::DumpCreation { .. }
         | K::UpgradeDatabase { .. }
         | K::SnapshotCreation => (),
     };
-    if let Some(Details::IndexSwap { swaps }) = &mut task.details {
-        for IndexSwap { indexes: (lhs, rhs) } in swaps.iter_mut() {
-            if lhs == swap.0 || lhs == swap.1 {
-                index_uids.push(lhs);
+    match &mut task.details {
+        Some(Details::IndexSwap { swaps }) => {
+            for IndexSwap { indexes: (lhs, rhs) } in swaps.iter_mut() {
+                if lhs == swap.0 || lhs == swap.1 {
+                    index_uids.push(lhs);
+                }
+                if rhs == swap.0 || rhs == swap.1 {
+                    index_uids.push(rhs);
+                }
             }
-            if rhs == swap.0 || rhs == swap.1 {
-                index_uids.push(rhs);
+        }
+        Some(Details::IndexAliasUpdate { aliases }) => {
+            for IndexAlias { alias: _, index_uid } in aliases.iter_mut() {
+                if let Some(index_uid) = index_uid {
+                    index_uids.push(index_uid);
+                }
             }
         }
+        Some(Details::IndexClone { new_index_uid }) => index_uids.push(new_index_uid),
+        _ => (),
     }
     for index_uid in index_uids {
         if index_uid == swap.0 {
@@ -341,6 +371,21 @@ pub(crate) fn check_index_swap_validity(task: &Task) -> Result<()> {
     Ok(())
 }
 
+pub(crate) fn check_index_alias_validity(task: &Task) -> Result<()> {
+    let aliases = if let KindWithContent::IndexAliasUpdate { aliases } = &task.kind {
+        aliases
+    } else {
+        return Ok(());
+    };
+    let mut all_aliases = HashSet::new();
+    for IndexAlias { alias, index_uid: _ } in aliases {
+        if !all_aliases.insert(alias) {
+            return Err(Error::IndexAliasDuplicateFound(alias.clone()));
+        }
+    }
+    Ok(())
+}
+
 /// Clamp the provided value to be a multiple of system page size.
 pub fn clamp_to_page_size(size: usize) -> usize {
     size / page_size::get() * page_size::get()
@@ -366,6 +411,7 @@ impl crate::IndexScheduler {
                 details,
                 status,
                 kind,
+                key_uid,
             } = task;
             assert_eq!(uid, task.uid);
             if task.status != Status::Enqueued {
@@ -442,6 +488,9 @@ impl crate::IndexScheduler {
                     _ => panic!(),
                 }
             }
+            if let Some(key_uid) = key_uid {
+                assert!(self.queue.tasks.key_tasks(&rtxn, key_uid).unwrap().contains(uid));
+            }
             if let Some(details) = details {
                 match details {
                     Details::IndexSwap { swaps: sw1 } => {
@@ -449,6 +498,16 @@ impl crate::IndexScheduler {
                             assert_eq!(&sw1, sw2);
                         }
                     }
+                    Details::IndexAliasUpdate { aliases: al1 } => {
+                        if let KindWithContent::IndexAliasUpdate { aliases: al2 } = &kind {
+                            assert_eq!(&al1, al2);
+                        }
+                    }
+                    Details::IndexClone { new_index_uid: uid1 } => {
+                        if let KindWithContent::IndexClone { new_index_uid: uid2, .. } = &kind {
+                            assert_eq!(&uid1, uid2);
+                        }
+                    }
                     Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
                         assert_eq!(kind.as_kind(), Kind::DocumentAdditionOrUpdate);
                         match indexed_documents {
@@ -600,6 +659,26 @@ impl crate::IndexScheduler {
                     Details::Dump { dump_uid: _ } => {
                         assert_eq!(kind.as_kind(), Kind::DumpCreation);
                     }
+                    Details::IndexExport { dump_uid } => {
+                        assert_eq!(kind.as_kind(), Kind::IndexExport);
+                        if dump_uid.is_some() {
+                            assert_eq!(status, Status::Succeeded);
+                        }
+                    }
+                    Details::IndexImport { dump_uid: uid1, indexed_documents } => {
+                        if let KindWithContent::IndexImport { dump_uid: uid2, .. } = &kind {
+                            assert_eq!(&uid1, uid2);
+                        } else {
+                            panic!()
+                        }
+                        match status {
+                            Status::Enqueued | Status::Processing => (),
+                            Status::Succeeded => assert!(indexed_documents.is_some()),
+                            Status::Failed | Status::Canceled => {
+                                assert_eq!(indexed_documents, Some(0))
+                            }
+                        }
+                    }
                     Details::UpgradeDatabase { from: _, to: _ } => {
                         assert_eq!(kind.as_kind(), Kind::UpgradeDatabase);
                     }
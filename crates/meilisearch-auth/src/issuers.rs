@@ -0,0 +1,309 @@
+//! External identity providers allowed to mint tenant tokens.
+//!
+//! A trusted issuer signs its tokens with an asymmetric key, so Meilisearch only needs its public
+//! keys to validate them. The permissions of the tokens are bounded by an existing API key
+//! designated by its uid, exactly like the tenant tokens signed with an API key.
+
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm, PublicKeyUse,
+};
+use jsonwebtoken::{Algorithm, DecodingKey};
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// The JWKS of an issuer is fetched again when a token uses an unknown key, but not more often
+/// than this, so that invalid tokens can't be used to flood the identity provider.
+const JWKS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+const JWKS_FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+const RSA_ALGORITHMS: &[Algorithm] = &[
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum TrustedIssuersError {
+    #[error("Could not read the trusted issuers file `{}`: {error}", .path.display())]
+    Read { path: PathBuf, error: std::io::Error },
+    #[error("Could not parse the trusted issuers file `{}`: {error}", .path.display())]
+    Parse { path: PathBuf, error: serde_json::Error },
+    #[error("Trusted issuer `{issuer}` must define a `publicKey` or a `jwks`.")]
+    MissingKey { issuer: String },
+    #[error("Trusted issuer `{issuer}` is defined twice.")]
+    DuplicateIssuer { issuer: String },
+    #[error("Trusted issuer `{issuer}`: `searchRulesClaim` must be a JSON pointer starting with `/`, got `{claim}`.")]
+    InvalidSearchRulesClaim { issuer: String, claim: String },
+    #[error("Trusted issuer `{issuer}`: could not read `{}`: {error}", .path.display())]
+    ReadKey { issuer: String, path: PathBuf, error: std::io::Error },
+    #[error("Trusted issuer `{issuer}`: invalid public key: {error}")]
+    InvalidKey { issuer: String, error: jsonwebtoken::errors::Error },
+    #[error("Trusted issuer `{issuer}`: invalid JWKS: {error}")]
+    InvalidJwks { issuer: String, error: serde_json::Error },
+    #[error("Trusted issuer `{issuer}`: could not fetch the JWKS from `{url}`: {error}")]
+    FetchJwks { issuer: String, url: String, error: String },
+}
+
+/// An identity provider as described in the trusted issuers file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TrustedIssuerConfig {
+    /// Value of the `iss` claim of the tokens minted by this issuer.
+    pub issuer: String,
+    /// The API key bounding the actions and indexes the tokens of this issuer can access.
+    pub api_key_uid: Uuid,
+    /// Path to a PEM encoded RSA, EC, or Ed25519 public key.
+    #[serde(default)]
+    pub public_key: Option<PathBuf>,
+    /// Path or `http(s)://` URL of the JSON Web Key Set of the issuer.
+    #[serde(default)]
+    pub jwks: Option<String>,
+    /// When defined, the `aud` claim of the tokens must contain this value.
+    #[serde(default)]
+    pub audience: Option<String>,
+    /// JSON pointer to the claim containing the search rules of the token.
+    #[serde(default = "default_search_rules_claim")]
+    pub search_rules_claim: String,
+}
+
+fn default_search_rules_claim() -> String {
+    String::from("/searchRules")
+}
+
+#[derive(Clone)]
+struct IssuerKey {
+    kid: Option<String>,
+    key: DecodingKey,
+    algorithms: Vec<Algorithm>,
+}
+
+#[derive(Default)]
+struct JwksCache {
+    keys: Vec<IssuerKey>,
+    fetched_at: Option<Instant>,
+}
+
+pub struct TrustedIssuer {
+    config: TrustedIssuerConfig,
+    /// The keys known at startup: the public key and the JWKS file.
+    static_keys: Vec<IssuerKey>,
+    /// The keys fetched from the JWKS URL.
+    remote_keys: RwLock<JwksCache>,
+}
+
+impl TrustedIssuer {
+    fn new(config: TrustedIssuerConfig) -> Result<Self, TrustedIssuersError> {
+        let issuer = &config.issuer;
+        if config.public_key.is_none() && config.jwks.is_none() {
+            return Err(TrustedIssuersError::MissingKey { issuer: issuer.clone() });
+        }
+        if !config.search_rules_claim.starts_with('/') {
+            return Err(TrustedIssuersError::InvalidSearchRulesClaim {
+                issuer: issuer.clone(),
+                claim: config.search_rules_claim.clone(),
+            });
+        }
+
+        let mut static_keys = Vec::new();
+        if let Some(path) = &config.public_key {
+            let pem = read_key_file(issuer, path)?;
+            static_keys.push(key_from_pem(&pem).map_err(|error| {
+                TrustedIssuersError::InvalidKey { issuer: issuer.clone(), error }
+            })?);
+        }
+        match config.jwks.as_deref() {
+            Some(url) if is_url(url) => (),
+            Some(path) => {
+                let jwks = read_key_file(issuer, Path::new(path))?;
+                let jwks = serde_json::from_slice(&jwks).map_err(|error| {
+                    TrustedIssuersError::InvalidJwks { issuer: issuer.clone(), error }
+                })?;
+                static_keys.extend(keys_from_jwks(&jwks));
+            }
+            None => (),
+        }
+
+        Ok(Self { config, static_keys, remote_keys: RwLock::default() })
+    }
+
+    pub fn issuer(&self) -> &str {
+        &self.config.issuer
+    }
+
+    pub fn api_key_uid(&self) -> Uuid {
+        self.config.api_key_uid
+    }
+
+    pub fn audience(&self) -> Option<&str> {
+        self.config.audience.as_deref()
+    }
+
+    pub fn search_rules_claim(&self) -> &str {
+        &self.config.search_rules_claim
+    }
+
+    /// Returns the key matching the `kid` and `alg` of the header of a token.
+    ///
+    /// Fetches the JWKS of the issuer when the key is unknown and it hasn't been fetched recently.
+    pub fn decoding_key(
+        &self,
+        kid: Option<&str>,
+        algorithm: Algorithm,
+    ) -> Result<Option<DecodingKey>, TrustedIssuersError> {
+        if let Some(key) = find_key(&self.static_keys, kid, algorithm) {
+            return Ok(Some(key));
+        }
+        let Some(url) = self.config.jwks.as_deref().filter(|jwks| is_url(jwks)) else {
+            return Ok(None);
+        };
+
+        {
+            let cache = self.remote_keys.read().unwrap();
+            if let Some(key) = find_key(&cache.keys, kid, algorithm) {
+                return Ok(Some(key));
+            }
+            if cache.fetched_at.is_some_and(|at| at.elapsed() < JWKS_REFRESH_INTERVAL) {
+                return Ok(None);
+            }
+        }
+
+        let mut cache = self.remote_keys.write().unwrap();
+        // another thread may have refreshed the keys while we were waiting for the lock
+        if cache.fetched_at.is_none_or(|at| at.elapsed() >= JWKS_REFRESH_INTERVAL) {
+            cache.fetched_at = Some(Instant::now());
+            cache.keys = keys_from_jwks(&self.fetch_jwks(url)?);
+        }
+        Ok(find_key(&cache.keys, kid, algorithm))
+    }
+
+    fn fetch_jwks(&self, url: &str) -> Result<JwkSet, TrustedIssuersError> {
+        let fetch_error = |error: String| TrustedIssuersError::FetchJwks {
+            issuer: self.config.issuer.clone(),
+            url: url.to_string(),
+            error,
+        };
+        let response = ureq::get(url)
+            .timeout(JWKS_FETCH_TIMEOUT)
+            .call()
+            .map_err(|error| fetch_error(error.to_string()))?;
+        serde_json::from_reader(response.into_reader())
+            .map_err(|error| fetch_error(error.to_string()))
+    }
+}
+
+/// The identity providers allowed to mint tenant tokens, indexed by their `iss` claim.
+#[derive(Default)]
+pub struct TrustedIssuers {
+    issuers: Vec<TrustedIssuer>,
+}
+
+impl TrustedIssuers {
+    /// Reads the trusted issuers file, a JSON array of [`TrustedIssuerConfig`].
+    pub fn from_path(path: &Path) -> Result<Self, TrustedIssuersError> {
+        let content = std::fs::read(path)
+            .map_err(|error| TrustedIssuersError::Read { path: path.to_owned(), error })?;
+        let configs: Vec<TrustedIssuerConfig> = serde_json::from_slice(&content)
+            .map_err(|error| TrustedIssuersError::Parse { path: path.to_owned(), error })?;
+        Self::new(configs)
+    }
+
+    pub fn new(configs: Vec<TrustedIssuerConfig>) -> Result<Self, TrustedIssuersError> {
+        let mut issuers: Vec<TrustedIssuer> = Vec::with_capacity(configs.len());
+        for config in configs {
+            if issuers.iter().any(|issuer| issuer.issuer() == config.issuer) {
+                return Err(TrustedIssuersError::DuplicateIssuer { issuer: config.issuer });
+            }
+            issuers.push(TrustedIssuer::new(config)?);
+        }
+        Ok(Self { issuers })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.issuers.is_empty()
+    }
+
+    pub fn get(&self, issuer: &str) -> Option<&TrustedIssuer> {
+        self.issuers.iter().find(|trusted| trusted.issuer() == issuer)
+    }
+}
+
+fn is_url(jwks: &str) -> bool {
+    jwks.starts_with("http://") || jwks.starts_with("https://")
+}
+
+fn read_key_file(issuer: &str, path: &Path) -> Result<Vec<u8>, TrustedIssuersError> {
+    std::fs::read(path).map_err(|error| TrustedIssuersError::ReadKey {
+        issuer: issuer.to_string(),
+        path: path.to_owned(),
+        error,
+    })
+}
+
+fn find_key(keys: &[IssuerKey], kid: Option<&str>, algorithm: Algorithm) -> Option<DecodingKey> {
+    keys.iter()
+        .filter(|key| key.algorithms.contains(&algorithm))
+        // a token without `kid` can use any key, while a key without `kid` matches any token
+        .find(|key| kid.is_none() || key.kid.is_none() || key.kid.as_deref() == kid)
+        .map(|key| key.key.clone())
+}
+
+fn key_from_pem(pem: &[u8]) -> jsonwebtoken::errors::Result<IssuerKey> {
+    let (key, algorithms) = match DecodingKey::from_rsa_pem(pem) {
+        Ok(key) => (key, RSA_ALGORITHMS.to_vec()),
+        Err(_) => match DecodingKey::from_ec_pem(pem) {
+            Ok(key) => (key, vec![Algorithm::ES256, Algorithm::ES384]),
+            Err(_) => (DecodingKey::from_ed_pem(pem)?, vec![Algorithm::EdDSA]),
+        },
+    };
+    Ok(IssuerKey { kid: None, key, algorithms })
+}
+
+/// Keeps the asymmetric signing keys of the set, and ignores the others.
+fn keys_from_jwks(jwks: &JwkSet) -> Vec<IssuerKey> {
+    jwks.keys.iter().filter_map(key_from_jwk).collect()
+}
+
+fn key_from_jwk(jwk: &Jwk) -> Option<IssuerKey> {
+    if jwk.common.public_key_use == Some(PublicKeyUse::Encryption) {
+        return None;
+    }
+    let mut algorithms = match &jwk.algorithm {
+        AlgorithmParameters::RSA(_) => RSA_ALGORITHMS.to_vec(),
+        AlgorithmParameters::EllipticCurve(params) => match params.curve {
+            EllipticCurve::P256 => vec![Algorithm::ES256],
+            EllipticCurve::P384 => vec![Algorithm::ES384],
+            _ => return None,
+        },
+        AlgorithmParameters::OctetKeyPair(_) => vec![Algorithm::EdDSA],
+        // symmetric keys would let anyone knowing them mint tokens
+        AlgorithmParameters::OctetKey(_) => return None,
+    };
+    if let Some(key_algorithm) = &jwk.common.key_algorithm {
+        let algorithm = signing_algorithm(key_algorithm)?;
+        algorithms.retain(|allowed| *allowed == algorithm);
+    }
+    let key = DecodingKey::from_jwk(jwk).ok()?;
+    Some(IssuerKey { kid: jwk.common.key_id.clone(), key, algorithms })
+}
+
+fn signing_algorithm(key_algorithm: &KeyAlgorithm) -> Option<Algorithm> {
+    match key_algorithm {
+        KeyAlgorithm::ES256 => Some(Algorithm::ES256),
+        KeyAlgorithm::ES384 => Some(Algorithm::ES384),
+        KeyAlgorithm::RS256 => Some(Algorithm::RS256),
+        KeyAlgorithm::RS384 => Some(Algorithm::RS384),
+        KeyAlgorithm::RS512 => Some(Algorithm::RS512),
+        KeyAlgorithm::PS256 => Some(Algorithm::PS256),
+        KeyAlgorithm::PS384 => Some(Algorithm::PS384),
+        KeyAlgorithm::PS512 => Some(Algorithm::PS512),
+        KeyAlgorithm::EdDSA => Some(Algorithm::EdDSA),
+        _ => None,
+    }
+}
@@ -1,18 +1,30 @@
 mod dump;
 pub mod error;
+pub mod issuers;
+pub mod network;
+pub mod rate_limit;
 mod store;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
 
 use error::{AuthControllerError, Result};
+use issuers::{TrustedIssuer, TrustedIssuers};
 use maplit::hashset;
+use meilisearch_types::audit_log::{AuditLog, AuditLogId};
 use meilisearch_types::heed::{Env, WithoutTls};
 use meilisearch_types::index_uid_pattern::IndexUidPattern;
-use meilisearch_types::keys::{Action, CreateApiKey, Key, PatchApiKey};
+use meilisearch_types::keys::{
+    Action, CreateApiKey, Key, PatchApiKey, PreviousSecret, RateLimits, RotateApiKey,
+};
+use meilisearch_types::milli::is_faceted_by;
 use meilisearch_types::milli::update::Setting;
+use network::{NetworkRestrictions, TrustedProxies};
+use rate_limit::{KeyUsage, PayloadQuota, RateLimitExceeded, RateLimiter, SearchPermit};
 use serde::{Deserialize, Serialize};
-pub use store::open_auth_store_env;
-use store::{generate_key_as_hexa, HeedAuthStore};
+use store::{generate_key_as_hexa, generate_salt, HeedAuthStore};
+pub use store::{open_audit_log_database, open_auth_store_env};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
@@ -20,6 +32,20 @@ use uuid::Uuid;
 pub struct AuthController {
     store: HeedAuthStore,
     master_key: Option<String>,
+    trusted_issuers: Arc<TrustedIssuers>,
+    trusted_proxies: Arc<TrustedProxies>,
+    rate_limiter: Arc<RateLimiter>,
+    audit_log_queue: Arc<Mutex<AuditLogQueue>>,
+    audit_log_flush: Arc<Mutex<()>>,
+    audit_log_max_entries: Option<u64>,
+}
+
+/// The entries of the audit log waiting to be written.
+#[derive(Default)]
+struct AuditLogQueue {
+    logs: Vec<AuditLog>,
+    /// Whether a flush of the queued entries is already planned.
+    flush_planned: bool,
 }
 
 impl AuthController {
@@ -30,7 +56,45 @@ impl AuthController {
             generate_default_keys(&store)?;
         }
 
-        Ok(Self { store, master_key: master_key.clone() })
+        Ok(Self {
+            store,
+            master_key: master_key.clone(),
+            trusted_issuers: Default::default(),
+            trusted_proxies: Default::default(),
+            rate_limiter: Default::default(),
+            audit_log_queue: Default::default(),
+            audit_log_flush: Default::default(),
+            audit_log_max_entries: None,
+        })
+    }
+
+    /// Allow the tenant tokens signed by these external identity providers.
+    pub fn set_trusted_issuers(&mut self, trusted_issuers: TrustedIssuers) {
+        self.trusted_issuers = Arc::new(trusted_issuers);
+    }
+
+    pub fn has_trusted_issuers(&self) -> bool {
+        !self.trusted_issuers.is_empty()
+    }
+
+    pub fn trusted_issuer(&self, issuer: &str) -> Option<&TrustedIssuer> {
+        self.trusted_issuers.get(issuer)
+    }
+
+    /// Deletes the oldest entries of the audit log beyond the `max_entries` last ones.
+    /// By default, the audit log is append-only and keeps all its entries.
+    pub fn set_audit_log_max_entries(&mut self, max_entries: Option<u64>) {
+        self.audit_log_max_entries = max_entries;
+    }
+
+    /// Trust the `X-Forwarded-For` headers set by these reverse proxies.
+    pub fn set_trusted_proxies(&mut self, trusted_proxies: TrustedProxies) {
+        self.trusted_proxies = Arc::new(trusted_proxies);
+    }
+
+    /// Returns the address of the client of a request received from `peer`, see [`TrustedProxies::client_ip`].
+    pub fn client_ip(&self, peer: Option<IpAddr>, forwarded_for: Option<&str>) -> Option<IpAddr> {
+        self.trusted_proxies.client_ip(peer, forwarded_for)
     }
 
     /// Return `Ok(())` if the auth controller is able to access one of its database.
@@ -66,6 +130,22 @@ impl AuthController {
             Setting::NotSet => (),
             name => key.name = name.set(),
         };
+        match patch.rate_limits {
+            Setting::NotSet => (),
+            rate_limits => {
+                key.rate_limits = rate_limits.set();
+                // the bucket is refilled according to the new limits
+                self.rate_limiter.forget(uid);
+            }
+        };
+        match patch.allowed_ips {
+            Setting::NotSet => (),
+            allowed_ips => key.allowed_ips = allowed_ips.set(),
+        };
+        match patch.allowed_origins {
+            Setting::NotSet => (),
+            allowed_origins => key.allowed_origins = allowed_origins.set(),
+        };
         key.updated_at = OffsetDateTime::now_utc();
         self.store.put_api_key(key)
     }
@@ -79,7 +159,11 @@ impl AuthController {
     pub fn get_optional_uid_from_encoded_key(&self, encoded_key: &[u8]) -> Result<Option<Uuid>> {
         match &self.master_key {
             Some(master_key) => {
-                self.store.get_uid_from_encoded_key(encoded_key, master_key.as_bytes())
+                match self.store.get_uid_from_encoded_key(encoded_key, master_key.as_bytes())? {
+                    Some(uid) => Ok(Some(uid)),
+                    // the key may still be derived from the previous master key.
+                    None => self.store.get_uid_from_previous_key_value(encoded_key),
+                }
             }
             None => Ok(None),
         }
@@ -101,7 +185,52 @@ impl AuthController {
 
         let allow_index_creation = self.is_key_authorized(uid, Action::IndexesAdd, None)?;
 
-        Ok(AuthFilter { search_rules, key_authorized_indexes, allow_index_creation })
+        Ok(AuthFilter {
+            search_rules,
+            key_authorized_indexes,
+            allow_index_creation,
+            key_uid: Some(uid),
+            rate_limits: key.rate_limits,
+            network_restrictions: NetworkRestrictions::new(key.allowed_ips, key.allowed_origins),
+            own_tasks_only: false,
+        })
+    }
+
+    /// Counts a request authenticated by the filters against the rate limits of its key.
+    ///
+    /// The returned permit must be kept until the end of the search, if the request is one.
+    pub fn acquire_rate_limit(
+        &self,
+        filters: &AuthFilter,
+        announced_payload_bytes: u64,
+        is_search: bool,
+    ) -> std::result::Result<Option<SearchPermit>, RateLimitExceeded> {
+        match (filters.key_uid, &filters.rate_limits) {
+            (Some(uid), Some(limits)) => {
+                self.rate_limiter.acquire(uid, limits, announced_payload_bytes, is_search)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the quota the payload of a request authenticated by the filters is counted in, if
+    /// its key limits the payload bytes per day.
+    pub fn payload_quota(&self, filters: &AuthFilter) -> Option<PayloadQuota> {
+        let uid = filters.key_uid?;
+        let limits =
+            filters.rate_limits.filter(|limits| limits.max_payload_bytes_per_day.is_some())?;
+        Some(PayloadQuota::new(self.rate_limiter.clone(), uid, limits))
+    }
+
+    /// Returns the current usage of a key that has rate limits.
+    pub fn key_usage(&self, key: &Key) -> Option<KeyUsage> {
+        let limits = key.rate_limits.as_ref()?;
+        let usage = self.rate_limiter.usage(key.uid, limits).unwrap_or(KeyUsage {
+            available_requests: limits.requests_per_second,
+            payload_bytes_today: 0,
+            concurrent_searches: 0,
+        });
+        Some(usage)
     }
 
     pub fn list_keys(&self) -> Result<Vec<Key>> {
@@ -110,6 +239,7 @@ impl AuthController {
 
     pub fn delete_key(&self, uid: Uuid) -> Result<()> {
         if self.store.delete_api_key(uid)? {
+            self.rate_limiter.forget(uid);
             Ok(())
         } else {
             Err(AuthControllerError::ApiKeyNotFound(uid.to_string()))
@@ -120,10 +250,61 @@ impl AuthController {
         self.master_key.as_ref()
     }
 
-    /// Generate a valid key from a key id using the current master key.
+    /// Generate the current value of a key using the current master key.
     /// Returns None if no master key has been set.
-    pub fn generate_key(&self, uid: Uuid) -> Option<String> {
-        self.master_key.as_ref().map(|master_key| generate_key_as_hexa(uid, master_key.as_bytes()))
+    pub fn generate_key(&self, key: &Key) -> Option<String> {
+        self.master_key.as_ref().map(|master_key| {
+            generate_key_as_hexa(key.uid, key.salt.as_deref(), master_key.as_bytes())
+        })
+    }
+
+    /// Returns the values of a key accepted right now, its current value first.
+    /// Returns an empty list if no master key has been set or the key doesn't exist.
+    pub fn valid_key_values(&self, uid: Uuid) -> Result<Vec<String>> {
+        let (Some(master_key), Some(key)) = (&self.master_key, self.store.get_api_key(uid)?) else {
+            return Ok(Vec::new());
+        };
+        let now = OffsetDateTime::now_utc();
+        Ok(key
+            .valid_salts(now)
+            .map(|salt| generate_key_as_hexa(uid, salt, master_key.as_bytes()))
+            .collect())
+    }
+
+    /// Gives a new value to the key without changing its uid.
+    /// The previous value is still accepted until the end of the grace period,
+    /// but the values derived from a previous master key are revoked right away.
+    pub fn rotate_key(&self, uid: Uuid, rotate: RotateApiKey) -> Result<Key> {
+        let mut key = self.get_key(uid)?;
+        self.store.delete_previous_key_values(uid)?;
+        let now = OffsetDateTime::now_utc();
+        let previous_salt = key.salt.replace(generate_salt());
+        key.previous_secret = (rotate.grace_period > 0).then(|| PreviousSecret {
+            salt: previous_salt,
+            valid_until: now + time::Duration::seconds(rotate.grace_period.into()),
+        });
+        key.updated_at = now;
+        self.store.put_api_key(key)
+    }
+
+    /// Keeps the values of the keys derived from `previous_master_key` valid for the `transition`,
+    /// so the master key can be changed without breaking the clients. Returns the number of values recorded.
+    ///
+    /// Only the hashes of the values are stored. The tenant tokens signed with these values can't be
+    /// verified anymore and must be signed again with the new values.
+    pub fn rotate_master_key(
+        &self,
+        previous_master_key: &str,
+        transition: time::Duration,
+    ) -> Result<usize> {
+        let valid_until = OffsetDateTime::now_utc() + transition;
+        self.store.put_previous_key_values(previous_master_key.as_bytes(), valid_until)
+    }
+
+    /// Returns the values derived from a previous master key that are still recorded, with when they
+    /// were last used.
+    pub fn list_previous_key_values(&self) -> Result<Vec<PreviousKeyValue>> {
+        self.store.list_previous_key_values()
     }
 
     /// Check if the provided key is authorized to make a specific action
@@ -153,6 +334,62 @@ impl AuthController {
         }
     }
 
+    /// Queues a request to be recorded in the audit log, the uid and key name of the entry are
+    /// filled when it is written.
+    ///
+    /// Returns `true` when no flush was planned, the caller must then make sure that
+    /// [`Self::flush_audit_logs`] is called soon to write the entries in a single transaction.
+    pub fn queue_audit_log(&self, log: AuditLog) -> bool {
+        let mut queue = self.audit_log_queue.lock().unwrap();
+        queue.logs.push(log);
+        !std::mem::replace(&mut queue.flush_planned, true)
+    }
+
+    /// Writes the queued entries of the audit log.
+    ///
+    /// The entries are queued again if they can't be written, the next flush writes them first.
+    pub fn flush_audit_logs(&self) -> Result<()> {
+        // the entries are written in the order they were queued, even by concurrent flushes
+        let _flushing = self.audit_log_flush.lock().unwrap();
+        let mut logs = {
+            let mut queue = self.audit_log_queue.lock().unwrap();
+            queue.flush_planned = false;
+            std::mem::take(&mut queue.logs)
+        };
+        if logs.is_empty() {
+            return Ok(());
+        }
+        if let Err(error) = self.store.append_audit_logs(&mut logs, self.audit_log_max_entries) {
+            let mut queue = self.audit_log_queue.lock().unwrap();
+            logs.append(&mut queue.logs);
+            queue.logs = logs;
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// Returns at most `limit` entries of the audit log matching the filter, from the most recent one.
+    pub fn list_audit_logs(
+        &self,
+        from: Option<AuditLogId>,
+        limit: usize,
+        filter: impl FnMut(&AuditLog) -> bool,
+    ) -> Result<Vec<AuditLog>> {
+        self.flush_audit_logs()?;
+        self.store.list_audit_logs(from, limit, filter)
+    }
+
+    /// Insert the entries of the audit log as-is, e.g. when importing a dump.
+    pub fn raw_insert_audit_logs<E>(
+        &mut self,
+        logs: impl Iterator<Item = std::result::Result<AuditLog, E>>,
+    ) -> std::result::Result<(), E>
+    where
+        E: From<AuthControllerError>,
+    {
+        self.store.put_audit_logs(logs)
+    }
+
     /// Delete all the keys in the DB.
     pub fn raw_delete_all_keys(&mut self) -> Result<()> {
         self.store.delete_all_keys()
@@ -165,10 +402,24 @@ impl AuthController {
     }
 }
 
+/// A value of a key derived from a previous master key, accepted until the end of the transition.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct PreviousKeyValue {
+    pub uid: Uuid,
+    #[serde(with = "time::serde::rfc3339")]
+    pub valid_until: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_used_at: Option<OffsetDateTime>,
+}
+
 pub struct AuthFilter {
     search_rules: Option<SearchRules>,
     key_authorized_indexes: SearchRules,
     allow_index_creation: bool,
+    key_uid: Option<Uuid>,
+    rate_limits: Option<RateLimits>,
+    network_restrictions: NetworkRestrictions,
+    own_tasks_only: bool,
 }
 
 impl Default for AuthFilter {
@@ -177,6 +428,10 @@ impl Default for AuthFilter {
             search_rules: None,
             key_authorized_indexes: SearchRules::default(),
             allow_index_creation: true,
+            key_uid: None,
+            rate_limits: None,
+            network_restrictions: NetworkRestrictions::default(),
+            own_tasks_only: false,
         }
     }
 }
@@ -193,11 +448,39 @@ impl AuthFilter {
         self.search_rules.is_some()
     }
 
+    /// The uid of the key used to authenticate, `None` for the master key.
+    pub fn key_uid(&self) -> Option<Uuid> {
+        self.key_uid
+    }
+
+    /// Returns wether the key can be used by this client, with this `Origin` header.
+    pub fn is_request_origin_allowed(
+        &self,
+        client_ip: Option<IpAddr>,
+        origin: Option<&str>,
+    ) -> bool {
+        self.network_restrictions.is_allowed(client_ip, origin)
+    }
+
+    /// Restricts the filter to the tasks enqueued by its key.
+    pub fn with_own_tasks_only(self) -> Self {
+        Self { own_tasks_only: true, ..self }
+    }
+
+    /// The uid of the key whose tasks are the only ones visible through this filter, if restricted.
+    pub fn tasks_owner(&self) -> Option<Uuid> {
+        self.key_uid.filter(|_| self.own_tasks_only)
+    }
+
     pub fn with_allowed_indexes(allowed_indexes: HashSet<IndexUidPattern>) -> Self {
         Self {
             search_rules: None,
             key_authorized_indexes: SearchRules::Set(allowed_indexes),
             allow_index_creation: false,
+            key_uid: None,
+            rate_limits: None,
+            network_restrictions: NetworkRestrictions::default(),
+            own_tasks_only: false,
         }
     }
 
@@ -265,6 +548,11 @@ impl AuthFilter {
         let search_rules = self.search_rules.as_ref().unwrap_or(&self.key_authorized_indexes);
         search_rules.get_index_search_rules(index)
     }
+
+    /// Return the restrictions on the attributes a search on this index can see.
+    pub fn get_index_attributes_rules(&self, index: &str) -> AttributesRules {
+        self.get_index_search_rules(index).map(|rules| rules.attributes).unwrap_or_default()
+    }
 }
 
 /// Transparent wrapper around a list of allowed indexes with the search rules to apply for each.
@@ -343,9 +631,62 @@ impl IntoIterator for SearchRules {
 /// Contains the rules to apply on the top of the search query for a specific index.
 ///
 /// filter: search filter to apply in addition to query filters.
+/// attributes: restrictions on the attributes the search can see.
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct IndexSearchRules {
     pub filter: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub attributes: AttributesRules,
+}
+
+/// Restrictions on the attributes of the documents a search can see.
+///
+/// They are intersected with the parameters of the search query and also apply to the facets,
+/// the highlighting, the cropping and the `_matchesPosition`.
+///
+/// attributes_to_retrieve: only these attributes are visible, all of them by default.
+/// hidden_attributes: these attributes are never visible.
+/// attributes_to_search_on: the query only matches these attributes, all the searchable ones by default.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributesRules {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attributes_to_retrieve: Option<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub hidden_attributes: BTreeSet<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attributes_to_search_on: Option<BTreeSet<String>>,
+}
+
+impl AttributesRules {
+    pub fn is_unrestricted(&self) -> bool {
+        self.attributes_to_retrieve.is_none()
+            && self.hidden_attributes.is_empty()
+            && self.attributes_to_search_on.is_none()
+    }
+
+    /// Whether the attribute, or the nested field, can be returned to the user.
+    ///
+    /// Hiding a nested field hides the whole object containing it.
+    pub fn is_visible(&self, attribute: &str) -> bool {
+        let retrievable = self.attributes_to_retrieve.as_ref().is_none_or(|attributes| {
+            attributes.iter().any(|allowed| allowed == "*" || is_faceted_by(attribute, allowed))
+        });
+        let hidden = self
+            .hidden_attributes
+            .iter()
+            .any(|hidden| is_faceted_by(attribute, hidden) || is_faceted_by(hidden, attribute));
+        retrievable && !hidden
+    }
+
+    /// Whether the query can match the attribute. An attribute that isn't visible can't be
+    /// searched either, otherwise its content could be guessed from the hits.
+    pub fn is_searchable(&self, attribute: &str) -> bool {
+        let searchable = self.attributes_to_search_on.as_ref().is_none_or(|attributes| {
+            attributes.iter().any(|allowed| allowed == "*" || is_faceted_by(attribute, allowed))
+        });
+        searchable && self.is_visible(attribute)
+    }
 }
 
 fn generate_default_keys(store: &HeedAuthStore) -> Result<()> {
@@ -0,0 +1,168 @@
+use std::net::IpAddr;
+
+use meilisearch_types::ip_cidr::IpCidr;
+
+/// The reverse proxies whose `X-Forwarded-For` headers are trusted to find the address of the clients.
+#[derive(Debug, Default, Clone)]
+pub struct TrustedProxies(Vec<IpCidr>);
+
+impl TrustedProxies {
+    pub fn new(ranges: Vec<IpCidr>) -> Self {
+        Self(ranges)
+    }
+
+    pub fn is_trusted(&self, ip: IpAddr) -> bool {
+        self.0.iter().any(|range| range.contains(ip))
+    }
+
+    /// Returns the address of the client of a request received from `peer`.
+    ///
+    /// When the peer is a trusted proxy, the `X-Forwarded-For` addresses are read from right to left,
+    /// and the first one that isn't a trusted proxy is the client. The addresses on its left may have
+    /// been forged by the client and are never considered. The client is unknown when a trusted
+    /// proxy doesn't forward any address or forwards one that can't be parsed.
+    pub fn client_ip(&self, peer: Option<IpAddr>, forwarded_for: Option<&str>) -> Option<IpAddr> {
+        let mut client = peer?;
+        if !self.is_trusted(client) {
+            return Some(client);
+        }
+
+        let forwarded_for = forwarded_for.map(str::trim).filter(|header| !header.is_empty())?;
+        for hop in forwarded_for.rsplit(',') {
+            client = parse_hop(hop)?;
+            if !self.is_trusted(client) {
+                break;
+            }
+        }
+
+        Some(client)
+    }
+}
+
+/// Parses an `X-Forwarded-For` address, which some proxies send with a port: `1.2.3.4:80` or `[::1]:80`.
+fn parse_hop(hop: &str) -> Option<IpAddr> {
+    let hop = hop.trim();
+    if let Ok(ip) = hop.parse() {
+        return Some(ip);
+    }
+    if let Some(rest) = hop.strip_prefix('[') {
+        let (ip, port) = rest.split_once(']')?;
+        if !port.is_empty() && !is_port(port.strip_prefix(':')?) {
+            return None;
+        }
+        return ip.parse().ok();
+    }
+    let (ip, port) = hop.rsplit_once(':')?;
+    if !is_port(port) {
+        return None;
+    }
+    ip.parse::<std::net::Ipv4Addr>().ok().map(IpAddr::V4)
+}
+
+fn is_port(port: &str) -> bool {
+    port.parse::<u16>().is_ok()
+}
+
+/// The network origins an API key, and the tenant tokens it signed, can be used from.
+#[derive(Debug, Default, Clone)]
+pub struct NetworkRestrictions {
+    allowed_ips: Option<Vec<IpCidr>>,
+    allowed_origins: Option<Vec<String>>,
+}
+
+impl NetworkRestrictions {
+    pub fn new(allowed_ips: Option<Vec<IpCidr>>, allowed_origins: Option<Vec<String>>) -> Self {
+        Self { allowed_ips, allowed_origins }
+    }
+
+    /// Returns wether a request sent by this client, with this `Origin` header, is allowed.
+    ///
+    /// An unknown client address or a missing `Origin` header never match a restriction.
+    pub fn is_allowed(&self, client_ip: Option<IpAddr>, origin: Option<&str>) -> bool {
+        let ip_allowed = match (&self.allowed_ips, client_ip) {
+            (None, _) => true,
+            (Some(allowed_ips), Some(ip)) => allowed_ips.iter().any(|range| range.contains(ip)),
+            (Some(_), None) => false,
+        };
+        let origin_allowed = match (&self.allowed_origins, origin) {
+            (None, _) => true,
+            (Some(allowed_origins), Some(origin)) => {
+                allowed_origins.iter().any(|allowed| same_origin(allowed, origin))
+            }
+            (Some(_), None) => false,
+        };
+
+        ip_allowed && origin_allowed
+    }
+}
+
+/// Origins are compared case-insensitively and a trailing slash is ignored.
+fn same_origin(allowed: &str, origin: &str) -> bool {
+    allowed.trim_end_matches('/').eq_ignore_ascii_case(origin.trim_end_matches('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proxies(ranges: &[&str]) -> TrustedProxies {
+        TrustedProxies::new(ranges.iter().map(|range| range.parse().unwrap()).collect())
+    }
+
+    #[test]
+    fn client_ip_behind_proxies() {
+        let ip = |s: &str| Some(s.parse().unwrap());
+
+        // no trusted proxy, the header is ignored
+        let none = proxies(&[]);
+        assert_eq!(none.client_ip(ip("203.0.113.7"), Some("10.0.0.1")), ip("203.0.113.7"));
+
+        let internal = proxies(&["10.0.0.0/8"]);
+        assert_eq!(internal.client_ip(ip("10.0.0.2"), Some("198.51.100.4")), ip("198.51.100.4"));
+        // the chain of trusted proxies is walked from the right
+        assert_eq!(
+            internal.client_ip(ip("10.0.0.2"), Some("1.2.3.4, 198.51.100.4, 10.0.0.3")),
+            ip("198.51.100.4")
+        );
+        // a request that didn't go through a trusted proxy can't pretend to
+        assert_eq!(internal.client_ip(ip("198.51.100.4"), Some("10.0.0.3")), ip("198.51.100.4"));
+        // the ports and brackets added by some proxies are ignored
+        assert_eq!(
+            internal.client_ip(ip("10.0.0.2"), Some("198.51.100.4:51234")),
+            ip("198.51.100.4")
+        );
+        assert_eq!(internal.client_ip(ip("10.0.0.2"), Some("[2001:db8::1]")), ip("2001:db8::1"));
+        assert_eq!(
+            internal.client_ip(ip("10.0.0.2"), Some("[2001:db8::1]:51234, 10.0.0.3:80")),
+            ip("2001:db8::1")
+        );
+        assert_eq!(internal.client_ip(ip("10.0.0.2"), Some("2001:db8::1")), ip("2001:db8::1"));
+        // the client is unknown when a trusted proxy doesn't tell who it is
+        assert_eq!(internal.client_ip(ip("10.0.0.2"), None), None);
+        assert_eq!(internal.client_ip(ip("10.0.0.2"), Some("")), None);
+        assert_eq!(internal.client_ip(ip("10.0.0.2"), Some("unknown")), None);
+        assert_eq!(internal.client_ip(ip("10.0.0.2"), Some("198.51.100.4, unknown")), None);
+        assert_eq!(internal.client_ip(ip("10.0.0.2"), Some("[2001:db8::1")), None);
+        assert_eq!(internal.client_ip(ip("10.0.0.2"), Some("198.51.100.4:http")), None);
+        // with a chain of trusted proxies only, the leftmost one is the client
+        assert_eq!(internal.client_ip(ip("10.0.0.2"), Some("10.0.0.3")), ip("10.0.0.3"));
+        assert_eq!(internal.client_ip(None, Some("198.51.100.4")), None);
+    }
+
+    #[test]
+    fn restrictions() {
+        let ip = |s: &str| Some(s.parse().unwrap());
+
+        assert!(NetworkRestrictions::default().is_allowed(None, None));
+
+        let restrictions = NetworkRestrictions::new(
+            Some(vec!["10.0.0.0/8".parse().unwrap()]),
+            Some(vec!["https://admin.example.com".to_string()]),
+        );
+        assert!(restrictions.is_allowed(ip("10.1.2.3"), Some("https://ADMIN.example.com/")));
+        assert!(!restrictions.is_allowed(ip("10.1.2.3"), Some("https://example.com")));
+        assert!(!restrictions.is_allowed(ip("10.1.2.3"), None));
+        assert!(!restrictions.is_allowed(ip("192.168.1.1"), Some("https://admin.example.com")));
+        assert!(!restrictions.is_allowed(None, Some("https://admin.example.com")));
+    }
+}
@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use meilisearch_types::error::{Code, ErrorCode};
+use meilisearch_types::keys::{KeyId, RateLimits};
+use time::{Date, OffsetDateTime, Time};
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum RateLimitExceeded {
+    #[error("Too many requests. The API key is limited to {limit} requests per second.")]
+    RequestsPerSecond { limit: u32, retry_after: u64 },
+    #[error("Too many requests. The API key is limited to {limit} bytes of payload per day.")]
+    PayloadBytesPerDay { limit: u64, retry_after: u64 },
+    #[error("Too many requests. The API key is limited to {limit} concurrent searches.")]
+    ConcurrentSearches { limit: u32, retry_after: u64 },
+}
+
+impl RateLimitExceeded {
+    /// The number of seconds after which the request is expected to succeed.
+    pub fn retry_after(&self) -> u64 {
+        match self {
+            RateLimitExceeded::RequestsPerSecond { retry_after, .. }
+            | RateLimitExceeded::PayloadBytesPerDay { retry_after, .. }
+            | RateLimitExceeded::ConcurrentSearches { retry_after, .. } => *retry_after,
+        }
+    }
+}
+
+impl ErrorCode for RateLimitExceeded {
+    fn error_code(&self) -> Code {
+        Code::TooManyRequests
+    }
+}
+
+/// The current usage of an API key, as seen by the rate limiter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyUsage {
+    /// The number of requests that can be made right now, if the requests per second are limited.
+    pub available_requests: Option<u32>,
+    /// The number of payload bytes received since the beginning of the UTC day.
+    pub payload_bytes_today: u64,
+    /// The number of searches currently running.
+    pub concurrent_searches: u32,
+}
+
+/// Holds a slot of the concurrent searches of a key until it is dropped.
+#[derive(Debug)]
+pub struct SearchPermit {
+    concurrent_searches: Arc<AtomicU32>,
+}
+
+impl Drop for SearchPermit {
+    fn drop(&mut self) {
+        self.concurrent_searches.fetch_sub(1, Ordering::Release);
+    }
+}
+
+/// Counts the bytes of a payload against the daily quota of an API key, as they are received.
+#[derive(Debug, Clone)]
+pub struct PayloadQuota {
+    rate_limiter: Arc<RateLimiter>,
+    uid: KeyId,
+    limits: RateLimits,
+}
+
+impl PayloadQuota {
+    pub(crate) fn new(rate_limiter: Arc<RateLimiter>, uid: KeyId, limits: RateLimits) -> Self {
+        PayloadQuota { rate_limiter, uid, limits }
+    }
+
+    /// Counts bytes of the payload, failing once the quota of the day is exceeded.
+    pub fn consume(&self, payload_bytes: u64) -> Result<(), RateLimitExceeded> {
+        self.rate_limiter.consume_payload_at(Clock::now(), self.uid, &self.limits, payload_bytes)
+    }
+}
+
+/// An in-memory token bucket per API key.
+///
+/// The usage is not persisted and restarts from scratch when Meilisearch restarts.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    buckets: Mutex<HashMap<KeyId, Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// The number of requests that can be made, refilled continuously up to one second worth of requests.
+    tokens: f64,
+    last_refill: Instant,
+    day: Date,
+    payload_bytes: u64,
+    concurrent_searches: Arc<AtomicU32>,
+}
+
+/// The time at which the usage of the keys is computed, passed explicitly to test the limiter
+/// without waiting.
+#[derive(Debug, Clone, Copy)]
+struct Clock {
+    instant: Instant,
+    now: OffsetDateTime,
+}
+
+impl Clock {
+    fn now() -> Self {
+        Clock { instant: Instant::now(), now: OffsetDateTime::now_utc() }
+    }
+}
+
+impl Bucket {
+    fn new(limits: &RateLimits, clock: Clock) -> Self {
+        Bucket {
+            tokens: limits.requests_per_second.unwrap_or_default() as f64,
+            last_refill: clock.instant,
+            day: clock.now.date(),
+            payload_bytes: 0,
+            concurrent_searches: Arc::default(),
+        }
+    }
+
+    fn refill(&mut self, limits: &RateLimits, clock: Clock) {
+        if let Some(requests_per_second) = limits.requests_per_second {
+            let capacity = requests_per_second as f64;
+            let elapsed = clock.instant.saturating_duration_since(self.last_refill).as_secs_f64();
+            self.tokens = (self.tokens + elapsed * capacity).min(capacity);
+        }
+        self.last_refill = clock.instant;
+
+        let today = clock.now.date();
+        if self.day != today {
+            self.day = today;
+            self.payload_bytes = 0;
+        }
+    }
+}
+
+impl RateLimiter {
+    /// Counts a request made with the key against its limits.
+    ///
+    /// The payload is not counted, it is only rejected upfront when its announced length already
+    /// exceeds the quota of the day. The bytes are counted as they are read with [`PayloadQuota`].
+    ///
+    /// Nothing is counted if one of the limits is exceeded. When the request is a search, the returned
+    /// permit must be kept until the search is over.
+    pub fn acquire(
+        &self,
+        uid: KeyId,
+        limits: &RateLimits,
+        announced_payload_bytes: u64,
+        is_search: bool,
+    ) -> Result<Option<SearchPermit>, RateLimitExceeded> {
+        self.acquire_at(Clock::now(), uid, limits, announced_payload_bytes, is_search)
+    }
+
+    fn acquire_at(
+        &self,
+        clock: Clock,
+        uid: KeyId,
+        limits: &RateLimits,
+        announced_payload_bytes: u64,
+        is_search: bool,
+    ) -> Result<Option<SearchPermit>, RateLimitExceeded> {
+        if limits.is_unlimited() {
+            return Ok(None);
+        }
+
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.entry(uid).or_insert_with(|| Bucket::new(limits, clock));
+        bucket.refill(limits, clock);
+
+        if let Some(limit) = limits.requests_per_second {
+            if bucket.tokens < 1.0 {
+                let missing = 1.0 - bucket.tokens;
+                let retry_after = (missing / limit.max(1) as f64).ceil() as u64;
+                return Err(RateLimitExceeded::RequestsPerSecond {
+                    limit,
+                    retry_after: retry_after.max(1),
+                });
+            }
+        }
+
+        if let Some(limit) = limits.max_payload_bytes_per_day {
+            if bucket.payload_bytes.saturating_add(announced_payload_bytes) > limit {
+                return Err(RateLimitExceeded::PayloadBytesPerDay {
+                    limit,
+                    retry_after: seconds_until_tomorrow(clock.now),
+                });
+            }
+        }
+
+        let permit = match (is_search, limits.max_concurrent_searches) {
+            (true, Some(limit)) => {
+                let concurrent_searches = bucket.concurrent_searches.clone();
+                // the bucket is locked, no other request of this key can increase the counter meanwhile.
+                if concurrent_searches.load(Ordering::Acquire) >= limit {
+                    return Err(RateLimitExceeded::ConcurrentSearches { limit, retry_after: 1 });
+                }
+                concurrent_searches.fetch_add(1, Ordering::AcqRel);
+                Some(SearchPermit { concurrent_searches })
+            }
+            _ => None,
+        };
+
+        if limits.requests_per_second.is_some() {
+            bucket.tokens -= 1.0;
+        }
+
+        Ok(permit)
+    }
+
+    /// Counts payload bytes received with the key, failing once they exceed the quota of the day.
+    ///
+    /// The bytes are counted even when the quota is exceeded, the rest of the payload is rejected anyway.
+    fn consume_payload_at(
+        &self,
+        clock: Clock,
+        uid: KeyId,
+        limits: &RateLimits,
+        payload_bytes: u64,
+    ) -> Result<(), RateLimitExceeded> {
+        let Some(limit) = limits.max_payload_bytes_per_day else { return Ok(()) };
+
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.entry(uid).or_insert_with(|| Bucket::new(limits, clock));
+        bucket.refill(limits, clock);
+
+        bucket.payload_bytes = bucket.payload_bytes.saturating_add(payload_bytes);
+        if bucket.payload_bytes > limit {
+            return Err(RateLimitExceeded::PayloadBytesPerDay {
+                limit,
+                retry_after: seconds_until_tomorrow(clock.now),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the current usage of the key, or `None` if it has not been used since the last restart.
+    pub fn usage(&self, uid: KeyId, limits: &RateLimits) -> Option<KeyUsage> {
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.get_mut(&uid)?;
+        bucket.refill(limits, Clock::now());
+
+        Some(KeyUsage {
+            available_requests: limits.requests_per_second.map(|_| bucket.tokens.floor() as u32),
+            payload_bytes_today: bucket.payload_bytes,
+            concurrent_searches: bucket.concurrent_searches.load(Ordering::Acquire),
+        })
+    }
+
+    /// Forgets the usage of the key, e.g. because it was deleted.
+    pub fn forget(&self, uid: KeyId) {
+        self.buckets.lock().unwrap().remove(&uid);
+    }
+}
+
+fn seconds_until_tomorrow(now: OffsetDateTime) -> u64 {
+    let tomorrow = now.date().next_day().map(|day| day.with_time(Time::MIDNIGHT).assume_utc());
+    match tomorrow {
+        Some(tomorrow) => (tomorrow - now).whole_seconds().max(1) as u64,
+        None => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn clock(start: Instant, elapsed_ms: u64) -> Clock {
+        let elapsed = Duration::from_millis(elapsed_ms);
+        Clock { instant: start + elapsed, now: datetime!(2025-01-01 23:59:59 UTC) + elapsed }
+    }
+
+    #[test]
+    fn requests_per_second() {
+        let limiter = RateLimiter::default();
+        let uid = KeyId::new_v4();
+        let limits = RateLimits { requests_per_second: Some(2), ..Default::default() };
+        let start = Instant::now();
+
+        assert!(limiter.acquire_at(clock(start, 0), uid, &limits, 0, false).is_ok());
+        assert!(limiter.acquire_at(clock(start, 0), uid, &limits, 0, false).is_ok());
+        let error = limiter.acquire_at(clock(start, 0), uid, &limits, 0, false).unwrap_err();
+        assert!(matches!(error, RateLimitExceeded::RequestsPerSecond { limit: 2, retry_after: 1 }));
+
+        // half a second later one request is available again, but not two
+        assert!(limiter.acquire_at(clock(start, 500), uid, &limits, 0, false).is_ok());
+        assert!(limiter.acquire_at(clock(start, 500), uid, &limits, 0, false).is_err());
+        // the bucket never holds more than one second worth of requests
+        assert!(limiter.acquire_at(clock(start, 10_000), uid, &limits, 0, false).is_ok());
+        assert!(limiter.acquire_at(clock(start, 10_000), uid, &limits, 0, false).is_ok());
+        assert!(limiter.acquire_at(clock(start, 10_000), uid, &limits, 0, false).is_err());
+    }
+
+    #[test]
+    fn payload_bytes_per_day() {
+        let limiter = RateLimiter::default();
+        let uid = KeyId::new_v4();
+        let limits = RateLimits { max_payload_bytes_per_day: Some(100), ..Default::default() };
+        let start = Instant::now();
+
+        // the announced length is rejected upfront but not counted
+        let error = limiter.acquire_at(clock(start, 0), uid, &limits, 101, false).unwrap_err();
+        assert!(matches!(
+            error,
+            RateLimitExceeded::PayloadBytesPerDay { limit: 100, retry_after: 1 }
+        ));
+        assert!(limiter.acquire_at(clock(start, 0), uid, &limits, 100, false).is_ok());
+
+        // the bytes actually received are counted, whatever was announced
+        assert!(limiter.consume_payload_at(clock(start, 0), uid, &limits, 60).is_ok());
+        assert!(limiter.consume_payload_at(clock(start, 0), uid, &limits, 40).is_ok());
+        assert!(limiter.consume_payload_at(clock(start, 0), uid, &limits, 1).is_err());
+        assert!(limiter.acquire_at(clock(start, 0), uid, &limits, 1, false).is_err());
+
+        // the quota is reset at midnight UTC
+        assert!(limiter.acquire_at(clock(start, 1_000), uid, &limits, 100, false).is_ok());
+        assert!(limiter.consume_payload_at(clock(start, 1_000), uid, &limits, 100).is_ok());
+    }
+
+    #[test]
+    fn concurrent_searches() {
+        let limiter = RateLimiter::default();
+        let uid = KeyId::new_v4();
+        let limits = RateLimits { max_concurrent_searches: Some(1), ..Default::default() };
+        let now = Clock::now();
+
+        let permit = limiter.acquire_at(now, uid, &limits, 0, true).unwrap();
+        assert!(permit.is_some());
+        assert!(limiter.acquire_at(now, uid, &limits, 0, true).is_err());
+        // the other requests are not searches
+        assert!(limiter.acquire_at(now, uid, &limits, 0, false).unwrap().is_none());
+        drop(permit);
+        assert!(limiter.acquire_at(now, uid, &limits, 0, true).is_ok());
+    }
+}
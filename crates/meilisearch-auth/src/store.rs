@@ -7,37 +7,66 @@ use std::str;
 use std::str::FromStr;
 
 use hmac::{Hmac, Mac};
+use meilisearch_types::audit_log::{AuditLog, AuditLogId, AuditLogKeyUid};
 use meilisearch_types::heed::{BoxedError, WithoutTls};
 use meilisearch_types::index_uid_pattern::IndexUidPattern;
-use meilisearch_types::keys::KeyId;
+use meilisearch_types::keys::{KeyId, PreviousSecret};
 use meilisearch_types::milli::heed;
 use meilisearch_types::milli::heed::types::{Bytes, DecodeIgnore, SerdeJson};
-use meilisearch_types::milli::heed::{Database, Env, EnvOpenOptions, RwTxn};
-use sha2::Sha256;
+use meilisearch_types::milli::heed::{Database, Env, EnvOpenOptions, RoTxn, RwTxn};
+use meilisearch_types::milli::BEU64;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use thiserror::Error;
-use time::OffsetDateTime;
+use time::{Duration, OffsetDateTime};
 use uuid::fmt::Hyphenated;
 use uuid::Uuid;
 
 use super::error::{AuthControllerError, Result};
-use super::{Action, Key};
+use super::{Action, Key, PreviousKeyValue};
 
 const AUTH_STORE_SIZE: usize = 1_073_741_824; //1GiB
 const KEY_DB_NAME: &str = "api-keys";
 const KEY_ID_ACTION_INDEX_EXPIRATION_DB_NAME: &str = "keyid-action-index-expiration";
+const AUDIT_LOG_DB_NAME: &str = "audit-logs";
+const PREVIOUS_KEY_VALUES_DB_NAME: &str = "previous-key-values";
 
 #[derive(Clone)]
 pub struct HeedAuthStore {
     env: Env<WithoutTls>,
     keys: Database<Bytes, SerdeJson<Key>>,
     action_keyid_index_expiration: Database<KeyIdActionCodec, SerdeJson<Option<OffsetDateTime>>>,
+    audit_logs: Database<BEU64, SerdeJson<AuditLog>>,
+    /// The values of the keys derived from a previous master key, indexed by their SHA-256 hash.
+    previous_key_values: Database<Bytes, SerdeJson<PreviousKeyValue>>,
+}
+
+/// The fields of a [`Key`] from which its values are derived, decoded without the rest of the key.
+#[derive(Deserialize)]
+struct KeySecrets {
+    #[serde(default)]
+    salt: Option<String>,
+    #[serde(default)]
+    previous_secret: Option<PreviousSecret>,
+}
+
+impl KeySecrets {
+    /// Same as [`Key::valid_salts`].
+    fn valid_salts(&self, now: OffsetDateTime) -> impl Iterator<Item = Option<&str>> {
+        let previous = self
+            .previous_secret
+            .as_ref()
+            .filter(|previous| previous.valid_until > now)
+            .map(|previous| previous.salt.as_deref());
+        std::iter::once(self.salt.as_deref()).chain(previous)
+    }
 }
 
 pub fn open_auth_store_env(path: &Path) -> heed::Result<Env<WithoutTls>> {
     let options = EnvOpenOptions::new();
     let mut options = options.read_txn_without_tls();
     options.map_size(AUTH_STORE_SIZE); // 1GB
-    options.max_dbs(2);
+    options.max_dbs(4);
     unsafe { options.open(path) }
 }
 
@@ -47,8 +76,11 @@ impl HeedAuthStore {
         let keys = env.create_database(&mut wtxn, Some(KEY_DB_NAME))?;
         let action_keyid_index_expiration =
             env.create_database(&mut wtxn, Some(KEY_ID_ACTION_INDEX_EXPIRATION_DB_NAME))?;
+        let audit_logs = env.create_database(&mut wtxn, Some(AUDIT_LOG_DB_NAME))?;
+        let previous_key_values =
+            env.create_database(&mut wtxn, Some(PREVIOUS_KEY_VALUES_DB_NAME))?;
         wtxn.commit()?;
-        Ok(Self { env, keys, action_keyid_index_expiration })
+        Ok(Self { env, keys, action_keyid_index_expiration, audit_logs, previous_key_values })
     }
 
     /// Return `Ok(())` if the auth store is able to access one of its database.
@@ -164,19 +196,21 @@ impl HeedAuthStore {
         master_key: &[u8],
     ) -> Result<Option<Uuid>> {
         let rtxn = self.env.read_txn()?;
+        let now = OffsetDateTime::now_utc();
         let uid = self
             .keys
-            .remap_data_type::<DecodeIgnore>()
+            .remap_data_type::<SerdeJson<KeySecrets>>()
             .iter(&rtxn)?
             .filter_map(|res| match res {
-                Ok((uid, _)) => {
+                Ok((uid, secrets)) => {
                     let (uid, _) = try_split_array_at(uid)?;
                     let uid = Uuid::from_bytes(*uid);
-                    if generate_key_as_hexa(uid, master_key).as_bytes() == encoded_key {
-                        Some(uid)
-                    } else {
-                        None
-                    }
+                    secrets
+                        .valid_salts(now)
+                        .any(|salt| {
+                            generate_key_as_hexa(uid, salt, master_key).as_bytes() == encoded_key
+                        })
+                        .then_some(uid)
                 }
                 Err(_) => None,
             })
@@ -185,10 +219,102 @@ impl HeedAuthStore {
         Ok(uid)
     }
 
+    /// Records the hashes of the values of the keys derived from a previous master key,
+    /// and forgets the ones of the transitions that are over.
+    pub fn put_previous_key_values(
+        &self,
+        previous_master_key: &[u8],
+        valid_until: OffsetDateTime,
+    ) -> Result<usize> {
+        let mut wtxn = self.env.write_txn()?;
+        let now = OffsetDateTime::now_utc();
+
+        let mut iter = self.previous_key_values.iter_mut(&mut wtxn)?;
+        while let Some((_, previous)) = iter.next().transpose()? {
+            if previous.valid_until <= now {
+                // safety: we don't keep references from inside the LMDB database.
+                unsafe { iter.del_current()? };
+            }
+        }
+        drop(iter);
+
+        let keys: Vec<_> = self.keys.remap_key_type::<DecodeIgnore>().iter(&wtxn)?.collect();
+        let mut count = 0;
+        for result in keys {
+            let (_, key) = result?;
+            for salt in key.valid_salts(now) {
+                let value = generate_key_as_hexa(key.uid, salt, previous_master_key);
+                let hash = Sha256::digest(value.as_bytes());
+                let previous = PreviousKeyValue { uid: key.uid, valid_until, last_used_at: None };
+                self.previous_key_values.put(&mut wtxn, &hash, &previous)?;
+                count += 1;
+            }
+        }
+        wtxn.commit()?;
+
+        Ok(count)
+    }
+
+    /// Returns the uid of the key whose value derived from a previous master key is the one
+    /// provided, if its transition isn't over, and records that it has been used.
+    pub fn get_uid_from_previous_key_value(&self, encoded_key: &[u8]) -> Result<Option<Uuid>> {
+        let hash = Sha256::digest(encoded_key);
+        let now = OffsetDateTime::now_utc();
+
+        let rtxn = self.env.read_txn()?;
+        let Some(mut previous) = self.previous_key_values.get(&rtxn, &hash)? else {
+            return Ok(None);
+        };
+        drop(rtxn);
+        if previous.valid_until <= now {
+            return Ok(None);
+        }
+
+        // the usage is recorded at most once per minute to avoid a write on every request.
+        if previous.last_used_at.is_none_or(|last_used_at| now - last_used_at > Duration::MINUTE) {
+            previous.last_used_at = Some(now);
+            let mut wtxn = self.env.write_txn()?;
+            self.previous_key_values.put(&mut wtxn, &hash, &previous)?;
+            wtxn.commit()?;
+        }
+
+        Ok(Some(previous.uid))
+    }
+
+    pub fn list_previous_key_values(&self) -> Result<Vec<PreviousKeyValue>> {
+        let rtxn = self.env.read_txn()?;
+        let mut list = Vec::new();
+        for result in self.previous_key_values.remap_key_type::<DecodeIgnore>().iter(&rtxn)? {
+            let (_, previous) = result?;
+            list.push(previous);
+        }
+        Ok(list)
+    }
+
+    /// Forgets the values of the key derived from a previous master key, they stop being accepted.
+    pub fn delete_previous_key_values(&self, uid: Uuid) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        self.delete_previous_key_values_of(&mut wtxn, uid)?;
+        wtxn.commit()?;
+        Ok(())
+    }
+
+    fn delete_previous_key_values_of(&self, wtxn: &mut RwTxn, uid: Uuid) -> Result<()> {
+        let mut iter = self.previous_key_values.iter_mut(wtxn)?;
+        while let Some((_, previous)) = iter.next().transpose()? {
+            if previous.uid == uid {
+                // safety: we don't keep references from inside the LMDB database.
+                unsafe { iter.del_current()? };
+            }
+        }
+        Ok(())
+    }
+
     pub fn delete_api_key(&self, uid: Uuid) -> Result<bool> {
         let mut wtxn = self.env.write_txn()?;
         let existing = self.keys.delete(&mut wtxn, uid.as_bytes())?;
         self.delete_key_from_inverted_db(&mut wtxn, &uid)?;
+        self.delete_previous_key_values_of(&mut wtxn, uid)?;
         wtxn.commit()?;
 
         Ok(existing)
@@ -257,6 +383,68 @@ impl HeedAuthStore {
         Ok(exp)
     }
 
+    /// Appends the entries at the end of the audit log in a single transaction, with the next
+    /// available uids, and deletes the oldest entries beyond the `max_entries` last ones if set.
+    pub fn append_audit_logs(&self, logs: &mut [AuditLog], max_entries: Option<u64>) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        let mut next_uid = match self.audit_logs.last(&wtxn)? {
+            Some((last_uid, _)) => last_uid + 1,
+            None => 0,
+        };
+        for log in logs {
+            log.uid = next_uid;
+            if let AuditLogKeyUid::Key(uid) = log.key_uid {
+                log.key_name = self.keys.get(&wtxn, uid.as_bytes())?.and_then(|key| key.name);
+            }
+            self.audit_logs.put(&mut wtxn, &log.uid, log)?;
+            next_uid += 1;
+        }
+        if let Some(oldest_kept) = max_entries.and_then(|max| next_uid.checked_sub(max)) {
+            self.audit_logs.delete_range(&mut wtxn, &(..oldest_kept))?;
+        }
+        wtxn.commit()?;
+
+        Ok(())
+    }
+
+    /// Inserts the entries with their own uids in a single transaction, e.g. when importing a dump.
+    pub fn put_audit_logs<E>(
+        &self,
+        logs: impl Iterator<Item = std::result::Result<AuditLog, E>>,
+    ) -> std::result::Result<(), E>
+    where
+        E: From<AuthControllerError>,
+    {
+        let mut wtxn = self.env.write_txn().map_err(AuthControllerError::from)?;
+        for log in logs {
+            let log = log?;
+            self.audit_logs.put(&mut wtxn, &log.uid, &log).map_err(AuthControllerError::from)?;
+        }
+        wtxn.commit().map_err(AuthControllerError::from)?;
+        Ok(())
+    }
+
+    /// Returns the entries matching the filter from the most recent one, starting at `from` if specified.
+    pub fn list_audit_logs(
+        &self,
+        from: Option<AuditLogId>,
+        limit: usize,
+        mut filter: impl FnMut(&AuditLog) -> bool,
+    ) -> Result<Vec<AuditLog>> {
+        let rtxn = self.env.read_txn()?;
+        let mut logs = Vec::new();
+        for result in self.audit_logs.rev_range(&rtxn, &(..=from.unwrap_or(AuditLogId::MAX)))? {
+            if logs.len() == limit {
+                break;
+            }
+            let (_, log) = result?;
+            if filter(&log) {
+                logs.push(log);
+            }
+        }
+        Ok(logs)
+    }
+
     fn delete_key_from_inverted_db(&self, wtxn: &mut RwTxn, key: &KeyId) -> Result<()> {
         let mut iter = self
             .action_keyid_index_expiration
@@ -271,6 +459,14 @@ impl HeedAuthStore {
     }
 }
 
+/// Opens the audit log of an auth environment, if it was already created.
+pub fn open_audit_log_database(
+    env: &Env<WithoutTls>,
+    rtxn: &RoTxn<WithoutTls>,
+) -> heed::Result<Option<Database<BEU64, SerdeJson<AuditLog>>>> {
+    env.open_database(rtxn, Some(AUDIT_LOG_DB_NAME))
+}
+
 /// Codec allowing to retrieve the expiration date of an action,
 /// optionally on a specific index, for a given key.
 pub struct KeyIdActionCodec;
@@ -319,7 +515,7 @@ pub struct InvalidActionError {
     pub action_byte: u8,
 }
 
-pub fn generate_key_as_hexa(uid: Uuid, master_key: &[u8]) -> String {
+pub fn generate_key_as_hexa(uid: Uuid, salt: Option<&str>, master_key: &[u8]) -> String {
     // format uid as hyphenated allowing user to generate their own keys.
     let mut uid_buffer = [0; Hyphenated::LENGTH];
     let uid = uid.hyphenated().encode_lower(&mut uid_buffer);
@@ -327,11 +523,25 @@ pub fn generate_key_as_hexa(uid: Uuid, master_key: &[u8]) -> String {
     // new_from_slice function never fail.
     let mut mac = Hmac::<Sha256>::new_from_slice(master_key).unwrap();
     mac.update(uid.as_bytes());
+    // the keys that were never rotated keep the value derived from their uid only.
+    if let Some(salt) = salt {
+        mac.update(salt.as_bytes());
+    }
 
     let result = mac.finalize();
     format!("{:x}", result.into_bytes())
 }
 
+/// Generates a random salt for the value of a key, hex encoded.
+pub fn generate_salt() -> String {
+    use rand::rngs::OsRng;
+    use rand::RngCore;
+
+    let mut salt = [0; 16];
+    OsRng.fill_bytes(&mut salt);
+    salt.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
 /// Divides one slice into two at an index, returns `None` if mid is out of bounds.
 pub fn try_split_at<T>(slice: &[T], mid: usize) -> Option<(&[T], &[T])> {
     if mid <= slice.len() {
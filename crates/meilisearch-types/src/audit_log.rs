@@ -0,0 +1,121 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+
+use crate::keys::{Action, KeyId};
+
+pub type AuditLogId = u64;
+
+/// A request made on an administrative route, as recorded in the audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct AuditLog {
+    /// The unique sequential identifier of the entry.
+    pub uid: AuditLogId,
+    /// The date and time at which the request was answered.
+    #[serde(with = "time::serde::rfc3339")]
+    pub timestamp: OffsetDateTime,
+    /// The uid of the API key used to make the request, `masterKey` for the master key, or
+    /// `unauthenticated` when the request could not be authenticated.
+    #[schema(value_type = String, example = "01b4bc42-eb33-4041-b481-254d00cce834")]
+    pub key_uid: AuditLogKeyUid,
+    /// The name of the API key used to make the request, if any.
+    pub key_name: Option<String>,
+    /// The action required by the route.
+    pub action: Action,
+    /// The index targeted by the request, if any.
+    pub index_uid: Option<String>,
+    /// The HTTP method of the request.
+    pub method: String,
+    /// The path of the request.
+    pub route: String,
+    /// The HTTP status code of the response.
+    pub status_code: u16,
+    /// Whether the request succeeded.
+    pub outcome: AuditLogOutcome,
+}
+
+/// The credentials a request was made with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AuditLogKeyUid {
+    /// An API key, or a tenant token signed by it.
+    Key(KeyId),
+    /// The master key, or no key at all when the instance is not protected by a master key.
+    MasterKey,
+    /// The request could not be authenticated.
+    Unauthenticated,
+}
+
+impl fmt::Display for AuditLogKeyUid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuditLogKeyUid::Key(uid) => uid.fmt(f),
+            AuditLogKeyUid::MasterKey => f.write_str("masterKey"),
+            AuditLogKeyUid::Unauthenticated => f.write_str("unauthenticated"),
+        }
+    }
+}
+
+impl FromStr for AuditLogKeyUid {
+    type Err = ParseAuditLogKeyUidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "masterKey" => Ok(AuditLogKeyUid::MasterKey),
+            "unauthenticated" => Ok(AuditLogKeyUid::Unauthenticated),
+            uid => uid
+                .parse()
+                .map(AuditLogKeyUid::Key)
+                .map_err(|_| ParseAuditLogKeyUidError(uid.to_owned())),
+        }
+    }
+}
+
+impl Serialize for AuditLogKeyUid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for AuditLogKeyUid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseAuditLogKeyUidError(pub String);
+
+impl fmt::Display for ParseAuditLogKeyUidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not a valid key uid. Available values are the uid of a key, `masterKey` and `unauthenticated`.",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseAuditLogKeyUidError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum AuditLogOutcome {
+    Succeeded,
+    Failed,
+}
+
+impl AuditLogOutcome {
+    pub fn from_status_code(status_code: u16) -> Self {
+        if status_code < 400 {
+            AuditLogOutcome::Succeeded
+        } else {
+            AuditLogOutcome::Failed
+        }
+    }
+}
@@ -76,6 +76,9 @@ pub struct BatchStats {
     pub status: BTreeMap<Status, u32>,
     pub types: BTreeMap<Kind, u32>,
     pub index_uids: BTreeMap<String, u32>,
+    /// The number of tasks of the batch enqueued by each API key.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub key_uids: BTreeMap<String, u32>,
     #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
     pub progress_trace: serde_json::Map<String, serde_json::Value>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -2,27 +2,75 @@ use std::fs::{create_dir_all, File};
 use std::io::Write;
 use std::path::Path;
 
+use file_store::encryption::{decrypted_reader, EncryptWriter, EncryptionKey};
 use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
 use flate2::Compression;
 use tar::{Archive, Builder};
 
-pub fn to_tar_gz(src: impl AsRef<Path>, dest: impl AsRef<Path>) -> anyhow::Result<()> {
+/// Tarball `src` into `dest`, encrypting the tarball if a key is provided.
+pub fn to_tar_gz(
+    src: impl AsRef<Path>,
+    dest: impl AsRef<Path>,
+    encryption_key: Option<&EncryptionKey>,
+) -> anyhow::Result<()> {
     let mut f = File::create(dest)?;
-    let gz_encoder = GzEncoder::new(&mut f, Compression::default());
+    match encryption_key {
+        Some(key) => {
+            let mut writer = EncryptWriter::new(&mut f, key)?;
+            write_tar_gz(src, &mut writer)?;
+            writer.finish()?;
+        }
+        None => write_tar_gz(src, &mut f)?,
+    }
+    f.flush()?;
+    Ok(())
+}
+
+fn write_tar_gz(src: impl AsRef<Path>, writer: impl Write) -> anyhow::Result<()> {
+    let gz_encoder = GzEncoder::new(writer, Compression::default());
     let mut tar_encoder = Builder::new(gz_encoder);
     tar_encoder.append_dir_all(".", src)?;
     let gz_encoder = tar_encoder.into_inner()?;
     gz_encoder.finish()?;
-    f.flush()?;
     Ok(())
 }
 
-pub fn from_tar_gz(src: impl AsRef<Path>, dest: impl AsRef<Path>) -> anyhow::Result<()> {
-    let f = File::open(&src)?;
+/// Unpack the tarball `src` into `dest`, decrypting it first if it is encrypted.
+pub fn from_tar_gz(
+    src: impl AsRef<Path>,
+    dest: impl AsRef<Path>,
+    encryption_key: Option<&EncryptionKey>,
+) -> anyhow::Result<()> {
+    let f = decrypted_reader(File::open(&src)?, encryption_key)?;
     let gz = GzDecoder::new(f);
     let mut ar = Archive::new(gz);
     create_dir_all(&dest)?;
     ar.unpack(&dest)?;
     Ok(())
 }
+
+/// Only unpack the entries of the tarball located under `dir` in `dest`.
+pub fn from_tar_gz_filtered(
+    src: impl AsRef<Path>,
+    dir: impl AsRef<Path>,
+    dest: impl AsRef<Path>,
+    encryption_key: Option<&EncryptionKey>,
+) -> anyhow::Result<()> {
+    let f = decrypted_reader(File::open(&src)?, encryption_key)?;
+    let gz = GzDecoder::new(f);
+    let mut ar = Archive::new(gz);
+    create_dir_all(&dest)?;
+    for entry in ar.entries()? {
+        let mut entry = entry?;
+        let in_dir = {
+            let path = entry.path()?;
+            // the entries of the tarballs we generate are all prefixed by `./`
+            path.strip_prefix(".").unwrap_or(&path).starts_with(&dir)
+        };
+        if in_dir {
+            entry.unpack_in(&dest)?;
+        }
+    }
+    Ok(())
+}
@@ -6,12 +6,15 @@ use std::ops::ControlFlow;
 use deserr::errors::{JsonError, QueryParamError};
 use deserr::{take_cf_content, DeserializeError, IntoValue, MergeWithError, ValuePointerRef};
 
+use crate::audit_log::ParseAuditLogKeyUidError;
 use crate::error::deserr_codes::*;
 use crate::error::{
     Code, DeserrParseBoolError, DeserrParseIntError, ErrorCode, InvalidTaskDateError,
     ParseOffsetDateTimeError,
 };
 use crate::index_uid::IndexUidFormatError;
+use crate::ip_cidr::IpCidrFormatError;
+use crate::keys::ParseActionError;
 use crate::tasks::{ParseTaskKindError, ParseTaskStatusError};
 
 pub mod query_params;
@@ -150,6 +153,9 @@ make_missing_field_convenience_builder!(MissingApiKeyActions, missing_api_key_ac
 make_missing_field_convenience_builder!(MissingApiKeyExpiresAt, missing_api_key_expires_at);
 make_missing_field_convenience_builder!(MissingApiKeyIndexes, missing_api_key_indexes);
 make_missing_field_convenience_builder!(MissingSwapIndexes, missing_swap_indexes);
+make_missing_field_convenience_builder!(MissingAutocompleteQ, missing_autocomplete_q);
+make_missing_field_convenience_builder!(MissingIndexAlias, missing_index_alias);
+make_missing_field_convenience_builder!(MissingIndexImportDumpUid, missing_index_import_dump_uid);
 make_missing_field_convenience_builder!(MissingDocumentFilter, missing_document_filter);
 make_missing_field_convenience_builder!(
     MissingFacetSearchFacetName,
@@ -191,7 +197,10 @@ merge_with_error_impl_take_error_message!(InvalidTaskDateError);
 merge_with_error_impl_take_error_message!(ParseOffsetDateTimeError);
 merge_with_error_impl_take_error_message!(ParseTaskKindError);
 merge_with_error_impl_take_error_message!(ParseTaskStatusError);
+merge_with_error_impl_take_error_message!(ParseActionError);
+merge_with_error_impl_take_error_message!(ParseAuditLogKeyUidError);
 merge_with_error_impl_take_error_message!(IndexUidFormatError);
+merge_with_error_impl_take_error_message!(IpCidrFormatError);
 merge_with_error_impl_take_error_message!(InvalidMultiSearchWeight);
 merge_with_error_impl_take_error_message!(InvalidNetworkUrl);
 merge_with_error_impl_take_error_message!(InvalidNetworkSearchApiKey);
@@ -16,9 +16,12 @@ use std::ops::Deref;
 use std::str::FromStr;
 
 use deserr::{DeserializeError, Deserr, MergeWithError, ValueKind};
+use uuid::Uuid;
 
 use super::{DeserrParseBoolError, DeserrParseIntError};
+use crate::audit_log::AuditLogKeyUid;
 use crate::index_uid::IndexUid;
+use crate::keys::Action;
 use crate::tasks::{Kind, Status};
 
 /// A wrapper type indicating that the inner value should be
@@ -88,6 +91,9 @@ macro_rules! impl_from_query_param_from_str {
 impl_from_query_param_from_str!(Kind);
 impl_from_query_param_from_str!(Status);
 impl_from_query_param_from_str!(IndexUid);
+impl_from_query_param_from_str!(Action);
+impl_from_query_param_from_str!(Uuid);
+impl_from_query_param_from_str!(AuditLogKeyUid);
 
 /// Implement `FromQueryParameter` for the given type using its `FromStr`
 /// trait implementation, replacing the returned error with a struct
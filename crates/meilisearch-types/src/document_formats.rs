@@ -150,7 +150,7 @@ fn parse_csv_header(header: &str) -> (&str, AllowedType) {
 }
 
 /// Reads CSV from file and write it in NDJSON in a file checking it along the way.
-pub fn read_csv(input: &File, output: impl io::Write, delimiter: u8) -> Result<u64> {
+pub fn read_csv(input: impl io::Read, output: impl io::Write, delimiter: u8) -> Result<u64> {
     let ptype = PayloadType::Csv { delimiter };
     let mut output = BufWriter::new(output);
     let mut reader = csv::ReaderBuilder::new().delimiter(delimiter).from_reader(input);
@@ -215,10 +215,15 @@ pub fn read_json(input: &File, output: impl io::Write) -> Result<u64> {
     // We memory map to be able to deserialize into a RawMap that
     // does not allocate when possible and only materialize the first/top level.
     let input = unsafe { Mmap::map(input).map_err(DocumentFormatError::Io)? };
+    read_json_bytes(&input, output)
+}
+
+/// Reads JSON from memory and write it in NDJSON in a file checking it along the way.
+pub fn read_json_bytes(input: &[u8], output: impl io::Write) -> Result<u64> {
     let mut doc_alloc = Bump::with_capacity(1024 * 1024); // 1MiB
 
     let mut out = BufWriter::new(output);
-    let mut deserializer = serde_json::Deserializer::from_slice(&input);
+    let mut deserializer = serde_json::Deserializer::from_slice(input);
     let res = array_each(&mut deserializer, |obj: &RawValue| {
         doc_alloc.reset();
         let map = RawMap::from_raw_value_and_hasher(obj, FxBuildHasher, &doc_alloc)?;
@@ -236,7 +241,7 @@ pub fn read_json(input: &File, output: impl io::Write) -> Result<u64> {
                 return Err(DocumentFormatError::from((PayloadType::Json, e)));
             }
 
-            let content: Object = serde_json::from_slice(&input)
+            let content: Object = serde_json::from_slice(input)
                 .map_err(Error::Json)
                 .map_err(|e| (PayloadType::Json, e))?;
             to_writer(&mut out, &content)
@@ -256,10 +261,15 @@ pub fn read_ndjson(input: &File) -> Result<u64> {
     // We memory map to be able to deserialize into a RawMap that
     // does not allocate when possible and only materialize the first/top level.
     let input = unsafe { Mmap::map(input).map_err(DocumentFormatError::Io)? };
+    read_ndjson_bytes(&input)
+}
+
+/// Reads NDJSON from memory and checks it.
+pub fn read_ndjson_bytes(input: &[u8]) -> Result<u64> {
     let mut bump = Bump::with_capacity(1024 * 1024);
 
     let mut count = 0;
-    for result in serde_json::Deserializer::from_slice(&input).into_iter() {
+    for result in serde_json::Deserializer::from_slice(input).into_iter() {
         bump.reset();
         match result {
             Ok(raw) => {
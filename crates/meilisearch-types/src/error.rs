@@ -28,6 +28,10 @@ pub struct ResponseError {
     /// A link to the documentation about this specific error.
     #[serde(rename = "link")]
     error_link: String,
+    /// The number of seconds after which the request can be retried.
+    #[serde(skip)]
+    #[schema(ignore)]
+    retry_after: Option<u64>,
 }
 
 impl ResponseError {
@@ -41,8 +45,15 @@ impl ResponseError {
             error_code: code.name(),
             error_type: code.type_(),
             error_link: code.url(),
+            retry_after: None,
         }
     }
+
+    /// Sends a `Retry-After` header with the given number of seconds along with the error.
+    pub fn with_retry_after(mut self, seconds: u64) -> Self {
+        self.retry_after = Some(seconds);
+        self
+    }
 }
 
 impl fmt::Display for ResponseError {
@@ -68,7 +79,9 @@ impl aweb::error::ResponseError for ResponseError {
         let mut builder = HttpResponseBuilder::new(self.status_code());
         builder.content_type("application/json");
 
-        if self.code == StatusCode::SERVICE_UNAVAILABLE {
+        if let Some(seconds) = self.retry_after {
+            builder.insert_header((header::RETRY_AFTER, seconds.to_string()));
+        } else if self.code == StatusCode::SERVICE_UNAVAILABLE {
             builder.insert_header((header::RETRY_AFTER, "10"));
         }
 
@@ -196,6 +209,7 @@ macro_rules! make_error_codes {
 make_error_codes! {
 ApiKeyAlreadyExists                   , InvalidRequest       , CONFLICT ;
 ApiKeyNotFound                        , InvalidRequest       , NOT_FOUND ;
+ApiKeyOriginNotAllowed                , Auth                 , FORBIDDEN ;
 BadParameter                          , InvalidRequest       , BAD_REQUEST;
 BadRequest                            , InvalidRequest       , BAD_REQUEST;
 DatabaseSizeLimitReached              , Internal             , INTERNAL_SERVER_ERROR;
@@ -214,6 +228,8 @@ ImmutableApiKeyUpdatedAt              , InvalidRequest       , BAD_REQUEST;
 ImmutableIndexCreatedAt               , InvalidRequest       , BAD_REQUEST;
 ImmutableIndexUid                     , InvalidRequest       , BAD_REQUEST;
 ImmutableIndexUpdatedAt               , InvalidRequest       , BAD_REQUEST;
+IndexAliasConflict                    , InvalidRequest       , CONFLICT ;
+IndexAliasNotFound                    , InvalidRequest       , NOT_FOUND ;
 IndexAlreadyExists                    , InvalidRequest       , CONFLICT ;
 IndexCreationFailed                   , Internal             , INTERNAL_SERVER_ERROR;
 IndexNotFound                         , InvalidRequest       , NOT_FOUND;
@@ -223,13 +239,28 @@ IndexPrimaryKeyNoCandidateFound       , InvalidRequest       , BAD_REQUEST ;
 Internal                              , Internal             , INTERNAL_SERVER_ERROR ;
 InvalidApiKey                         , Auth                 , FORBIDDEN ;
 InvalidApiKeyActions                  , InvalidRequest       , BAD_REQUEST ;
+InvalidApiKeyAllowedIps               , InvalidRequest       , BAD_REQUEST ;
+InvalidApiKeyAllowedOrigins           , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyDescription              , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyExpiresAt                , InvalidRequest       , BAD_REQUEST ;
+InvalidApiKeyGracePeriod              , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyIndexes                  , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyLimit                    , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyName                     , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyOffset                   , InvalidRequest       , BAD_REQUEST ;
+InvalidApiKeyRateLimits               , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyUid                      , InvalidRequest       , BAD_REQUEST ;
+InvalidAuditLogActions                , InvalidRequest       , BAD_REQUEST ;
+InvalidAuditLogAfterDate              , InvalidRequest       , BAD_REQUEST ;
+InvalidAuditLogBeforeDate             , InvalidRequest       , BAD_REQUEST ;
+InvalidAuditLogFrom                   , InvalidRequest       , BAD_REQUEST ;
+InvalidAuditLogIndexUids              , InvalidRequest       , BAD_REQUEST ;
+InvalidAuditLogKeyUids                , InvalidRequest       , BAD_REQUEST ;
+InvalidAuditLogLimit                  , InvalidRequest       , BAD_REQUEST ;
+InvalidAutocompleteFilter             , InvalidRequest       , BAD_REQUEST ;
+InvalidAutocompleteLimit              , InvalidRequest       , BAD_REQUEST ;
+InvalidAutocompleteLocales            , InvalidRequest       , BAD_REQUEST ;
+InvalidAutocompleteQ                  , InvalidRequest       , BAD_REQUEST ;
 InvalidContentType                    , InvalidRequest       , UNSUPPORTED_MEDIA_TYPE ;
 InvalidDocumentCsvDelimiter           , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentFields                 , InvalidRequest       , BAD_REQUEST ;
@@ -247,6 +278,10 @@ InvalidDocumentOffset                 , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchEmbedder                 , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarEmbedder                , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHybridQuery              , InvalidRequest       , BAD_REQUEST ;
+InvalidIndexAlias                     , InvalidRequest       , BAD_REQUEST ;
+InvalidIndexAliasDuplicateFound       , InvalidRequest       , BAD_REQUEST ;
+InvalidIndexAliasIndexUid             , InvalidRequest       , BAD_REQUEST ;
+InvalidIndexImportDumpUid             , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexLimit                     , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexOffset                    , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexPrimaryKey                , InvalidRequest       , BAD_REQUEST ;
@@ -258,10 +293,13 @@ InvalidMultiSearchFederated           , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchFederationOptions   , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchMaxValuesPerFacet   , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchMergeFacets         , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchQueryCollapse       , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchQueryExplain        , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchQueryFacets         , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchQueryPagination     , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchQueryRankingRules   , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchQueryPosition       , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchQuerySuggestions    , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchRemote              , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchWeight              , InvalidRequest       , BAD_REQUEST ;
 InvalidNetworkRemotes                 , InvalidRequest       , BAD_REQUEST ;
@@ -297,6 +335,7 @@ InvalidSimilarOffset                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchOffset                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchPage                     , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchQ                        , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchQuerySyntax              , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchQuery               , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchName                , InvalidRequest       , BAD_REQUEST ;
 FacetSearchDisabled                   , InvalidRequest       , BAD_REQUEST ;
@@ -307,7 +346,11 @@ InvalidSimilarShowRankingScore        , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowRankingScoreDetails  , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarShowRankingScoreDetails , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSort                     , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchSuggestions              , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchExplain                  , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchExplainDocuments         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchDistinct                 , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchCollapse                 , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDisplayedAttributes    , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDistinctAttribute      , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsProximityPrecision     , InvalidRequest       , BAD_REQUEST ;
@@ -315,7 +358,9 @@ InvalidSettingsFacetSearch            , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsPrefixSearch           , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFaceting               , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFilterableAttributes   , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsNgramAttributes        , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsPagination             , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsQueryRules             , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSearchCutoffMs         , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsEmbedders              , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsRankingRules           , InvalidRequest       , BAD_REQUEST ;
@@ -339,6 +384,7 @@ InvalidTaskBeforeEnqueuedAt           , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskBeforeFinishedAt           , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskBeforeStartedAt            , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskCanceledBy                 , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskKeyUids                    , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskFrom                       , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskLimit                      , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskReverse                    , InvalidRequest       , BAD_REQUEST ;
@@ -354,9 +400,12 @@ MissingApiKeyActions                  , InvalidRequest       , BAD_REQUEST ;
 MissingApiKeyExpiresAt                , InvalidRequest       , BAD_REQUEST ;
 MissingApiKeyIndexes                  , InvalidRequest       , BAD_REQUEST ;
 MissingAuthorizationHeader            , Auth                 , UNAUTHORIZED ;
+MissingAutocompleteQ                  , InvalidRequest       , BAD_REQUEST ;
 MissingContentType                    , InvalidRequest       , UNSUPPORTED_MEDIA_TYPE ;
 MissingDocumentId                     , InvalidRequest       , BAD_REQUEST ;
 MissingFacetSearchFacetName           , InvalidRequest       , BAD_REQUEST ;
+MissingIndexAlias                     , InvalidRequest       , BAD_REQUEST ;
+MissingIndexImportDumpUid             , InvalidRequest       , BAD_REQUEST ;
 MissingIndexUid                       , InvalidRequest       , BAD_REQUEST ;
 MissingMasterKey                      , Auth                 , UNAUTHORIZED ;
 MissingNetworkUrl                     , InvalidRequest       , BAD_REQUEST ;
@@ -373,6 +422,7 @@ RemoteInvalidApiKey                   , Auth                 , FORBIDDEN ;
 RemoteRemoteError                     , System               , BAD_GATEWAY ;
 RemoteTimeout                         , System               , BAD_GATEWAY ;
 TooManySearchRequests                 , System               , SERVICE_UNAVAILABLE ;
+TooManyRequests                       , Auth                 , TOO_MANY_REQUESTS ;
 TaskNotFound                          , InvalidRequest       , NOT_FOUND ;
 TaskFileNotFound                      , InvalidRequest       , NOT_FOUND ;
 BatchNotFound                         , InvalidRequest       , NOT_FOUND ;
@@ -443,16 +493,21 @@ impl ErrorCode for milli::Error {
                     }
                     UserError::PrimaryKeyCannotBeChanged(_) => Code::IndexPrimaryKeyAlreadyExists,
                     UserError::InvalidDistinctAttribute { .. } => Code::InvalidSearchDistinct,
+                    UserError::InvalidCollapseAttribute { .. } => Code::InvalidSearchCollapse,
                     UserError::SortRankingRuleMissing => Code::InvalidSearchSort,
                     UserError::InvalidFacetsDistribution { .. } => Code::InvalidSearchFacets,
                     UserError::InvalidSortableAttribute { .. } => Code::InvalidSearchSort,
                     UserError::InvalidSearchableAttribute { .. } => {
                         Code::InvalidSearchAttributesToSearchOn
                     }
+                    UserError::InvalidSearchQueryAttribute { .. }
+                    | UserError::InvalidAdvancedQuery(_)
+                    | UserError::InvalidPhraseSlop { .. } => Code::InvalidSearchQ,
                     UserError::InvalidFacetSearchFacetName { .. } => {
                         Code::InvalidFacetSearchFacetName
                     }
                     UserError::CriterionError(_) => Code::InvalidSettingsRankingRules,
+                    UserError::InvalidQueryRule { .. } => Code::InvalidSettingsQueryRules,
                     UserError::InvalidGeoField { .. } => Code::InvalidDocumentGeoField,
                     UserError::InvalidVectorDimensions { .. }
                     | UserError::InvalidIndexingVectorDimensions { .. } => {
@@ -487,7 +542,10 @@ impl ErrorCode for file_store::Error {
         match self {
             Self::IoError(e) => e.error_code(),
             Self::PersistError(e) => e.error_code(),
-            Self::CouldNotParseFileNameAsUtf8 | Self::UuidError(_) => Code::Internal,
+            Self::UpdateFileTooLarge { .. } => Code::PayloadTooLarge,
+            Self::CouldNotParseFileNameAsUtf8 | Self::InvalidEncryptionKey | Self::UuidError(_) => {
+                Code::Internal
+            }
         }
     }
 }
@@ -0,0 +1,138 @@
+use std::error::Error;
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use deserr::Deserr;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Code, ErrorCode};
+
+/// A range of IP addresses in the CIDR notation, e.g. `10.0.0.0/8` or `fd00::/8`.
+/// A single address without a prefix length is a range containing only itself.
+#[derive(Serialize, Deserialize, Deserr, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+#[deserr(try_from(&String) = FromStr::from_str -> IpCidrFormatError)]
+pub struct IpCidr {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpCidr {
+    /// Returns wether this address is part of the range.
+    ///
+    /// IPv4 addresses mapped in IPv6, e.g. `::ffff:10.0.0.1`, are matched as IPv4 addresses.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.addr, ip.to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix_len as u32).unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix_len as u32).unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for IpCidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+impl FromStr for IpCidr {
+    type Err = IpCidrFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || IpCidrFormatError { invalid_range: s.to_string() };
+        let (addr, prefix_len) = match s.trim().split_once('/') {
+            Some((addr, prefix_len)) => (addr, Some(prefix_len)),
+            None => (s.trim(), None),
+        };
+        let addr = IpAddr::from_str(addr).map_err(|_| error())?.to_canonical();
+        let max_prefix_len = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match prefix_len {
+            Some(prefix_len) => prefix_len.parse().map_err(|_| error())?,
+            None => max_prefix_len,
+        };
+        if prefix_len > max_prefix_len {
+            return Err(error());
+        }
+
+        Ok(IpCidr { addr, prefix_len })
+    }
+}
+
+impl TryFrom<String> for IpCidr {
+    type Error = IpCidrFormatError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<IpCidr> for String {
+    fn from(cidr: IpCidr) -> Self {
+        cidr.to_string()
+    }
+}
+
+#[derive(Debug)]
+pub struct IpCidrFormatError {
+    pub invalid_range: String,
+}
+
+impl fmt::Display for IpCidrFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not a valid IP range. IP ranges must be an IPv4 or IPv6 address, \
+            optionally followed by a prefix length, e.g. `10.0.0.0/8`.",
+            self.invalid_range,
+        )
+    }
+}
+
+impl Error for IpCidrFormatError {}
+
+impl ErrorCode for IpCidrFormatError {
+    fn error_code(&self) -> Code {
+        Code::InvalidApiKeyAllowedIps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains() {
+        let range = IpCidr::from_str("10.1.0.0/16").unwrap();
+        assert!(range.contains("10.1.42.3".parse().unwrap()));
+        assert!(range.contains("::ffff:10.1.0.1".parse().unwrap()));
+        assert!(!range.contains("10.2.0.1".parse().unwrap()));
+        assert!(!range.contains("fd00::1".parse().unwrap()));
+
+        let range = IpCidr::from_str("fd00::/8").unwrap();
+        assert!(range.contains("fd12:3456::1".parse().unwrap()));
+        assert!(!range.contains("fe80::1".parse().unwrap()));
+
+        let range = IpCidr::from_str("192.168.1.12").unwrap();
+        assert_eq!(range.to_string(), "192.168.1.12/32");
+        assert!(range.contains("192.168.1.12".parse().unwrap()));
+        assert!(!range.contains("192.168.1.13".parse().unwrap()));
+
+        let range = IpCidr::from_str("0.0.0.0/0").unwrap();
+        assert!(range.contains("203.0.113.7".parse().unwrap()));
+    }
+
+    #[test]
+    fn invalid_ranges() {
+        for invalid in ["", "10.0.0.0/33", "fd00::/129", "10.0.0/8", "localhost", "10.0.0.0/"] {
+            assert!(IpCidr::from_str(invalid).is_err(), "{invalid}");
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use std::convert::Infallible;
+use std::fmt;
 use std::hash::Hash;
 use std::str::FromStr;
 
@@ -16,6 +17,7 @@ use crate::deserr::{immutable_field_error, DeserrError, DeserrJsonError};
 use crate::error::deserr_codes::*;
 use crate::error::{Code, ErrorCode, ParseOffsetDateTimeError};
 use crate::index_uid_pattern::{IndexUidPattern, IndexUidPatternFormatError};
+use crate::ip_cidr::IpCidr;
 
 pub type KeyId = Uuid;
 
@@ -60,11 +62,33 @@ pub struct CreateApiKey {
     /// Represent the expiration date and time as RFC 3339 format. `null` equals to no expiration time.
     #[deserr(error = DeserrJsonError<InvalidApiKeyExpiresAt>, try_from(Option<String>) = parse_expiration_date -> ParseOffsetDateTimeError, missing_field_error = DeserrJsonError::missing_api_key_expires_at)]
     pub expires_at: Option<OffsetDateTime>,
+    /// The limits enforced on the requests made with the key. `null` for no limit.
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyRateLimits>)]
+    #[schema(value_type = Option<RateLimits>, example = json!(null))]
+    pub rate_limits: Option<RateLimits>,
+    /// The IP ranges, in the CIDR notation, from which the key can be used. `null` to allow any address.
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyAllowedIps>)]
+    #[schema(value_type = Option<Vec<String>>, example = json!(["10.0.0.0/8"]))]
+    pub allowed_ips: Option<Vec<IpCidr>>,
+    /// The origins, e.g. `https://admin.example.com`, from which the key can be used. `null` to allow any origin.
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyAllowedOrigins>)]
+    #[schema(example = json!(null))]
+    pub allowed_origins: Option<Vec<String>>,
 }
 
 impl CreateApiKey {
     pub fn to_key(self) -> Key {
-        let CreateApiKey { description, name, uid, actions, indexes, expires_at } = self;
+        let CreateApiKey {
+            description,
+            name,
+            uid,
+            actions,
+            indexes,
+            expires_at,
+            rate_limits,
+            allowed_ips,
+            allowed_origins,
+        } = self;
         let now = OffsetDateTime::now_utc();
         Key {
             description,
@@ -73,12 +97,66 @@ impl CreateApiKey {
             actions,
             indexes,
             expires_at,
+            rate_limits,
+            allowed_ips,
+            allowed_origins,
+            salt: None,
+            previous_secret: None,
             created_at: now,
             updated_at: now,
         }
     }
 }
 
+/// The limits enforced on the requests made with an API key or the tenant tokens it signed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Deserr, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidApiKeyRateLimits>, rename_all = camelCase, deny_unknown_fields, validate = validate_rate_limits -> DeserrJsonError<InvalidApiKeyRateLimits>)]
+#[schema(rename_all = "camelCase")]
+pub struct RateLimits {
+    /// The maximum number of requests per second, at least `1`. `null` for no limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    #[schema(example = 20)]
+    pub requests_per_second: Option<u32>,
+    /// The maximum number of bytes sent in the payloads of the requests per UTC day. `null` for no limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    #[schema(example = 104857600)]
+    pub max_payload_bytes_per_day: Option<u64>,
+    /// The maximum number of searches running at the same time. `null` for no limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    #[schema(example = 4)]
+    pub max_concurrent_searches: Option<u32>,
+}
+
+fn validate_rate_limits<E: DeserializeError>(
+    limits: RateLimits,
+    location: ValuePointerRef,
+) -> Result<RateLimits, E> {
+    if limits.requests_per_second == Some(0) {
+        return Err(deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            deserr::ErrorKind::Unexpected {
+                msg: "`requestsPerSecond` must be greater than `0`.".to_string(),
+            },
+            location,
+        )));
+    }
+    Ok(limits)
+}
+
+impl RateLimits {
+    pub fn is_unlimited(&self) -> bool {
+        let RateLimits { requests_per_second, max_payload_bytes_per_day, max_concurrent_searches } =
+            self;
+        requests_per_second.is_none()
+            && max_payload_bytes_per_day.is_none()
+            && max_concurrent_searches.is_none()
+    }
+}
+
 fn deny_immutable_fields_api_key(
     field: &str,
     accepted: &[&str],
@@ -109,6 +187,25 @@ pub struct PatchApiKey {
     #[deserr(default, error = DeserrJsonError<InvalidApiKeyName>)]
     #[schema(value_type = Option<String>, example = "Indexing Products API key")]
     pub name: Setting<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyRateLimits>)]
+    #[schema(value_type = Option<RateLimits>)]
+    pub rate_limits: Setting<RateLimits>,
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyAllowedIps>)]
+    #[schema(value_type = Option<Vec<String>>)]
+    pub allowed_ips: Setting<Vec<IpCidr>>,
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyAllowedOrigins>)]
+    #[schema(value_type = Option<Vec<String>>)]
+    pub allowed_origins: Setting<Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserr, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct RotateApiKey {
+    /// The number of seconds during which the previous value of the key is still accepted. `0` by default.
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyGracePeriod>)]
+    #[schema(example = 3600)]
+    pub grace_period: u32,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
@@ -122,6 +219,17 @@ pub struct Key {
     pub indexes: Vec<IndexUidPattern>,
     #[serde(with = "time::serde::rfc3339::option")]
     pub expires_at: Option<OffsetDateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limits: Option<RateLimits>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_ips: Option<Vec<IpCidr>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_origins: Option<Vec<String>>,
+    /// Mixed in the derivation of the value of the key, `None` for the keys that were never rotated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub salt: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_secret: Option<PreviousSecret>,
     #[serde(with = "time::serde::rfc3339")]
     pub created_at: OffsetDateTime,
     #[serde(with = "time::serde::rfc3339")]
@@ -139,6 +247,11 @@ impl Key {
             actions: vec![Action::All],
             indexes: vec![IndexUidPattern::all()],
             expires_at: None,
+            rate_limits: None,
+            allowed_ips: None,
+            allowed_origins: None,
+            salt: None,
+            previous_secret: None,
             created_at: now,
             updated_at: now,
         }
@@ -154,10 +267,34 @@ impl Key {
             actions: vec![Action::Search],
             indexes: vec![IndexUidPattern::all()],
             expires_at: None,
+            rate_limits: None,
+            allowed_ips: None,
+            allowed_origins: None,
+            salt: None,
+            previous_secret: None,
             created_at: now,
             updated_at: now,
         }
     }
+
+    /// Returns the salts of the values of the key accepted at this date, the current one first.
+    pub fn valid_salts(&self, now: OffsetDateTime) -> impl Iterator<Item = Option<&str>> {
+        let previous = self
+            .previous_secret
+            .as_ref()
+            .filter(|previous| previous.valid_until > now)
+            .map(|previous| previous.salt.as_deref());
+        std::iter::once(self.salt.as_deref()).chain(previous)
+    }
+}
+
+/// The value of a key before its last rotation, still accepted until the end of the grace period.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PreviousSecret {
+    /// `None` if the key had never been rotated before.
+    pub salt: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub valid_until: OffsetDateTime,
 }
 
 fn parse_expiration_date(
@@ -308,6 +445,12 @@ pub enum Action {
     #[serde(rename = "network.update")]
     #[deserr(rename = "network.update")]
     NetworkUpdate,
+    #[serde(rename = "auditLogs.get")]
+    #[deserr(rename = "auditLogs.get")]
+    AuditLogsGet,
+    #[serde(rename = "tasks.own")]
+    #[deserr(rename = "tasks.own")]
+    TasksOwn,
 }
 
 impl Action {
@@ -349,6 +492,8 @@ impl Action {
             EXPERIMENTAL_FEATURES_UPDATE => Some(Self::ExperimentalFeaturesUpdate),
             NETWORK_GET => Some(Self::NetworkGet),
             NETWORK_UPDATE => Some(Self::NetworkUpdate),
+            AUDIT_LOGS_GET => Some(Self::AuditLogsGet),
+            TASKS_OWN => Some(Self::TasksOwn),
             _otherwise => None,
         }
     }
@@ -358,6 +503,35 @@ impl Action {
     }
 }
 
+impl FromStr for Action {
+    type Err = ParseActionError;
+
+    fn from_str(action: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(action.to_owned()))
+            .map_err(|_| ParseActionError(action.to_owned()))
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseActionError(pub String);
+
+impl fmt::Display for ParseActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not a valid action. Available actions are {}.",
+            self.0,
+            enum_iterator::all::<Action>()
+                .filter_map(|action| serde_json::to_value(action).ok())
+                .filter_map(|action| action.as_str().map(|action| format!("`{action}`")))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+}
+
+impl std::error::Error for ParseActionError {}
+
 pub mod actions {
     use super::Action::*;
 
@@ -397,4 +571,8 @@ pub mod actions {
 
     pub const NETWORK_GET: u8 = NetworkGet.repr();
     pub const NETWORK_UPDATE: u8 = NetworkUpdate.repr();
+
+    pub const AUDIT_LOGS_GET: u8 = AuditLogsGet.repr();
+
+    pub const TASKS_OWN: u8 = TasksOwn.repr();
 }
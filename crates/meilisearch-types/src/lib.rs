@@ -1,3 +1,4 @@
+pub mod audit_log;
 pub mod batch_view;
 pub mod batches;
 pub mod compression;
@@ -8,9 +9,12 @@ pub mod facet_values_sort;
 pub mod features;
 pub mod index_uid;
 pub mod index_uid_pattern;
+pub mod ip_cidr;
 pub mod keys;
 pub mod locales;
+pub mod query_rules;
 pub mod settings;
+pub mod snapshot;
 pub mod star_or;
 pub mod task_view;
 pub mod tasks;
@@ -0,0 +1,66 @@
+use deserr::Deserr;
+use milli::{QueryPattern, QueryRule, QueryRuleConditions, QueryRuleConsequences};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::locales::Locale;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserr, Serialize, Deserialize, ToSchema)]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct QueryRuleView {
+    /// The identifier of the rule, reported in the search responses it is applied to.
+    pub uid: String,
+    #[serde(default)]
+    #[deserr(default)]
+    pub conditions: QueryRuleConditionsView,
+    pub consequences: QueryRuleConsequences,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserr, Serialize, Deserialize, ToSchema)]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct QueryRuleConditionsView {
+    /// The query of the search must match this pattern.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub query: Option<QueryPattern>,
+    /// Filter expressions that must all be part of the filter of the search.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[deserr(default)]
+    pub filters: Vec<String>,
+    /// The search must be restricted to one of these locales.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[deserr(default)]
+    pub locales: Vec<Locale>,
+}
+
+impl From<QueryRule> for QueryRuleView {
+    fn from(rule: QueryRule) -> Self {
+        let QueryRuleConditions { query, filters, locales } = rule.conditions;
+        Self {
+            uid: rule.uid,
+            conditions: QueryRuleConditionsView {
+                query,
+                filters,
+                locales: locales.into_iter().map(|l| l.into()).collect(),
+            },
+            consequences: rule.consequences,
+        }
+    }
+}
+
+impl From<QueryRuleView> for QueryRule {
+    fn from(view: QueryRuleView) -> Self {
+        let QueryRuleConditionsView { query, filters, locales } = view.conditions;
+        Self {
+            uid: view.uid,
+            conditions: QueryRuleConditions {
+                query,
+                filters,
+                locales: locales.into_iter().map(|l| l.into()).collect(),
+            },
+            consequences: view.consequences,
+        }
+    }
+}
@@ -20,6 +20,7 @@ use crate::deserr::DeserrJsonError;
 use crate::error::deserr_codes::*;
 use crate::facet_values_sort::FacetValuesSort;
 use crate::locales::LocalizedAttributesRuleView;
+use crate::query_rules::QueryRuleView;
 
 /// The maximum number of results that the engine
 /// will be able to return in one search call.
@@ -293,6 +294,16 @@ pub struct Settings<T> {
     #[deserr(default, error = DeserrJsonError<InvalidSettingsPrefixSearch>)]
     #[schema(value_type = Option<PrefixSearchSettings>, example = json!("Hemlo"))]
     pub prefix_search: Setting<PrefixSearchSettings>,
+    /// Attributes in which words can also be matched by one of their infixes or suffixes.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsNgramAttributes>)]
+    #[schema(value_type = Option<Vec<String>>, example = json!(["sku", "title"]))]
+    pub ngram_attributes: Setting<BTreeSet<String>>,
+    /// Rules pinning, hiding or filtering documents for the searches matching their conditions.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsQueryRules>)]
+    #[schema(value_type = Option<Vec<QueryRuleView>>)]
+    pub query_rules: Setting<Vec<QueryRuleView>>,
 
     #[serde(skip)]
     #[deserr(skip)]
@@ -359,6 +370,8 @@ impl Settings<Checked> {
             localized_attributes: Setting::Reset,
             facet_search: Setting::Reset,
             prefix_search: Setting::Reset,
+            ngram_attributes: Setting::Reset,
+            query_rules: Setting::Reset,
             _kind: PhantomData,
         }
     }
@@ -385,6 +398,8 @@ impl Settings<Checked> {
             localized_attributes: localized_attributes_rules,
             facet_search,
             prefix_search,
+            ngram_attributes,
+            query_rules,
             _kind,
         } = self;
 
@@ -409,6 +424,8 @@ impl Settings<Checked> {
             localized_attributes: localized_attributes_rules,
             facet_search,
             prefix_search,
+            ngram_attributes,
+            query_rules,
             _kind: PhantomData,
         }
     }
@@ -459,6 +476,8 @@ impl Settings<Unchecked> {
             localized_attributes: self.localized_attributes,
             facet_search: self.facet_search,
             prefix_search: self.prefix_search,
+            ngram_attributes: self.ngram_attributes,
+            query_rules: self.query_rules,
             _kind: PhantomData,
         }
     }
@@ -535,6 +554,8 @@ impl Settings<Unchecked> {
             },
             prefix_search: other.prefix_search.or(self.prefix_search),
             facet_search: other.facet_search.or(self.facet_search),
+            ngram_attributes: other.ngram_attributes.clone().or(self.ngram_attributes.clone()),
+            query_rules: other.query_rules.clone().or(self.query_rules.clone()),
             _kind: PhantomData,
         }
     }
@@ -573,6 +594,8 @@ pub fn apply_settings_to_builder(
         localized_attributes: localized_attributes_rules,
         facet_search,
         prefix_search,
+        ngram_attributes,
+        query_rules,
         _kind,
     } = settings;
 
@@ -783,6 +806,20 @@ pub fn apply_settings_to_builder(
         Setting::Reset => builder.reset_facet_search(),
         Setting::NotSet => (),
     }
+
+    match ngram_attributes {
+        Setting::Set(ref attrs) => builder.set_ngram_attributes(attrs.iter().cloned().collect()),
+        Setting::Reset => builder.reset_ngram_attributes(),
+        Setting::NotSet => (),
+    }
+
+    match query_rules {
+        Setting::Set(rules) => {
+            builder.set_query_rules(rules.iter().cloned().map(|rule| rule.into()).collect())
+        }
+        Setting::Reset => builder.reset_query_rules(),
+        Setting::NotSet => (),
+    }
 }
 
 pub enum SecretPolicy {
@@ -889,6 +926,10 @@ pub fn settings(
 
     let facet_search = index.facet_search(rtxn)?;
 
+    let ngram_attributes = index.ngram_attributes(rtxn)?.into_iter().map(String::from).collect();
+
+    let query_rules = index.query_rules(rtxn)?.into_iter().map(|rule| rule.into()).collect();
+
     let mut settings = Settings {
         displayed_attributes: match displayed_attributes {
             Some(attrs) => Setting::Set(attrs),
@@ -927,6 +968,8 @@ pub fn settings(
         },
         prefix_search: Setting::Set(prefix_search.unwrap_or_default()),
         facet_search: Setting::Set(facet_search),
+        ngram_attributes: Setting::Set(ngram_attributes),
+        query_rules: Setting::Set(query_rules),
         _kind: PhantomData,
     };
 
@@ -954,6 +997,9 @@ pub enum RankingRuleView {
     Sort,
     /// Sorted by the similarity of the matched words with the query words.
     Exactness,
+    /// Sorted by decreasing BM25 score, the frequency of the query terms in the documents
+    /// weighted by their rarity in the index and normalized by the length of the documents.
+    Bm25,
     /// Sorted by the increasing value of the field specified.
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
@@ -1012,6 +1058,7 @@ impl From<Criterion> for RankingRuleView {
             Criterion::Attribute => RankingRuleView::Attribute,
             Criterion::Sort => RankingRuleView::Sort,
             Criterion::Exactness => RankingRuleView::Exactness,
+            Criterion::Bm25 => RankingRuleView::Bm25,
             Criterion::Asc(x) => RankingRuleView::Asc(x),
             Criterion::Desc(x) => RankingRuleView::Desc(x),
         }
@@ -1026,6 +1073,7 @@ impl From<RankingRuleView> for Criterion {
             RankingRuleView::Attribute => Criterion::Attribute,
             RankingRuleView::Sort => Criterion::Sort,
             RankingRuleView::Exactness => Criterion::Exactness,
+            RankingRuleView::Bm25 => Criterion::Bm25,
             RankingRuleView::Asc(x) => Criterion::Asc(x),
             RankingRuleView::Desc(x) => Criterion::Desc(x),
         }
@@ -1154,6 +1202,8 @@ pub(crate) mod test {
             search_cutoff_ms: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            ngram_attributes: Setting::NotSet,
+            query_rules: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -1185,6 +1235,8 @@ pub(crate) mod test {
             search_cutoff_ms: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            ngram_attributes: Setting::NotSet,
+            query_rules: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
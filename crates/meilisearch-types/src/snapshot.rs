@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use file_store::encryption::EncryptionKey;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::compression;
+
+/// Name of the manifest stored at the root of an incremental snapshot.
+pub const SNAPSHOT_MANIFEST_FILE_NAME: &str = "snapshot-manifest.json";
+
+/// Describes where each index of an incremental snapshot can be found.
+///
+/// The indexes that didn't change since the previous snapshot are not part of the
+/// tarball and must be extracted from the snapshot referenced here instead.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotManifest {
+    pub indexes: BTreeMap<Uuid, IndexSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexSnapshot {
+    /// The `updated_at` of the index when it was snapshotted.
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+    /// The name of the snapshot file, in the snapshots directory, containing the index.
+    pub snapshot: String,
+}
+
+impl SnapshotManifest {
+    pub fn from_path(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let content = fs::read(path)?;
+        Ok(serde_json::from_slice(&content)?)
+    }
+
+    pub fn persist(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Unpack the snapshot `src` into `dest`, decrypting it with the key if it is encrypted.
+///
+/// If the snapshot is incremental, the indexes it doesn't contain are unpacked from
+/// the snapshots it references, which must be in the same directory as `src`.
+pub fn import_snapshot(
+    src: impl AsRef<Path>,
+    dest: impl AsRef<Path>,
+    encryption_key: Option<&EncryptionKey>,
+) -> anyhow::Result<()> {
+    let (src, dest) = (src.as_ref(), dest.as_ref());
+    compression::from_tar_gz(src, dest, encryption_key)?;
+
+    let manifest_path = dest.join(SNAPSHOT_MANIFEST_FILE_NAME);
+    if !manifest_path.exists() {
+        return Ok(());
+    }
+    let manifest = SnapshotManifest::from_path(&manifest_path)?;
+    let snapshots_dir = src.parent().unwrap_or_else(|| Path::new("."));
+
+    for (uuid, IndexSnapshot { snapshot, .. }) in &manifest.indexes {
+        let index_dir = Path::new("indexes").join(uuid.to_string());
+        if dest.join(&index_dir).exists() {
+            continue;
+        }
+        // The name is read from the snapshot, it must not lead outside of the snapshots directory.
+        if !is_valid_snapshot_name(snapshot) {
+            anyhow::bail!(
+                "Invalid snapshot name `{snapshot}` for the index `{uuid}` in the manifest."
+            );
+        }
+        let previous = snapshots_dir.join(snapshot);
+        if !previous.exists() {
+            anyhow::bail!(
+                "The snapshot `{}` required to import the index `{uuid}` doesn't exist.",
+                previous.display()
+            );
+        }
+        compression::from_tar_gz_filtered(&previous, &index_dir, dest, encryption_key)?;
+        if !dest.join(&index_dir).exists() {
+            anyhow::bail!(
+                "The index `{uuid}` is missing from the snapshot `{}`.",
+                previous.display()
+            );
+        }
+    }
+
+    fs::remove_file(manifest_path)?;
+    Ok(())
+}
+
+/// A snapshot name must be a file name of the snapshots directory.
+fn is_valid_snapshot_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(['/', '\\']) && !name.contains("..")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_names() {
+        assert!(is_valid_snapshot_name("20240808-170555791.snapshot"));
+        assert!(is_valid_snapshot_name("data.ms.snapshot"));
+        assert!(!is_valid_snapshot_name(""));
+        assert!(!is_valid_snapshot_name(".."));
+        assert!(!is_valid_snapshot_name("../data.ms.snapshot"));
+        assert!(!is_valid_snapshot_name("/etc/passwd"));
+        assert!(!is_valid_snapshot_name("backups/data.ms.snapshot"));
+        assert!(!is_valid_snapshot_name("..\\data.ms.snapshot"));
+    }
+
+    #[test]
+    fn import_snapshot_outside_of_the_snapshots_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = dir.path().join("content");
+        fs::create_dir_all(&content).unwrap();
+        let mut manifest = SnapshotManifest::default();
+        manifest.indexes.insert(
+            Uuid::nil(),
+            IndexSnapshot {
+                updated_at: OffsetDateTime::UNIX_EPOCH,
+                snapshot: "../data.ms.snapshot".to_string(),
+            },
+        );
+        manifest.persist(content.join(SNAPSHOT_MANIFEST_FILE_NAME)).unwrap();
+
+        let snapshots = dir.path().join("snapshots");
+        fs::create_dir_all(&snapshots).unwrap();
+        let src = snapshots.join("data.ms.snapshot");
+        compression::to_tar_gz(&content, &src, None).unwrap();
+
+        let err = import_snapshot(&src, dir.path().join("data.ms"), None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid snapshot name `../data.ms.snapshot` for the index `00000000-0000-0000-0000-000000000000` in the manifest."
+        );
+    }
+}
@@ -2,11 +2,14 @@ use milli::Object;
 use serde::{Deserialize, Serialize};
 use time::{Duration, OffsetDateTime};
 use utoipa::ToSchema;
+use uuid::Uuid;
 
 use crate::batches::BatchId;
 use crate::error::ResponseError;
 use crate::settings::{Settings, Unchecked};
-use crate::tasks::{serialize_duration, Details, IndexSwap, Kind, Status, Task, TaskId};
+use crate::tasks::{
+    serialize_duration, Details, IndexAlias, IndexSwap, Kind, Status, Task, TaskId,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -46,6 +49,10 @@ pub struct TaskView {
     #[schema(value_type = String, example = json!("2024-08-08_14:12:09.393Z"))]
     #[serde(with = "time::serde::rfc3339::option", default)]
     pub finished_at: Option<OffsetDateTime>,
+    /// The uid of the API key that enqueued the task. Omitted for the tasks enqueued with the master key.
+    #[schema(value_type = Option<String>, example = json!("01b4bc42-eb33-4041-b481-254d00cce834"))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_uid: Option<Uuid>,
 }
 
 impl TaskView {
@@ -63,6 +70,7 @@ impl TaskView {
             enqueued_at: task.enqueued_at,
             started_at: task.started_at,
             finished_at: task.finished_at,
+            key_uid: task.key_uid,
         }
     }
 }
@@ -101,7 +109,7 @@ pub struct DetailsView {
     /// Original filter query for taskCancelation or taskDeletion tasks.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub original_filter: Option<Option<String>>,
-    /// Identifier generated for the dump for dumpCreation task.
+    /// Identifier generated for the dump for dumpCreation and indexExport tasks, or of the dump imported by an indexImport task.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dump_uid: Option<Option<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -118,6 +126,12 @@ pub struct DetailsView {
     pub upgrade_from: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub upgrade_to: Option<String>,
+    /// The aliases created, repointed or removed by an indexAliasUpdate task.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aliases: Option<Vec<IndexAlias>>,
+    /// The uid of the index created by an indexClone task.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_index_uid: Option<String>,
 }
 
 impl DetailsView {
@@ -250,6 +264,20 @@ impl DetailsView {
                 (None, Some(to)) | (Some(to), None) => Some(to),
                 (Some(_), Some(to)) => Some(to),
             },
+            aliases: match (self.aliases.clone(), other.aliases.clone()) {
+                (None, None) => None,
+                (None, Some(aliases)) | (Some(aliases), None) => Some(aliases),
+                (Some(mut left), Some(mut right)) => {
+                    left.append(&mut right);
+                    Some(left)
+                }
+            },
+            new_index_uid: match (self.new_index_uid.clone(), other.new_index_uid.clone()) {
+                (None, None) => None,
+                (None, Some(uid)) | (Some(uid), None) => Some(uid),
+                // We should never be able to batch multiple clones at the same time
+                (Some(left), Some(_right)) => Some(left),
+            },
         }
     }
 }
@@ -332,6 +360,20 @@ impl From<Details> for DetailsView {
                 upgrade_to: Some(format!("v{}.{}.{}", to.0, to.1, to.2)),
                 ..Default::default()
             },
+            Details::IndexAliasUpdate { aliases } => {
+                DetailsView { aliases: Some(aliases), ..Default::default() }
+            }
+            Details::IndexClone { new_index_uid } => {
+                DetailsView { new_index_uid: Some(new_index_uid), ..Default::default() }
+            }
+            Details::IndexExport { dump_uid } => {
+                DetailsView { dump_uid: Some(dump_uid), ..Default::default() }
+            }
+            Details::IndexImport { dump_uid, indexed_documents } => DetailsView {
+                dump_uid: Some(Some(dump_uid)),
+                indexed_documents: Some(indexed_documents),
+                ..Default::default()
+            },
         }
     }
 }
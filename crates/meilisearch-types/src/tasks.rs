@@ -39,6 +39,10 @@ pub struct Task {
 
     pub status: Status,
     pub kind: KindWithContent,
+
+    /// The uid of the API key that enqueued the task, `None` for the master key and internal tasks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_uid: Option<Uuid>,
 }
 
 impl Task {
@@ -51,7 +55,8 @@ impl Task {
             | TaskCancelation { .. }
             | TaskDeletion { .. }
             | UpgradeDatabase { .. }
-            | IndexSwap { .. } => None,
+            | IndexSwap { .. }
+            | IndexAliasUpdate { .. } => None,
             DocumentAdditionOrUpdate { index_uid, .. }
             | DocumentEdition { index_uid, .. }
             | DocumentDeletion { index_uid, .. }
@@ -60,7 +65,10 @@ impl Task {
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
-            | IndexDeletion { index_uid } => Some(index_uid),
+            | IndexDeletion { index_uid }
+            | IndexClone { index_uid, .. }
+            | IndexExport { index_uid }
+            | IndexImport { index_uid, .. } => Some(index_uid),
         }
     }
 
@@ -82,11 +90,15 @@ impl Task {
             | KindWithContent::IndexCreation { .. }
             | KindWithContent::IndexUpdate { .. }
             | KindWithContent::IndexSwap { .. }
+            | KindWithContent::IndexClone { .. }
+            | KindWithContent::IndexExport { .. }
+            | KindWithContent::IndexImport { .. }
             | KindWithContent::TaskCancelation { .. }
             | KindWithContent::TaskDeletion { .. }
             | KindWithContent::DumpCreation { .. }
             | KindWithContent::SnapshotCreation
-            | KindWithContent::UpgradeDatabase { .. } => None,
+            | KindWithContent::UpgradeDatabase { .. }
+            | KindWithContent::IndexAliasUpdate { .. } => None,
         }
     }
 }
@@ -139,6 +151,17 @@ pub enum KindWithContent {
     IndexSwap {
         swaps: Vec<IndexSwap>,
     },
+    IndexClone {
+        index_uid: String,
+        new_index_uid: String,
+    },
+    IndexExport {
+        index_uid: String,
+    },
+    IndexImport {
+        index_uid: String,
+        dump_uid: String,
+    },
     TaskCancelation {
         query: String,
         tasks: RoaringBitmap,
@@ -155,6 +178,9 @@ pub enum KindWithContent {
     UpgradeDatabase {
         from: (u32, u32, u32),
     },
+    IndexAliasUpdate {
+        aliases: Vec<IndexAlias>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
@@ -163,6 +189,15 @@ pub struct IndexSwap {
     pub indexes: (String, String),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexAlias {
+    /// The name of the alias.
+    pub alias: String,
+    /// The index the alias resolves to, `None` when the alias is removed.
+    pub index_uid: Option<String>,
+}
+
 impl KindWithContent {
     pub fn as_kind(&self) -> Kind {
         match self {
@@ -176,11 +211,15 @@ impl KindWithContent {
             KindWithContent::IndexDeletion { .. } => Kind::IndexDeletion,
             KindWithContent::IndexUpdate { .. } => Kind::IndexUpdate,
             KindWithContent::IndexSwap { .. } => Kind::IndexSwap,
+            KindWithContent::IndexClone { .. } => Kind::IndexClone,
+            KindWithContent::IndexExport { .. } => Kind::IndexExport,
+            KindWithContent::IndexImport { .. } => Kind::IndexImport,
             KindWithContent::TaskCancelation { .. } => Kind::TaskCancelation,
             KindWithContent::TaskDeletion { .. } => Kind::TaskDeletion,
             KindWithContent::DumpCreation { .. } => Kind::DumpCreation,
             KindWithContent::SnapshotCreation => Kind::SnapshotCreation,
             KindWithContent::UpgradeDatabase { .. } => Kind::UpgradeDatabase,
+            KindWithContent::IndexAliasUpdate { .. } => Kind::IndexAliasUpdate,
         }
     }
 
@@ -201,7 +240,10 @@ impl KindWithContent {
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
-            | IndexDeletion { index_uid } => vec![index_uid],
+            | IndexDeletion { index_uid }
+            | IndexExport { index_uid }
+            | IndexImport { index_uid, .. } => vec![index_uid],
+            IndexClone { index_uid, new_index_uid } => vec![index_uid, new_index_uid],
             IndexSwap { swaps } => {
                 let mut indexes = HashSet::<&str>::default();
                 for swap in swaps {
@@ -210,6 +252,16 @@ impl KindWithContent {
                 }
                 indexes.into_iter().collect()
             }
+            IndexAliasUpdate { aliases } => {
+                let mut indexes = HashSet::<&str>::default();
+                for IndexAlias { alias, index_uid } in aliases {
+                    indexes.insert(alias.as_str());
+                    if let Some(index_uid) = index_uid {
+                        indexes.insert(index_uid.as_str());
+                    }
+                }
+                indexes.into_iter().collect()
+            }
         }
     }
 
@@ -257,6 +309,17 @@ impl KindWithContent {
             KindWithContent::IndexSwap { swaps } => {
                 Some(Details::IndexSwap { swaps: swaps.clone() })
             }
+            KindWithContent::IndexClone { new_index_uid, .. } => {
+                Some(Details::IndexClone { new_index_uid: new_index_uid.clone() })
+            }
+            KindWithContent::IndexExport { .. } => Some(Details::IndexExport { dump_uid: None }),
+            KindWithContent::IndexImport { dump_uid, .. } => Some(Details::IndexImport {
+                dump_uid: dump_uid.clone(),
+                indexed_documents: None,
+            }),
+            KindWithContent::IndexAliasUpdate { aliases } => {
+                Some(Details::IndexAliasUpdate { aliases: aliases.clone() })
+            }
             KindWithContent::TaskCancelation { query, tasks } => Some(Details::TaskCancelation {
                 matched_tasks: tasks.len(),
                 canceled_tasks: None,
@@ -323,6 +386,17 @@ impl KindWithContent {
             KindWithContent::IndexSwap { .. } => {
                 todo!()
             }
+            KindWithContent::IndexClone { new_index_uid, .. } => {
+                Some(Details::IndexClone { new_index_uid: new_index_uid.clone() })
+            }
+            KindWithContent::IndexExport { .. } => Some(Details::IndexExport { dump_uid: None }),
+            KindWithContent::IndexImport { dump_uid, .. } => Some(Details::IndexImport {
+                dump_uid: dump_uid.clone(),
+                indexed_documents: Some(0),
+            }),
+            KindWithContent::IndexAliasUpdate { aliases } => {
+                Some(Details::IndexAliasUpdate { aliases: aliases.clone() })
+            }
             KindWithContent::TaskCancelation { query, tasks } => Some(Details::TaskCancelation {
                 matched_tasks: tasks.len(),
                 canceled_tasks: Some(0),
@@ -371,6 +445,17 @@ impl From<&KindWithContent> for Option<Details> {
                 Some(Details::IndexInfo { primary_key: primary_key.clone() })
             }
             KindWithContent::IndexSwap { .. } => None,
+            KindWithContent::IndexClone { new_index_uid, .. } => {
+                Some(Details::IndexClone { new_index_uid: new_index_uid.clone() })
+            }
+            KindWithContent::IndexExport { .. } => Some(Details::IndexExport { dump_uid: None }),
+            KindWithContent::IndexImport { dump_uid, .. } => Some(Details::IndexImport {
+                dump_uid: dump_uid.clone(),
+                indexed_documents: None,
+            }),
+            KindWithContent::IndexAliasUpdate { aliases } => {
+                Some(Details::IndexAliasUpdate { aliases: aliases.clone() })
+            }
             KindWithContent::TaskCancelation { query, tasks } => Some(Details::TaskCancelation {
                 matched_tasks: tasks.len(),
                 canceled_tasks: None,
@@ -500,6 +585,10 @@ pub enum Kind {
     DumpCreation,
     SnapshotCreation,
     UpgradeDatabase,
+    IndexAliasUpdate,
+    IndexClone,
+    IndexExport,
+    IndexImport,
 }
 
 impl Kind {
@@ -511,8 +600,12 @@ impl Kind {
             | Kind::SettingsUpdate
             | Kind::IndexCreation
             | Kind::IndexDeletion
-            | Kind::IndexUpdate => true,
+            | Kind::IndexUpdate
+            | Kind::IndexClone
+            | Kind::IndexExport
+            | Kind::IndexImport => true,
             Kind::IndexSwap
+            | Kind::IndexAliasUpdate
             | Kind::TaskCancelation
             | Kind::TaskDeletion
             | Kind::DumpCreation
@@ -537,6 +630,10 @@ impl Display for Kind {
             Kind::DumpCreation => write!(f, "dumpCreation"),
             Kind::SnapshotCreation => write!(f, "snapshotCreation"),
             Kind::UpgradeDatabase => write!(f, "upgradeDatabase"),
+            Kind::IndexAliasUpdate => write!(f, "indexAliasUpdate"),
+            Kind::IndexClone => write!(f, "indexClone"),
+            Kind::IndexExport => write!(f, "indexExport"),
+            Kind::IndexImport => write!(f, "indexImport"),
         }
     }
 }
@@ -570,6 +667,14 @@ impl FromStr for Kind {
             Ok(Kind::SnapshotCreation)
         } else if kind.eq_ignore_ascii_case("upgradeDatabase") {
             Ok(Kind::UpgradeDatabase)
+        } else if kind.eq_ignore_ascii_case("indexAliasUpdate") {
+            Ok(Kind::IndexAliasUpdate)
+        } else if kind.eq_ignore_ascii_case("indexClone") {
+            Ok(Kind::IndexClone)
+        } else if kind.eq_ignore_ascii_case("indexExport") {
+            Ok(Kind::IndexExport)
+        } else if kind.eq_ignore_ascii_case("indexImport") {
+            Ok(Kind::IndexImport)
         } else {
             Err(ParseTaskKindError(kind.to_owned()))
         }
@@ -647,6 +752,19 @@ pub enum Details {
         from: (u32, u32, u32),
         to: (u32, u32, u32),
     },
+    IndexAliasUpdate {
+        aliases: Vec<IndexAlias>,
+    },
+    IndexClone {
+        new_index_uid: String,
+    },
+    IndexExport {
+        dump_uid: Option<String>,
+    },
+    IndexImport {
+        dump_uid: String,
+        indexed_documents: Option<u64>,
+    },
 }
 
 impl Details {
@@ -662,13 +780,17 @@ impl Details {
                 *deleted_documents = Some(0)
             }
             Self::ClearAll { deleted_documents } => *deleted_documents = Some(0),
+            Self::IndexImport { indexed_documents, .. } => *indexed_documents = Some(0),
             Self::TaskCancelation { canceled_tasks, .. } => *canceled_tasks = Some(0),
             Self::TaskDeletion { deleted_tasks, .. } => *deleted_tasks = Some(0),
             Self::SettingsUpdate { .. }
             | Self::IndexInfo { .. }
             | Self::Dump { .. }
             | Self::UpgradeDatabase { .. }
-            | Self::IndexSwap { .. } => (),
+            | Self::IndexSwap { .. }
+            | Self::IndexAliasUpdate { .. }
+            | Self::IndexClone { .. }
+            | Self::IndexExport { .. } => (),
         }
 
         details
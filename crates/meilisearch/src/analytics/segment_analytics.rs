@@ -1,6 +1,7 @@
 use std::any::TypeId;
 use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::num::NonZeroU64;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -25,7 +26,7 @@ use uuid::Uuid;
 
 use super::{config_user_id_path, Aggregate, MEILISEARCH_CONFIG_PATH};
 use crate::option::{
-    default_http_addr, IndexerOpts, LogMode, MaxMemory, MaxThreads, ScheduleSnapshot,
+    default_http_addr, IndexerOpts, LogMode, MaxMemory, MaxThreads, S3Opts, ScheduleSnapshot,
 };
 use crate::routes::{create_all_stats, Stats};
 use crate::Opt;
@@ -191,6 +192,9 @@ struct Infos {
     experimental_nb_searches_per_core: usize,
     experimental_logs_mode: LogMode,
     experimental_dumpless_upgrade: bool,
+    experimental_incremental_snapshots: bool,
+    experimental_s3_upload: bool,
+    experimental_s3_retention: Option<usize>,
     experimental_replication_parameters: bool,
     experimental_enable_logs_route: bool,
     experimental_reduce_indexing_memory_usage: bool,
@@ -200,6 +204,7 @@ struct Infos {
     experimental_get_task_documents_route: bool,
     experimental_composite_embedders: bool,
     experimental_embedding_cache_entries: usize,
+    experimental_trusted_issuers: bool,
     gpu_enabled: bool,
     db_path: bool,
     import_dump: bool,
@@ -213,6 +218,9 @@ struct Infos {
     ignore_snapshot_if_db_exists: bool,
     http_addr: bool,
     http_payload_size_limit: Byte,
+    trusted_proxies: bool,
+    audit_log_max_entries: Option<u64>,
+    encryption_at_rest: bool,
     task_queue_webhook: bool,
     task_webhook_authorization_header: bool,
     log_level: String,
@@ -242,12 +250,14 @@ impl Infos {
             experimental_nb_searches_per_core,
             experimental_logs_mode,
             experimental_dumpless_upgrade,
+            experimental_incremental_snapshots,
             experimental_replication_parameters,
             experimental_enable_logs_route,
             experimental_reduce_indexing_memory_usage,
             experimental_max_number_of_batched_tasks,
             experimental_limit_batched_tasks_total_size,
             experimental_embedding_cache_entries,
+            experimental_trusted_issuers,
             http_addr,
             master_key: _,
             env,
@@ -256,6 +266,10 @@ impl Infos {
             max_index_size: _,
             max_task_db_size: _,
             http_payload_size_limit,
+            trusted_proxies,
+            audit_log_max_entries,
+            encryption_key,
+            encryption_key_path,
             ssl_cert_path,
             ssl_key_path,
             ssl_auth_path,
@@ -274,6 +288,7 @@ impl Infos {
             dump_dir,
             log_level,
             indexer_options,
+            s3_options,
             config_file_path,
             no_analytics: _,
         } = options;
@@ -286,6 +301,17 @@ impl Infos {
         let IndexerOpts { max_indexing_memory, max_indexing_threads, skip_index_budget: _ } =
             indexer_options;
 
+        let S3Opts {
+            experimental_s3_bucket_url,
+            experimental_s3_bucket_region: _,
+            experimental_s3_bucket_name: _,
+            experimental_s3_access_key: _,
+            experimental_s3_secret_key: _,
+            experimental_s3_prefix: _,
+            experimental_s3_multipart_part_size: _,
+            experimental_s3_retention,
+        } = s3_options;
+
         let RuntimeTogglableFeatures {
             metrics,
             logs_route,
@@ -308,6 +334,9 @@ impl Infos {
             experimental_nb_searches_per_core: experimental_nb_searches_per_core.into(),
             experimental_logs_mode,
             experimental_dumpless_upgrade,
+            experimental_incremental_snapshots,
+            experimental_s3_upload: experimental_s3_bucket_url.is_some(),
+            experimental_s3_retention,
             experimental_replication_parameters,
             experimental_enable_logs_route: experimental_enable_logs_route | logs_route,
             experimental_reduce_indexing_memory_usage,
@@ -315,6 +344,7 @@ impl Infos {
             experimental_get_task_documents_route: get_task_documents_route,
             experimental_composite_embedders: composite_embedders,
             experimental_embedding_cache_entries,
+            experimental_trusted_issuers: experimental_trusted_issuers.is_some(),
             gpu_enabled: meilisearch_types::milli::vector::is_cuda_enabled(),
             db_path: db_path != PathBuf::from("./data.ms"),
             import_dump: import_dump.is_some(),
@@ -328,6 +358,9 @@ impl Infos {
             ignore_snapshot_if_db_exists,
             http_addr: http_addr != default_http_addr(),
             http_payload_size_limit,
+            trusted_proxies: !trusted_proxies.is_empty(),
+            audit_log_max_entries: audit_log_max_entries.map(NonZeroU64::get),
+            encryption_at_rest: encryption_key.is_some() || encryption_key_path.is_some(),
             experimental_max_number_of_batched_tasks,
             experimental_limit_batched_tasks_total_size:
                 experimental_limit_batched_tasks_total_size.into(),
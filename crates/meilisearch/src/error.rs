@@ -1,6 +1,7 @@
 use actix_web as aweb;
 use aweb::error::{JsonPayloadError, QueryPayloadError};
 use byte_unit::{Byte, UnitType};
+use meilisearch_auth::rate_limit::RateLimitExceeded;
 use meilisearch_types::document_formats::{DocumentFormatError, PayloadType};
 use meilisearch_types::error::{Code, ErrorCode, ResponseError};
 use meilisearch_types::index_uid::{IndexUid, IndexUidFormatError};
@@ -33,6 +34,12 @@ pub enum MeilisearchHttpError {
     FederationOptionsInNonFederatedRequest(usize),
     #[error("Inside `.queries[{0}]`: Using pagination options is not allowed in federated queries.\n - Hint: remove `{1}` from query #{0} or remove `federation` from the request\n - Hint: pass `federation.limit` and `federation.offset` for pagination in federated search")]
     PaginationInFederatedQuery(usize, &'static str),
+    #[error("Inside `.queries[{0}]`: Using `collapse` is not allowed in federated queries.\n - Hint: remove `collapse` from query #{0} or remove `federation` from the request")]
+    CollapseInFederatedQuery(usize),
+    #[error("Inside `.queries[{0}]`: Using `suggestions` is not allowed in federated queries.\n - Hint: remove `suggestions` from query #{0} or remove `federation` from the request")]
+    SuggestionsInFederatedQuery(usize),
+    #[error("Inside `.queries[{0}]`: Using `explain` is not allowed in federated queries.\n - Hint: remove `explain` from query #{0} or remove `federation` from the request")]
+    ExplainInFederatedQuery(usize),
     #[error("Inside `.queries[{0}]`: Using facet options is not allowed in federated queries.\n - Hint: remove `facets` from query #{0} or remove `federation` from the request\n - Hint: pass `federation.facetsByIndex.{1}: {2:?}` for facets in federated search")]
     FacetsInFederatedQuery(usize, String, Vec<String>),
     #[error("Inconsistent order for values in facet `{facet}`: index `{previous_uid}` orders {previous_facet_order}, but index `{current_uid}` orders {index_facet_order}.\n - Hint: Remove `federation.mergeFacets` or change `faceting.sortFacetValuesBy` to be consistent in settings.")]
@@ -78,6 +85,8 @@ pub enum MeilisearchHttpError {
     Join(#[from] JoinError),
     #[error("Invalid request: missing `hybrid` parameter when `vector` is present.")]
     MissingSearchHybrid,
+    #[error(transparent)]
+    RateLimitExceeded(#[from] RateLimitExceeded),
 }
 
 impl MeilisearchHttpError {
@@ -107,10 +116,11 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::IndexScheduler(e) => e.error_code(),
             MeilisearchHttpError::Milli { error, .. } => error.error_code(),
             MeilisearchHttpError::Payload(e) => e.error_code(),
-            MeilisearchHttpError::FileStore(_) => Code::Internal,
+            MeilisearchHttpError::FileStore(e) => e.error_code(),
             MeilisearchHttpError::DocumentFormat(e) => e.error_code(),
             MeilisearchHttpError::Join(_) => Code::Internal,
             MeilisearchHttpError::MissingSearchHybrid => Code::MissingSearchHybrid,
+            MeilisearchHttpError::RateLimitExceeded(e) => e.error_code(),
             MeilisearchHttpError::FederationOptionsInNonFederatedRequest(_) => {
                 Code::InvalidMultiSearchFederationOptions
             }
@@ -118,6 +128,15 @@ impl ErrorCode for MeilisearchHttpError {
                 Code::InvalidMultiSearchQueryPagination
             }
             MeilisearchHttpError::FacetsInFederatedQuery(..) => Code::InvalidMultiSearchQueryFacets,
+            MeilisearchHttpError::CollapseInFederatedQuery(_) => {
+                Code::InvalidMultiSearchQueryCollapse
+            }
+            MeilisearchHttpError::SuggestionsInFederatedQuery(_) => {
+                Code::InvalidMultiSearchQuerySuggestions
+            }
+            MeilisearchHttpError::ExplainInFederatedQuery(_) => {
+                Code::InvalidMultiSearchQueryExplain
+            }
             MeilisearchHttpError::InconsistentFacetOrder { .. } => {
                 Code::InvalidMultiSearchFacetOrder
             }
@@ -133,6 +152,9 @@ impl From<MeilisearchHttpError> for aweb::Error {
 
 impl From<aweb::error::PayloadError> for MeilisearchHttpError {
     fn from(error: aweb::error::PayloadError) -> Self {
+        if let Some(exceeded) = rate_limit_exceeded(&error) {
+            return MeilisearchHttpError::RateLimitExceeded(exceeded);
+        }
         match error {
             aweb::error::PayloadError::Incomplete(_) => MeilisearchHttpError::Payload(
                 PayloadError::Payload(ActixPayloadError::IncompleteError),
@@ -144,6 +166,14 @@ impl From<aweb::error::PayloadError> for MeilisearchHttpError {
     }
 }
 
+/// Returns the rate limit exceeded while receiving the payload, if that's why it failed.
+pub fn rate_limit_exceeded(error: &aweb::error::PayloadError) -> Option<RateLimitExceeded> {
+    match error {
+        aweb::error::PayloadError::Io(e) => e.get_ref()?.downcast_ref().cloned(),
+        _ => None,
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ActixPayloadError {
     #[error("The provided payload is incomplete and cannot be parsed")]
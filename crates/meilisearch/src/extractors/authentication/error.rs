@@ -11,6 +11,8 @@ pub enum AuthenticationError {
     IrretrievableState,
     #[error("Meilisearch is running without a master key. To access this API endpoint, you must have set a master key at launch.")]
     MissingMasterKey,
+    #[error("The provided API key can't be used from this address or origin.")]
+    OriginNotAllowed,
 }
 
 impl ErrorCode for AuthenticationError {
@@ -20,6 +22,7 @@ impl ErrorCode for AuthenticationError {
             AuthenticationError::InvalidToken => Code::InvalidApiKey,
             AuthenticationError::IrretrievableState => Code::Internal,
             AuthenticationError::MissingMasterKey => Code::MissingMasterKey,
+            AuthenticationError::OriginNotAllowed => Code::ApiKeyOriginNotAllowed,
         }
     }
 }
@@ -1,34 +1,69 @@
 mod error;
 
+use std::cell::OnceCell;
 use std::marker::PhantomData;
 use std::ops::Deref;
 use std::pin::Pin;
+use std::rc::Rc;
 
+use actix_web::http::header::{CONTENT_LENGTH, ORIGIN};
 use actix_web::web::Data;
-use actix_web::FromRequest;
+use actix_web::{FromRequest, HttpMessage, HttpRequest};
 pub use error::AuthenticationError;
 use futures::future::err;
 use futures::Future;
+use index_scheduler::IndexScheduler;
+use meilisearch_auth::rate_limit::{PayloadQuota, SearchPermit};
 use meilisearch_auth::{AuthController, AuthFilter};
+use meilisearch_types::audit_log::AuditLogKeyUid;
 use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::keys::Action;
 
 use self::policies::AuthError;
+use crate::extractors::payload::QuotaPayload;
 
 pub struct GuardedData<P, D> {
     data: D,
     filters: AuthFilter,
+    /// Counts the request in the concurrent searches of the key until the handler is over.
+    _search_permit: Option<SearchPermit>,
     _marker: PhantomData<P>,
 }
 
+/// An administrative request, recorded in the audit log once it is answered.
+#[derive(Debug, Clone)]
+pub struct AuditedRequest {
+    /// The key used to authenticate the request, `Unauthenticated` until the request is authenticated.
+    pub key_uid: AuditLogKeyUid,
+    pub action: Action,
+    pub index_uid: Option<String>,
+}
+
+impl AuditedRequest {
+    /// Records the key the request was authenticated with, if the request is audited.
+    fn authenticated(req: &HttpRequest, filters: &AuthFilter) {
+        if let Some(audited) = req.extensions_mut().get_mut::<AuditedRequest>() {
+            audited.key_uid = match filters.key_uid() {
+                Some(uid) => AuditLogKeyUid::Key(uid),
+                None => AuditLogKeyUid::MasterKey,
+            };
+        }
+    }
+}
+
 impl<P, D> GuardedData<P, D> {
     pub fn filters(&self) -> &AuthFilter {
         &self.filters
     }
 
     async fn auth_bearer(
+        req: HttpRequest,
         auth: Data<AuthController>,
         token: String,
         index: Option<String>,
+        announced_payload_bytes: u64,
+        payload_quota: Rc<OnceCell<PayloadQuota>>,
         data: Option<D>,
     ) -> Result<Self, ResponseError>
     where
@@ -36,9 +71,33 @@ impl<P, D> GuardedData<P, D> {
     {
         let missing_master_key = auth.get_master_key().is_none();
 
-        match Self::authenticate(auth, token, index).await? {
+        match Self::authenticate(auth.clone(), token, index).await? {
             Ok(filters) => match data {
-                Some(data) => Ok(Self { data, filters, _marker: PhantomData }),
+                Some(data) => {
+                    AuditedRequest::authenticated(&req, &filters);
+                    let forwarded_for = req
+                        .headers()
+                        .get_all("X-Forwarded-For")
+                        .filter_map(|value| value.to_str().ok())
+                        .collect::<Vec<_>>()
+                        .join(",");
+                    let client_ip =
+                        auth.client_ip(req.peer_addr().map(|addr| addr.ip()), Some(&forwarded_for));
+                    let origin = req.headers().get(ORIGIN).and_then(|origin| origin.to_str().ok());
+                    if !filters.is_request_origin_allowed(client_ip, origin) {
+                        return Err(AuthenticationError::OriginNotAllowed.into());
+                    }
+                    let _search_permit = auth
+                        .acquire_rate_limit(&filters, announced_payload_bytes, P::is_search())
+                        .map_err(|e| {
+                            let retry_after = e.retry_after();
+                            ResponseError::from(e).with_retry_after(retry_after)
+                        })?;
+                    if let Some(quota) = auth.payload_quota(&filters) {
+                        let _ = payload_quota.set(quota);
+                    }
+                    Ok(Self { data, filters, _search_permit, _marker: PhantomData })
+                }
                 None => Err(AuthenticationError::IrretrievableState.into()),
             },
             Err(_) if missing_master_key => Err(AuthenticationError::MissingMasterKey.into()),
@@ -46,7 +105,11 @@ impl<P, D> GuardedData<P, D> {
         }
     }
 
-    async fn auth_token(auth: Data<AuthController>, data: Option<D>) -> Result<Self, ResponseError>
+    async fn auth_token(
+        req: HttpRequest,
+        auth: Data<AuthController>,
+        data: Option<D>,
+    ) -> Result<Self, ResponseError>
     where
         P: Policy + 'static,
     {
@@ -54,7 +117,10 @@ impl<P, D> GuardedData<P, D> {
 
         match Self::authenticate(auth, String::new(), None).await? {
             Ok(filters) => match data {
-                Some(data) => Ok(Self { data, filters, _marker: PhantomData }),
+                Some(data) => {
+                    AuditedRequest::authenticated(&req, &filters);
+                    Ok(Self { data, filters, _search_permit: None, _marker: PhantomData })
+                }
                 None => Err(AuthenticationError::IrretrievableState.into()),
             },
             Err(_) if missing_master_key => Err(AuthenticationError::MissingMasterKey.into()),
@@ -91,8 +157,17 @@ impl<P: Policy + 'static, D: 'static + Clone> FromRequest for GuardedData<P, D>
 
     fn from_request(
         req: &actix_web::HttpRequest,
-        _payload: &mut actix_web::dev::Payload,
+        payload: &mut actix_web::dev::Payload,
     ) -> Self::Future {
+        if let Some(action) = P::audited_action() {
+            let index_uid = req.match_info().get("index_uid").map(String::from);
+            req.extensions_mut().insert(AuditedRequest {
+                key_uid: AuditLogKeyUid::Unauthenticated,
+                action,
+                index_uid,
+            });
+        }
+
         match req.app_data::<Data<AuthController>>().cloned() {
             Some(auth) => match req
                 .headers()
@@ -101,13 +176,27 @@ impl<P: Policy + 'static, D: 'static + Clone> FromRequest for GuardedData<P, D>
             {
                 Some(mut type_token) => match type_token.next() {
                     Some("Bearer") => {
-                        // TODO: find a less hardcoded way?
-                        let index = req.match_info().get("index_uid");
+                        let index = match requested_index(req) {
+                            Ok(index) => index,
+                            Err(e) => return Box::pin(err(e)),
+                        };
+                        // the announced length only allows to reject the payload early, the bytes
+                        // are counted as they are read, whether the payload is chunked or not.
+                        let announced_payload_bytes = req
+                            .headers()
+                            .get(CONTENT_LENGTH)
+                            .and_then(|length| length.to_str().ok()?.parse().ok())
+                            .unwrap_or_default();
+                        let payload_quota = Rc::new(OnceCell::new());
+                        QuotaPayload::wrap(payload, payload_quota.clone());
                         match type_token.next() {
                             Some(token) => Box::pin(Self::auth_bearer(
+                                req.clone(),
                                 auth,
                                 token.to_string(),
-                                index.map(String::from),
+                                index,
+                                announced_payload_bytes,
+                                payload_quota,
                                 req.app_data::<D>().cloned(),
                             )),
                             None => Box::pin(err(AuthenticationError::InvalidToken.into())),
@@ -117,24 +206,50 @@ impl<P: Policy + 'static, D: 'static + Clone> FromRequest for GuardedData<P, D>
                         Box::pin(err(AuthenticationError::MissingAuthorizationHeader.into()))
                     }
                 },
-                None => Box::pin(Self::auth_token(auth, req.app_data::<D>().cloned())),
+                None => Box::pin(Self::auth_token(req.clone(), auth, req.app_data::<D>().cloned())),
             },
             None => Box::pin(err(AuthenticationError::IrretrievableState.into())),
         }
     }
 }
 
+/// Returns the uid of the index targeted by the request.
+///
+/// An alias is resolved to the index it points to, so that the key must be granted that index.
+fn requested_index(req: &HttpRequest) -> Result<Option<String>, ResponseError> {
+    // TODO: find a less hardcoded way?
+    let Some(index_uid) = req.match_info().get("index_uid") else { return Ok(None) };
+    match req.app_data::<Data<IndexScheduler>>() {
+        Some(index_scheduler) => {
+            let index_uid = IndexUid::new_unchecked(index_uid);
+            Ok(Some(index_scheduler.resolve_index_alias(index_uid)?.into_inner()))
+        }
+        None => Ok(Some(index_uid.to_string())),
+    }
+}
+
 pub trait Policy {
     fn authenticate(
         auth: Data<AuthController>,
         token: &str,
         index: Option<&str>,
     ) -> Result<AuthFilter, policies::AuthError>;
+
+    /// Whether the requests guarded by this policy count in the concurrent searches of the rate limits.
+    fn is_search() -> bool {
+        false
+    }
+
+    /// The action recorded in the audit log for the requests guarded by this policy, if they are audited.
+    fn audited_action() -> Option<Action> {
+        None
+    }
 }
 
 pub mod policies {
     use actix_web::web::Data;
-    use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+    use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+    use meilisearch_auth::issuers::{TrustedIssuer, TrustedIssuersError};
     use meilisearch_auth::{AuthController, AuthFilter, SearchRules};
     use meilisearch_types::error::{Code, ErrorCode};
     // reexport actions in policies in order to be used in routes configuration.
@@ -172,6 +287,10 @@ pub mod policies {
         CouldNotDecodeTenantToken(jsonwebtoken::errors::Error),
         #[error("Invalid action `{0}`.")]
         InternalInvalidAction(u8),
+        #[error("The tenant token does not contain valid search rules at `{0}`.")]
+        InvalidIssuerSearchRules(String),
+        #[error(transparent)]
+        TrustedIssuer(#[from] TrustedIssuersError),
     }
 
     impl From<jsonwebtoken::errors::Error> for AuthError {
@@ -188,7 +307,7 @@ pub mod policies {
     impl ErrorCode for AuthError {
         fn error_code(&self) -> Code {
             match self {
-                AuthError::InternalInvalidAction(_) => Code::Internal,
+                AuthError::InternalInvalidAction(_) | AuthError::TrustedIssuer(_) => Code::Internal,
                 _ => Code::InvalidApiKey,
             }
         }
@@ -202,6 +321,18 @@ pub mod policies {
         validation
     }
 
+    /// Extracts the `iss` claim of the payload, without performing any validation.
+    fn extract_issuer(token: &str) -> Option<String> {
+        let mut validation = Validation::default();
+        validation.insecure_disable_signature_validation();
+        validation.validate_exp = false;
+        validation.validate_aud = false;
+        validation.required_spec_claims.clear();
+        let dummy_key = DecodingKey::from_secret(b"secret");
+        let token_data = decode::<IssuerClaims>(token, &dummy_key, &validation).ok()?;
+        token_data.claims.iss
+    }
+
     /// Extracts the key id used to sign the payload, without performing any validation.
     fn extract_key_id(token: &str) -> Result<Uuid, AuthError> {
         let mut validation = tenant_token_validation();
@@ -219,6 +350,17 @@ pub mod policies {
         matches!(action, KEYS_GET | KEYS_CREATE | KEYS_UPDATE | KEYS_DELETE)
     }
 
+    /// The actions that a key can perform on its own tasks with the `tasks.own` action.
+    fn is_own_tasks_action(action: u8) -> bool {
+        use actions::*;
+        matches!(action, TASKS_GET | TASKS_CANCEL)
+    }
+
+    fn is_tenant_token_action(action: u8) -> bool {
+        use actions::*;
+        matches!(action, SEARCH | DOCUMENTS_GET)
+    }
+
     pub struct ActionPolicy<const A: u8>;
 
     impl<const A: u8> Policy for ActionPolicy<A> {
@@ -289,9 +431,34 @@ pub mod policies {
             if auth.is_key_authorized(key_uuid, action, index).unwrap_or(false) {
                 return Ok(auth_filter);
             }
+            if is_own_tasks_action(A)
+                && auth.is_key_authorized(key_uuid, Action::TasksOwn, index).unwrap_or(false)
+            {
+                return Ok(auth_filter.with_own_tasks_only());
+            }
 
             Err(AuthError::InvalidApiKey)
         }
+
+        fn is_search() -> bool {
+            A == actions::SEARCH
+        }
+
+        fn audited_action() -> Option<Action> {
+            // the searches and the read-only routes polled by the clients are too frequent to be
+            // audited, they would drown the other entries.
+            Action::from_repr(A).filter(|action| {
+                !matches!(
+                    action,
+                    Action::Search
+                        | Action::TasksGet
+                        | Action::StatsGet
+                        | Action::MetricsGet
+                        | Action::Version
+                        | Action::AuditLogsGet
+                )
+            })
+        }
     }
 
     impl<const A: u8> ActionPolicy<A> {
@@ -299,25 +466,36 @@ pub mod policies {
             auth: &AuthController,
             token: &str,
         ) -> Result<TenantTokenOutcome, AuthError> {
-            // Only search action can be accessed by a tenant token.
-            if A != actions::SEARCH {
+            // Only the search and document fetching actions can be accessed by a tenant token.
+            if !is_tenant_token_action(A) {
                 return Ok(TenantTokenOutcome::NotATenantToken);
             }
 
-            let uid = extract_key_id(token)?;
+            if auth.has_trusted_issuers() {
+                if let Some(issuer) = extract_issuer(token) {
+                    if let Some(issuer) = auth.trusted_issuer(&issuer) {
+                        return Self::authenticate_issuer_token(issuer, token);
+                    }
+                }
+            }
 
-            // Check if tenant token is valid.
-            let key = if let Some(key) = auth.generate_key(uid) {
-                key
-            } else {
-                return Err(AuthError::InvalidTenantToken);
-            };
+            let uid = extract_key_id(token)?;
 
-            let data = decode::<Claims>(
-                token,
-                &DecodingKey::from_secret(key.as_bytes()),
-                &tenant_token_validation(),
-            )?;
+            // Check if tenant token is valid, the tokens signed with the previous value of
+            // a rotated key are accepted until the end of its grace period.
+            let mut data = Err(AuthError::InvalidTenantToken);
+            for key in auth.valid_key_values(uid).map_err(|_| AuthError::InvalidTenantToken)? {
+                data = decode::<Claims>(
+                    token,
+                    &DecodingKey::from_secret(key.as_bytes()),
+                    &tenant_token_validation(),
+                )
+                .map_err(AuthError::from);
+                if data.is_ok() {
+                    break;
+                }
+            }
+            let data = data?;
 
             // Check if token is expired.
             if let Some(exp) = data.claims.exp {
@@ -329,6 +507,50 @@ pub mod policies {
 
             Ok(TenantTokenOutcome::Valid(uid, data.claims.search_rules))
         }
+
+        /// Validates a tenant token minted by an external identity provider.
+        fn authenticate_issuer_token(
+            issuer: &TrustedIssuer,
+            token: &str,
+        ) -> Result<TenantTokenOutcome, AuthError> {
+            let header = decode_header(token)?;
+            let key = issuer
+                .decoding_key(header.kid.as_deref(), header.alg)?
+                .ok_or(AuthError::InvalidTenantToken)?;
+
+            let mut validation = Validation::new(header.alg);
+            validation.validate_exp = false;
+            validation.required_spec_claims.remove("exp");
+            validation.validate_nbf = true;
+            validation.set_issuer(&[issuer.issuer()]);
+            match issuer.audience() {
+                Some(audience) => validation.set_audience(&[audience]),
+                None => validation.validate_aud = false,
+            }
+            let data = decode::<serde_json::Value>(token, &key, &validation)?;
+
+            // Check if token is expired.
+            if let Some(exp) = data.claims.get("exp").and_then(|exp| exp.as_i64()) {
+                let now = OffsetDateTime::now_utc().unix_timestamp();
+                if now > exp {
+                    return Err(AuthError::ExpiredTenantToken { exp, now });
+                }
+            }
+
+            let claim = issuer.search_rules_claim();
+            let search_rules = data
+                .claims
+                .pointer(claim)
+                .and_then(|rules| SearchRules::deserialize(rules).ok())
+                .ok_or_else(|| AuthError::InvalidIssuerSearchRules(claim.to_string()))?;
+
+            Ok(TenantTokenOutcome::Valid(issuer.api_key_uid(), search_rules))
+        }
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct IssuerClaims {
+        iss: Option<String>,
     }
 
     #[derive(Debug, Serialize, Deserialize)]
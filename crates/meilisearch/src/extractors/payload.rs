@@ -1,10 +1,13 @@
+use std::cell::OnceCell;
 use std::pin::Pin;
+use std::rc::Rc;
 use std::task::{Context, Poll};
 
 use actix_http::encoding::Decoder as Decompress;
 use actix_web::{dev, web, FromRequest, HttpRequest};
 use futures::future::{ready, Ready};
 use futures::Stream;
+use meilisearch_auth::rate_limit::PayloadQuota;
 
 use crate::error::MeilisearchHttpError;
 
@@ -72,6 +75,44 @@ impl Stream for Payload {
     }
 }
 
+/// Counts the bytes of the payload in the daily quota of the API key of the request, as they are
+/// received, whatever the extractor reading them.
+///
+/// The quota is only known once the request is authenticated, after the payload has been taken
+/// from the request, hence the cell.
+pub struct QuotaPayload {
+    payload: dev::Payload,
+    quota: Rc<OnceCell<PayloadQuota>>,
+}
+
+impl QuotaPayload {
+    /// Replaces the payload of the request by one counted in the quota set later in the cell.
+    pub fn wrap(payload: &mut dev::Payload, quota: Rc<OnceCell<PayloadQuota>>) {
+        let counted = QuotaPayload { payload: payload.take(), quota };
+        let stream: actix_http::BoxedPayloadStream = Box::pin(counted);
+        *payload = dev::Payload::from(stream);
+    }
+}
+
+impl Stream for QuotaPayload {
+    type Item = Result<web::Bytes, actix_web::error::PayloadError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match Pin::new(&mut self.payload).poll_next(cx) {
+            Poll::Ready(Some(Ok(bytes))) => match self.quota.get() {
+                Some(quota) => match quota.consume(bytes.len() as u64) {
+                    Ok(()) => Poll::Ready(Some(Ok(bytes))),
+                    Err(e) => Poll::Ready(Some(Err(actix_web::error::PayloadError::Io(
+                        std::io::Error::other(e),
+                    )))),
+                },
+                None => Poll::Ready(Some(Ok(bytes))),
+            },
+            otherwise => otherwise,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use actix_http::encoding::Decoder as Decompress;
@@ -15,6 +15,7 @@ pub mod search_queue;
 
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
+use std::num::NonZeroU64;
 use std::path::Path;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -32,18 +33,22 @@ use analytics::Analytics;
 use anyhow::bail;
 use error::PayloadError;
 use extractors::payload::PayloadConfig;
+use file_store::encryption::{decrypted_reader, EncryptionKey};
+use index_scheduler::s3::{self, S3Client, S3Options};
 use index_scheduler::versioning::Versioning;
 use index_scheduler::{IndexScheduler, IndexSchedulerOptions};
+use meilisearch_auth::issuers::TrustedIssuers;
+use meilisearch_auth::network::TrustedProxies;
 use meilisearch_auth::{open_auth_store_env, AuthController};
 use meilisearch_types::milli::constants::VERSION_MAJOR;
 use meilisearch_types::milli::documents::{DocumentsBatchBuilder, DocumentsBatchReader};
-use meilisearch_types::milli::update::{IndexDocumentsConfig, IndexDocumentsMethod};
+use meilisearch_types::milli::update::{IndexDocumentsConfig, IndexDocumentsMethod, IndexerConfig};
 use meilisearch_types::settings::apply_settings_to_builder;
 use meilisearch_types::tasks::KindWithContent;
 use meilisearch_types::versioning::{
     create_current_version_file, get_version, VersionFileError, VERSION_MINOR, VERSION_PATCH,
 };
-use meilisearch_types::{compression, heed, milli, VERSION_FILE_NAME};
+use meilisearch_types::{heed, milli, snapshot, VERSION_FILE_NAME};
 pub use option::Opt;
 use option::ScheduleSnapshot;
 use search_queue::SearchQueue;
@@ -152,7 +157,7 @@ pub fn create_app(
         .configure(routes::configure)
         .configure(|s| dashboard(s, enable_dashboard));
 
-    let app = app.wrap(middleware::RouteMetrics);
+    let app = app.wrap(middleware::RouteMetrics).wrap(middleware::AuditLogRecorder);
     app.wrap(
         Cors::default()
             .send_wildcard()
@@ -210,6 +215,30 @@ enum OnFailure {
 }
 
 pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Arc<AuthController>)> {
+    let s3_options = opt.s3_options.to_s3_options()?;
+    // An incremental snapshot only contains the indexes updated since the previous snapshot and
+    // references the previous ones for the others. Once uploaded, the retention could delete the
+    // referenced snapshots and importing it from S3 would require downloading the whole chain.
+    if s3_options.is_some() && opt.experimental_incremental_snapshots {
+        bail!("`--experimental-incremental-snapshots` can't be used along `--experimental-s3-bucket-url`, the incremental snapshots can't be uploaded to S3 as they depend on the previous snapshots")
+    }
+    let trusted_issuers =
+        opt.experimental_trusted_issuers.as_deref().map(TrustedIssuers::from_path).transpose()?;
+    let encryption_key = opt.get_encryption_key()?;
+    if encryption_key.is_some() {
+        // the indexes can't be opened as encrypted LMDB environments, see `--encryption-key`.
+        tracing::warn!(
+            "The encryption key doesn't encrypt the databases of the indexes, tasks and API keys stored in `{}`, keep them on an encrypted volume.",
+            opt.db_path.display()
+        );
+    }
+    let indexer_config: IndexerConfig = (&opt.indexer_options).try_into()?;
+    let mut batched_tasks_size_limit = opt.experimental_limit_batched_tasks_total_size.as_u64();
+    // the encrypted update files of a batch are decrypted in memory while it is processed.
+    if let (Some(_), Some(max_memory)) = (&encryption_key, indexer_config.max_memory) {
+        batched_tasks_size_limit = batched_tasks_size_limit.min(max_memory as u64);
+    }
+
     let index_scheduler_opt = IndexSchedulerOptions {
         version_file_path: opt.db_path.join(VERSION_FILE_NAME),
         auth_path: opt.db_path.join("auth"),
@@ -223,26 +252,33 @@ pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Arc<
         task_db_size: opt.max_task_db_size.as_u64() as usize,
         index_base_map_size: opt.max_index_size.as_u64() as usize,
         enable_mdb_writemap: opt.experimental_reduce_indexing_memory_usage,
-        indexer_config: Arc::new((&opt.indexer_options).try_into()?),
+        indexer_config: Arc::new(indexer_config),
         autobatching_enabled: true,
         cleanup_enabled: !opt.experimental_replication_parameters,
         max_number_of_tasks: 1_000_000,
         max_number_of_batched_tasks: opt.experimental_max_number_of_batched_tasks,
-        batched_tasks_size_limit: opt.experimental_limit_batched_tasks_total_size.into(),
+        batched_tasks_size_limit,
         index_growth_amount: byte_unit::Byte::from_str("10GiB").unwrap().as_u64() as usize,
         index_count: DEFAULT_INDEX_COUNT,
         instance_features: opt.to_instance_features(),
         auto_upgrade: opt.experimental_dumpless_upgrade,
         embedding_cache_cap: opt.experimental_embedding_cache_entries,
+        incremental_snapshots: opt.experimental_incremental_snapshots,
+        s3_options: s3_options.clone(),
+        encryption_key: encryption_key.clone(),
     };
     let binary_version = (VERSION_MAJOR, VERSION_MINOR, VERSION_PATCH);
 
     let empty_db = is_empty_db(&opt.db_path);
     let (index_scheduler, auth_controller) = if let Some(ref snapshot_path) = opt.import_snapshot {
+        let downloaded = download_from_s3(snapshot_path, s3_options.as_ref(), empty_db)?;
+        let snapshot_path = downloaded.as_ref().map_or(snapshot_path.as_path(), |f| f.path());
         let snapshot_path_exists = snapshot_path.exists();
         // the db is empty and the snapshot exists, import it
         if empty_db && snapshot_path_exists {
-            match compression::from_tar_gz(snapshot_path, &opt.db_path) {
+            let imported =
+                snapshot::import_snapshot(snapshot_path, &opt.db_path, encryption_key.as_ref());
+            match imported {
                 Ok(()) => open_or_create_database_unchecked(
                     opt,
                     index_scheduler_opt,
@@ -268,6 +304,8 @@ pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Arc<
             open_or_create_database(opt, index_scheduler_opt, empty_db, binary_version)?
         }
     } else if let Some(ref path) = opt.import_dump {
+        let downloaded = download_from_s3(path, s3_options.as_ref(), empty_db)?;
+        let path = downloaded.as_ref().map_or(path.as_path(), |f| f.path());
         let src_path_exists = path.exists();
         // the db is empty and the dump exists, import it
         if empty_db && src_path_exists {
@@ -277,7 +315,13 @@ pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Arc<
                 OnFailure::RemoveDb,
                 binary_version, // the db is empty
             )?;
-            match import_dump(&opt.db_path, path, &mut index_scheduler, &mut auth_controller) {
+            match import_dump(
+                &opt.db_path,
+                path,
+                encryption_key.as_ref(),
+                &mut index_scheduler,
+                &mut auth_controller,
+            ) {
                 Ok(()) => (index_scheduler, auth_controller),
                 Err(e) => {
                     std::fs::remove_dir_all(&opt.db_path)?;
@@ -302,6 +346,13 @@ pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Arc<
         open_or_create_database(opt, index_scheduler_opt, empty_db, binary_version)?
     };
 
+    let mut auth_controller = auth_controller;
+    if let Some(trusted_issuers) = trusted_issuers {
+        auth_controller.set_trusted_issuers(trusted_issuers);
+    }
+    auth_controller.set_trusted_proxies(TrustedProxies::new(opt.trusted_proxies.clone()));
+    auth_controller.set_audit_log_max_entries(opt.audit_log_max_entries.map(NonZeroU64::get));
+
     // We create a loop in a thread that registers snapshotCreation tasks
     let index_scheduler = Arc::new(index_scheduler);
     let auth_controller = Arc::new(auth_controller);
@@ -454,13 +505,43 @@ fn open_or_create_database(
     open_or_create_database_unchecked(opt, index_scheduler_opt, OnFailure::KeepDb, version)
 }
 
+/// Downloads the object in a temporary file if the path is an `s3://bucket/key` URL.
+///
+/// Nothing is downloaded if the database isn't empty as the import won't happen,
+/// nor if the object doesn't exist so the path is reported as missing.
+fn download_from_s3(
+    path: &Path,
+    s3_options: Option<&S3Options>,
+    empty_db: bool,
+) -> anyhow::Result<Option<tempfile::NamedTempFile>> {
+    let Some((bucket, key)) = path.to_str().map(s3::parse_url).transpose()?.flatten() else {
+        return Ok(None);
+    };
+    let Some(s3_options) = s3_options else {
+        bail!("`--experimental-s3-bucket-url` must be set to import `{}`", path.display())
+    };
+    if !empty_db {
+        return Ok(None);
+    }
+
+    let client = S3Client::new(s3_options.clone()).with_bucket(bucket);
+    let mut file = tempfile::NamedTempFile::new()?;
+    tracing::info!("Downloading `{}`", path.display());
+    match client.download(key, file.as_file_mut()) {
+        Ok(_) => Ok(Some(file)),
+        Err(s3::Error::ObjectNotFound(_)) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
 fn import_dump(
     db_path: &Path,
     dump_path: &Path,
+    encryption_key: Option<&EncryptionKey>,
     index_scheduler: &mut IndexScheduler,
     auth: &mut AuthController,
 ) -> Result<(), anyhow::Error> {
-    let reader = File::open(dump_path)?;
+    let reader = decrypted_reader(File::open(dump_path)?, encryption_key)?;
     let mut dump_reader = dump::DumpReader::open(reader)?;
 
     if let Some(date) = dump_reader.date() {
@@ -492,6 +573,9 @@ fn import_dump(
         auth.raw_insert_key(key.clone())?;
         keys.push(key);
     }
+    auth.raw_insert_audit_logs(
+        dump_reader.audit_logs()?.map(|log| log.map_err(anyhow::Error::from)),
+    )?;
 
     // 3. Import the runtime features and network
     let features = dump_reader.features()?.unwrap_or_default();
@@ -573,6 +657,11 @@ fn import_dump(
         index_scheduler.refresh_index_stats(&uid)?;
     }
 
+    // 4.4 Import the index aliases, now that the indexes they point to exist.
+    if let Some(aliases) = dump_reader.index_aliases()? {
+        index_scheduler.put_index_aliases(aliases)?;
+    }
+
     // 5. Import the queue
     let mut index_scheduler_dump = index_scheduler.register_dumped_task()?;
     // 5.1. Import the batches
@@ -623,6 +712,11 @@ pub fn configure_data(
                         ])
                         .into(),
                     },
+                    JsonPayloadError::Payload(err)
+                        if error::rate_limit_exceeded(&err).is_some() =>
+                    {
+                        MeilisearchHttpError::from(err).into()
+                    }
                     err => PayloadError::from(err).into(),
                 }),
         )
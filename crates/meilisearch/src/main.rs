@@ -129,9 +129,14 @@ async fn try_main() -> anyhow::Result<()> {
 
     print_launch_resume(&opt, analytics.clone(), config_read_from);
 
+    // the entries of the audit log still queued are written before exiting
+    let audit_log_controller = auth_controller.clone();
     tokio::spawn(async move {
-        tokio::signal::ctrl_c().await.unwrap();
-        std::process::exit(130);
+        let exit_code = shutdown_signal().await;
+        if let Err(error) = audit_log_controller.flush_audit_logs() {
+            tracing::error!(%error, "Could not write the audit log");
+        }
+        std::process::exit(exit_code);
     });
 
     run_http(index_scheduler, auth_controller, opt, log_handle, Arc::new(analytics)).await?;
@@ -139,6 +144,25 @@ async fn try_main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Waits for CTRL-C, or a SIGTERM on unix, and returns the exit code of the signal.
+async fn shutdown_signal() -> i32 {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut terminate = signal(SignalKind::terminate()).unwrap();
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => 130,
+            _ = terminate.recv() => 143,
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        tokio::signal::ctrl_c().await.unwrap();
+        130
+    }
+}
+
 async fn run_http(
     index_scheduler: Arc<IndexScheduler>,
     auth_controller: Arc<AuthController>,
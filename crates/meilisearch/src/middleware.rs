@@ -1,13 +1,19 @@
 //! Contains all the custom middleware used in meilisearch
 
 use std::future::{ready, Ready};
+use std::time::Duration;
 
 use actix_web::dev::{self, Service, ServiceRequest, ServiceResponse, Transform};
 use actix_web::web::Data;
 use actix_web::Error;
 use futures_util::future::LocalBoxFuture;
 use index_scheduler::IndexScheduler;
+use meilisearch_auth::AuthController;
+use meilisearch_types::audit_log::{AuditLog, AuditLogOutcome};
 use prometheus::HistogramTimer;
+use time::OffsetDateTime;
+
+use crate::extractors::authentication::AuditedRequest;
 
 pub struct RouteMetrics;
 
@@ -89,3 +95,93 @@ where
         })
     }
 }
+
+/// How long the entries of the audit log are queued before being written.
+const AUDIT_LOG_FLUSH_DELAY: Duration = Duration::from_secs(1);
+
+/// Records the requests made on the administrative routes in the audit log, once they are answered.
+pub struct AuditLogRecorder;
+
+impl<S, B> Transform<S, ServiceRequest> for AuditLogRecorder
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = AuditLogRecorderMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AuditLogRecorderMiddleware { service }))
+    }
+}
+
+pub struct AuditLogRecorderMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for AuditLogRecorderMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let auth_controller = req.app_data::<Data<AuthController>>().cloned();
+        let fut = self.service.call(req);
+
+        Box::pin(async move {
+            let res = fut.await?;
+
+            // the authentication extractor marks the requests that must be audited
+            let audited = res.request().extensions().get::<AuditedRequest>().cloned();
+            let (Some(audited), Some(auth_controller)) = (audited, auth_controller) else {
+                return Ok(res);
+            };
+
+            let AuditedRequest { key_uid, action, index_uid } = audited;
+            let status_code = res.status().as_u16();
+            let log = AuditLog {
+                // the uid and the name of the key are filled by the store
+                uid: 0,
+                timestamp: OffsetDateTime::now_utc(),
+                key_uid,
+                key_name: None,
+                action,
+                index_uid,
+                method: res.request().method().to_string(),
+                route: res.request().path().to_string(),
+                status_code,
+                outcome: AuditLogOutcome::from_status_code(status_code),
+            };
+
+            // the entries are written together once in a while rather than one transaction per request
+            if auth_controller.queue_audit_log(log) {
+                tokio::spawn(async move {
+                    tokio::time::sleep(AUDIT_LOG_FLUSH_DELAY).await;
+                    let result =
+                        tokio::task::spawn_blocking(move || auth_controller.flush_audit_logs())
+                            .await;
+                    match result {
+                        Ok(Ok(())) => (),
+                        Ok(Err(error)) => {
+                            tracing::error!(%error, "Could not write the audit log")
+                        }
+                        Err(error) => tracing::error!(%error, "Could not write the audit log"),
+                    }
+                });
+            }
+
+            Ok(res)
+        })
+    }
+}
@@ -2,16 +2,20 @@ use std::env::VarError;
 use std::ffi::OsStr;
 use std::fmt::Display;
 use std::io::{BufReader, Read};
-use std::num::{NonZeroUsize, ParseIntError};
+use std::num::{NonZeroU64, NonZeroUsize, ParseIntError};
 use std::ops::Deref;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::{env, fmt, fs};
 
+use anyhow::Context;
 use byte_unit::{Byte, ParseError, UnitType};
 use clap::Parser;
+use file_store::encryption::EncryptionKey;
+use index_scheduler::s3::{S3Options, DEFAULT_MULTIPART_PART_SIZE, MIN_MULTIPART_PART_SIZE};
 use meilisearch_types::features::InstanceTogglableFeatures;
+use meilisearch_types::ip_cidr::IpCidr;
 use meilisearch_types::milli::update::IndexerConfig;
 use meilisearch_types::milli::ThreadPoolNoAbortBuilder;
 use rustls::server::{ServerSessionMemoryCache, WebPkiClientVerifier};
@@ -31,6 +35,10 @@ const MEILI_TASK_WEBHOOK_URL: &str = "MEILI_TASK_WEBHOOK_URL";
 const MEILI_TASK_WEBHOOK_AUTHORIZATION_HEADER: &str = "MEILI_TASK_WEBHOOK_AUTHORIZATION_HEADER";
 const MEILI_NO_ANALYTICS: &str = "MEILI_NO_ANALYTICS";
 const MEILI_HTTP_PAYLOAD_SIZE_LIMIT: &str = "MEILI_HTTP_PAYLOAD_SIZE_LIMIT";
+const MEILI_TRUSTED_PROXIES: &str = "MEILI_TRUSTED_PROXIES";
+const MEILI_AUDIT_LOG_MAX_ENTRIES: &str = "MEILI_AUDIT_LOG_MAX_ENTRIES";
+const MEILI_ENCRYPTION_KEY: &str = "MEILI_ENCRYPTION_KEY";
+const MEILI_ENCRYPTION_KEY_PATH: &str = "MEILI_ENCRYPTION_KEY_PATH";
 const MEILI_SSL_CERT_PATH: &str = "MEILI_SSL_CERT_PATH";
 const MEILI_SSL_KEY_PATH: &str = "MEILI_SSL_KEY_PATH";
 const MEILI_SSL_AUTH_PATH: &str = "MEILI_SSL_AUTH_PATH";
@@ -43,6 +51,7 @@ const MEILI_IGNORE_MISSING_SNAPSHOT: &str = "MEILI_IGNORE_MISSING_SNAPSHOT";
 const MEILI_IGNORE_SNAPSHOT_IF_DB_EXISTS: &str = "MEILI_IGNORE_SNAPSHOT_IF_DB_EXISTS";
 const MEILI_SNAPSHOT_DIR: &str = "MEILI_SNAPSHOT_DIR";
 const MEILI_SCHEDULE_SNAPSHOT: &str = "MEILI_SCHEDULE_SNAPSHOT";
+const MEILI_EXPERIMENTAL_INCREMENTAL_SNAPSHOTS: &str = "MEILI_EXPERIMENTAL_INCREMENTAL_SNAPSHOTS";
 const MEILI_IMPORT_DUMP: &str = "MEILI_IMPORT_DUMP";
 const MEILI_IGNORE_MISSING_DUMP: &str = "MEILI_IGNORE_MISSING_DUMP";
 const MEILI_IGNORE_DUMP_IF_DB_EXISTS: &str = "MEILI_IGNORE_DUMP_IF_DB_EXISTS";
@@ -65,6 +74,7 @@ const MEILI_EXPERIMENTAL_LIMIT_BATCHED_TASKS_TOTAL_SIZE: &str =
     "MEILI_EXPERIMENTAL_LIMIT_BATCHED_TASKS_SIZE";
 const MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES: &str =
     "MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES";
+const MEILI_EXPERIMENTAL_TRUSTED_ISSUERS: &str = "MEILI_EXPERIMENTAL_TRUSTED_ISSUERS";
 const DEFAULT_CONFIG_FILE_PATH: &str = "./config.toml";
 const DEFAULT_DB_PATH: &str = "./data.ms";
 const DEFAULT_HTTP_ADDR: &str = "localhost:7700";
@@ -75,6 +85,15 @@ const DEFAULT_SNAPSHOT_INTERVAL_SEC: u64 = 86400;
 const DEFAULT_SNAPSHOT_INTERVAL_SEC_STR: &str = "86400";
 const DEFAULT_DUMP_DIR: &str = "dumps/";
 
+const MEILI_EXPERIMENTAL_S3_BUCKET_URL: &str = "MEILI_EXPERIMENTAL_S3_BUCKET_URL";
+const MEILI_EXPERIMENTAL_S3_BUCKET_REGION: &str = "MEILI_EXPERIMENTAL_S3_BUCKET_REGION";
+const MEILI_EXPERIMENTAL_S3_BUCKET_NAME: &str = "MEILI_EXPERIMENTAL_S3_BUCKET_NAME";
+const MEILI_EXPERIMENTAL_S3_ACCESS_KEY: &str = "MEILI_EXPERIMENTAL_S3_ACCESS_KEY";
+const MEILI_EXPERIMENTAL_S3_SECRET_KEY: &str = "MEILI_EXPERIMENTAL_S3_SECRET_KEY";
+const MEILI_EXPERIMENTAL_S3_PREFIX: &str = "MEILI_EXPERIMENTAL_S3_PREFIX";
+const MEILI_EXPERIMENTAL_S3_MULTIPART_PART_SIZE: &str = "MEILI_EXPERIMENTAL_S3_MULTIPART_PART_SIZE";
+const MEILI_EXPERIMENTAL_S3_RETENTION: &str = "MEILI_EXPERIMENTAL_S3_RETENTION";
+
 const MEILI_MAX_INDEXING_MEMORY: &str = "MEILI_MAX_INDEXING_MEMORY";
 const MEILI_MAX_INDEXING_THREADS: &str = "MEILI_MAX_INDEXING_THREADS";
 const DEFAULT_LOG_EVERY_N: usize = 100_000;
@@ -237,6 +256,38 @@ pub struct Opt {
     #[serde(default = "default_http_payload_size_limit")]
     pub http_payload_size_limit: Byte,
 
+    /// Lists the IP ranges of the reverse proxies in front of Meilisearch, separated by commas (for
+    /// instance: '10.0.0.0/8,fd00::/8'). The `X-Forwarded-For` header of their requests is used to find
+    /// the address of the clients, which is checked against the `allowedIps` of the API keys.
+    #[clap(long, env = MEILI_TRUSTED_PROXIES, value_delimiter = ',')]
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpCidr>,
+
+    /// Sets the maximum number of entries kept in the audit log, the oldest entries are deleted
+    /// beyond it. By default, the audit log is append-only and keeps all its entries.
+    #[clap(long, env = MEILI_AUDIT_LOG_MAX_ENTRIES)]
+    #[serde(default)]
+    pub audit_log_max_entries: Option<NonZeroU64>,
+
+    /// Sets the key used to encrypt the update files, dumps and snapshots at rest. The key must be
+    /// made of 64 hexadecimal characters (for instance, generated with `openssl rand -hex 32`).
+    ///
+    /// The files written before the key was set stay readable, and the batched update files are
+    /// decrypted in memory, within the limit of `--max-indexing-memory`: the document additions
+    /// exceeding it are rejected.
+    ///
+    /// The LMDB databases of the indexes, tasks and API keys are out of the scope of this option:
+    /// encrypting their pages requires opening every environment as an encrypted one, which the
+    /// indexes don't support. Only their copies in the snapshots are encrypted, the databases
+    /// themselves must be kept on an encrypted volume.
+    #[clap(long, env = MEILI_ENCRYPTION_KEY, conflicts_with = "encryption_key_path")]
+    pub encryption_key: Option<String>,
+
+    /// Sets the path of a file containing the key used to encrypt the update files, dumps and
+    /// snapshots at rest.
+    #[clap(long, env = MEILI_ENCRYPTION_KEY_PATH)]
+    pub encryption_key_path: Option<PathBuf>,
+
     /// Sets the server's SSL certificates.
     #[clap(long, env = MEILI_SSL_CERT_PATH, value_parser)]
     pub ssl_cert_path: Option<PathBuf>,
@@ -271,6 +322,9 @@ pub struct Opt {
     pub ssl_tickets: bool,
 
     /// Launches Meilisearch after importing a previously-generated snapshot at the given filepath.
+    ///
+    /// An `s3://bucket/key` URL can be used to import a snapshot from the object storage
+    /// configured with the `--experimental-s3-*` options.
     #[clap(long, env = MEILI_IMPORT_SNAPSHOT)]
     pub import_snapshot: Option<PathBuf>,
 
@@ -311,8 +365,22 @@ pub struct Opt {
     #[serde(default, deserialize_with = "schedule_snapshot_deserialize")]
     pub schedule_snapshot: ScheduleSnapshot,
 
+    /// Experimentally only copies the indexes that were updated since the previous snapshot.
+    ///
+    /// Each snapshot is kept in the snapshot directory under a unique name and references the
+    /// previous snapshots containing the unchanged indexes. Importing the latest snapshot
+    /// requires all the snapshots it references to be in the same directory.
+    ///
+    /// Can't be enabled along `--experimental-s3-bucket-url` as the referenced snapshots aren't uploaded.
+    #[clap(long, env = MEILI_EXPERIMENTAL_INCREMENTAL_SNAPSHOTS)]
+    #[serde(default)]
+    pub experimental_incremental_snapshots: bool,
+
     /// Imports the dump file located at the specified path. Path must point to a `.dump` file.
     /// If a database already exists, Meilisearch will throw an error and abort launch.
+    ///
+    /// An `s3://bucket/key` URL can be used to import a dump from the object storage
+    /// configured with the `--experimental-s3-*` options.
     #[clap(long, env = MEILI_IMPORT_DUMP, conflicts_with = "import_snapshot")]
     pub import_dump: Option<PathBuf>,
 
@@ -455,10 +523,23 @@ pub struct Opt {
     #[serde(default = "default_embedding_cache_entries")]
     pub experimental_embedding_cache_entries: usize,
 
+    /// Experimental support of tenant tokens minted by external identity providers.
+    ///
+    /// Path to a JSON file listing the trusted issuers. Each issuer defines its `issuer` claim,
+    /// the `apiKeyUid` bounding the permissions of its tokens, a PEM `publicKey` or a `jwks`
+    /// file or URL, an optional `audience`, and the `searchRulesClaim` JSON pointer to the search
+    /// rules in its tokens.
+    #[clap(long, env = MEILI_EXPERIMENTAL_TRUSTED_ISSUERS)]
+    pub experimental_trusted_issuers: Option<PathBuf>,
+
     #[serde(flatten)]
     #[clap(flatten)]
     pub indexer_options: IndexerOpts,
 
+    #[serde(flatten)]
+    #[clap(flatten)]
+    pub s3_options: S3Opts,
+
     /// Set the path to a configuration file that should be used to setup the engine.
     /// Format must be TOML.
     #[clap(long)]
@@ -526,6 +607,10 @@ impl Opt {
             max_index_size: _,
             max_task_db_size: _,
             http_payload_size_limit,
+            trusted_proxies,
+            audit_log_max_entries,
+            encryption_key,
+            encryption_key_path,
             ssl_cert_path,
             ssl_key_path,
             ssl_auth_path,
@@ -535,9 +620,11 @@ impl Opt {
             ssl_tickets,
             snapshot_dir,
             schedule_snapshot,
+            experimental_incremental_snapshots,
             dump_dir,
             log_level,
             indexer_options,
+            s3_options,
             import_snapshot: _,
             ignore_missing_snapshot: _,
             ignore_snapshot_if_db_exists: _,
@@ -559,6 +646,7 @@ impl Opt {
             experimental_max_number_of_batched_tasks,
             experimental_limit_batched_tasks_total_size,
             experimental_embedding_cache_entries,
+            experimental_trusted_issuers,
         } = self;
         export_to_env_if_not_present(MEILI_DB_PATH, db_path);
         export_to_env_if_not_present(MEILI_HTTP_ADDR, http_addr);
@@ -581,6 +669,22 @@ impl Opt {
             MEILI_HTTP_PAYLOAD_SIZE_LIMIT,
             http_payload_size_limit.to_string(),
         );
+        if !trusted_proxies.is_empty() {
+            let trusted_proxies: Vec<_> = trusted_proxies.iter().map(ToString::to_string).collect();
+            export_to_env_if_not_present(MEILI_TRUSTED_PROXIES, trusted_proxies.join(","));
+        }
+        if let Some(audit_log_max_entries) = audit_log_max_entries {
+            export_to_env_if_not_present(
+                MEILI_AUDIT_LOG_MAX_ENTRIES,
+                audit_log_max_entries.to_string(),
+            );
+        }
+        if let Some(encryption_key) = encryption_key {
+            export_to_env_if_not_present(MEILI_ENCRYPTION_KEY, encryption_key);
+        }
+        if let Some(encryption_key_path) = encryption_key_path {
+            export_to_env_if_not_present(MEILI_ENCRYPTION_KEY_PATH, encryption_key_path);
+        }
         if let Some(ssl_cert_path) = ssl_cert_path {
             export_to_env_if_not_present(MEILI_SSL_CERT_PATH, ssl_cert_path);
         }
@@ -631,6 +735,10 @@ impl Opt {
             MEILI_EXPERIMENTAL_DUMPLESS_UPGRADE,
             experimental_dumpless_upgrade.to_string(),
         );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_INCREMENTAL_SNAPSHOTS,
+            experimental_incremental_snapshots.to_string(),
+        );
         export_to_env_if_not_present(
             MEILI_EXPERIMENTAL_REPLICATION_PARAMETERS,
             experimental_replication_parameters.to_string(),
@@ -655,7 +763,14 @@ impl Opt {
             MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES,
             experimental_embedding_cache_entries.to_string(),
         );
+        if let Some(experimental_trusted_issuers) = experimental_trusted_issuers {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_TRUSTED_ISSUERS,
+                experimental_trusted_issuers,
+            );
+        }
         indexer_options.export_to_env();
+        s3_options.export_to_env();
     }
 
     pub fn get_ssl_config(&self) -> anyhow::Result<Option<rustls::ServerConfig>> {
@@ -702,6 +817,16 @@ impl Opt {
         }
     }
 
+    /// Returns the key used to encrypt the files at rest, read from the file if a path is provided.
+    pub fn get_encryption_key(&self) -> anyhow::Result<Option<EncryptionKey>> {
+        let key = match (&self.encryption_key, &self.encryption_key_path) {
+            (Some(key), _) => key.parse(),
+            (None, Some(path)) => EncryptionKey::from_path(path),
+            (None, None) => return Ok(None),
+        };
+        key.map(Some).context("While reading the encryption key")
+    }
+
     pub(crate) fn to_instance_features(&self) -> InstanceTogglableFeatures {
         InstanceTogglableFeatures {
             metrics: self.experimental_enable_metrics,
@@ -753,6 +878,129 @@ impl IndexerOpts {
     }
 }
 
+#[derive(Debug, Default, Clone, Parser, Deserialize)]
+pub struct S3Opts {
+    /// Experimentally uploads the snapshots and dumps to an S3-compatible object storage
+    /// reachable at this URL, e.g. `https://s3.eu-west-3.amazonaws.com` or `http://localhost:9000`.
+    ///
+    /// The snapshots and dumps are still written to their local directories.
+    /// The incremental snapshots are not supported and Meilisearch refuses to start if both are enabled.
+    #[clap(long, env = MEILI_EXPERIMENTAL_S3_BUCKET_URL)]
+    pub experimental_s3_bucket_url: Option<String>,
+
+    /// The region of the bucket used to upload the snapshots and dumps.
+    #[clap(long, env = MEILI_EXPERIMENTAL_S3_BUCKET_REGION)]
+    pub experimental_s3_bucket_region: Option<String>,
+
+    /// The name of the bucket used to upload the snapshots and dumps.
+    #[clap(long, env = MEILI_EXPERIMENTAL_S3_BUCKET_NAME)]
+    pub experimental_s3_bucket_name: Option<String>,
+
+    /// The access key used to authenticate on the object storage.
+    #[clap(long, env = MEILI_EXPERIMENTAL_S3_ACCESS_KEY)]
+    pub experimental_s3_access_key: Option<String>,
+
+    /// The secret key used to authenticate on the object storage.
+    #[clap(long, env = MEILI_EXPERIMENTAL_S3_SECRET_KEY)]
+    pub experimental_s3_secret_key: Option<String>,
+
+    /// The prefix of the keys under which the snapshots and dumps are uploaded.
+    /// They are respectively stored under `{prefix}/snapshots/` and `{prefix}/dumps/`.
+    #[clap(long, env = MEILI_EXPERIMENTAL_S3_PREFIX)]
+    pub experimental_s3_prefix: Option<String>,
+
+    /// The size of the parts sent to the object storage, each part is kept in memory while
+    /// being uploaded. Defaults to 64MiB and can't be lower than 5MiB.
+    #[clap(long, env = MEILI_EXPERIMENTAL_S3_MULTIPART_PART_SIZE)]
+    pub experimental_s3_multipart_part_size: Option<Byte>,
+
+    /// The number of snapshots, and of dumps, to keep in the bucket. The oldest ones are deleted
+    /// after each upload. All of them are kept by default.
+    #[clap(long, env = MEILI_EXPERIMENTAL_S3_RETENTION)]
+    pub experimental_s3_retention: Option<usize>,
+}
+
+impl S3Opts {
+    /// Exports the values to their corresponding env vars if they are not set.
+    pub fn export_to_env(self) {
+        let S3Opts {
+            experimental_s3_bucket_url,
+            experimental_s3_bucket_region,
+            experimental_s3_bucket_name,
+            experimental_s3_access_key,
+            experimental_s3_secret_key,
+            experimental_s3_prefix,
+            experimental_s3_multipart_part_size,
+            experimental_s3_retention,
+        } = self;
+        if let Some(bucket_url) = experimental_s3_bucket_url {
+            export_to_env_if_not_present(MEILI_EXPERIMENTAL_S3_BUCKET_URL, bucket_url);
+        }
+        if let Some(bucket_region) = experimental_s3_bucket_region {
+            export_to_env_if_not_present(MEILI_EXPERIMENTAL_S3_BUCKET_REGION, bucket_region);
+        }
+        if let Some(bucket_name) = experimental_s3_bucket_name {
+            export_to_env_if_not_present(MEILI_EXPERIMENTAL_S3_BUCKET_NAME, bucket_name);
+        }
+        if let Some(access_key) = experimental_s3_access_key {
+            export_to_env_if_not_present(MEILI_EXPERIMENTAL_S3_ACCESS_KEY, access_key);
+        }
+        if let Some(secret_key) = experimental_s3_secret_key {
+            export_to_env_if_not_present(MEILI_EXPERIMENTAL_S3_SECRET_KEY, secret_key);
+        }
+        if let Some(prefix) = experimental_s3_prefix {
+            export_to_env_if_not_present(MEILI_EXPERIMENTAL_S3_PREFIX, prefix);
+        }
+        if let Some(part_size) = experimental_s3_multipart_part_size {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_S3_MULTIPART_PART_SIZE,
+                part_size.to_string(),
+            );
+        }
+        if let Some(retention) = experimental_s3_retention {
+            export_to_env_if_not_present(MEILI_EXPERIMENTAL_S3_RETENTION, retention.to_string());
+        }
+    }
+
+    /// Returns the options of the object storage if one is configured.
+    pub fn to_s3_options(&self) -> anyhow::Result<Option<S3Options>> {
+        let Some(bucket_url) = &self.experimental_s3_bucket_url else { return Ok(None) };
+        let missing = |name: &str| anyhow::anyhow!("`--{name}` is required to use S3");
+
+        let multipart_part_size = match self.experimental_s3_multipart_part_size {
+            Some(size) if size.as_u64() < MIN_MULTIPART_PART_SIZE => anyhow::bail!(
+                "`--experimental-s3-multipart-part-size` must be at least 5MiB, got {}",
+                size.get_appropriate_unit(UnitType::Binary)
+            ),
+            Some(size) => size.as_u64(),
+            None => DEFAULT_MULTIPART_PART_SIZE,
+        };
+
+        Ok(Some(S3Options {
+            bucket_url: bucket_url.clone(),
+            bucket_region: self
+                .experimental_s3_bucket_region
+                .clone()
+                .ok_or_else(|| missing("experimental-s3-bucket-region"))?,
+            bucket_name: self
+                .experimental_s3_bucket_name
+                .clone()
+                .ok_or_else(|| missing("experimental-s3-bucket-name"))?,
+            access_key: self
+                .experimental_s3_access_key
+                .clone()
+                .ok_or_else(|| missing("experimental-s3-access-key"))?,
+            secret_key: self
+                .experimental_s3_secret_key
+                .clone()
+                .ok_or_else(|| missing("experimental-s3-secret-key"))?,
+            prefix: self.experimental_s3_prefix.clone().unwrap_or_default(),
+            multipart_part_size,
+            retention: self.experimental_s3_retention,
+        }))
+    }
+}
+
 impl TryFrom<&IndexerOpts> for IndexerConfig {
     type Error = anyhow::Error;
 
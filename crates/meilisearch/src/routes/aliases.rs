@@ -0,0 +1,203 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::{InvalidIndexAlias, InvalidIndexAliasIndexUid};
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::tasks::{IndexAlias, KindWithContent};
+use serde::Serialize;
+use tracing::debug;
+use utoipa::{OpenApi, ToSchema};
+
+use super::{get_task_id, is_dry_run, SummarizedTaskView};
+use crate::analytics::{Aggregate, Analytics};
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::Opt;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(get_aliases, update_aliases),
+    tags((
+        name = "Aliases",
+        description = "Aliases are alternative names that can be used in place of an index uid to add documents, update settings or search an index.
+Repointing an alias to another index is atomic, which makes it possible to reindex in the background and switch without downtime.",
+        external_docs(url = "https://www.meilisearch.com/docs/reference/api/aliases"),
+    )),
+)]
+pub struct AliasesApi;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(SeqHandler(get_aliases)))
+            .route(web::post().to(SeqHandler(update_aliases))),
+    );
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AliasView {
+    /// The name of the alias
+    alias: String,
+    /// The uid of the index the alias points to
+    index_uid: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AliasesView {
+    results: Vec<AliasView>,
+}
+
+/// List aliases
+///
+/// List all the index aliases along with the index they point to.
+#[utoipa::path(
+    get,
+    path = "",
+    tag = "Aliases",
+    security(("Bearer" = ["indexes.get", "indexes.*", "*"])),
+    responses(
+        (status = 200, description = "The aliases are returned", body = AliasesView, content_type = "application/json", example = json!(
+            {
+                "results": [
+                    {
+                        "alias": "movies",
+                        "indexUid": "movies_v2"
+                    }
+                ]
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn get_aliases(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::INDEXES_GET }>, Data<IndexScheduler>>,
+) -> Result<HttpResponse, ResponseError> {
+    let filters = index_scheduler.filters();
+    let results = index_scheduler
+        .index_aliases()?
+        .into_iter()
+        .filter(|(_, index_uid)| filters.is_index_authorized(index_uid))
+        .map(|(alias, index_uid)| AliasView { alias, index_uid })
+        .collect();
+    let aliases = AliasesView { results };
+
+    debug!(returns = ?aliases, "Get aliases");
+    Ok(HttpResponse::Ok().json(aliases))
+}
+
+#[derive(Deserr, Debug, Clone, PartialEq, Eq, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct IndexAliasPayload {
+    /// The name of the alias
+    #[schema(example = "movies")]
+    #[deserr(error = DeserrJsonError<InvalidIndexAlias>, missing_field_error = DeserrJsonError::missing_index_alias)]
+    alias: IndexUid,
+    /// The uid of the index the alias must point to, or `null` to remove the alias
+    #[schema(example = "movies_v2")]
+    #[deserr(default, error = DeserrJsonError<InvalidIndexAliasIndexUid>)]
+    index_uid: Option<IndexUid>,
+}
+
+#[derive(Serialize)]
+struct IndexAliasesUpdatedAnalytics {
+    alias_operation_number: usize,
+}
+
+impl Aggregate for IndexAliasesUpdatedAnalytics {
+    fn event_name(&self) -> &'static str {
+        "Index Aliases Updated"
+    }
+
+    fn aggregate(self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        Box::new(Self {
+            alias_operation_number: self.alias_operation_number.max(new.alias_operation_number),
+        })
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        serde_json::to_value(*self).unwrap_or_default()
+    }
+}
+
+/// Update aliases
+///
+/// Create, repoint or remove index aliases. An alias with an `indexUid` set to `null` is removed.
+/// All the aliases of a request are updated atomically: either all of them are updated, or none are.
+#[utoipa::path(
+    post,
+    path = "",
+    tag = "Aliases",
+    security(("Bearer" = ["indexes.update", "indexes.*", "*"])),
+    request_body = Vec<IndexAliasPayload>,
+    responses(
+        (status = 202, description = "Task successfully enqueued", body = SummarizedTaskView, content_type = "application/json", example = json!(
+            {
+                "taskUid": 3,
+                "indexUid": null,
+                "status": "enqueued",
+                "type": "indexAliasUpdate",
+                "enqueuedAt": "2021-08-12T10:00:00.000000Z"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn update_aliases(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::INDEXES_UPDATE }>, Data<IndexScheduler>>,
+    params: AwebJson<Vec<IndexAliasPayload>, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let params = params.into_inner();
+    debug!(parameters = ?params, "Update aliases");
+    analytics.publish(IndexAliasesUpdatedAnalytics { alias_operation_number: params.len() }, &req);
+    let filters = index_scheduler.filters();
+
+    let mut aliases = vec![];
+    for IndexAliasPayload { alias, index_uid } in params.into_iter() {
+        if !filters.is_index_authorized(&alias)
+            || index_uid.as_ref().is_some_and(|index_uid| !filters.is_index_authorized(index_uid))
+        {
+            return Err(AuthenticationError::InvalidToken.into());
+        }
+        aliases.push(IndexAlias {
+            alias: alias.into_inner(),
+            index_uid: index_uid.map(IndexUid::into_inner),
+        });
+    }
+
+    let task = KindWithContent::IndexAliasUpdate { aliases };
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let key_uid = index_scheduler.filters().key_uid();
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_key(task, uid, key_uid, dry_run)
+    })
+    .await??
+    .into();
+    debug!(returns = ?task, "Update aliases");
+    Ok(HttpResponse::Accepted().json(task))
+}
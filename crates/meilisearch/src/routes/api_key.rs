@@ -5,18 +5,21 @@ use actix_web::{web, HttpRequest, HttpResponse};
 use deserr::actix_web::{AwebJson, AwebQueryParameter};
 use deserr::Deserr;
 use meilisearch_auth::error::AuthControllerError;
+use meilisearch_auth::rate_limit::KeyUsage;
 use meilisearch_auth::AuthController;
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
-use meilisearch_types::keys::{CreateApiKey, Key, PatchApiKey};
+use meilisearch_types::ip_cidr::IpCidr;
+use meilisearch_types::keys::{CreateApiKey, Key, PatchApiKey, RateLimits, RotateApiKey};
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 use utoipa::{IntoParams, OpenApi, ToSchema};
 use uuid::Uuid;
 
 use super::{PaginationView, PAGINATION_DEFAULT_LIMIT, PAGINATION_DEFAULT_LIMIT_FN};
+use crate::error::PayloadError;
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::extractors::sequential_extractor::SeqHandler;
@@ -24,7 +27,7 @@ use crate::routes::Pagination;
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(create_api_key, list_api_keys, get_api_key, patch_api_key, delete_api_key),
+    paths(create_api_key, list_api_keys, get_api_key, patch_api_key, rotate_api_key, delete_api_key),
     tags((
         name = "Keys",
         description = "Manage API `keys` for a Meilisearch instance. Each key has a given set of permissions.
@@ -46,7 +49,8 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .route(web::get().to(SeqHandler(get_api_key)))
             .route(web::patch().to(SeqHandler(patch_api_key)))
             .route(web::delete().to(SeqHandler(delete_api_key))),
-    );
+    )
+    .service(web::resource("/{key}/rotate").route(web::post().to(SeqHandler(rotate_api_key))));
 }
 
 /// Create an API Key
@@ -221,6 +225,15 @@ pub async fn list_api_keys(
                     "movies"
                 ],
                 "expiresAt": "2022-11-12T10:00:00Z",
+                "rateLimits": {
+                    "requestsPerSecond": 20,
+                    "maxPayloadBytesPerDay": 104857600
+                },
+                "usage": {
+                    "availableRequests": 18,
+                    "payloadBytesToday": 3481,
+                    "concurrentSearches": 0
+                },
                 "createdAt": "2021-11-12T10:00:00Z",
                 "updatedAt": "2021-11-12T10:00:00Z"
             }
@@ -253,8 +266,9 @@ pub async fn get_api_key(
         let uid =
             Uuid::parse_str(&key).or_else(|_| auth_controller.get_uid_from_encoded_key(&key))?;
         let key = auth_controller.get_key(uid)?;
+        let usage = auth_controller.key_usage(&key).map(KeyUsageView::from);
 
-        Ok(KeyView::from_key(key, &auth_controller))
+        Ok(KeyView { usage, ..KeyView::from_key(key, &auth_controller) })
     })
     .await
     .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))??;
@@ -264,7 +278,7 @@ pub async fn get_api_key(
 
 /// Update a Key
 ///
-/// Update the name and description of an API key.
+/// Update the name, description, rate limits and network restrictions of an API key.
 /// Updates to keys are partial. This means you should provide only the fields you intend to update, as any fields not present in the payload will remain unchanged.
 #[utoipa::path(
     patch,
@@ -329,6 +343,82 @@ pub async fn patch_api_key(
     Ok(HttpResponse::Ok().json(res))
 }
 
+/// Rotate a Key
+///
+/// Give a new value to an API key without changing its uid, actions and indexes.
+/// The previous value, and the tenant tokens signed with it, are still accepted during the optional grace period.
+/// The body can be omitted to revoke the previous value immediately.
+#[utoipa::path(
+    post,
+    path = "/{uidOrKey}/rotate",
+    tag = "Keys",
+    security(("Bearer" = ["keys.update", "keys.*", "*"])),
+    params(("uidOrKey" = String, Path, format = Password, example = "7b198a7f-52a0-4188-8762-9ad93cd608b2", description = "The `uid` or `key` field of an existing API key", nullable = false)),
+    request_body = RotateApiKey,
+    responses(
+        (status = 200, description = "The key have been rotated", body = KeyView, content_type = "application/json", example = json!(
+            {
+                "uid": "01b4bc42-eb33-4041-b481-254d00cce834",
+                "key": "4f0c6a3c0a3c2d6ec6f8e3b9d4d2f6a1d0c1b8e7f5a4c3b2a1908f7e6d5c4b3a",
+                "name": "An API Key",
+                "description": null,
+                "actions": [
+                    "search"
+                ],
+                "indexes": [
+                    "movies"
+                ],
+                "expiresAt": null,
+                "previousKeyExpiresAt": "2021-11-12T11:00:00Z",
+                "createdAt": "2021-11-12T10:00:00Z",
+                "updatedAt": "2021-11-12T10:00:00Z"
+            }
+        )),
+        (status = 401, description = "The route has been hit on an unprotected instance", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Meilisearch is running without a master key. To access this API endpoint, you must have set a master key at launch.",
+                "code": "missing_master_key",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_master_key"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn rotate_api_key(
+    auth_controller: GuardedData<ActionPolicy<{ actions::KEYS_UPDATE }>, Data<AuthController>>,
+    body: web::Bytes,
+    path: web::Path<AuthParam>,
+) -> Result<HttpResponse, ResponseError> {
+    let key = path.into_inner().key;
+    // the body is optional, without one the previous value is immediately revoked.
+    let rotate_api_key = if body.is_empty() {
+        RotateApiKey::default()
+    } else {
+        let value: serde_json::Value =
+            serde_json::from_slice(&body).map_err(PayloadError::MalformedPayload)?;
+        deserr::deserialize::<_, _, DeserrJsonError>(value)?
+    };
+    let res = tokio::task::spawn_blocking(move || -> Result<_, AuthControllerError> {
+        let uid =
+            Uuid::parse_str(&key).or_else(|_| auth_controller.get_uid_from_encoded_key(&key))?;
+        let key = auth_controller.rotate_key(uid, rotate_api_key)?;
+
+        Ok(KeyView::from_key(key, &auth_controller))
+    })
+    .await
+    .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))??;
+
+    Ok(HttpResponse::Ok().json(res))
+}
+
 /// Delete a key
 ///
 /// Delete the specified API key.
@@ -397,6 +487,26 @@ pub(super) struct KeyView {
     /// The expiration date of the key. Once this timestamp is exceeded the key is not deleted but cannot be used anymore.
     #[serde(serialize_with = "time::serde::rfc3339::option::serialize")]
     expires_at: Option<OffsetDateTime>,
+    /// The limits enforced on the requests made with this key, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate_limits: Option<RateLimits>,
+    /// The IP ranges from which this key can be used, if restricted.
+    #[schema(value_type = Option<Vec<String>>)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allowed_ips: Option<Vec<IpCidr>>,
+    /// The origins from which this key can be used, if restricted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allowed_origins: Option<Vec<String>>,
+    /// The date until which the value of the key before its last rotation is still accepted, if any.
+    #[serde(
+        serialize_with = "time::serde::rfc3339::option::serialize",
+        skip_serializing_if = "Option::is_none"
+    )]
+    previous_key_expires_at: Option<OffsetDateTime>,
+    /// The current usage of the rate limits of this key. Only returned when fetching a single key.
+    #[schema(read_only)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    usage: Option<KeyUsageView>,
     /// The date of creation of this API Key.
     #[schema(read_only)]
     #[serde(serialize_with = "time::serde::rfc3339::serialize")]
@@ -409,7 +519,13 @@ pub(super) struct KeyView {
 
 impl KeyView {
     fn from_key(key: Key, auth: &AuthController) -> Self {
-        let generated_key = auth.generate_key(key.uid).unwrap_or_default();
+        let generated_key = auth.generate_key(&key).unwrap_or_default();
+        let now = OffsetDateTime::now_utc();
+        let previous_key_expires_at = key
+            .previous_secret
+            .as_ref()
+            .map(|previous| previous.valid_until)
+            .filter(|valid_until| *valid_until > now);
 
         KeyView {
             name: key.name,
@@ -419,8 +535,31 @@ impl KeyView {
             actions: key.actions,
             indexes: key.indexes.into_iter().map(|x| x.to_string()).collect(),
             expires_at: key.expires_at,
+            rate_limits: key.rate_limits,
+            allowed_ips: key.allowed_ips,
+            allowed_origins: key.allowed_origins,
+            previous_key_expires_at,
+            usage: None,
             created_at: key.created_at,
             updated_at: key.updated_at,
         }
     }
 }
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct KeyUsageView {
+    /// The number of requests that can be made right now. `null` if the requests per second are not limited.
+    available_requests: Option<u32>,
+    /// The number of payload bytes received with this key since the beginning of the UTC day.
+    payload_bytes_today: u64,
+    /// The number of searches currently running with this key.
+    concurrent_searches: u32,
+}
+
+impl From<KeyUsage> for KeyUsageView {
+    fn from(usage: KeyUsage) -> Self {
+        let KeyUsage { available_requests, payload_bytes_today, concurrent_searches } = usage;
+        KeyUsageView { available_requests, payload_bytes_today, concurrent_searches }
+    }
+}
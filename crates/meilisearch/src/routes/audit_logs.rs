@@ -0,0 +1,174 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpResponse};
+use deserr::actix_web::AwebQueryParameter;
+use deserr::Deserr;
+use meilisearch_auth::AuthController;
+use meilisearch_types::audit_log::{AuditLog, AuditLogId, AuditLogKeyUid};
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{Code, InvalidTaskDateError, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::keys::Action;
+use meilisearch_types::star_or::{OptionStarOr, OptionStarOrList};
+use serde::Serialize;
+use time::OffsetDateTime;
+use tracing::debug;
+use utoipa::{IntoParams, OpenApi, ToSchema};
+
+use super::tasks::{deserialize_date_after, deserialize_date_before};
+use super::PAGINATION_DEFAULT_LIMIT;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(get_audit_logs),
+    tags((
+        name = "Audit logs",
+        description = "The audit log records who made each request on the administrative routes, i.e. every route except the searches and the read-only routes polled by the clients: the tasks, batches, stats, metrics, version and the audit log itself.
+The log is kept in the database, next to the API keys. Only the last million entries are kept.",
+        external_docs(url = "https://www.meilisearch.com/docs/reference/api/audit_logs"),
+    )),
+)]
+pub struct AuditLogsApi;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(SeqHandler(get_audit_logs))));
+}
+
+#[derive(Debug, Deserr, IntoParams)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+#[into_params(rename_all = "camelCase", parameter_in = Query)]
+pub struct AuditLogsFilterQuery {
+    /// Maximum number of results to return.
+    #[deserr(default = Param(PAGINATION_DEFAULT_LIMIT as u32), error = DeserrQueryParamError<InvalidAuditLogLimit>)]
+    #[param(required = false, value_type = u32, example = 12, default = json!(PAGINATION_DEFAULT_LIMIT))]
+    pub limit: Param<u32>,
+    /// Fetch the next set of results from the given uid.
+    #[deserr(default, error = DeserrQueryParamError<InvalidAuditLogFrom>)]
+    #[param(required = false, value_type = Option<u64>, example = 12421)]
+    pub from: Option<Param<usize>>,
+    /// Permits to filter the entries by the uid of the API key used, `masterKey` for the master key and `unauthenticated` for the requests that could not be authenticated. It's possible to specify several key uids by separating them with the `,` character.
+    #[deserr(default, error = DeserrQueryParamError<InvalidAuditLogKeyUids>)]
+    #[param(required = false, value_type = Option<Vec<String>>, example = json!(["01b4bc42-eb33-4041-b481-254d00cce834", "masterKey"]))]
+    pub key_uids: OptionStarOrList<AuditLogKeyUid>,
+    /// Permits to filter the entries by the action required by the route. It's possible to specify several actions by separating them with the `,` character.
+    #[deserr(default, error = DeserrQueryParamError<InvalidAuditLogActions>)]
+    #[param(required = false, value_type = Option<Vec<Action>>, example = json!(["settings.update", "indexes.delete"]))]
+    pub actions: OptionStarOrList<Action>,
+    /// Permits to filter the entries by the index targeted by the request. It's possible to specify several indexes by separating them with the `,` character.
+    #[deserr(default, error = DeserrQueryParamError<InvalidAuditLogIndexUids>)]
+    #[param(required = false, value_type = Option<Vec<String>>, example = json!(["movies", "theater"]))]
+    pub index_uids: OptionStarOrList<IndexUid>,
+    /// Matches the entries recorded after the given date. Supports RFC 3339 date format.
+    #[deserr(default, error = DeserrQueryParamError<InvalidAuditLogAfterDate>, try_from(OptionStarOr<String>) = deserialize_date_after -> InvalidTaskDateError)]
+    #[param(required = false, value_type = Option<String>, example = json!(["2024-08-08T16:37:09.971Z", "*"]))]
+    pub after_date: OptionStarOr<OffsetDateTime>,
+    /// Matches the entries recorded before the given date. Supports RFC 3339 date format.
+    #[deserr(default, error = DeserrQueryParamError<InvalidAuditLogBeforeDate>, try_from(OptionStarOr<String>) = deserialize_date_before -> InvalidTaskDateError)]
+    #[param(required = false, value_type = Option<String>, example = json!(["2024-08-08T16:37:09.971Z", "*"]))]
+    pub before_date: OptionStarOr<OffsetDateTime>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AllAuditLogs {
+    results: Vec<AuditLog>,
+    limit: u32,
+    from: Option<AuditLogId>,
+    next: Option<AuditLogId>,
+}
+
+/// Get the audit log
+///
+/// Get the requests made on the administrative routes, from the most recent one.
+#[utoipa::path(
+    get,
+    path = "",
+    tag = "Audit logs",
+    security(("Bearer" = ["auditLogs.get", "*"])),
+    params(AuditLogsFilterQuery),
+    responses(
+        (status = 200, description = "The entries of the audit log are returned", body = AllAuditLogs, content_type = "application/json", example = json!(
+            {
+                "results": [
+                    {
+                        "uid": 12,
+                        "timestamp": "2024-08-08T09:01:13.348471Z",
+                        "keyUid": "01b4bc42-eb33-4041-b481-254d00cce834",
+                        "keyName": "Backoffice",
+                        "action": "settings.update",
+                        "indexUid": "movies",
+                        "method": "PATCH",
+                        "route": "/indexes/movies/settings",
+                        "statusCode": 202,
+                        "outcome": "succeeded"
+                    }
+                ],
+                "limit": 1,
+                "from": 12,
+                "next": 11
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn get_audit_logs(
+    auth_controller: GuardedData<ActionPolicy<{ actions::AUDIT_LOGS_GET }>, Data<AuthController>>,
+    params: AwebQueryParameter<AuditLogsFilterQuery, DeserrQueryParamError>,
+) -> Result<HttpResponse, ResponseError> {
+    let AuditLogsFilterQuery {
+        limit,
+        from,
+        key_uids,
+        actions,
+        index_uids,
+        after_date,
+        before_date,
+    } = params.into_inner();
+    let limit = limit.0;
+    let from = from.map(|from| from.0 as AuditLogId);
+    let key_uids = key_uids.merge_star_and_none();
+    let actions = actions.merge_star_and_none();
+    let index_uids = index_uids.map(|index_uid| index_uid.to_string()).merge_star_and_none();
+    let after_date = after_date.merge_star_and_none();
+    let before_date = before_date.merge_star_and_none();
+
+    let mut logs = tokio::task::spawn_blocking(move || {
+        let filters = auth_controller.filters();
+        // We +1 just to know if there is more after this "page" or not.
+        auth_controller.list_audit_logs(from, limit.saturating_add(1) as usize, |log| {
+            key_uids.as_ref().is_none_or(|uids| uids.contains(&log.key_uid))
+                && actions.as_ref().is_none_or(|actions| actions.contains(&log.action))
+                && index_uids.as_ref().is_none_or(|index_uids| {
+                    log.index_uid.as_ref().is_some_and(|index_uid| index_uids.contains(index_uid))
+                })
+                // the entries without index, e.g. about the keys, are reserved to the keys
+                // authorized on all the indexes
+                && match &log.index_uid {
+                    Some(index_uid) => filters.is_index_authorized(index_uid),
+                    None => filters.all_indexes_authorized(),
+                }
+                && after_date.is_none_or(|after| log.timestamp > after)
+                && before_date.is_none_or(|before| log.timestamp < before)
+        })
+    })
+    .await
+    .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))??;
+
+    let next = if logs.len() > limit as usize { logs.pop().map(|log| log.uid) } else { None };
+    let from = logs.first().map(|log| log.uid);
+    let audit_logs = AllAuditLogs { results: logs, limit, from, next };
+
+    debug!(returns = ?audit_logs, "Get audit logs");
+    Ok(HttpResponse::Ok().json(audit_logs))
+}
@@ -38,7 +38,7 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
     get,
     path = "/{batchUid}",
     tag = "Batches",
-    security(("Bearer" = ["tasks.get", "tasks.*", "*"])),
+    security(("Bearer" = ["tasks.get", "tasks.own", "tasks.*", "*"])),
     params(
         ("batchUid" = String, Path, example = "8685", description = "The unique batch id", nullable = false),
     ),
@@ -123,7 +123,7 @@ pub struct AllBatches {
     get,
     path = "",
     tag = "Batches",
-    security(("Bearer" = ["tasks.get", "tasks.*", "*"])),
+    security(("Bearer" = ["tasks.get", "tasks.own", "tasks.*", "*"])),
     params(TasksFilterQuery),
     responses(
         (status = OK, description = "Return the batches", body = AllBatches, content_type = "application/json", example = json!(
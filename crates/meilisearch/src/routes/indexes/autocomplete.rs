@@ -0,0 +1,201 @@
+use std::collections::BinaryHeap;
+
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+use tracing::debug;
+use utoipa::OpenApi;
+
+use crate::analytics::{Aggregate, Analytics};
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::search::{
+    add_search_rules, perform_autocomplete, AutocompleteQuery, AutocompleteResult,
+};
+use crate::search_queue::SearchQueue;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(autocomplete),
+    tags(
+        (
+            name = "Autocomplete",
+            description = "The `/autocomplete` route proposes completions of a query that is being typed. The last term of the query is completed with the words of the index, with typo tolerance, and followed by the words most often found after it. The completions are sorted by the number of documents containing them.",
+        ),
+    ),
+)]
+pub struct AutocompleteApi;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(autocomplete)));
+}
+
+#[derive(Default)]
+pub struct AutocompleteAggregator {
+    // requests
+    total_received: usize,
+    total_succeeded: usize,
+    time_spent: BinaryHeap<usize>,
+
+    // parameters
+    filter: bool,
+    max_limit: usize,
+    locales: bool,
+}
+
+impl AutocompleteAggregator {
+    pub fn from_query(query: &AutocompleteQuery) -> Self {
+        let AutocompleteQuery { q: _, filter, limit, locales } = query;
+
+        Self {
+            total_received: 1,
+            filter: filter.is_some(),
+            max_limit: *limit,
+            locales: locales.is_some(),
+            ..Default::default()
+        }
+    }
+
+    pub fn succeed(&mut self, result: &AutocompleteResult) {
+        let AutocompleteResult { completions: _, query: _, processing_time_ms } = result;
+        self.total_succeeded = 1;
+        self.time_spent.push(*processing_time_ms as usize);
+    }
+}
+
+impl Aggregate for AutocompleteAggregator {
+    fn event_name(&self) -> &'static str {
+        "Autocomplete POST"
+    }
+
+    fn aggregate(mut self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        for time in new.time_spent {
+            self.time_spent.push(time);
+        }
+
+        Box::new(Self {
+            total_received: self.total_received.saturating_add(new.total_received),
+            total_succeeded: self.total_succeeded.saturating_add(new.total_succeeded),
+            time_spent: self.time_spent,
+            filter: self.filter | new.filter,
+            max_limit: self.max_limit.max(new.max_limit),
+            locales: self.locales | new.locales,
+        })
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        let Self { total_received, total_succeeded, time_spent, filter, max_limit, locales } =
+            *self;
+        // the index of the 99th percentage of value
+        let percentile_99th = 0.99 * (total_succeeded as f64 - 1.) + 1.;
+        // we get all the values in a sorted manner
+        let time_spent = time_spent.into_sorted_vec();
+        // We are only interested by the slowest value of the 99th fastest results
+        let time_spent = time_spent.get(percentile_99th as usize);
+
+        serde_json::json!({
+            "requests": {
+                "99th_response_time":  time_spent.map(|t| format!("{:.2}", t)),
+                "total_succeeded": total_succeeded,
+                "total_failed": total_received.saturating_sub(total_succeeded), // just to be sure we never panics
+                "total_received": total_received,
+            },
+            "filter": {
+                "used": filter,
+            },
+            "pagination": {
+                "max_limit": max_limit,
+            },
+            "locales": {
+                "used": locales,
+            },
+        })
+    }
+}
+
+/// Autocomplete a query
+///
+/// Propose completions of a query that is being typed, made of words found in the documents.
+#[utoipa::path(
+    post,
+    path = "{indexUid}/autocomplete",
+    tag = "Autocomplete",
+    security(("Bearer" = ["search", "*"])),
+    params(("indexUid", example = "products", description = "Index Unique Identifier", nullable = false)),
+    request_body = AutocompleteQuery,
+    responses(
+        (status = 200, description = "The completions are returned", body = serde_json::Value, content_type = "application/json", example = json!(
+            {
+              "completions": [
+                { "value": "iphone", "count": 12 },
+                { "value": "iphone 15", "count": 5 },
+                { "value": "iphone 15 pro", "count": 3 }
+              ],
+              "query": "iph",
+              "processingTimeMs": 1
+            }
+        )),
+        (status = 404, description = "Index not found", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Index `products` not found.",
+                "code": "index_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#index_not_found"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn autocomplete(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    search_queue: Data<SearchQueue>,
+    index_uid: web::Path<String>,
+    params: AwebJson<AutocompleteQuery, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let mut query = params.into_inner();
+    debug!(parameters = ?query, "Autocomplete");
+
+    let mut aggregate = AutocompleteAggregator::from_query(&query);
+
+    let index_uid = index_scheduler.resolve_index_alias(index_uid)?;
+
+    // Tenant token search_rules.
+    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        add_search_rules(&mut query.filter, search_rules, Code::InvalidAutocompleteFilter)?;
+    }
+    let attributes_rules = index_scheduler.filters().get_index_attributes_rules(&index_uid);
+
+    let index = index_scheduler.index(&index_uid)?;
+    let permit = search_queue.try_get_search_permit().await?;
+    let result = tokio::task::spawn_blocking(move || {
+        perform_autocomplete(&index, query, index_scheduler.features(), attributes_rules)
+    })
+    .await;
+    permit.drop().await;
+    let result = result?;
+
+    if let Ok(ref result) = result {
+        aggregate.succeed(result);
+    }
+    analytics.publish(aggregate, &req);
+
+    let result = result?;
+
+    debug!(returns = ?result, "Autocomplete");
+    Ok(HttpResponse::Ok().json(result))
+}
@@ -1,5 +1,5 @@
 use std::collections::HashSet;
-use std::io::{ErrorKind, Seek as _};
+use std::io::{ErrorKind, Seek as _, Write as _};
 use std::marker::PhantomData;
 
 use actix_web::http::header::CONTENT_TYPE;
@@ -8,11 +8,15 @@ use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
 use bstr::ByteSlice as _;
 use deserr::actix_web::{AwebJson, AwebQueryParameter};
 use deserr::Deserr;
+use file_store::encryption::{decrypted_mmap, EncryptWriter, EncryptionKey};
 use futures::StreamExt;
 use index_scheduler::{IndexScheduler, RoFeatures, TaskId};
+use meilisearch_auth::AttributesRules;
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
-use meilisearch_types::document_formats::{read_csv, read_json, read_ndjson, PayloadType};
+use meilisearch_types::document_formats::{
+    read_csv, read_json_bytes, read_ndjson, read_ndjson_bytes, PayloadType,
+};
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::heed::RoTxn;
@@ -45,7 +49,7 @@ use crate::extractors::sequential_extractor::SeqHandler;
 use crate::routes::{
     get_task_id, is_dry_run, PaginationView, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT,
 };
-use crate::search::{parse_filter, ExternalDocumentId, RetrieveVectors};
+use crate::search::{add_search_rules, parse_filter, ExternalDocumentId, RetrieveVectors};
 use crate::{aggregate_methods, Opt};
 
 static ACCEPTED_CONTENT_TYPE: Lazy<Vec<String>> = Lazy::new(|| {
@@ -265,6 +269,14 @@ pub async fn get_document(
     let DocumentParam { index_uid, document_id } = document_param.into_inner();
     debug!(parameters = ?params, "Get document");
     let index_uid = IndexUid::try_from(index_uid)?;
+    let index_uid = index_scheduler.resolve_index_alias(index_uid)?;
+
+    // Tenant token search_rules.
+    let mut filter = None;
+    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        add_search_rules(&mut filter, search_rules, Code::InvalidDocumentFilter)?;
+    }
+    let attributes_rules = index_scheduler.filters().get_index_attributes_rules(&index_uid);
 
     let GetDocument { fields, retrieve_vectors: param_retrieve_vectors } = params.into_inner();
     let attributes_to_retrieve = fields.merge_star_and_none();
@@ -285,8 +297,15 @@ pub async fn get_document(
     );
 
     let index = index_scheduler.index(&index_uid)?;
-    let document =
-        retrieve_document(&index, &document_id, attributes_to_retrieve, retrieve_vectors)?;
+    let document = retrieve_document(
+        &index,
+        &document_id,
+        filter,
+        attributes_to_retrieve,
+        retrieve_vectors,
+        &attributes_rules,
+        index_scheduler.features(),
+    )?;
     debug!(returns = ?document, "Get document");
     Ok(HttpResponse::Ok().json(document))
 }
@@ -358,7 +377,7 @@ pub async fn delete_document(
     analytics: web::Data<Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
     let DocumentParam { index_uid, document_id } = path.into_inner();
-    let index_uid = IndexUid::try_from(index_uid)?;
+    let index_uid = index_scheduler.resolve_index_alias(IndexUid::try_from(index_uid)?)?;
 
     analytics.publish(
         DocumentsDeletionAggregator {
@@ -376,10 +395,12 @@ pub async fn delete_document(
     };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let key_uid = index_scheduler.filters().key_uid();
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_key(task, uid, key_uid, dry_run)
+    })
+    .await??
+    .into();
     debug!("returns: {:?}", task);
     Ok(HttpResponse::Accepted().json(task))
 }
@@ -610,12 +631,20 @@ pub async fn get_documents(
 }
 
 fn documents_by_query(
-    index_scheduler: &IndexScheduler,
+    index_scheduler: &GuardedData<ActionPolicy<{ actions::DOCUMENTS_GET }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
     query: BrowseQuery,
 ) -> Result<HttpResponse, ResponseError> {
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
-    let BrowseQuery { offset, limit, fields, retrieve_vectors, filter, ids } = query;
+    let BrowseQuery { offset, limit, fields, retrieve_vectors, mut filter, ids } = query;
+
+    let index_uid = index_scheduler.resolve_index_alias(index_uid)?;
+
+    // Tenant token search_rules.
+    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        add_search_rules(&mut filter, search_rules, Code::InvalidDocumentFilter)?;
+    }
+    let attributes_rules = index_scheduler.filters().get_index_attributes_rules(&index_uid);
 
     let retrieve_vectors = RetrieveVectors::new(retrieve_vectors);
 
@@ -642,6 +671,7 @@ fn documents_by_query(
         filter,
         fields,
         retrieve_vectors,
+        &attributes_rules,
         index_scheduler.features(),
     )?;
 
@@ -767,7 +797,8 @@ pub async fn replace_documents(
     opt: web::Data<Opt>,
     analytics: web::Data<Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
-    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index_uid =
+        index_scheduler.resolve_index_alias(IndexUid::try_from(index_uid.into_inner())?)?;
 
     debug!(parameters = ?params, "Replace documents");
     let params = params.into_inner();
@@ -868,7 +899,8 @@ pub async fn update_documents(
     opt: web::Data<Opt>,
     analytics: web::Data<Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
-    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index_uid =
+        index_scheduler.resolve_index_alias(IndexUid::try_from(index_uid.into_inner())?)?;
 
     let params = params.into_inner();
     debug!(parameters = ?params, "Update documents");
@@ -959,14 +991,14 @@ async fn document_addition(
         }
     };
 
-    let (uuid, mut update_file) = index_scheduler.queue.create_update_file(dry_run)?;
-    let documents_count = match format {
-        PayloadType::Ndjson => {
-            let (path, file) = update_file.into_parts();
+    let (uuid, update_file) = index_scheduler.queue.create_update_file(dry_run)?;
+    let encryption_key = index_scheduler.queue.update_files_encryption_key().cloned();
+    let documents_count = match (format, update_file.into_parts()) {
+        (PayloadType::Ndjson, Ok((path, file))) => {
             let file = match file {
                 Some(file) => {
                     let (file, path) = file.into_parts();
-                    let mut file = copy_body_to_file(file, body, format).await?;
+                    let mut file = copy_body_to_file(file, body, format, None).await?;
                     file.rewind().map_err(|e| {
                         index_scheduler::Error::FileStore(file_store::Error::IoError(e))
                     })?;
@@ -989,21 +1021,34 @@ async fn document_addition(
 
             Ok(documents_count)
         }
-        PayloadType::Json | PayloadType::Csv { delimiter: _ } => {
+        // an encrypted update file can only be written through its encryption, so the NDJSON
+        // payloads are received in a temporary file first, like the other formats. This file is
+        // encrypted like the update files and only decrypted in memory to be read.
+        (format, update_file) => {
+            let mut update_file = update_file
+                .map_or_else(|file| file, |(path, file)| file_store::File::from_parts(path, file));
             let temp_file = match tempfile() {
                 Ok(file) => file,
                 Err(e) => return Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e)))),
             };
 
-            let read_file = copy_body_to_file(temp_file, body, format).await?;
+            let read_file =
+                copy_body_to_file(temp_file, body, format, encryption_key.as_ref()).await?;
             tokio::task::spawn_blocking(move || {
+                let payload = decrypted_mmap(read_file, encryption_key.as_ref()).map_err(|e| {
+                    index_scheduler::Error::FileStore(file_store::Error::IoError(e))
+                })?;
                 let documents_count = match format {
-                    PayloadType::Json => read_json(&read_file, &mut update_file)?,
+                    PayloadType::Json => read_json_bytes(&payload, &mut update_file)?,
                     PayloadType::Csv { delimiter } => {
-                        read_csv(&read_file, &mut update_file, delimiter)?
+                        read_csv(&payload[..], &mut update_file, delimiter)?
                     }
                     PayloadType::Ndjson => {
-                        unreachable!("We already wrote the user content into the update file")
+                        let documents_count = read_ndjson_bytes(&payload)?;
+                        update_file.write_all(&payload).map_err(|e| {
+                            index_scheduler::Error::FileStore(file_store::Error::IoError(e))
+                        })?;
+                        documents_count
                     }
                 };
                 // we NEED to persist the file here because we moved the `udpate_file` in another task.
@@ -1046,9 +1091,12 @@ async fn document_addition(
         index_uid: index_uid.to_string(),
     };
 
+    let key_uid = index_scheduler.filters().key_uid();
     let scheduler = index_scheduler.clone();
-    let task = match tokio::task::spawn_blocking(move || scheduler.register(task, task_id, dry_run))
-        .await?
+    let task = match tokio::task::spawn_blocking(move || {
+        scheduler.register_with_key(task, task_id, key_uid, dry_run)
+    })
+    .await?
     {
         Ok(task) => task,
         Err(e) => {
@@ -1060,14 +1108,22 @@ async fn document_addition(
     Ok(task.into())
 }
 
+/// Writes the payload in the file, encrypted with the key if any.
 async fn copy_body_to_file(
     output: std::fs::File,
     mut body: Payload,
     format: PayloadType,
+    encryption_key: Option<&EncryptionKey>,
 ) -> Result<std::fs::File, MeilisearchHttpError> {
     let async_file = File::from_std(output);
     let mut buffer = BufWriter::new(async_file);
     let mut buffer_write_size: usize = 0;
+    // the payload is encrypted in memory, chunk by chunk, before being written in the file.
+    let mut encryption = match encryption_key.map(|key| EncryptWriter::new(Vec::new(), key)) {
+        Some(Ok(writer)) => Some(writer),
+        Some(Err(e)) => return Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e)))),
+        None => None,
+    };
     while let Some(result) = body.next().await {
         let byte = result?;
 
@@ -1075,11 +1131,27 @@ async fn copy_body_to_file(
             return Err(MeilisearchHttpError::MissingPayload(format));
         }
 
-        match buffer.write_all(&byte).await {
+        let written = match encryption.as_mut() {
+            Some(writer) => match writer.write_all(&byte) {
+                Ok(()) => buffer.write_all(&std::mem::take(writer.get_mut())).await,
+                Err(e) => Err(e),
+            },
+            None => buffer.write_all(&byte).await,
+        };
+        match written {
             Ok(()) => buffer_write_size += 1,
             Err(e) => return Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e)))),
         }
     }
+    if let Some(writer) = encryption {
+        let written = match writer.finish() {
+            Ok(last_chunk) => buffer.write_all(&last_chunk).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = written {
+            return Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e))));
+        }
+    }
     if let Err(e) = buffer.flush().await {
         return Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e))));
     }
@@ -1134,7 +1206,8 @@ pub async fn delete_documents_batch(
     analytics: web::Data<Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
     debug!(parameters = ?body, "Delete documents by batch");
-    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index_uid =
+        index_scheduler.resolve_index_alias(IndexUid::try_from(index_uid.into_inner())?)?;
 
     analytics.publish(
         DocumentsDeletionAggregator {
@@ -1155,10 +1228,12 @@ pub async fn delete_documents_batch(
         KindWithContent::DocumentDeletion { index_uid: index_uid.to_string(), documents_ids: ids };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let key_uid = index_scheduler.filters().key_uid();
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_key(task, uid, key_uid, dry_run)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Delete documents by batch");
     Ok(HttpResponse::Accepted().json(task))
@@ -1211,7 +1286,8 @@ pub async fn delete_documents_by_filter(
     analytics: web::Data<Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
     debug!(parameters = ?body, "Delete documents by filter");
-    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index_uid =
+        index_scheduler.resolve_index_alias(IndexUid::try_from(index_uid.into_inner())?)?;
     let index_uid = index_uid.into_inner();
     let filter = body.into_inner().filter;
 
@@ -1233,10 +1309,12 @@ pub async fn delete_documents_by_filter(
 
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let key_uid = index_scheduler.filters().key_uid();
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_key(task, uid, key_uid, dry_run)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Delete documents by filter");
     Ok(HttpResponse::Accepted().json(task))
@@ -1330,7 +1408,8 @@ pub async fn edit_documents_by_function(
         .features()
         .check_edit_documents_by_function("Using the documents edit route")?;
 
-    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index_uid =
+        index_scheduler.resolve_index_alias(IndexUid::try_from(index_uid.into_inner())?)?;
     let index_uid = index_uid.into_inner();
     let params = params.into_inner();
 
@@ -1376,10 +1455,12 @@ pub async fn edit_documents_by_function(
 
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let key_uid = index_scheduler.filters().key_uid();
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_key(task, uid, key_uid, dry_run)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Edit documents by function");
     Ok(HttpResponse::Accepted().json(task))
@@ -1421,7 +1502,8 @@ pub async fn clear_all_documents(
     opt: web::Data<Opt>,
     analytics: web::Data<Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
-    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index_uid =
+        index_scheduler.resolve_index_alias(IndexUid::try_from(index_uid.into_inner())?)?;
     analytics.publish(
         DocumentsDeletionAggregator {
             clear_all: true,
@@ -1435,10 +1517,12 @@ pub async fn clear_all_documents(
     let task = KindWithContent::DocumentClear { index_uid: index_uid.to_string() };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let key_uid = index_scheduler.filters().key_uid();
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_key(task, uid, key_uid, dry_run)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Delete all documents");
     Ok(HttpResponse::Accepted().json(task))
@@ -1449,9 +1533,21 @@ fn some_documents<'a, 't: 'a>(
     rtxn: &'t RoTxn,
     doc_ids: impl IntoIterator<Item = DocumentId> + 'a,
     retrieve_vectors: RetrieveVectors,
+    attributes_rules: &AttributesRules,
 ) -> Result<impl Iterator<Item = Result<Document, ResponseError>> + 'a, ResponseError> {
     let fields_ids_map = index.fields_ids_map(rtxn)?;
-    let all_fields: Vec<_> = fields_ids_map.iter().map(|(id, _)| id).collect();
+    // the attributes hidden by the search rules of a tenant token are never returned
+    let all_fields: Vec<_> = fields_ids_map
+        .iter()
+        .filter(|(_, name)| attributes_rules.is_visible(name))
+        .map(|(id, _)| id)
+        .collect();
+    let retrieve_vectors =
+        if attributes_rules.is_visible(milli::constants::RESERVED_VECTORS_FIELD_NAME) {
+            retrieve_vectors
+        } else {
+            RetrieveVectors::Hide
+        };
     let embedding_configs = index.embedding_configs(rtxn)?;
 
     Ok(index.iter_documents(rtxn, doc_ids)?.map(move |ret| {
@@ -1500,6 +1596,7 @@ fn retrieve_documents<S: AsRef<str>>(
     filter: Option<Value>,
     attributes_to_retrieve: Option<Vec<S>>,
     retrieve_vectors: RetrieveVectors,
+    attributes_rules: &AttributesRules,
     features: RoFeatures,
 ) -> Result<(u64, Vec<Document>), ResponseError> {
     let rtxn = index.read_txn()?;
@@ -1541,6 +1638,7 @@ fn retrieve_documents<S: AsRef<str>>(
                 &rtxn,
                 candidates.into_iter().skip(offset).take(limit),
                 retrieve_vectors,
+                attributes_rules,
             )?,
             number_of_documents,
         )
@@ -1566,8 +1664,11 @@ fn retrieve_documents<S: AsRef<str>>(
 fn retrieve_document<S: AsRef<str>>(
     index: &Index,
     doc_id: &str,
+    filter: Option<Value>,
     attributes_to_retrieve: Option<Vec<S>>,
     retrieve_vectors: RetrieveVectors,
+    attributes_rules: &AttributesRules,
+    features: RoFeatures,
 ) -> Result<Document, ResponseError> {
     let txn = index.read_txn()?;
 
@@ -1576,9 +1677,19 @@ fn retrieve_document<S: AsRef<str>>(
         .get(&txn, doc_id)?
         .ok_or_else(|| MeilisearchHttpError::DocumentNotFound(doc_id.to_string()))?;
 
-    let document = some_documents(index, &txn, Some(internal_id), retrieve_vectors)?
-        .next()
-        .ok_or_else(|| MeilisearchHttpError::DocumentNotFound(doc_id.to_string()))??;
+    // a document filtered out by the tenant token must be indistinguishable from a missing one
+    if let Some(filter) = filter {
+        if let Some(filter) = parse_filter(&filter, Code::InvalidDocumentFilter, features)? {
+            if !filter.evaluate(&txn, index)?.contains(internal_id) {
+                return Err(MeilisearchHttpError::DocumentNotFound(doc_id.to_string()).into());
+            }
+        }
+    }
+
+    let document =
+        some_documents(index, &txn, Some(internal_id), retrieve_vectors, attributes_rules)?
+            .next()
+            .ok_or_else(|| MeilisearchHttpError::DocumentNotFound(doc_id.to_string()))??;
 
     let document = match &attributes_to_retrieve {
         Some(attributes_to_retrieve) => permissive_json_pointer::select_values(
@@ -6,7 +6,7 @@ use deserr::actix_web::AwebJson;
 use index_scheduler::IndexScheduler;
 use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::deserr_codes::*;
-use meilisearch_types::error::ResponseError;
+use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::locales::Locale;
 use serde_json::Value;
@@ -19,9 +19,9 @@ use crate::extractors::authentication::GuardedData;
 use crate::routes::indexes::search::search_kind;
 use crate::search::{
     add_search_rules, perform_facet_search, FacetSearchResult, HybridQuery, MatchingStrategy,
-    RankingScoreThreshold, SearchQuery, SearchResult, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
-    DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
-    DEFAULT_SEARCH_OFFSET,
+    QuerySyntax, RankingScoreThreshold, SearchQuery, SearchResult, DEFAULT_CROP_LENGTH,
+    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
+    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
 };
 use crate::search_queue::SearchQueue;
 
@@ -62,6 +62,8 @@ pub struct FacetSearchQuery {
     pub filter: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
     pub matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchQuerySyntax>, default)]
+    pub query_syntax: QuerySyntax,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>, default)]
     pub attributes_to_search_on: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>, default)]
@@ -96,6 +98,7 @@ impl FacetSearchAggregator {
             q,
             filter,
             matching_strategy,
+            query_syntax,
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
@@ -110,6 +113,7 @@ impl FacetSearchAggregator {
                 || vector.is_some()
                 || filter.is_some()
                 || *matching_strategy != MatchingStrategy::default()
+                || *query_syntax != QuerySyntax::default()
                 || attributes_to_search_on.is_some()
                 || hybrid.is_some()
                 || ranking_score_threshold.is_some()
@@ -250,10 +254,13 @@ pub async fn search(
     let locales = query.locales.clone().map(|l| l.into_iter().map(Into::into).collect());
     let mut search_query = SearchQuery::from(query);
 
+    let index_uid = index_scheduler.resolve_index_alias(index_uid)?;
+
     // Tenant token search_rules.
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
-        add_search_rules(&mut search_query.filter, search_rules);
+        add_search_rules(&mut search_query.filter, search_rules, Code::InvalidSearchFilter)?;
     }
+    let attributes_rules = index_scheduler.filters().get_index_attributes_rules(&index_uid);
 
     let index = index_scheduler.index(&index_uid)?;
     let search_kind = search_kind(&search_query, &index_scheduler, index_uid.to_string(), &index)?;
@@ -267,6 +274,7 @@ pub async fn search(
             search_kind,
             index_scheduler.features(),
             locales,
+            attributes_rules,
         )
     })
     .await;
@@ -293,6 +301,7 @@ impl From<FacetSearchQuery> for SearchQuery {
             vector,
             filter,
             matching_strategy,
+            query_syntax,
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
@@ -327,16 +336,21 @@ impl From<FacetSearchQuery> for SearchQuery {
             filter,
             sort: None,
             distinct: None,
+            collapse: None,
             facets: None,
             highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
             highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
             crop_marker: DEFAULT_CROP_MARKER(),
             matching_strategy,
+            query_syntax,
             vector,
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
             locales,
+            suggestions: false,
+            explain: false,
+            explain_documents: None,
         }
     }
 }
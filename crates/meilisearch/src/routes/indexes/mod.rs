@@ -28,6 +28,7 @@ use crate::extractors::sequential_extractor::SeqHandler;
 use crate::routes::is_dry_run;
 use crate::Opt;
 
+pub mod autocomplete;
 pub mod documents;
 pub mod facet_search;
 pub mod search;
@@ -42,12 +43,13 @@ mod similar_analytics;
 #[derive(OpenApi)]
 #[openapi(
     nest(
+        (path = "/", api = autocomplete::AutocompleteApi),
         (path = "/", api = documents::DocumentsApi),
         (path = "/", api = facet_search::FacetSearchApi),
         (path = "/", api = similar::SimilarApi),
         (path = "/", api = settings::SettingsApi),
     ),
-    paths(list_indexes, create_index, get_index, update_index, delete_index, get_index_stats),
+    paths(list_indexes, create_index, get_index, update_index, delete_index, clone_index, export_index, import_index, get_index_stats),
     tags(
         (
             name = "Indexes",
@@ -73,9 +75,13 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                     .route(web::delete().to(SeqHandler(delete_index))),
             )
             .service(web::resource("/stats").route(web::get().to(SeqHandler(get_index_stats))))
+            .service(web::resource("/clone").route(web::post().to(SeqHandler(clone_index))))
+            .service(web::resource("/export").route(web::post().to(SeqHandler(export_index))))
+            .service(web::resource("/import").route(web::post().to(SeqHandler(import_index))))
             .service(web::scope("/documents").configure(documents::configure))
             .service(web::scope("/search").configure(search::configure))
             .service(web::scope("/facet-search").configure(facet_search::configure))
+            .service(web::scope("/autocomplete").configure(autocomplete::configure))
             .service(web::scope("/similar").configure(similar::configure))
             .service(web::scope("/settings").configure(settings::configure)),
     );
@@ -270,10 +276,12 @@ pub async fn create_index(
         let task = KindWithContent::IndexCreation { index_uid: uid.to_string(), primary_key };
         let uid = get_task_id(&req, &opt)?;
         let dry_run = is_dry_run(&req, &opt)?;
-        let task: SummarizedTaskView =
-            tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-                .await??
-                .into();
+        let key_uid = index_scheduler.filters().key_uid();
+        let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+            index_scheduler.register_with_key(task, uid, key_uid, dry_run)
+        })
+        .await??
+        .into();
         debug!(returns = ?task, "Create index");
 
         Ok(HttpResponse::Accepted().json(task))
@@ -431,10 +439,12 @@ pub async fn update_index(
 
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let key_uid = index_scheduler.filters().key_uid();
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_key(task, uid, key_uid, dry_run)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Update index");
     Ok(HttpResponse::Accepted().json(task))
@@ -479,15 +489,295 @@ pub async fn delete_index(
     let task = KindWithContent::IndexDeletion { index_uid: index_uid.into_inner() };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let key_uid = index_scheduler.filters().key_uid();
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_key(task, uid, key_uid, dry_run)
+    })
+    .await??
+    .into();
     debug!(returns = ?task, "Delete index");
 
     Ok(HttpResponse::Accepted().json(task))
 }
 
+#[derive(Deserr, Debug, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct IndexCloneRequest {
+    /// The name of the index to create
+    #[schema(example = "movies_copy")]
+    #[deserr(error = DeserrJsonError<InvalidIndexUid>, missing_field_error = DeserrJsonError::missing_index_uid)]
+    uid: IndexUid,
+}
+
+#[derive(Serialize)]
+struct IndexClonedAggregate {
+    total_received: usize,
+}
+
+impl Aggregate for IndexClonedAggregate {
+    fn event_name(&self) -> &'static str {
+        "Index Cloned"
+    }
+
+    fn aggregate(self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        Box::new(Self { total_received: self.total_received.saturating_add(new.total_received) })
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        serde_json::to_value(*self).unwrap_or_default()
+    }
+}
+
+/// Clone index
+///
+/// Create a new index with a copy of the documents, settings and embeddings of an existing index.
+/// Nothing is reindexed nor re-embedded.
+#[utoipa::path(
+    post,
+    path = "/{indexUid}/clone",
+    tag = "Indexes",
+    security(("Bearer" = ["indexes.create", "indexes.*", "*"])),
+    params(("indexUid", example = "movies", description = "Index Unique Identifier", nullable = false)),
+    request_body = IndexCloneRequest,
+    responses(
+        (status = ACCEPTED, description = "Task successfully enqueued", body = SummarizedTaskView, content_type = "application/json", example = json!(
+            {
+                "taskUid": 148,
+                "indexUid": "movies",
+                "status": "enqueued",
+                "type": "indexClone",
+                "enqueuedAt": "2024-08-08T17:05:55.791772Z"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn clone_index(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::INDEXES_CREATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<IndexCloneRequest, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index_uid = index_scheduler.resolve_index_alias(index_uid)?;
+    debug!(parameters = ?body, "Clone index");
+    let IndexCloneRequest { uid: new_index_uid } = body.into_inner();
+
+    if !index_scheduler.filters().allow_index_creation(&new_index_uid) {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+    analytics.publish(IndexClonedAggregate { total_received: 1 }, &req);
+
+    let task = KindWithContent::IndexClone {
+        index_uid: index_uid.into_inner(),
+        new_index_uid: new_index_uid.into_inner(),
+    };
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let key_uid = index_scheduler.filters().key_uid();
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_key(task, uid, key_uid, dry_run)
+    })
+    .await??
+    .into();
+    debug!(returns = ?task, "Clone index");
+
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+#[derive(Serialize)]
+struct IndexExportedAggregate {
+    total_received: usize,
+}
+
+impl Aggregate for IndexExportedAggregate {
+    fn event_name(&self) -> &'static str {
+        "Index Exported"
+    }
+
+    fn aggregate(self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        Box::new(Self { total_received: self.total_received.saturating_add(new.total_received) })
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        serde_json::to_value(*self).unwrap_or_default()
+    }
+}
+
+/// Export index
+///
+/// Write a dump containing only the documents, settings and embeddings of an index in the dumps directory.
+/// The `dumpUid` of the dump can be found in the details of the task once it succeeded.
+#[utoipa::path(
+    post,
+    path = "/{indexUid}/export",
+    tag = "Indexes",
+    security(("Bearer" = ["dumps.create", "dumps.*", "*"])),
+    params(("indexUid", example = "movies", description = "Index Unique Identifier", nullable = false)),
+    responses(
+        (status = ACCEPTED, description = "Task successfully enqueued", body = SummarizedTaskView, content_type = "application/json", example = json!(
+            {
+                "taskUid": 149,
+                "indexUid": "movies",
+                "status": "enqueued",
+                "type": "indexExport",
+                "enqueuedAt": "2024-08-08T17:05:55.791772Z"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn export_index(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DUMPS_CREATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index_uid = index_scheduler.resolve_index_alias(index_uid)?;
+    analytics.publish(IndexExportedAggregate { total_received: 1 }, &req);
+
+    let task = KindWithContent::IndexExport { index_uid: index_uid.into_inner() };
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let key_uid = index_scheduler.filters().key_uid();
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_key(task, uid, key_uid, dry_run)
+    })
+    .await??
+    .into();
+    debug!(returns = ?task, "Export index");
+
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+#[derive(Deserr, Debug, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct IndexImportRequest {
+    /// The uid of a dump of the dumps directory containing a single index
+    #[schema(example = "movies-20240808-170555791")]
+    #[deserr(error = DeserrJsonError<InvalidIndexImportDumpUid>, missing_field_error = DeserrJsonError::missing_index_import_dump_uid)]
+    dump_uid: String,
+}
+
+#[derive(Serialize)]
+struct IndexImportedAggregate {
+    total_received: usize,
+}
+
+impl Aggregate for IndexImportedAggregate {
+    fn event_name(&self) -> &'static str {
+        "Index Imported"
+    }
+
+    fn aggregate(self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        Box::new(Self { total_received: self.total_received.saturating_add(new.total_received) })
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        serde_json::to_value(*self).unwrap_or_default()
+    }
+}
+
+/// Import index
+///
+/// Import the documents, settings and embeddings of a dump containing a single index, such as the
+/// ones written by the export route. The index is created if it doesn't exist. Otherwise, the
+/// settings of the dump are applied and its documents replace the ones with the same id.
+///
+/// Any dump of the dumps directory can be imported, the key must be able to create dumps
+/// and access all the indexes.
+#[utoipa::path(
+    post,
+    path = "/{indexUid}/import",
+    tag = "Indexes",
+    security(("Bearer" = ["dumps.create", "dumps.*", "*"])),
+    params(("indexUid", example = "movies", description = "Index Unique Identifier", nullable = false)),
+    request_body = IndexImportRequest,
+    responses(
+        (status = ACCEPTED, description = "Task successfully enqueued", body = SummarizedTaskView, content_type = "application/json", example = json!(
+            {
+                "taskUid": 150,
+                "indexUid": "movies",
+                "status": "enqueued",
+                "type": "indexImport",
+                "enqueuedAt": "2024-08-08T17:05:55.791772Z"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn import_index(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DUMPS_CREATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<IndexImportRequest, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index_uid = index_scheduler.resolve_index_alias(index_uid)?;
+    debug!(parameters = ?body, "Import index");
+    let IndexImportRequest { dump_uid } = body.into_inner();
+
+    // The dump uid is used as a file name in the dumps directory, we must not let it escape from it.
+    if dump_uid.is_empty()
+        || !dump_uid.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(ResponseError::from_msg(
+            format!("Invalid value at `.dumpUid`: `{dump_uid}` is not a valid dump uid. A dump uid can only contain alphanumeric characters, hyphens (-) and underscores (_)."),
+            Code::InvalidIndexImportDumpUid,
+        ));
+    }
+    // The dump may contain an index of another tenant, only a key accessing all the indexes
+    // can read any of them.
+    if !index_scheduler.filters().all_indexes_authorized()
+        || !index_scheduler.filters().allow_index_creation(&index_uid)
+    {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+    analytics.publish(IndexImportedAggregate { total_received: 1 }, &req);
+
+    let task = KindWithContent::IndexImport { index_uid: index_uid.into_inner(), dump_uid };
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let key_uid = index_scheduler.filters().key_uid();
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_key(task, uid, key_uid, dry_run)
+    })
+    .await??
+    .into();
+    debug!(returns = ?task, "Import index");
+
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 /// Stats of an `Index`, as known to the `stats` route.
 #[derive(Serialize, Debug, ToSchema)]
 #[serde(rename_all = "camelCase")]
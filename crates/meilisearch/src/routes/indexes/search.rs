@@ -5,7 +5,7 @@ use index_scheduler::IndexScheduler;
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::error::deserr_codes::*;
-use meilisearch_types::error::ResponseError;
+use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::locales::Locale;
 use meilisearch_types::milli;
@@ -22,10 +22,11 @@ use crate::extractors::sequential_extractor::SeqHandler;
 use crate::metrics::MEILISEARCH_DEGRADED_SEARCH_REQUESTS;
 use crate::routes::indexes::search_analytics::{SearchAggregator, SearchGET, SearchPOST};
 use crate::search::{
-    add_search_rules, perform_search, HybridQuery, MatchingStrategy, RankingScoreThreshold,
-    RetrieveVectors, SearchKind, SearchQuery, SearchResult, SemanticRatio, DEFAULT_CROP_LENGTH,
-    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
-    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
+    add_search_rules, perform_search, CollapseQuery, HybridQuery, MatchingStrategy, QuerySyntax,
+    RankingScoreThreshold, RetrieveVectors, SearchKind, SearchQuery, SearchResult, SemanticRatio,
+    DEFAULT_COLLAPSE_INNER_HITS, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
+    DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
+    DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
 };
 use crate::search_queue::SearchQueue;
 
@@ -95,6 +96,11 @@ pub struct SearchQueryGet {
     sort: Option<String>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchDistinct>)]
     distinct: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchCollapse>)]
+    collapse_field: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchCollapse>)]
+    #[param(value_type = Option<usize>)]
+    collapse_inner_hits: Option<Param<usize>>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowMatchesPosition>)]
     #[param(value_type = bool)]
     show_matches_position: Param<bool>,
@@ -118,6 +124,8 @@ pub struct SearchQueryGet {
     crop_marker: String,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchMatchingStrategy>)]
     matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchQuerySyntax>)]
+    query_syntax: QuerySyntax,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchAttributesToSearchOn>)]
     #[param(value_type = Vec<String>, explode = false)]
     pub attributes_to_search_on: Option<CS<String>>,
@@ -132,6 +140,15 @@ pub struct SearchQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchLocales>)]
     #[param(value_type = Vec<Locale>, explode = false)]
     pub locales: Option<CS<Locale>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchSuggestions>)]
+    #[param(value_type = bool)]
+    pub suggestions: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchExplain>)]
+    #[param(value_type = bool)]
+    pub explain: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchExplainDocuments>)]
+    #[param(value_type = Vec<String>, explode = false)]
+    pub explain_documents: Option<CS<String>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, deserr::Deserr)]
@@ -196,6 +213,20 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
             }
         };
 
+        let collapse = match (other.collapse_field, other.collapse_inner_hits) {
+            (None, None) => None,
+            (None, Some(_)) => {
+                return Err(ResponseError::from_msg(
+                    "`collapseField` is mandatory when `collapseInnerHits` is present".into(),
+                    meilisearch_types::error::Code::InvalidSearchCollapse,
+                ));
+            }
+            (Some(field), inner_hits) => Some(CollapseQuery {
+                field,
+                inner_hits: inner_hits.map_or_else(DEFAULT_COLLAPSE_INNER_HITS, |o| o.0),
+            }),
+        };
+
         if other.vector.is_some() && hybrid.is_none() {
             return Err(ResponseError::from_msg(
                 "`hybridEmbedder` is mandatory when `vector` is present".into(),
@@ -218,6 +249,7 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
             filter,
             sort: other.sort.map(|attr| fix_sort_query_parameters(&attr)),
             distinct: other.distinct,
+            collapse,
             show_matches_position: other.show_matches_position.0,
             show_ranking_score: other.show_ranking_score.0,
             show_ranking_score_details: other.show_ranking_score_details.0,
@@ -226,10 +258,14 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
             highlight_post_tag: other.highlight_post_tag,
             crop_marker: other.crop_marker,
             matching_strategy: other.matching_strategy,
+            query_syntax: other.query_syntax,
             attributes_to_search_on: other.attributes_to_search_on.map(|o| o.into_iter().collect()),
             hybrid,
             ranking_score_threshold: other.ranking_score_threshold.map(|o| o.0),
             locales: other.locales.map(|o| o.into_iter().collect()),
+            suggestions: other.suggestions.0,
+            explain: other.explain.0,
+            explain_documents: other.explain_documents.map(|o| o.into_iter().collect()),
         })
     }
 }
@@ -328,10 +364,13 @@ pub async fn search_with_url_query(
 
     let mut query: SearchQuery = params.into_inner().try_into()?;
 
+    let index_uid = index_scheduler.resolve_index_alias(index_uid)?;
+
     // Tenant token search_rules.
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
-        add_search_rules(&mut query.filter, search_rules);
+        add_search_rules(&mut query.filter, search_rules, Code::InvalidSearchFilter)?;
     }
+    let attributes_rules = index_scheduler.filters().get_index_attributes_rules(&index_uid);
 
     let mut aggregate = SearchAggregator::<SearchGET>::from_query(&query);
 
@@ -349,6 +388,7 @@ pub async fn search_with_url_query(
             search_kind,
             retrieve_vector,
             index_scheduler.features(),
+            attributes_rules,
         )
     })
     .await;
@@ -434,10 +474,13 @@ pub async fn search_with_post(
     let mut query = params.into_inner();
     debug!(parameters = ?query, "Search post");
 
+    let index_uid = index_scheduler.resolve_index_alias(index_uid)?;
+
     // Tenant token search_rules.
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
-        add_search_rules(&mut query.filter, search_rules);
+        add_search_rules(&mut query.filter, search_rules, Code::InvalidSearchFilter)?;
     }
+    let attributes_rules = index_scheduler.filters().get_index_attributes_rules(&index_uid);
 
     let mut aggregate = SearchAggregator::<SearchPOST>::from_query(&query);
 
@@ -456,6 +499,7 @@ pub async fn search_with_post(
             search_kind,
             retrieve_vectors,
             index_scheduler.features(),
+            attributes_rules,
         )
     })
     .await;
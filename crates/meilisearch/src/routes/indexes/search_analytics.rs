@@ -8,7 +8,7 @@ use serde_json::{json, Value};
 use crate::aggregate_methods;
 use crate::analytics::{Aggregate, AggregateMethod};
 use crate::search::{
-    SearchQuery, SearchResult, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
+    QuerySyntax, SearchQuery, SearchResult, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
     DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
     DEFAULT_SEMANTIC_RATIO,
 };
@@ -37,6 +37,10 @@ pub struct SearchAggregator<Method: AggregateMethod> {
     // distinct
     distinct: bool,
 
+    // collapse
+    // every time a search collapses its hits
+    collapse_total_number_of_uses: usize,
+
     // filter
     filter_with_geo_radius: bool,
     filter_with_geo_bounding_box: bool,
@@ -53,6 +57,12 @@ pub struct SearchAggregator<Method: AggregateMethod> {
     // q
     // The maximum number of terms in a q request
     max_terms_number: usize,
+    // every time a search is done using the advanced query syntax
+    advanced_query_syntax_total_number_of_uses: usize,
+    // every time a search asks for query suggestions
+    suggestions_total_number_of_uses: usize,
+    // every time a search asks for an explanation of the ranking
+    explain_total_number_of_uses: usize,
 
     // vector
     // The maximum number of floats in a vector request
@@ -116,15 +126,20 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             filter,
             sort,
             distinct,
+            collapse,
             facets: _,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
+            query_syntax,
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
             locales,
+            suggestions,
+            explain,
+            explain_documents: _,
         } = query;
 
         let mut ret = Self::default();
@@ -139,6 +154,10 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
 
         ret.distinct = distinct.is_some();
 
+        if collapse.is_some() {
+            ret.collapse_total_number_of_uses = 1;
+        }
+
         if let Some(ref filter) = filter {
             static RE: Lazy<Regex> = Lazy::new(|| Regex::new("AND | OR").unwrap());
             ret.filter_total_number_of_criteria = 1;
@@ -172,6 +191,18 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             ret.max_terms_number = q.split_whitespace().count();
         }
 
+        if *query_syntax == QuerySyntax::Advanced {
+            ret.advanced_query_syntax_total_number_of_uses = 1;
+        }
+
+        if *suggestions {
+            ret.suggestions_total_number_of_uses = 1;
+        }
+
+        if *explain {
+            ret.explain_total_number_of_uses = 1;
+        }
+
         if let Some(ref vector) = vector {
             ret.max_vector_size = vector.len();
         }
@@ -219,6 +250,9 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             processing_time_ms,
             hits_info: _,
             semantic_hit_count: _,
+            suggestions: _,
+            applied_rules: _,
+            explanation: _,
             facet_distribution: _,
             facet_stats: _,
             degraded,
@@ -250,6 +284,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             sort_sum_of_criteria_terms,
             sort_total_number_of_criteria,
             distinct,
+            collapse_total_number_of_uses,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
             filter_sum_of_criteria_terms,
@@ -257,6 +292,9 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             used_syntax,
             attributes_to_search_on_total_number_of_uses,
             max_terms_number,
+            advanced_query_syntax_total_number_of_uses,
+            suggestions_total_number_of_uses,
+            explain_total_number_of_uses,
             max_vector_size,
             retrieve_vectors,
             matching_strategy,
@@ -302,6 +340,10 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
         // distinct
         self.distinct |= distinct;
 
+        // collapse
+        self.collapse_total_number_of_uses =
+            self.collapse_total_number_of_uses.saturating_add(collapse_total_number_of_uses);
+
         // filter
         self.filter_with_geo_radius |= filter_with_geo_radius;
         self.filter_with_geo_bounding_box |= filter_with_geo_bounding_box;
@@ -321,6 +363,13 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
 
         // q
         self.max_terms_number = self.max_terms_number.max(max_terms_number);
+        self.advanced_query_syntax_total_number_of_uses = self
+            .advanced_query_syntax_total_number_of_uses
+            .saturating_add(advanced_query_syntax_total_number_of_uses);
+        self.suggestions_total_number_of_uses =
+            self.suggestions_total_number_of_uses.saturating_add(suggestions_total_number_of_uses);
+        self.explain_total_number_of_uses =
+            self.explain_total_number_of_uses.saturating_add(explain_total_number_of_uses);
 
         // vector
         self.max_vector_size = self.max_vector_size.max(max_vector_size);
@@ -376,6 +425,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             sort_sum_of_criteria_terms,
             sort_total_number_of_criteria,
             distinct,
+            collapse_total_number_of_uses,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
             filter_sum_of_criteria_terms,
@@ -383,6 +433,9 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             used_syntax,
             attributes_to_search_on_total_number_of_uses,
             max_terms_number,
+            advanced_query_syntax_total_number_of_uses,
+            suggestions_total_number_of_uses,
+            explain_total_number_of_uses,
             max_vector_size,
             retrieve_vectors,
             matching_strategy,
@@ -431,6 +484,9 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
                 "avg_criteria_number": format!("{:.2}", sort_sum_of_criteria_terms as f64 / sort_total_number_of_criteria as f64),
             },
             "distinct": distinct,
+            "collapse": {
+                "total_number_of_uses": collapse_total_number_of_uses,
+            },
             "filter": {
                "with_geoRadius": filter_with_geo_radius,
                "with_geoBoundingBox": filter_with_geo_bounding_box,
@@ -442,6 +498,9 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             },
             "q": {
                "max_terms_number": max_terms_number,
+               "advanced_syntax_total_number_of_uses": advanced_query_syntax_total_number_of_uses,
+               "suggestions_total_number_of_uses": suggestions_total_number_of_uses,
+               "explain_total_number_of_uses": explain_total_number_of_uses,
             },
             "vector": {
                 "max_vector_size": max_vector_size,
@@ -126,6 +126,7 @@ macro_rules! make_setting_route {
                 opt: web::Data<Opt>,
             ) -> Result<HttpResponse, ResponseError> {
                 let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+                let index_uid = index_scheduler.resolve_index_alias(index_uid)?;
 
                 let new_settings = Settings { $attr: Setting::Reset.into(), ..Default::default() };
 
@@ -140,10 +141,12 @@ macro_rules! make_setting_route {
                 };
                 let uid = get_task_id(&req, &opt)?;
                 let dry_run = is_dry_run(&req, &opt)?;
-                let task: SummarizedTaskView =
-                    tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-                        .await??
-                        .into();
+                let key_uid = index_scheduler.filters().key_uid();
+                let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+                    index_scheduler.register_with_key(task, uid, key_uid, dry_run)
+                })
+                .await??
+                .into();
 
                 debug!(returns = ?task, "Delete settings");
                 Ok(HttpResponse::Accepted().json(task))
@@ -192,6 +195,7 @@ macro_rules! make_setting_route {
                 analytics: web::Data<Analytics>,
             ) -> std::result::Result<HttpResponse, ResponseError> {
                 let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+                let index_uid = index_scheduler.resolve_index_alias(index_uid)?;
 
                 let body = body.into_inner();
                 debug!(parameters = ?body, "Update settings");
@@ -226,10 +230,12 @@ macro_rules! make_setting_route {
                 };
                 let uid = get_task_id(&req, &opt)?;
                 let dry_run = is_dry_run(&req, &opt)?;
-                let task: SummarizedTaskView =
-                    tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-                        .await??
-                        .into();
+                let key_uid = index_scheduler.filters().key_uid();
+                let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+                    index_scheduler.register_with_key(task, uid, key_uid, dry_run)
+                })
+                .await??
+                .into();
 
                 debug!(returns = ?task, "Update settings");
                 Ok(HttpResponse::Accepted().json(task))
@@ -267,6 +273,7 @@ macro_rules! make_setting_route {
                 index_uid: actix_web::web::Path<String>,
             ) -> std::result::Result<HttpResponse, ResponseError> {
                 let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+                let index_uid = index_scheduler.resolve_index_alias(index_uid)?;
 
                 let index = index_scheduler.index(&index_uid)?;
                 let rtxn = index.read_txn()?;
@@ -508,6 +515,28 @@ make_setting_routes!(
         camelcase_attr: "prefixSearch",
         analytics: PrefixSearchAnalytics
     },
+    {
+        route: "/ngram-attributes",
+        update_verb: put,
+        value_type: std::collections::BTreeSet<String>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsNgramAttributes,
+        >,
+        attr: ngram_attributes,
+        camelcase_attr: "ngramAttributes",
+        analytics: NgramAttributesAnalytics
+    },
+    {
+        route: "/query-rules",
+        update_verb: put,
+        value_type: Vec<meilisearch_types::query_rules::QueryRuleView>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsQueryRules,
+        >,
+        attr: query_rules,
+        camelcase_attr: "queryRules",
+        analytics: QueryRulesAnalytics
+    },
 );
 
 #[utoipa::path(
@@ -551,7 +580,8 @@ pub async fn update_all(
     opt: web::Data<Opt>,
     analytics: web::Data<Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
-    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index_uid =
+        index_scheduler.resolve_index_alias(IndexUid::try_from(index_uid.into_inner())?)?;
 
     let new_settings = body.into_inner();
     debug!(parameters = ?new_settings, "Update all settings");
@@ -597,6 +627,10 @@ pub async fn update_all(
             ),
             facet_search: FacetSearchAnalytics::new(new_settings.facet_search.as_ref().set()),
             prefix_search: PrefixSearchAnalytics::new(new_settings.prefix_search.as_ref().set()),
+            ngram_attributes: NgramAttributesAnalytics::new(
+                new_settings.ngram_attributes.as_ref().set(),
+            ),
+            query_rules: QueryRulesAnalytics::new(new_settings.query_rules.as_ref().set()),
         },
         &req,
     );
@@ -611,10 +645,12 @@ pub async fn update_all(
     };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let key_uid = index_scheduler.filters().key_uid();
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_key(task, uid, key_uid, dry_run)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Update all settings");
     Ok(HttpResponse::Accepted().json(task))
@@ -647,7 +683,8 @@ pub async fn get_all(
     index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_GET }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
 ) -> Result<HttpResponse, ResponseError> {
-    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index_uid =
+        index_scheduler.resolve_index_alias(IndexUid::try_from(index_uid.into_inner())?)?;
 
     let index = index_scheduler.index(&index_uid)?;
     let rtxn = index.read_txn()?;
@@ -691,7 +728,8 @@ pub async fn delete_all(
     req: HttpRequest,
     opt: web::Data<Opt>,
 ) -> Result<HttpResponse, ResponseError> {
-    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index_uid =
+        index_scheduler.resolve_index_alias(IndexUid::try_from(index_uid.into_inner())?)?;
 
     let new_settings = Settings::cleared().into_unchecked();
 
@@ -705,10 +743,12 @@ pub async fn delete_all(
     };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let key_uid = index_scheduler.filters().key_uid();
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_key(task, uid, key_uid, dry_run)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Delete all settings");
     Ok(HttpResponse::Accepted().json(task))
@@ -9,6 +9,7 @@ use meilisearch_types::facet_values_sort::FacetValuesSort;
 use meilisearch_types::locales::{Locale, LocalizedAttributesRuleView};
 use meilisearch_types::milli::update::Setting;
 use meilisearch_types::milli::FilterableAttributesRule;
+use meilisearch_types::query_rules::QueryRuleView;
 use meilisearch_types::settings::{
     FacetingSettings, PaginationSettings, PrefixSearchSettings, ProximityPrecisionView,
     RankingRuleView, SettingEmbeddingSettings, TypoSettings,
@@ -39,6 +40,8 @@ pub struct SettingsAnalytics {
     pub non_separator_tokens: NonSeparatorTokensAnalytics,
     pub facet_search: FacetSearchAnalytics,
     pub prefix_search: PrefixSearchAnalytics,
+    pub ngram_attributes: NgramAttributesAnalytics,
+    pub query_rules: QueryRulesAnalytics,
 }
 
 impl Aggregate for SettingsAnalytics {
@@ -67,6 +70,7 @@ impl Aggregate for SettingsAnalytics {
                     .ranking_rules
                     .exactness_position
                     .or(self.ranking_rules.exactness_position),
+                bm25_position: new.ranking_rules.bm25_position.or(self.ranking_rules.bm25_position),
                 values: new.ranking_rules.values.or(self.ranking_rules.values),
             },
             searchable_attributes: SearchableAttributesAnalytics {
@@ -198,6 +202,12 @@ impl Aggregate for SettingsAnalytics {
                 set: new.prefix_search.set | self.prefix_search.set,
                 value: new.prefix_search.value.or(self.prefix_search.value),
             },
+            ngram_attributes: NgramAttributesAnalytics {
+                total: new.ngram_attributes.total.or(self.ngram_attributes.total),
+            },
+            query_rules: QueryRulesAnalytics {
+                total: new.query_rules.total.or(self.query_rules.total),
+            },
         })
     }
 
@@ -214,6 +224,7 @@ pub struct RankingRulesAnalytics {
     pub attribute_position: Option<usize>,
     pub sort_position: Option<usize>,
     pub exactness_position: Option<usize>,
+    pub bm25_position: Option<usize>,
     pub values: Option<String>,
 }
 
@@ -247,6 +258,10 @@ impl RankingRulesAnalytics {
                     matches!(s, meilisearch_types::settings::RankingRuleView::Exactness)
                 })
             }),
+            bm25_position: rr.as_ref().and_then(|rr| {
+                rr.iter()
+                    .position(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Bm25))
+            }),
             values: rr.as_ref().map(|rr| {
                 rr.iter()
                     .filter(|s| {
@@ -674,3 +689,33 @@ impl PrefixSearchAnalytics {
         SettingsAnalytics { prefix_search: self, ..Default::default() }
     }
 }
+
+#[derive(Serialize, Default)]
+pub struct NgramAttributesAnalytics {
+    pub total: Option<usize>,
+}
+
+impl NgramAttributesAnalytics {
+    pub fn new(attributes: Option<&BTreeSet<String>>) -> Self {
+        Self { total: attributes.as_ref().map(|attributes| attributes.len()) }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { ngram_attributes: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct QueryRulesAnalytics {
+    pub total: Option<usize>,
+}
+
+impl QueryRulesAnalytics {
+    pub fn new(rules: Option<&Vec<QueryRuleView>>) -> Self {
+        Self { total: rules.as_ref().map(|rules| rules.len()) }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { query_rules: self, ..Default::default() }
+    }
+}
@@ -5,7 +5,7 @@ use index_scheduler::IndexScheduler;
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::error::deserr_codes::*;
-use meilisearch_types::error::ResponseError;
+use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::keys::actions;
 use meilisearch_types::serde_cs::vec::CS;
@@ -218,10 +218,13 @@ async fn similar(
 ) -> Result<SimilarResult, ResponseError> {
     let retrieve_vectors = RetrieveVectors::new(query.retrieve_vectors);
 
+    let index_uid = index_scheduler.resolve_index_alias(index_uid)?;
+
     // Tenant token search_rules.
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
-        add_search_rules(&mut query.filter, search_rules);
+        add_search_rules(&mut query.filter, search_rules, Code::InvalidSimilarFilter)?;
     }
+    let attributes_rules = index_scheduler.filters().get_index_attributes_rules(&index_uid);
 
     let index = index_scheduler.index(&index_uid)?;
 
@@ -243,6 +246,7 @@ async fn similar(
             quantized,
             retrieve_vectors,
             index_scheduler.features(),
+            attributes_rules,
         )
     })
     .await?
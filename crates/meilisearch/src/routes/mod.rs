@@ -4,11 +4,12 @@ use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
 use index_scheduler::IndexScheduler;
 use meilisearch_auth::AuthController;
+use meilisearch_types::audit_log::{AuditLog, AuditLogOutcome};
 use meilisearch_types::batch_view::BatchView;
 use meilisearch_types::batches::BatchStats;
 use meilisearch_types::error::{Code, ErrorType, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
-use meilisearch_types::keys::CreateApiKey;
+use meilisearch_types::keys::{CreateApiKey, RateLimits, RotateApiKey};
 use meilisearch_types::milli::{
     AttributePatterns, FilterFeatures, FilterableAttributesFeatures, FilterableAttributesPatterns,
     FilterableAttributesRule,
@@ -24,15 +25,19 @@ use time::OffsetDateTime;
 use tracing::debug;
 use utoipa::{OpenApi, ToSchema};
 
-use self::api_key::KeyView;
+use self::api_key::{KeyUsageView, KeyView};
 use self::indexes::documents::BrowseQuery;
-use self::indexes::{IndexCreateRequest, IndexStats, UpdateIndexRequest};
+use self::indexes::{
+    IndexCloneRequest, IndexCreateRequest, IndexImportRequest, IndexStats, UpdateIndexRequest,
+};
 use self::logs::{GetLogs, LogMode, UpdateStderrLogs};
 use self::open_api_utils::OpenApiAuth;
 use self::tasks::AllTasks;
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::milli::progress::{ProgressStepView, ProgressView};
+use crate::routes::aliases::{AliasView, AliasesView, IndexAliasPayload};
+use crate::routes::audit_logs::AllAuditLogs;
 use crate::routes::batches::AllBatches;
 use crate::routes::features::RuntimeTogglableFeatures;
 use crate::routes::indexes::documents::{DocumentDeletionByFilter, DocumentEditionByFunction};
@@ -50,7 +55,9 @@ use crate::Opt;
 const PAGINATION_DEFAULT_LIMIT: usize = 20;
 const PAGINATION_DEFAULT_LIMIT_FN: fn() -> usize = || 20;
 
+mod aliases;
 mod api_key;
+mod audit_logs;
 pub mod batches;
 mod dump;
 pub mod features;
@@ -82,6 +89,8 @@ mod tasks_test;
         (path = "/logs", api = logs::LogsApi),
         (path = "/multi-search", api = multi_search::MultiSearchApi),
         (path = "/swap-indexes", api = swap_indexes::SwapIndexesApi),
+        (path = "/aliases", api = aliases::AliasesApi),
+        (path = "/audit-logs", api = audit_logs::AuditLogsApi),
         (path = "/experimental-features", api = features::ExperimentalFeaturesApi),
         (path = "/network", api = network::NetworkApi),
     ),
@@ -94,7 +103,7 @@ mod tasks_test;
         url = "/",
         description = "Local server",
     )),
-    components(schemas(PaginationView<KeyView>, PaginationView<IndexView>, IndexView, DocumentDeletionByFilter, AllBatches, BatchStats, ProgressStepView, ProgressView, BatchView, RuntimeTogglableFeatures, SwapIndexesPayload, DocumentEditionByFunction, MergeFacets, FederationOptions, SearchQueryWithIndex, Federation, FederatedSearch, FederatedSearchResult, SearchResults, SearchResultWithIndex, SimilarQuery, SimilarResult, PaginationView<serde_json::Value>, BrowseQuery, UpdateIndexRequest, IndexUid, IndexCreateRequest, KeyView, Action, CreateApiKey, UpdateStderrLogs, LogMode, GetLogs, IndexStats, Stats, HealthStatus, HealthResponse, VersionResponse, Code, ErrorType, AllTasks, TaskView, Status, DetailsView, ResponseError, Settings<Unchecked>, Settings<Checked>, TypoSettings, MinWordSizeTyposSetting, FacetingSettings, PaginationSettings, SummarizedTaskView, Kind, Network, Remote, FilterableAttributesRule, FilterableAttributesPatterns, AttributePatterns, FilterableAttributesFeatures, FilterFeatures))
+    components(schemas(PaginationView<KeyView>, PaginationView<IndexView>, IndexView, DocumentDeletionByFilter, AllBatches, BatchStats, ProgressStepView, ProgressView, BatchView, RuntimeTogglableFeatures, SwapIndexesPayload, IndexAliasPayload, AliasView, AliasesView, DocumentEditionByFunction, MergeFacets, FederationOptions, SearchQueryWithIndex, Federation, FederatedSearch, FederatedSearchResult, SearchResults, SearchResultWithIndex, SimilarQuery, SimilarResult, PaginationView<serde_json::Value>, BrowseQuery, UpdateIndexRequest, IndexUid, IndexCreateRequest, IndexCloneRequest, IndexImportRequest, KeyView, KeyUsageView, Action, CreateApiKey, RateLimits, RotateApiKey, AllAuditLogs, AuditLog, AuditLogOutcome, UpdateStderrLogs, LogMode, GetLogs, IndexStats, Stats, HealthStatus, HealthResponse, VersionResponse, Code, ErrorType, AllTasks, TaskView, Status, DetailsView, ResponseError, Settings<Unchecked>, Settings<Checked>, TypoSettings, MinWordSizeTyposSetting, FacetingSettings, PaginationSettings, SummarizedTaskView, Kind, Network, Remote, FilterableAttributesRule, FilterableAttributesPatterns, AttributePatterns, FilterableAttributesFeatures, FilterFeatures))
 )]
 pub struct MeilisearchApi;
 
@@ -111,6 +120,8 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .service(web::scope("/indexes").configure(indexes::configure))
         .service(web::scope("/multi-search").configure(multi_search::configure))
         .service(web::scope("/swap-indexes").configure(swap_indexes::configure))
+        .service(web::scope("/aliases").configure(aliases::configure))
+        .service(web::scope("/audit-logs").configure(audit_logs::configure))
         .service(web::scope("/metrics").configure(metrics::configure))
         .service(web::scope("/experimental-features").configure(features::configure))
         .service(web::scope("/network").configure(network::configure));
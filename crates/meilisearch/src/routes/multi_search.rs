@@ -4,7 +4,7 @@ use actix_web::{HttpRequest, HttpResponse};
 use deserr::actix_web::AwebJson;
 use index_scheduler::IndexScheduler;
 use meilisearch_types::deserr::DeserrJsonError;
-use meilisearch_types::error::ResponseError;
+use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::keys::actions;
 use serde::Serialize;
 use tracing::debug;
@@ -163,7 +163,13 @@ pub async fn multi_search_with_post(
     // regardless of federation, check authorization and apply search rules
     let auth = 'check_authorization: {
         for (query_index, federated_query) in queries.iter_mut().enumerate() {
-            let index_uid = federated_query.index_uid.as_str();
+            // The permissions are the ones of the index the alias points to
+            let index_uid =
+                match index_scheduler.resolve_index_alias(federated_query.index_uid.clone()) {
+                    Ok(index_uid) => index_uid,
+                    Err(err) => break 'check_authorization Err(err).with_index(query_index),
+                };
+            let index_uid = index_uid.as_str();
             // Check index from API key
             if !index_scheduler.filters().is_index_authorized(index_uid) {
                 break 'check_authorization Err(AuthenticationError::InvalidToken)
@@ -172,7 +178,13 @@ pub async fn multi_search_with_post(
             // Apply search rules from tenant token
             if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(index_uid)
             {
-                add_search_rules(&mut federated_query.filter, search_rules);
+                if let Err(err) = add_search_rules(
+                    &mut federated_query.filter,
+                    search_rules,
+                    Code::InvalidSearchFilter,
+                ) {
+                    break 'check_authorization Err(err).with_index(query_index);
+                }
             }
         }
         Ok(())
@@ -193,9 +205,15 @@ pub async fn multi_search_with_post(
                 .headers()
                 .get(PROXY_SEARCH_HEADER)
                 .is_some_and(|value| value.as_bytes() == PROXY_SEARCH_HEADER_VALUE.as_bytes());
-            let search_result =
-                perform_federated_search(&index_scheduler, queries, federation, features, is_proxy)
-                    .await;
+            let search_result = perform_federated_search(
+                &index_scheduler,
+                index_scheduler.filters(),
+                queries,
+                federation,
+                features,
+                is_proxy,
+            )
+            .await;
             permit.drop().await;
 
             if search_result.is_ok() {
@@ -228,8 +246,11 @@ pub async fn multi_search_with_post(
                         ));
                     }
 
+                    let target_uid = index_scheduler
+                        .resolve_index_alias(index_uid.clone())
+                        .with_index(query_index)?;
                     let index = index_scheduler
-                        .index(&index_uid)
+                        .index(&target_uid)
                         .map_err(|err| {
                             let mut err = ResponseError::from(err);
                             // Patch the HTTP status code to 400 as it defaults to 404 for `index_not_found`, but
@@ -240,6 +261,8 @@ pub async fn multi_search_with_post(
                         .with_index(query_index)?;
 
                     let index_uid_str = index_uid.to_string();
+                    let attributes_rules =
+                        index_scheduler.filters().get_index_attributes_rules(&target_uid);
 
                     let search_kind = search_kind(
                         &query,
@@ -258,6 +281,7 @@ pub async fn multi_search_with_post(
                             search_kind,
                             retrieve_vector,
                             features,
+                            attributes_rules,
                         )
                     })
                     .await
@@ -57,15 +57,20 @@ impl MultiSearchAggregator {
             filter: _,
             sort: _,
             distinct: _,
+            collapse: _,
             facets: _,
             highlight_pre_tag: _,
             highlight_post_tag: _,
             crop_marker: _,
             matching_strategy: _,
+            query_syntax: _,
             attributes_to_search_on: _,
             hybrid: _,
             ranking_score_threshold: _,
             locales: _,
+            suggestions: _,
+            explain: _,
+            explain_documents: _,
         } in &federated_search.queries
         {
             if let Some(federation_options) = federation_options {
@@ -1,4 +1,4 @@
-use std::io::ErrorKind;
+use std::io::{ErrorKind, Read};
 
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
@@ -18,9 +18,9 @@ use serde::Serialize;
 use time::format_description::well_known::Rfc3339;
 use time::macros::format_description;
 use time::{Date, Duration, OffsetDateTime, Time};
-use tokio::io::AsyncReadExt;
 use tokio::task;
 use utoipa::{IntoParams, OpenApi, ToSchema};
+use uuid::Uuid;
 
 use super::{get_task_id, is_dry_run, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT};
 use crate::analytics::{Aggregate, AggregateMethod, Analytics};
@@ -96,6 +96,10 @@ pub struct TasksFilterQuery {
     #[deserr(default, error = DeserrQueryParamError<InvalidIndexUid>)]
     #[param(required = false, value_type = Option<Vec<String>>, example = json!(["movies", "theater", "*"]))]
     pub index_uids: OptionStarOrList<IndexUid>,
+    /// Permits to filter tasks by the uid of the API key that enqueued them. By default, when `keyUids` query parameter is not set, the tasks of all the keys are returned. It is possible to specify several keys by separating them with the `,` character.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskKeyUids>)]
+    #[param(required = false, value_type = Option<Vec<String>>, example = json!(["01b4bc42-eb33-4041-b481-254d00cce834", "*"]))]
+    pub key_uids: OptionStarOrList<Uuid>,
 
     /// Permits to filter tasks based on their enqueuedAt time. Matches tasks enqueued after the given date. Supports RFC 3339 date format.
     #[deserr(default, error = DeserrQueryParamError<InvalidTaskAfterEnqueuedAt>, try_from(OptionStarOr<String>) = deserialize_date_after -> InvalidTaskDateError)]
@@ -133,6 +137,7 @@ impl TasksFilterQuery {
             statuses: self.statuses.merge_star_and_none(),
             types: self.types.merge_star_and_none(),
             index_uids: self.index_uids.map(|x| x.to_string()).merge_star_and_none(),
+            key_uids: self.key_uids.merge_star_and_none(),
             uids: self.uids.merge_star_and_none(),
             canceled_by: self.canceled_by.merge_star_and_none(),
             before_enqueued_at: self.before_enqueued_at.merge_star_and_none(),
@@ -156,6 +161,7 @@ impl TaskDeletionOrCancelationQuery {
                 types: OptionStarOrList::None,
                 statuses: OptionStarOrList::None,
                 index_uids: OptionStarOrList::None,
+                key_uids: OptionStarOrList::None,
                 after_enqueued_at: OptionStarOr::None,
                 before_enqueued_at: OptionStarOr::None,
                 after_started_at: OptionStarOr::None,
@@ -195,6 +201,10 @@ pub struct TaskDeletionOrCancelationQuery {
     #[deserr(default, error = DeserrQueryParamError<InvalidIndexUid>)]
     #[param(required = false, value_type = Option<Vec<String>>, example = json!(["movies", "theater", "*"]))]
     pub index_uids: OptionStarOrList<IndexUid>,
+    /// Permits to filter tasks by the uid of the API key that enqueued them. By default, when `keyUids` query parameter is not set, the tasks of all the keys are returned. It is possible to specify several keys by separating them with the `,` character.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskKeyUids>)]
+    #[param(required = false, value_type = Option<Vec<String>>, example = json!(["01b4bc42-eb33-4041-b481-254d00cce834", "*"]))]
+    pub key_uids: OptionStarOrList<Uuid>,
 
     /// Permits to filter tasks based on their enqueuedAt time. Matches tasks enqueued after the given date. Supports RFC 3339 date format.
     #[deserr(default, error = DeserrQueryParamError<InvalidTaskAfterEnqueuedAt>, try_from(OptionStarOr<String>) = deserialize_date_after -> InvalidTaskDateError)]
@@ -232,6 +242,7 @@ impl TaskDeletionOrCancelationQuery {
             statuses: self.statuses.merge_star_and_none(),
             types: self.types.merge_star_and_none(),
             index_uids: self.index_uids.map(|x| x.to_string()).merge_star_and_none(),
+            key_uids: self.key_uids.merge_star_and_none(),
             uids: self.uids.merge_star_and_none(),
             canceled_by: self.canceled_by.merge_star_and_none(),
             before_enqueued_at: self.before_enqueued_at.merge_star_and_none(),
@@ -253,6 +264,7 @@ aggregate_methods!(
 struct TaskFilterAnalytics<Method: AggregateMethod> {
     filtered_by_uid: bool,
     filtered_by_index_uid: bool,
+    filtered_by_key_uid: bool,
     filtered_by_type: bool,
     filtered_by_status: bool,
     filtered_by_canceled_by: bool,
@@ -276,6 +288,7 @@ impl<Method: AggregateMethod + 'static> Aggregate for TaskFilterAnalytics<Method
         Box::new(Self {
             filtered_by_uid: self.filtered_by_uid | new.filtered_by_uid,
             filtered_by_index_uid: self.filtered_by_index_uid | new.filtered_by_index_uid,
+            filtered_by_key_uid: self.filtered_by_key_uid | new.filtered_by_key_uid,
             filtered_by_type: self.filtered_by_type | new.filtered_by_type,
             filtered_by_status: self.filtered_by_status | new.filtered_by_status,
             filtered_by_canceled_by: self.filtered_by_canceled_by | new.filtered_by_canceled_by,
@@ -308,7 +321,7 @@ impl<Method: AggregateMethod + 'static> Aggregate for TaskFilterAnalytics<Method
     post,
     path = "/cancel",
     tag = "Tasks",
-    security(("Bearer" = ["tasks.cancel", "tasks.*", "*"])),
+    security(("Bearer" = ["tasks.cancel", "tasks.own", "tasks.*", "*"])),
     params(TaskDeletionOrCancelationQuery),
     responses(
         (status = 200, description = "Task successfully enqueued", body = SummarizedTaskView, content_type = "application/json", example = json!(
@@ -363,6 +376,7 @@ async fn cancel_tasks(
         TaskFilterAnalytics::<CancelTasks> {
             filtered_by_uid: params.uids.is_some(),
             filtered_by_index_uid: params.index_uids.is_some(),
+            filtered_by_key_uid: params.key_uids.is_some(),
             filtered_by_type: params.types.is_some(),
             filtered_by_status: params.statuses.is_some(),
             filtered_by_canceled_by: params.canceled_by.is_some(),
@@ -387,9 +401,11 @@ async fn cancel_tasks(
 
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task =
-        task::spawn_blocking(move || index_scheduler.register(task_cancelation, uid, dry_run))
-            .await??;
+    let key_uid = index_scheduler.filters().key_uid();
+    let task = task::spawn_blocking(move || {
+        index_scheduler.register_with_key(task_cancelation, uid, key_uid, dry_run)
+    })
+    .await??;
     let task: SummarizedTaskView = task.into();
 
     Ok(HttpResponse::Ok().json(task))
@@ -457,6 +473,7 @@ async fn delete_tasks(
         TaskFilterAnalytics::<DeleteTasks> {
             filtered_by_uid: params.uids.is_some(),
             filtered_by_index_uid: params.index_uids.is_some(),
+            filtered_by_key_uid: params.key_uids.is_some(),
             filtered_by_type: params.types.is_some(),
             filtered_by_status: params.statuses.is_some(),
             filtered_by_canceled_by: params.canceled_by.is_some(),
@@ -481,8 +498,11 @@ async fn delete_tasks(
 
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task = task::spawn_blocking(move || index_scheduler.register(task_deletion, uid, dry_run))
-        .await??;
+    let key_uid = index_scheduler.filters().key_uid();
+    let task = task::spawn_blocking(move || {
+        index_scheduler.register_with_key(task_deletion, uid, key_uid, dry_run)
+    })
+    .await??;
     let task: SummarizedTaskView = task.into();
 
     Ok(HttpResponse::Ok().json(task))
@@ -509,7 +529,7 @@ pub struct AllTasks {
     get,
     path = "",
     tag = "Tasks",
-    security(("Bearer" = ["tasks.get", "tasks.*", "*"])),
+    security(("Bearer" = ["tasks.get", "tasks.own", "tasks.*", "*"])),
     params(TasksFilterQuery),
     responses(
         (status = 200, description = "Get all tasks", body = AllTasks, content_type = "application/json", example = json!(
@@ -582,7 +602,7 @@ async fn get_tasks(
     get,
     path = "/{taskUid}",
     tag = "Tasks",
-    security(("Bearer" = ["tasks.get", "tasks.*", "*"])),
+    security(("Bearer" = ["tasks.get", "tasks.own", "tasks.*", "*"])),
     params(("taskUid", format = UInt32, example = 0, description = "The task identifier", nullable = false)),
     responses(
         (status = 200, description = "Task successfully retrieved", body = TaskView, content_type = "application/json", example = json!(
@@ -653,7 +673,7 @@ async fn get_task(
     get,
     path = "/{taskUid}/documents",
     tag = "Tasks",
-    security(("Bearer" = ["tasks.get", "tasks.*", "*"])),
+    security(("Bearer" = ["tasks.get", "tasks.own", "tasks.*", "*"])),
     params(("taskUid", format = UInt32, example = 0, description = "The task identifier", nullable = false)),
     responses(
         (status = 200, description = "The content of the task update", body = serde_json::Value, content_type = "application/x-ndjson"),
@@ -696,8 +716,8 @@ async fn get_task_documents_file(
     if let Some(task) = tasks.first() {
         match task.content_uuid() {
             Some(uuid) => {
-                let mut tfile = match index_scheduler.queue.update_file(uuid) {
-                    Ok(file) => tokio::fs::File::from_std(file),
+                let tfile = match index_scheduler.queue.update_file(uuid) {
+                    Ok(file) => file,
                     Err(file_store::Error::IoError(e)) if e.kind() == ErrorKind::NotFound => {
                         return Err(index_scheduler::Error::TaskFileNotFound(task_uid).into())
                     }
@@ -705,8 +725,11 @@ async fn get_task_documents_file(
                 };
                 // Yes, that's awful to put everything in memory when we could have streamed it from
                 // disk but it's really (really) complex to do with the current state of async Rust.
-                let mut content = String::new();
-                tfile.read_to_string(&mut content).await?;
+                let content = task::spawn_blocking(move || {
+                    let mut content = String::new();
+                    tfile.read_to_string(&mut content).map(|_| content)
+                })
+                .await??;
                 Ok(HttpResponse::Ok().content_type("application/x-ndjson").body(content))
             }
             None => Err(index_scheduler::Error::TaskFileNotFound(task_uid).into()),
@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+
+use meilisearch_types::heed::RoTxn;
+use meilisearch_types::milli::{
+    self, DocumentBucket, Index, QueryNodeExplanation, QueryTermDerivations,
+    RankingRuleExplanation, SearchExplanation,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// How the ranking rules sorted the documents of a search.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchExplanationView {
+    /// The ranking rules in the order they were applied
+    pub ranking_rules: Vec<RankingRuleView>,
+    /// The terms of the query and the words they matched, empty for a placeholder search
+    pub query_graph: Vec<QueryNodeView>,
+    /// The buckets the documents of `explainDocuments` landed in, by external id
+    pub documents: BTreeMap<String, Vec<DocumentBucketView>>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RankingRuleView {
+    pub name: String,
+    /// The time spent in the ranking rule, without the ranking rules following it
+    pub duration: String,
+    pub iterations: usize,
+    pub bucket_count: usize,
+    /// The sizes of the first buckets computed by the ranking rule
+    pub bucket_sizes: Vec<u64>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryNodeView {
+    pub id: u16,
+    /// The first and last positions of the words of the query the term stands for
+    pub positions: [u16; 2],
+    pub original: String,
+    pub is_prefix: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exact: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub prefix_of: Vec<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub use_prefix_db: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub synonyms: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split_words: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub one_typo: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub two_typos: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ngram_words: Vec<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub infix: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub stem: bool,
+    /// The ids of the terms that can follow this one
+    pub successors: Vec<u16>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentBucketView {
+    pub ranking_rule: String,
+    /// The rank of the bucket among the buckets the ranking rule made, starting from 0
+    pub bucket: usize,
+    pub bucket_size: u64,
+}
+
+/// Returns the internal ids of the documents to explain, ignoring the unknown ones.
+pub fn tracked_documents(
+    index: &Index,
+    rtxn: &RoTxn,
+    external_ids: &[String],
+) -> milli::Result<BTreeMap<u32, String>> {
+    let external_documents_ids = index.external_documents_ids();
+    let mut tracked = BTreeMap::new();
+    for id in external_ids {
+        if let Some(docid) = external_documents_ids.get(rtxn, id)? {
+            tracked.insert(docid, id.clone());
+        }
+    }
+    Ok(tracked)
+}
+
+impl SearchExplanationView {
+    pub fn new(explanation: SearchExplanation, tracked: &BTreeMap<u32, String>) -> Self {
+        let SearchExplanation { ranking_rules, query_graph, documents } = explanation;
+
+        let names: Vec<_> = ranking_rules.iter().map(|rr| rr.name.clone()).collect();
+        let documents = documents
+            .into_iter()
+            .filter_map(|(docid, buckets)| {
+                let id = tracked.get(&docid)?.clone();
+                let buckets = buckets
+                    .into_iter()
+                    .map(|DocumentBucket { ranking_rule, bucket, bucket_size }| {
+                        DocumentBucketView {
+                            ranking_rule: names[ranking_rule].clone(),
+                            bucket,
+                            bucket_size,
+                        }
+                    })
+                    .collect();
+                Some((id, buckets))
+            })
+            .collect();
+
+        Self {
+            ranking_rules: ranking_rules.into_iter().map(RankingRuleView::from).collect(),
+            query_graph: query_graph.into_iter().map(QueryNodeView::from).collect(),
+            documents,
+        }
+    }
+}
+
+impl From<RankingRuleExplanation> for RankingRuleView {
+    fn from(explanation: RankingRuleExplanation) -> Self {
+        let RankingRuleExplanation { name, duration, iterations, bucket_count, bucket_sizes } =
+            explanation;
+        Self { name, duration: format!("{duration:.2?}"), iterations, bucket_count, bucket_sizes }
+    }
+}
+
+impl From<QueryNodeExplanation> for QueryNodeView {
+    fn from(explanation: QueryNodeExplanation) -> Self {
+        let QueryNodeExplanation { id, positions: (start, end), derivations, successors } =
+            explanation;
+        let QueryTermDerivations {
+            original,
+            is_prefix,
+            exact,
+            prefix_of,
+            use_prefix_db,
+            synonyms,
+            split_words,
+            one_typo,
+            two_typos,
+            ngram_words,
+            infix,
+            stem,
+        } = derivations;
+        Self {
+            id,
+            positions: [start, end],
+            original,
+            is_prefix,
+            exact,
+            prefix_of,
+            use_prefix_db,
+            synonyms,
+            split_words,
+            one_typo,
+            two_typos,
+            ngram_words,
+            infix,
+            stem,
+            successors,
+        }
+    }
+}
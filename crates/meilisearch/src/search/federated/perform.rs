@@ -9,18 +9,22 @@ use std::vec::{IntoIter, Vec};
 use actix_http::StatusCode;
 use index_scheduler::{IndexScheduler, RoFeatures};
 use itertools::Itertools;
+use meilisearch_auth::AuthFilter;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::features::{Network, Remote};
+use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::milli::order_by_map::OrderByMap;
 use meilisearch_types::milli::score_details::{ScoreDetails, WeightedScoreValue};
 use meilisearch_types::milli::{self, DocumentId, OrderBy, TimeBudget, DEFAULT_VALUES_PER_FACET};
 use roaring::RoaringBitmap;
 use tokio::task::JoinHandle;
 
+use super::super::query_rules::AppliedQueryRules;
 use super::super::ranking_rules::{self, RankingRules};
 use super::super::{
-    compute_facet_distribution_stats, prepare_search, AttributesFormat, ComputedFacets, HitMaker,
-    HitsInfo, RetrieveVectors, SearchHit, SearchKind, SearchQuery, SearchQueryWithIndex,
+    check_hidden_attributes, compute_facet_distribution_stats, prepare_search,
+    restrict_attributes_to_search_on, AttributesFormat, ComputedFacets, HitMaker, HitsInfo,
+    RetrieveVectors, SearchHit, SearchKind, SearchQuery, SearchQueryWithIndex,
 };
 use super::proxy::{proxy_search, ProxySearchError, ProxySearchParams};
 use super::types::{
@@ -34,6 +38,7 @@ use crate::search::federated::types::{INDEX_UID, QUERIES_POSITION, WEIGHTED_RANK
 
 pub async fn perform_federated_search(
     index_scheduler: &IndexScheduler,
+    auth_filter: &AuthFilter,
     queries: Vec<SearchQueryWithIndex>,
     federation: Federation,
     features: RoFeatures,
@@ -67,6 +72,7 @@ pub async fn perform_federated_search(
     // 2.2. concurrently execute local queries
     let params = SearchByIndexParams {
         index_scheduler,
+        auth_filter,
         features,
         is_proxy,
         network: &network,
@@ -474,6 +480,18 @@ impl PartitionedQueries {
             .into());
         }
 
+        if federated_query.collapse.is_some() {
+            return Err(MeilisearchHttpError::CollapseInFederatedQuery(query_index).into());
+        }
+
+        if federated_query.suggestions {
+            return Err(MeilisearchHttpError::SuggestionsInFederatedQuery(query_index).into());
+        }
+
+        if federated_query.explain {
+            return Err(MeilisearchHttpError::ExplainInFederatedQuery(query_index).into());
+        }
+
         let (index_uid, query, federation_options) = federated_query.into_index_query_federation();
 
         let federation_options = federation_options.unwrap_or_default();
@@ -644,6 +662,7 @@ impl RemoteSearch {
 
 struct SearchByIndexParams<'a> {
     index_scheduler: &'a IndexScheduler,
+    auth_filter: &'a AuthFilter,
     required_hit_count: usize,
     features: RoFeatures,
     is_proxy: bool,
@@ -685,8 +704,13 @@ impl SearchByIndex {
         params: &SearchByIndexParams<'_>,
     ) -> Result<(), ResponseError> {
         let first_query_index = queries.first().map(|query| query.query_index);
-        let index = match params.index_scheduler.index(&index_uid) {
-            Ok(index) => index,
+        let (target_uid, index) = match params
+            .index_scheduler
+            .resolve_index_alias(IndexUid::new_unchecked(index_uid.clone()))
+            .and_then(|target_uid| {
+                params.index_scheduler.index(&target_uid).map(|index| (target_uid, index))
+            }) {
+            Ok(target) => target,
             Err(err) => {
                 let mut err = ResponseError::from(err);
                 // Patch the HTTP status code to 400 as it defaults to 404 for `index_not_found`, but
@@ -710,6 +734,8 @@ impl SearchByIndex {
         let mut degraded = false;
         let mut used_negative_operator = false;
         let mut candidates = RoaringBitmap::new();
+        // the attributes rules are the ones of the index the alias points to
+        let attributes_rules = params.auth_filter.get_index_attributes_rules(&target_uid);
         let facets_by_index = self.federation.facets_by_index.remove(&index_uid).flatten();
         if let Err(mut error) =
             self.facet_order.check_facet_order(&index_uid, &facets_by_index, &index, &rtxn)
@@ -725,7 +751,7 @@ impl SearchByIndex {
             return Err(error);
         }
         let mut results_by_query = Vec::with_capacity(queries.len());
-        for QueryByIndex { query, weight, query_index } in queries {
+        for QueryByIndex { mut query, weight, query_index } in queries {
             // use an immediately invoked lambda to capture the result without returning from the function
 
             let res: Result<(), ResponseError> = (|| {
@@ -800,6 +826,23 @@ impl SearchByIndex {
                     None => TimeBudget::default(),
                 };
 
+                check_hidden_attributes(&query, &attributes_rules)?;
+                restrict_attributes_to_search_on(
+                    &index,
+                    &rtxn,
+                    &mut query.attributes_to_search_on,
+                    &attributes_rules,
+                )?;
+
+                // the positions of the pinned documents are relative to the hits of a single index,
+                // only the query, filters and hidden documents of the query rules are applied.
+                let mut applied_rules = AppliedQueryRules::new(index.query_rules(&rtxn)?, &query);
+                if let Some(q) = applied_rules.replace_query.take() {
+                    query.q = Some(q);
+                }
+                applied_rules.add_filters(&mut query.filter);
+                let (hidden, _pinned) = applied_rules.resolve_documents(&index, &rtxn)?;
+
                 let (mut search, _is_finite_pagination, _max_total_hits, _offset) = prepare_search(
                     &index,
                     &rtxn,
@@ -808,13 +851,14 @@ impl SearchByIndex {
                     time_budget,
                     params.features,
                 )?;
+                search.excluded_documents(hidden);
 
                 search.scoring_strategy(milli::score_details::ScoringStrategy::Detailed);
                 search.offset(0);
                 search.limit(params.required_hit_count);
 
                 let (result, _semantic_hit_count) =
-                    super::super::search_from_kind(index_uid.to_string(), search_kind, search)?;
+                    super::super::search_from_kind(index_uid.to_string(), search_kind, &search)?;
                 let format = AttributesFormat {
                     attributes_to_retrieve: query.attributes_to_retrieve,
                     retrieve_vectors,
@@ -829,6 +873,7 @@ impl SearchByIndex {
                     show_ranking_score: query.show_ranking_score,
                     show_ranking_score_details: query.show_ranking_score_details,
                     locales: query.locales.map(|l| l.iter().copied().map(Into::into).collect()),
+                    attributes_rules: attributes_rules.clone(),
                 };
 
                 let milli::SearchResult {
@@ -924,6 +969,7 @@ impl SearchByIndex {
                     &rtxn,
                     candidates,
                     super::super::Route::MultiSearch,
+                    &attributes_rules,
                 )
             })
             .transpose()
@@ -955,7 +1001,10 @@ impl SearchByIndex {
         index_scheduler: &IndexScheduler,
     ) -> Result<(), ResponseError> {
         for (index_uid, facets) in std::mem::take(&mut self.federation.facets_by_index) {
-            let index = match index_scheduler.index(&index_uid) {
+            let index = match index_scheduler
+                .resolve_index_alias(IndexUid::new_unchecked(index_uid.clone()))
+                .and_then(|index_uid| index_scheduler.index(&index_uid))
+            {
                 Ok(index) => index,
                 Err(err) => {
                     let mut err = ResponseError::from(err);
@@ -989,6 +1038,7 @@ impl SearchByIndex {
                     &rtxn,
                     Default::default(),
                     super::super::Route::MultiSearch,
+                    &Default::default(),
                 ) {
                     error.message =
                 format!("Inside `.federation.facetsByIndex.{index_uid}`: {}\n - Note: index `{index_uid}` is not used in queries", error.message);
@@ -9,7 +9,7 @@ use deserr::Deserr;
 use either::Either;
 use index_scheduler::RoFeatures;
 use indexmap::IndexMap;
-use meilisearch_auth::IndexSearchRules;
+use meilisearch_auth::{AttributesRules, IndexSearchRules};
 use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
@@ -20,14 +20,16 @@ use meilisearch_types::milli::score_details::{ScoreDetails, ScoringStrategy};
 use meilisearch_types::milli::vector::parsed_vectors::ExplicitVectors;
 use meilisearch_types::milli::vector::Embedder;
 use meilisearch_types::milli::{
-    FacetValueHit, InternalError, OrderBy, PatternMatch, SearchForFacetValues, TimeBudget,
+    FacetValueHit, InternalError, OrderBy, PatternMatch, QueryCompletion, SearchForFacetValues,
+    TimeBudget,
 };
 use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
 use meilisearch_types::{milli, Document};
 use milli::tokenizer::{Language, TokenizerBuilder};
 use milli::{
     AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, LocalizedAttributesRule,
-    MatchBounds, MatcherBuilder, SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    MatchBounds, MatcherBuilder, Member, SortError, TermsMatchingStrategy,
+    DEFAULT_VALUES_PER_FACET,
 };
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -38,12 +40,15 @@ use utoipa::ToSchema;
 
 use crate::error::MeilisearchHttpError;
 
+mod explain;
 mod federated;
+pub use explain::SearchExplanationView;
 pub use federated::{
     perform_federated_search, FederatedSearch, FederatedSearchResult, Federation,
     FederationOptions, MergeFacets, PROXY_SEARCH_HEADER, PROXY_SEARCH_HEADER_VALUE,
 };
 
+mod query_rules;
 mod ranking_rules;
 
 type MatchesPosition = BTreeMap<String, Vec<MatchBounds>>;
@@ -55,6 +60,10 @@ pub const DEFAULT_CROP_MARKER: fn() -> String = || "…".to_string();
 pub const DEFAULT_HIGHLIGHT_PRE_TAG: fn() -> String = || "<em>".to_string();
 pub const DEFAULT_HIGHLIGHT_POST_TAG: fn() -> String = || "</em>".to_string();
 pub const DEFAULT_SEMANTIC_RATIO: fn() -> SemanticRatio = || SemanticRatio(0.5);
+pub const DEFAULT_AUTOCOMPLETE_LIMIT: fn() -> usize = || 10;
+pub const DEFAULT_COLLAPSE_INNER_HITS: fn() -> usize = || 3;
+/// The maximum number of documents returned for each collapsed group.
+pub const MAX_COLLAPSE_INNER_HITS: usize = 100;
 
 #[derive(Clone, Default, PartialEq, Deserr, ToSchema)]
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
@@ -98,6 +107,8 @@ pub struct SearchQuery {
     pub sort: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
     pub distinct: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchCollapse>)]
+    pub collapse: Option<CollapseQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
     #[deserr(error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
@@ -111,12 +122,20 @@ pub struct SearchQuery {
     pub crop_marker: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>)]
     pub matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchQuerySyntax>)]
+    pub query_syntax: QuerySyntax,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>)]
     pub attributes_to_search_on: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>)]
     pub ranking_score_threshold: Option<RankingScoreThreshold>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>)]
     pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSuggestions>)]
+    pub suggestions: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchExplain>)]
+    pub explain: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchExplainDocuments>)]
+    pub explain_documents: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Deserr, ToSchema, Serialize)]
@@ -178,14 +197,19 @@ impl fmt::Debug for SearchQuery {
             filter,
             sort,
             distinct,
+            collapse,
             facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
+            query_syntax,
             attributes_to_search_on,
             ranking_score_threshold,
             locales,
+            suggestions,
+            explain,
+            explain_documents,
         } = self;
 
         let mut debug = f.debug_struct("SearchQuery");
@@ -231,10 +255,16 @@ impl fmt::Debug for SearchQuery {
         if let Some(distinct) = distinct {
             debug.field("distinct", &distinct);
         }
+        if let Some(collapse) = collapse {
+            debug.field("collapse", &collapse);
+        }
         if let Some(facets) = facets {
             debug.field("facets", &facets);
         }
         debug.field("matching_strategy", &matching_strategy);
+        if *query_syntax != QuerySyntax::default() {
+            debug.field("query_syntax", &query_syntax);
+        }
 
         // Then everything related to the formatting
         debug.field("crop_length", &crop_length);
@@ -270,6 +300,15 @@ impl fmt::Debug for SearchQuery {
         if let Some(locales) = locales {
             debug.field("locales", &locales);
         }
+        if *suggestions {
+            debug.field("suggestions", &suggestions);
+        }
+        if *explain {
+            debug.field("explain", &explain);
+        }
+        if let Some(explain_documents) = explain_documents {
+            debug.field("explain_documents", &explain_documents);
+        }
 
         debug.finish()
     }
@@ -287,6 +326,18 @@ pub struct HybridQuery {
     pub embedder: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Deserr, ToSchema, Serialize)]
+#[deserr(error = DeserrJsonError<InvalidSearchCollapse>, rename_all = camelCase, deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct CollapseQuery {
+    /// The filterable attribute grouping the hits, one hit is returned for each of its values
+    pub field: String,
+    /// The maximum number of documents returned for each group, at most 100
+    #[deserr(default = DEFAULT_COLLAPSE_INNER_HITS())]
+    #[schema(default = DEFAULT_COLLAPSE_INNER_HITS)]
+    pub inner_hits: usize,
+}
+
 #[derive(Clone)]
 pub enum SearchKind {
     KeywordOnly,
@@ -457,6 +508,8 @@ pub struct SearchQueryWithIndex {
     pub sort: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
     pub distinct: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchCollapse>)]
+    pub collapse: Option<CollapseQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
@@ -467,12 +520,20 @@ pub struct SearchQueryWithIndex {
     pub crop_marker: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
     pub matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchQuerySyntax>, default)]
+    pub query_syntax: QuerySyntax,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>, default)]
     pub attributes_to_search_on: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>, default)]
     pub ranking_score_threshold: Option<RankingScoreThreshold>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>, default)]
     pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSuggestions>, default)]
+    pub suggestions: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchExplain>, default)]
+    pub explain: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchExplainDocuments>, default)]
+    pub explain_documents: Option<Vec<String>>,
 
     #[deserr(default)]
     pub federation_options: Option<FederationOptions>,
@@ -521,14 +582,19 @@ impl SearchQueryWithIndex {
             filter,
             sort,
             distinct,
+            collapse,
             facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
+            query_syntax,
             attributes_to_search_on,
             ranking_score_threshold,
             locales,
+            suggestions,
+            explain,
+            explain_documents,
         } = query;
 
         SearchQueryWithIndex {
@@ -551,14 +617,19 @@ impl SearchQueryWithIndex {
             filter,
             sort,
             distinct,
+            collapse,
             facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
+            query_syntax,
             attributes_to_search_on,
             ranking_score_threshold,
             locales,
+            suggestions,
+            explain,
+            explain_documents,
             federation_options,
         }
     }
@@ -584,15 +655,20 @@ impl SearchQueryWithIndex {
             filter,
             sort,
             distinct,
+            collapse,
             facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
+            query_syntax,
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
             locales,
+            suggestions,
+            explain,
+            explain_documents,
         } = self;
         (
             index_uid,
@@ -614,15 +690,20 @@ impl SearchQueryWithIndex {
                 filter,
                 sort,
                 distinct,
+                collapse,
                 facets,
                 highlight_pre_tag,
                 highlight_post_tag,
                 crop_marker,
                 matching_strategy,
+                query_syntax,
                 attributes_to_search_on,
                 hybrid,
                 ranking_score_threshold,
                 locales,
+                suggestions,
+                explain,
+                explain_documents,
                 // do not use ..Default::default() here,
                 // rather add any missing field from `SearchQuery` to `SearchQueryWithIndex`
             },
@@ -658,6 +739,19 @@ pub struct SimilarQuery {
     pub ranking_score_threshold: Option<RankingScoreThresholdSimilar>,
 }
 
+#[derive(Debug, Clone, PartialEq, Deserr, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct AutocompleteQuery {
+    #[deserr(error = DeserrJsonError<InvalidAutocompleteQ>, missing_field_error = DeserrJsonError::missing_autocomplete_q)]
+    pub q: String,
+    #[deserr(default, error = DeserrJsonError<InvalidAutocompleteFilter>)]
+    pub filter: Option<Value>,
+    #[deserr(default = DEFAULT_AUTOCOMPLETE_LIMIT(), error = DeserrJsonError<InvalidAutocompleteLimit>)]
+    pub limit: usize,
+    #[deserr(default, error = DeserrJsonError<InvalidAutocompleteLocales>, default)]
+    pub locales: Option<Vec<Locale>>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ExternalDocumentId(String);
 
@@ -717,6 +811,26 @@ impl From<MatchingStrategy> for TermsMatchingStrategy {
     }
 }
 
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserr, ToSchema, Serialize)]
+#[deserr(rename_all = camelCase)]
+#[serde(rename_all = "camelCase")]
+pub enum QuerySyntax {
+    /// Quoted phrases and `-negative` words
+    #[default]
+    Simple,
+    /// `field:word` restrictions, `OR` and parenthesised groups on top of the simple syntax
+    Advanced,
+}
+
+impl From<QuerySyntax> for milli::QuerySyntax {
+    fn from(other: QuerySyntax) -> Self {
+        match other {
+            QuerySyntax::Simple => Self::Simple,
+            QuerySyntax::Advanced => Self::Advanced,
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Deserr)]
 #[deserr(rename_all = camelCase)]
 pub enum FacetValuesSort {
@@ -751,6 +865,19 @@ pub struct SearchHit {
     pub ranking_score: Option<f64>,
     #[serde(default, rename = "_rankingScoreDetails", skip_serializing_if = "Option::is_none")]
     pub ranking_score_details: Option<serde_json::Map<String, serde_json::Value>>,
+    #[serde(default, rename = "_innerHits", skip_serializing_if = "Option::is_none")]
+    pub inner_hits: Option<InnerHits>,
+}
+
+/// The best documents of the group of a hit when the search is collapsed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InnerHits {
+    /// The number of documents of the group matching the search
+    pub total_hits: u64,
+    /// The documents of the group, sorted like the hits
+    #[schema(no_recursion)]
+    pub hits: Vec<SearchHit>,
 }
 
 #[derive(Serialize, Clone, PartialEq, ToSchema)]
@@ -771,6 +898,16 @@ pub struct SearchResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub semantic_hit_count: Option<u32>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestions: Option<Vec<QuerySuggestion>>,
+
+    /// The uids of the query rules applied to the search
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub applied_rules: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<SearchExplanationView>,
+
     // These fields are only used for analytics purposes
     #[serde(skip)]
     pub degraded: bool,
@@ -788,6 +925,9 @@ impl fmt::Debug for SearchResult {
             facet_distribution,
             facet_stats,
             semantic_hit_count,
+            suggestions,
+            applied_rules,
+            explanation,
             degraded,
             used_negative_operator,
         } = self;
@@ -813,11 +953,36 @@ impl fmt::Debug for SearchResult {
         if let Some(semantic_hit_count) = semantic_hit_count {
             debug.field("semantic_hit_count", &semantic_hit_count);
         }
+        if let Some(suggestions) = suggestions {
+            debug.field("suggestions", &suggestions);
+        }
+        if !applied_rules.is_empty() {
+            debug.field("applied_rules", &applied_rules);
+        }
+        if let Some(explanation) = explanation {
+            debug.field("explanation", &explanation);
+        }
 
         debug.finish()
     }
 }
 
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QuerySuggestion {
+    /// An alternative query, made of words that exist in the index
+    pub query: String,
+    /// The number of documents containing all the words of the alternative query
+    pub estimated_total_hits: u64,
+}
+
+impl From<milli::QuerySuggestion> for QuerySuggestion {
+    fn from(suggestion: milli::QuerySuggestion) -> Self {
+        let milli::QuerySuggestion { query, estimated_total_hits } = suggestion;
+        Self { query, estimated_total_hits }
+    }
+}
+
 #[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SimilarResult {
@@ -862,8 +1027,30 @@ pub struct FacetSearchResult {
     pub processing_time_ms: u128,
 }
 
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AutocompleteResult {
+    pub completions: Vec<QueryCompletion>,
+    pub query: String,
+    pub processing_time_ms: u128,
+}
+
 /// Incorporate search rules in search query
-pub fn add_search_rules(filter: &mut Option<Value>, rules: IndexSearchRules) {
+///
+/// The filter of the query can't use the attributes hidden by the search rules, otherwise their
+/// values could be guessed by probing them. Errors are reported with the given code.
+pub fn add_search_rules(
+    filter: &mut Option<Value>,
+    rules: IndexSearchRules,
+    code: Code,
+) -> Result<(), ResponseError> {
+    if let Some(Ok(Some(query_filter))) = filter.as_ref().map(Filter::from_json) {
+        if let Some(field) = query_filter.fields().find(|field| !rules.attributes.is_visible(field))
+        {
+            return Err(hidden_attribute_error(field, "filter", code));
+        }
+    }
+
     *filter = match (filter.take(), rules.filter) {
         (None, rules_filter) => rules_filter,
         (filter, None) => filter,
@@ -879,7 +1066,88 @@ pub fn add_search_rules(filter: &mut Option<Value>, rules: IndexSearchRules) {
 
             Some(Value::Array([filter, rules_filter].concat()))
         }
+    };
+    Ok(())
+}
+
+fn hidden_attribute_error(attribute: &str, parameter: &str, code: Code) -> ResponseError {
+    let message = format!(
+        "Attribute `{attribute}` is hidden by the search rules and cannot be used in `{parameter}`."
+    );
+    ResponseError::from_msg(message, code)
+}
+
+/// Rejects the parameters of the query using an attribute hidden by the search rules.
+///
+/// Like in the filter, the values of a hidden attribute could otherwise be guessed by sorting,
+/// grouping or computing the facets on it.
+fn check_hidden_attributes(
+    query: &SearchQuery,
+    attributes_rules: &AttributesRules,
+) -> Result<(), ResponseError> {
+    if attributes_rules.is_unrestricted() {
+        return Ok(());
+    }
+
+    let attributes_to_search_on = query.attributes_to_search_on.iter().flatten();
+    if let Some(attribute) = attributes_to_search_on
+        .filter(|attribute| *attribute != "*")
+        .find(|attribute| !attributes_rules.is_visible(attribute))
+    {
+        return Err(hidden_attribute_error(
+            attribute,
+            "attributesToSearchOn",
+            Code::InvalidSearchAttributesToSearchOn,
+        ));
+    }
+
+    for sort in query.sort.iter().flatten() {
+        if let Ok(AscDesc::Asc(Member::Field(field)) | AscDesc::Desc(Member::Field(field))) =
+            AscDesc::from_str(sort)
+        {
+            if !attributes_rules.is_visible(&field) {
+                return Err(hidden_attribute_error(&field, "sort", Code::InvalidSearchSort));
+            }
+        }
     }
+
+    if let Some(distinct) = query.distinct.as_deref().filter(|d| !attributes_rules.is_visible(d)) {
+        return Err(hidden_attribute_error(distinct, "distinct", Code::InvalidSearchDistinct));
+    }
+
+    if let Some(CollapseQuery { field, .. }) =
+        query.collapse.as_ref().filter(|collapse| !attributes_rules.is_visible(&collapse.field))
+    {
+        return Err(hidden_attribute_error(field, "collapse", Code::InvalidSearchCollapse));
+    }
+
+    let facets = query.facets.iter().flatten();
+    if let Some(facet) =
+        facets.filter(|facet| *facet != "*").find(|facet| !attributes_rules.is_visible(facet))
+    {
+        return Err(hidden_attribute_error(facet, "facets", Code::InvalidSearchFacets));
+    }
+
+    Ok(())
+}
+
+/// Computes the offset and the limit of the search depending on the pagination mode.
+fn pagination_bounds(query: &SearchQuery, max_total_hits: usize) -> (usize, usize) {
+    let (offset, limit) = if query.is_finite_pagination() {
+        let limit = query.hits_per_page.unwrap_or_else(DEFAULT_SEARCH_LIMIT);
+        let page = query.page.unwrap_or(1);
+
+        // page 0 gives a limit of 0 forcing Meilisearch to return no document.
+        page.checked_sub(1).map_or((0, 0), |p| (limit * p, limit))
+    } else {
+        (query.offset, query.limit)
+    };
+
+    // Make sure that a user can't get more documents than the hard limit,
+    // we align that on the offset too.
+    let offset = min(offset, max_total_hits);
+    let limit = min(limit, max_total_hits.saturating_sub(offset));
+    (offset, limit)
 }
 
 fn prepare_search<'t>(
@@ -900,6 +1168,10 @@ fn prepare_search<'t>(
         search.distinct(distinct.clone());
     }
 
+    if let Some(CollapseQuery { field, inner_hits }) = &query.collapse {
+        search.collapse(field.clone(), *inner_hits);
+    }
+
     match search_kind {
         SearchKind::KeywordOnly => {
             if let Some(q) = &query.q {
@@ -928,13 +1200,25 @@ fn prepare_search<'t>(
             if let Some(q) = &query.q {
                 search.query(q);
             }
-            // will be embedded in hybrid search if necessary
-            search.semantic(
-                embedder_name.clone(),
-                embedder.clone(),
-                *quantized,
-                query.vector.clone(),
-            );
+            // will be embedded in hybrid search if necessary, but the collapsed groups are each
+            // searched again so the query is embedded once for all of them
+            let vector = match (&query.vector, &query.q) {
+                (None, Some(q)) if query.collapse.is_some() => {
+                    let span = tracing::trace_span!(target: "search::hybrid", "embed_one");
+                    let _entered = span.enter();
+
+                    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(3);
+                    embedder.embed_search(q, Some(deadline)).map(Some)
+                }
+                (vector, _) => Ok(vector.clone()),
+            };
+            match vector {
+                Ok(vector) => {
+                    search.semantic(embedder_name.clone(), embedder.clone(), *quantized, vector);
+                }
+                // like the hybrid search, falls back to a keyword search
+                Err(error) => tracing::error!(error=%error, "Embedding failed"),
+            }
         }
     }
 
@@ -944,6 +1228,7 @@ fn prepare_search<'t>(
 
     let is_finite_pagination = query.is_finite_pagination();
     search.terms_matching_strategy(query.matching_strategy.into());
+    search.query_syntax(query.query_syntax.into());
 
     let max_total_hits = index
         .pagination_max_total_hits(rtxn)
@@ -963,22 +1248,7 @@ fn prepare_search<'t>(
         },
     );
 
-    // compute the offset on the limit depending on the pagination mode.
-    let (offset, limit) = if is_finite_pagination {
-        let limit = query.hits_per_page.unwrap_or_else(DEFAULT_SEARCH_LIMIT);
-        let page = query.page.unwrap_or(1);
-
-        // page 0 gives a limit of 0 forcing Meilisearch to return no document.
-        page.checked_sub(1).map_or((0, 0), |p| (limit * p, limit))
-    } else {
-        (query.offset, query.limit)
-    };
-
-    // Make sure that a user can't get more documents than the hard limit,
-    // we align that on the offset too.
-    let offset = min(offset, max_total_hits);
-    let limit = min(limit, max_total_hits.saturating_sub(offset));
-
+    let (offset, limit) = pagination_bounds(query, max_total_hits);
     search.offset(offset);
     search.limit(limit);
 
@@ -1009,10 +1279,11 @@ fn prepare_search<'t>(
 pub fn perform_search(
     index_uid: String,
     index: &Index,
-    query: SearchQuery,
+    mut query: SearchQuery,
     search_kind: SearchKind,
     retrieve_vectors: RetrieveVectors,
     features: RoFeatures,
+    attributes_rules: AttributesRules,
 ) -> Result<SearchResult, ResponseError> {
     let before_search = Instant::now();
     let rtxn = index.read_txn()?;
@@ -1021,23 +1292,104 @@ pub fn perform_search(
         None => TimeBudget::default(),
     };
 
-    let (search, is_finite_pagination, max_total_hits, offset) =
+    if query.explain && !matches!(search_kind, SearchKind::KeywordOnly) {
+        return Err(ResponseError::from_msg(
+            "`explain` is only available for keyword searches, it cannot be used along `vector` or `hybrid`.".to_string(),
+            Code::InvalidSearchExplain,
+        ));
+    }
+
+    if query.collapse.is_some() && query.distinct.is_some() {
+        return Err(ResponseError::from_msg(
+            "`collapse` cannot be used along `distinct`, the hits are already distinct by the collapse field.".to_string(),
+            Code::InvalidSearchCollapse,
+        ));
+    }
+
+    if let Some(collapse) = query.collapse.as_ref() {
+        if collapse.inner_hits > MAX_COLLAPSE_INNER_HITS {
+            return Err(ResponseError::from_msg(
+                format!(
+                    "`collapse.innerHits` cannot be greater than {MAX_COLLAPSE_INNER_HITS}, received {}.",
+                    collapse.inner_hits
+                ),
+                Code::InvalidSearchCollapse,
+            ));
+        }
+    }
+
+    check_hidden_attributes(&query, &attributes_rules)?;
+    restrict_attributes_to_search_on(
+        index,
+        &rtxn,
+        &mut query.attributes_to_search_on,
+        &attributes_rules,
+    )?;
+
+    let original_q = query.q.clone();
+    let mut applied_rules = query_rules::AppliedQueryRules::new(index.query_rules(&rtxn)?, &query);
+    if let Some(q) = applied_rules.replace_query.take() {
+        query.q = Some(q);
+    }
+    applied_rules.add_filters(&mut query.filter);
+    let (hidden, mut pinned) = applied_rules.resolve_documents(index, &rtxn)?;
+    if !pinned.is_empty() {
+        // the pinned documents are returned only if they match the filter
+        let universe = filtered_universe(index, &rtxn, &query.filter, features)?;
+        pinned.retain(|(docid, _)| universe.contains(*docid));
+    }
+    let pinned_ids: roaring::RoaringBitmap = pinned.iter().map(|(docid, _)| *docid).collect();
+
+    let (mut search, is_finite_pagination, max_total_hits, offset) =
         prepare_search(index, &rtxn, &query, &search_kind, time_budget, features)?;
+    search.excluded_documents(&hidden | &pinned_ids);
+    let (_, page_limit) = pagination_bounds(&query, max_total_hits);
+    if !pinned.is_empty() {
+        // the pinned documents are inserted among the hits preceding the page too
+        search.offset(0);
+        search.limit(offset + page_limit);
+    }
 
-    let (
-        milli::SearchResult {
-            documents_ids,
-            matching_words,
-            candidates,
-            document_scores,
-            degraded,
-            used_negative_operator,
-        },
-        semantic_hit_count,
-    ) = search_from_kind(index_uid, search_kind, search)?;
+    let semantic_ratio = match &search_kind {
+        SearchKind::Hybrid { semantic_ratio, .. } => Some(*semantic_ratio),
+        _ => None,
+    };
+
+    let (milli_result, semantic_hit_count, explanation) = if query.explain {
+        let tracked_documents = match &query.explain_documents {
+            Some(ids) => explain::tracked_documents(index, &rtxn, ids)?,
+            None => BTreeMap::new(),
+        };
+        let (milli_result, explanation) = search
+            .execute_explained(tracked_documents.keys().copied().collect())
+            .map_err(|e| MeilisearchHttpError::from_milli(e, Some(index_uid)))?;
+        let explanation = SearchExplanationView::new(explanation, &tracked_documents);
+        (milli_result, None, Some(explanation))
+    } else {
+        let (milli_result, semantic_hit_count) = search_from_kind(index_uid, search_kind, &search)?;
+        (milli_result, semantic_hit_count, None)
+    };
+    let milli::SearchResult {
+        documents_ids,
+        matching_words,
+        mut candidates,
+        document_scores,
+        degraded,
+        used_negative_operator,
+    } = milli_result;
+
+    let (documents_ids, document_scores) = if pinned.is_empty() {
+        (documents_ids, document_scores)
+    } else {
+        candidates |= pinned_ids;
+        query_rules::pin_documents(documents_ids, document_scores, &pinned, offset, page_limit)
+    };
+
+    // the groups are computed once the documents are pinned to return the group of the pinned documents too
+    let collapsed_groups = search.collapsed_groups(&documents_ids, semantic_ratio)?;
 
     let SearchQuery {
-        q,
+        q: _,
         limit,
         page,
         hits_per_page,
@@ -1056,15 +1408,20 @@ pub fn perform_search(
         highlight_post_tag,
         crop_marker,
         locales,
+        suggestions,
+        filter,
         // already used in prepare_search
         vector: _,
         hybrid: _,
         offset: _,
         ranking_score_threshold: _,
         matching_strategy: _,
+        query_syntax: _,
         attributes_to_search_on: _,
-        filter: _,
         distinct: _,
+        collapse: _,
+        explain: _,
+        explain_documents: _,
     } = query;
 
     let format = AttributesFormat {
@@ -1080,7 +1437,8 @@ pub fn perform_search(
         sort,
         show_ranking_score,
         show_ranking_score_details,
-        locales: locales.map(|l| l.iter().copied().map(Into::into).collect()),
+        locales: locales.as_ref().map(|l| l.iter().copied().map(Into::into).collect()),
+        attributes_rules: attributes_rules.clone(),
     };
 
     let documents = make_hits(
@@ -1089,8 +1447,34 @@ pub fn perform_search(
         format,
         matching_words,
         documents_ids.iter().copied().zip(document_scores.iter()),
+        collapsed_groups,
     )?;
 
+    let suggestions = match original_q.as_deref() {
+        Some(q) if suggestions => {
+            let mut query_suggestions = milli::QuerySuggestions::new(q, index, &rtxn);
+            if let Some(locales) = locales {
+                query_suggestions.locales(locales.into_iter().map(Into::into).collect());
+            }
+            // The words of the documents and attributes the search can't return must not be proposed.
+            let universe = filtered_universe(index, &rtxn, &filter, features)? - &hidden;
+            query_suggestions.universe(universe);
+            if !attributes_rules.is_unrestricted() {
+                let fields_ids_map = index.fields_ids_map(&rtxn)?;
+                query_suggestions.fields_ids(
+                    fields_ids_map
+                        .iter()
+                        .filter(|(_, name)| attributes_rules.is_searchable(name))
+                        .map(|(fid, _)| fid)
+                        .collect(),
+                );
+            }
+            let suggestions = query_suggestions.execute()?;
+            Some(suggestions.into_iter().map(QuerySuggestion::from).collect())
+        }
+        _ => None,
+    };
+
     let number_of_hits = min(candidates.len() as usize, max_total_hits);
     let hits_info = if is_finite_pagination {
         let hits_per_page = hits_per_page.unwrap_or_else(DEFAULT_SEARCH_LIMIT);
@@ -1111,7 +1495,14 @@ pub fn perform_search(
 
     let (facet_distribution, facet_stats) = facets
         .map(move |facets| {
-            compute_facet_distribution_stats(&facets, index, &rtxn, candidates, Route::Search)
+            compute_facet_distribution_stats(
+                &facets,
+                index,
+                &rtxn,
+                candidates,
+                Route::Search,
+                &attributes_rules,
+            )
         })
         .transpose()?
         .map(|ComputedFacets { distribution, stats }| (distribution, stats))
@@ -1120,13 +1511,16 @@ pub fn perform_search(
     let result = SearchResult {
         hits: documents,
         hits_info,
-        query: q.unwrap_or_default(),
+        query: original_q.unwrap_or_default(),
         processing_time_ms: before_search.elapsed().as_millis(),
         facet_distribution,
         facet_stats,
         degraded,
         used_negative_operator,
         semantic_hit_count,
+        suggestions,
+        applied_rules: applied_rules.uids,
+        explanation,
     };
     Ok(result)
 }
@@ -1150,6 +1544,7 @@ fn compute_facet_distribution_stats<S: AsRef<str>>(
     rtxn: &RoTxn,
     candidates: roaring::RoaringBitmap,
     route: Route,
+    attributes_rules: &AttributesRules,
 ) -> Result<ComputedFacets, ResponseError> {
     let mut facet_distribution = index.facets_distribution(rtxn);
 
@@ -1170,7 +1565,7 @@ fn compute_facet_distribution_stats<S: AsRef<str>>(
         facet_distribution.facets(fields);
     }
 
-    let distribution = facet_distribution
+    let mut distribution = facet_distribution
         .candidates(candidates)
         .default_order_by(sort_facet_values_by.get("*"))
         .execute()
@@ -1184,14 +1579,62 @@ fn compute_facet_distribution_stats<S: AsRef<str>>(
             (error, _) => error.into(),
         })?;
     let stats = facet_distribution.compute_stats()?;
-    let stats = stats.into_iter().map(|(k, (min, max))| (k, FacetStats { min, max })).collect();
+    let stats = stats
+        .into_iter()
+        .filter(|(k, _)| attributes_rules.is_visible(k))
+        .map(|(k, (min, max))| (k, FacetStats { min, max }))
+        .collect();
+    distribution.retain(|k, _| attributes_rules.is_visible(k));
     Ok(ComputedFacets { distribution, stats })
 }
 
+/// Returns the documents matching the filter of the query.
+fn filtered_universe(
+    index: &Index,
+    rtxn: &RoTxn,
+    filter: &Option<Value>,
+    features: RoFeatures,
+) -> Result<roaring::RoaringBitmap, ResponseError> {
+    let filter = match filter {
+        Some(filter) => parse_filter(filter, Code::InvalidSearchFilter, features)?,
+        None => None,
+    };
+    Ok(milli::filtered_universe(index, rtxn, &filter)?)
+}
+
+/// Intersect the attributes the query searches on with the ones the search rules allow.
+fn restrict_attributes_to_search_on(
+    index: &Index,
+    rtxn: &RoTxn,
+    attributes_to_search_on: &mut Option<Vec<String>>,
+    attributes_rules: &AttributesRules,
+) -> milli::Result<()> {
+    if attributes_rules.is_unrestricted() {
+        return Ok(());
+    }
+
+    let wildcard = attributes_to_search_on
+        .as_ref()
+        .is_none_or(|attributes| attributes.iter().any(|attribute| attribute == "*"));
+    let attributes = if wildcard {
+        index.searchable_fields(rtxn)?.into_iter().map(|field| field.into_owned()).collect()
+    } else {
+        attributes_to_search_on.take().unwrap_or_default()
+    };
+
+    *attributes_to_search_on = Some(
+        attributes
+            .into_iter()
+            .filter(|attribute| attributes_rules.is_searchable(attribute))
+            .collect(),
+    );
+    Ok(())
+}
+
 pub fn search_from_kind(
     index_uid: String,
     search_kind: SearchKind,
-    search: milli::Search<'_>,
+    search: &milli::Search<'_>,
 ) -> Result<(milli::SearchResult, Option<u32>), MeilisearchHttpError> {
     let (milli_result, semantic_hit_count) = match &search_kind {
         SearchKind::KeywordOnly => {
@@ -1228,6 +1671,7 @@ struct AttributesFormat {
     show_ranking_score: bool,
     show_ranking_score_details: bool,
     locales: Option<Vec<Language>>,
+    attributes_rules: AttributesRules,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -1328,8 +1772,14 @@ impl<'a> HitMaker<'a> {
             (Some(map), Some(vectors_fid)) => map.contains(&vectors_fid),
         };
 
-        let displayed_ids =
+        let mut displayed_ids =
             displayed_ids.unwrap_or_else(|| fields_ids_map.iter().map(|(id, _)| id).collect());
+        // the attributes hidden by the search rules can't be retrieved, highlighted, or cropped
+        displayed_ids.retain(|fid| {
+            fields_ids_map.name(*fid).is_some_and(|name| format.attributes_rules.is_visible(name))
+        });
+        let vectors_is_hidden = vectors_is_hidden
+            || !format.attributes_rules.is_visible(milli::constants::RESERVED_VECTORS_FIELD_NAME);
 
         let retrieve_vectors = if let RetrieveVectors::Retrieve = format.retrieve_vectors {
             if vectors_is_hidden {
@@ -1476,6 +1926,7 @@ impl<'a> HitMaker<'a> {
             matches_position,
             ranking_score_details,
             ranking_score,
+            inner_hits: None,
         };
 
         Ok(hit)
@@ -1488,6 +1939,7 @@ fn make_hits<'a>(
     format: AttributesFormat,
     matching_words: milli::MatchingWords,
     documents_ids_scores: impl Iterator<Item = (u32, &'a Vec<ScoreDetails>)> + 'a,
+    collapsed_groups: Vec<milli::CollapsedGroup>,
 ) -> milli::Result<Vec<SearchHit>> {
     let mut documents = Vec::new();
 
@@ -1504,20 +1956,33 @@ fn make_hits<'a>(
 
     let hit_maker = HitMaker::new(index, rtxn, format, formatter_builder)?;
 
+    let mut collapsed_groups = collapsed_groups.into_iter();
     for (id, score) in documents_ids_scores {
-        documents.push(hit_maker.make_hit(id, score)?);
+        let mut hit = hit_maker.make_hit(id, score)?;
+        if let Some(group) = collapsed_groups.next() {
+            let milli::CollapsedGroup { total_hits, documents_ids, document_scores } = group;
+            let hits = documents_ids
+                .into_iter()
+                .zip(&document_scores)
+                .map(|(id, score)| hit_maker.make_hit(id, score))
+                .collect::<milli::Result<_>>()?;
+            hit.inner_hits = Some(InnerHits { total_hits, hits });
+        }
+        documents.push(hit);
     }
     Ok(documents)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn perform_facet_search(
     index: &Index,
-    search_query: SearchQuery,
+    mut search_query: SearchQuery,
     facet_query: Option<String>,
     facet_name: String,
     search_kind: SearchKind,
     features: RoFeatures,
     locales: Option<Vec<Language>>,
+    attributes_rules: AttributesRules,
 ) -> Result<FacetSearchResult, ResponseError> {
     let before_search = Instant::now();
     let rtxn = index.read_txn()?;
@@ -1533,6 +1998,20 @@ pub fn perform_facet_search(
         ));
     }
 
+    if !attributes_rules.is_visible(&facet_name) {
+        return Err(ResponseError::from_msg(
+            format!("Attribute `{facet_name}` is not facet-searchable."),
+            Code::InvalidFacetSearchFacetName,
+        ));
+    }
+    check_hidden_attributes(&search_query, &attributes_rules)?;
+    restrict_attributes_to_search_on(
+        index,
+        &rtxn,
+        &mut search_query.attributes_to_search_on,
+        &attributes_rules,
+    )?;
+
     // In the faceted search context, we want to use the intersection between the locales provided by the user
     // and the locales of the facet string.
     // If the facet string is not localized, we **ignore** the locales provided by the user because the facet data has no locale.
@@ -1573,6 +2052,64 @@ pub fn perform_facet_search(
     })
 }
 
+pub fn perform_autocomplete(
+    index: &Index,
+    query: AutocompleteQuery,
+    features: RoFeatures,
+    attributes_rules: AttributesRules,
+) -> Result<AutocompleteResult, ResponseError> {
+    let before_autocomplete = Instant::now();
+    let rtxn = index.read_txn()?;
+
+    let mut applied_rules =
+        query_rules::AppliedQueryRules::for_autocomplete(index.query_rules(&rtxn)?, &query);
+    let AutocompleteQuery { q, mut filter, limit, locales } = query;
+
+    let mut autocomplete = milli::Autocomplete::new(q.as_str(), index, &rtxn);
+    autocomplete.limit(limit);
+
+    // the words of the documents the query rules hide or filter out must not be proposed
+    applied_rules.add_filters(&mut filter);
+    let (hidden, _pinned) = applied_rules.resolve_documents(index, &rtxn)?;
+    autocomplete.excluded_documents(hidden);
+
+    if let Some(ref filter) = filter {
+        if let Some(facets) = parse_filter(filter, Code::InvalidAutocompleteFilter, features)? {
+            autocomplete.filter(facets);
+        }
+    }
+
+    if let Some(locales) = locales {
+        autocomplete.locales(locales.into_iter().map(Into::into).collect());
+    }
+
+    // The words of the attributes the tenant token can't search on must not be proposed.
+    if !attributes_rules.is_unrestricted() {
+        let fields_ids_map = index.fields_ids_map(&rtxn)?;
+        autocomplete.fields_ids(
+            fields_ids_map
+                .iter()
+                .filter(|(_, name)| attributes_rules.is_searchable(name))
+                .map(|(fid, _)| fid)
+                .collect(),
+        );
+    }
+
+    let completions = autocomplete.execute().map_err(|err| match err {
+        milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
+            ResponseError::from_msg(err.to_string(), Code::InvalidAutocompleteFilter)
+        }
+        err => err.into(),
+    })?;
+
+    Ok(AutocompleteResult {
+        completions,
+        query: q,
+        processing_time_ms: before_autocomplete.elapsed().as_millis(),
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn perform_similar(
     index: &Index,
     query: SimilarQuery,
@@ -1581,6 +2118,7 @@ pub fn perform_similar(
     quantized: bool,
     retrieve_vectors: RetrieveVectors,
     features: RoFeatures,
+    attributes_rules: AttributesRules,
 ) -> Result<SimilarResult, ResponseError> {
     let before_search = Instant::now();
     let rtxn = index.read_txn()?;
@@ -1661,6 +2199,7 @@ pub fn perform_similar(
         show_ranking_score,
         show_ranking_score_details,
         locales: None,
+        attributes_rules,
     };
 
     let hits = make_hits(
@@ -1669,6 +2208,7 @@ pub fn perform_similar(
         format,
         Default::default(),
         documents_ids.iter().copied().zip(document_scores.iter()),
+        Vec::new(),
     )?;
 
     let max_total_hits = index
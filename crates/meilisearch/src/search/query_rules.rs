@@ -0,0 +1,176 @@
+use meilisearch_types::heed::RoTxn;
+use meilisearch_types::locales::Locale;
+use meilisearch_types::milli::score_details::ScoreDetails;
+use meilisearch_types::milli::tokenizer::Language;
+use meilisearch_types::milli::{self, Index, QueryRule};
+use roaring::RoaringBitmap;
+use serde_json::Value;
+
+use super::{AutocompleteQuery, SearchQuery};
+
+/// The consequences of the query rules matching a search, combined in the order of the rules.
+#[derive(Debug, Default)]
+pub struct AppliedQueryRules {
+    /// The uids of the rules, reported in the search response.
+    pub uids: Vec<String>,
+    /// The query of the first rule replacing it.
+    pub replace_query: Option<String>,
+    pub filters: Vec<String>,
+    /// The external ids of the pinned documents and their positions.
+    pub pinned: Vec<(String, usize)>,
+    pub hidden: Vec<String>,
+}
+
+impl AppliedQueryRules {
+    /// Keeps the rules whose conditions match the search query.
+    pub fn new(rules: Vec<QueryRule>, query: &SearchQuery) -> Self {
+        Self::matching(rules, query.q.as_deref(), query.filter.as_ref(), query.locales.as_deref())
+    }
+
+    /// Keeps the rules whose conditions match the query being completed.
+    ///
+    /// Only the filters and hidden documents of the rules apply to the completions.
+    pub fn for_autocomplete(rules: Vec<QueryRule>, query: &AutocompleteQuery) -> Self {
+        Self::matching(rules, Some(&query.q), query.filter.as_ref(), query.locales.as_deref())
+    }
+
+    fn matching(
+        rules: Vec<QueryRule>,
+        q: Option<&str>,
+        filter: Option<&Value>,
+        locales: Option<&[Locale]>,
+    ) -> Self {
+        let q = q.unwrap_or_default();
+        let search_filters = search_filters(filter);
+        let search_locales: Option<Vec<Language>> =
+            locales.map(|locales| locales.iter().copied().map(Into::into).collect());
+
+        let mut applied = Self::default();
+        for QueryRule { uid, conditions, consequences } in rules {
+            let query_matches = conditions.query.as_ref().is_none_or(|pattern| pattern.matches(q));
+            let filters_match =
+                conditions.filters.iter().all(|filter| search_filters.contains(&normalize(filter)));
+            let locales_match = conditions.locales.is_empty()
+                || search_locales
+                    .iter()
+                    .flatten()
+                    .any(|locale| conditions.locales.contains(locale));
+            if !(query_matches && filters_match && locales_match) {
+                continue;
+            }
+
+            applied.uids.push(uid);
+            if applied.replace_query.is_none() {
+                applied.replace_query = consequences.replace_query;
+            }
+            applied.filters.extend(consequences.filter);
+            applied.pinned.extend(consequences.pin.into_iter().map(|pin| (pin.id, pin.position)));
+            applied.hidden.extend(consequences.hide);
+        }
+
+        applied
+    }
+
+    /// Adds the filters of the rules to the filter of the search.
+    pub fn add_filters(&mut self, filter: &mut Option<Value>) {
+        if self.filters.is_empty() {
+            return;
+        }
+        let filters = std::mem::take(&mut self.filters).into_iter().map(Value::String);
+        let mut ands = match filter.take() {
+            None => Vec::new(),
+            Some(Value::Array(ands)) => ands,
+            Some(filter) => vec![filter],
+        };
+        ands.extend(filters);
+        *filter = Some(Value::Array(ands));
+    }
+
+    /// Returns the internal ids of the hidden documents, and of the pinned ones with their positions.
+    ///
+    /// Unknown documents are ignored, hidden documents are never pinned
+    /// and a document pinned several times keeps its first position.
+    pub fn resolve_documents(
+        &self,
+        index: &Index,
+        rtxn: &RoTxn,
+    ) -> milli::Result<(RoaringBitmap, Vec<(u32, usize)>)> {
+        let external_documents_ids = index.external_documents_ids();
+
+        let mut hidden = RoaringBitmap::new();
+        for id in &self.hidden {
+            if let Some(docid) = external_documents_ids.get(rtxn, id)? {
+                hidden.insert(docid);
+            }
+        }
+
+        let mut seen = hidden.clone();
+        let mut pinned = Vec::new();
+        for (id, position) in &self.pinned {
+            if let Some(docid) = external_documents_ids.get(rtxn, id)? {
+                if seen.insert(docid) {
+                    pinned.push((docid, *position));
+                }
+            }
+        }
+        pinned.sort_by_key(|(_, position)| *position);
+
+        Ok((hidden, pinned))
+    }
+}
+
+/// Inserts the pinned documents at their positions among the hits of the search
+/// and returns the page starting at `offset`.
+///
+/// The hits must have been computed from the first one, and the pinned documents must be sorted by position.
+/// The pinned documents that are further than the hits are appended after them.
+pub fn pin_documents(
+    documents_ids: Vec<u32>,
+    document_scores: Vec<Vec<ScoreDetails>>,
+    pinned: &[(u32, usize)],
+    offset: usize,
+    limit: usize,
+) -> (Vec<u32>, Vec<Vec<ScoreDetails>>) {
+    let mut hits = documents_ids.into_iter().zip(document_scores);
+    let mut pinned = pinned.iter().peekable();
+
+    let mut merged = Vec::new();
+    while merged.len() < offset + limit {
+        let hit = match pinned.next_if(|(_, position)| *position <= merged.len()) {
+            Some(&(docid, _)) => (docid, Vec::new()),
+            None => match hits.next() {
+                Some(hit) => hit,
+                None => match pinned.next() {
+                    Some(&(docid, _)) => (docid, Vec::new()),
+                    None => break,
+                },
+            },
+        };
+        merged.push(hit);
+    }
+
+    merged.into_iter().skip(offset).unzip()
+}
+
+/// The filter expressions that must all be matched by the documents, normalized.
+fn search_filters(filter: Option<&Value>) -> Vec<String> {
+    match filter {
+        Some(Value::String(filter)) => vec![normalize(filter)],
+        Some(Value::Array(ands)) => ands
+            .iter()
+            .filter_map(|filter| match filter {
+                Value::String(filter) => Some(normalize(filter)),
+                Value::Array(ors) => match ors.as_slice() {
+                    [Value::String(filter)] => Some(normalize(filter)),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn normalize(filter: &str) -> String {
+    filter.split_whitespace().collect::<Vec<_>>().join(" ")
+}
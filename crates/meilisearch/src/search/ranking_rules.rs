@@ -209,7 +209,8 @@ impl RankingRules {
                 | Criterion::Typo
                 | Criterion::Proximity
                 | Criterion::Attribute
-                | Criterion::Exactness => {
+                | Criterion::Exactness
+                | Criterion::Bm25 => {
                     canonicalization_actions.push(CanonicalizationAction::RemovedPlaceholder {
                         removed_occurrence: RankingRuleSource::Criterion(criterion_index),
                     })
@@ -272,7 +273,8 @@ impl RankingRules {
                 | Criterion::Typo
                 | Criterion::Proximity
                 | Criterion::Attribute
-                | Criterion::Exactness => match vector {
+                | Criterion::Exactness
+                | Criterion::Bm25 => match vector {
                     Some(previous_occurrence) => {
                         if sorted_fields.is_empty() {
                             canonicalization_actions.push(CanonicalizationAction::RemovedVector {
@@ -344,6 +346,7 @@ impl RankingRules {
         let mut sort = None;
         let mut attribute = None;
         let mut exactness = None;
+        let mut bm25 = None;
         let mut sorted_fields = HashMap::new();
 
         let mut canonical_criteria = Vec::new();
@@ -428,6 +431,17 @@ impl RankingRules {
                         &mut exactness,
                     );
                 }
+                Criterion::Bm25 => {
+                    canonicalize_criterion(
+                        criterion,
+                        criterion_index,
+                        terms_matching_strategy,
+                        &mut words,
+                        &mut canonicalization_actions,
+                        &mut canonical_criteria,
+                        &mut bm25,
+                    );
+                }
 
                 Criterion::Sort => {
                     if let Some(previous_index) = sort {
@@ -723,7 +737,8 @@ impl RankingRule {
             | Criterion::Typo
             | Criterion::Proximity
             | Criterion::Attribute
-            | Criterion::Exactness => RankingRuleKind::Relevancy,
+            | Criterion::Exactness
+            | Criterion::Bm25 => RankingRuleKind::Relevancy,
             Criterion::Asc(s) if s == "_geo" => RankingRuleKind::AscendingGeoSort,
 
             Criterion::Asc(_) => RankingRuleKind::AscendingSort,
@@ -0,0 +1,178 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn alias_can_be_used_in_place_of_the_index_uid() {
+    let server = Server::new().await;
+    let index = server.index("movies_v1");
+    let (task, code) = index.add_documents(json!([{ "id": 1, "title": "Carol" }]), None).await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (task, code) =
+        server.update_aliases(json!([{ "alias": "movies", "indexUid": "movies_v1" }])).await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(task.uid()).await.succeeded();
+    snapshot!(task["type"], @r###""indexAliasUpdate""###);
+    snapshot!(json_string!(task["details"]), @r###"
+    {
+      "aliases": [
+        {
+          "alias": "movies",
+          "indexUid": "movies_v1"
+        }
+      ]
+    }
+    "###);
+
+    let (aliases, code) = server.aliases().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(aliases), @r###"
+    {
+      "results": [
+        {
+          "alias": "movies",
+          "indexUid": "movies_v1"
+        }
+      ]
+    }
+    "###);
+
+    // Documents sent through the alias end up in the index it points to.
+    let alias = server.index("movies");
+    let (task, code) = alias.add_documents(json!([{ "id": 2, "title": "Wall-E" }]), None).await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(task.uid()).await.succeeded();
+    snapshot!(task["indexUid"], @r###""movies_v1""###);
+
+    let (response, code) = alias.search_post(json!({ "q": "wall" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 2,
+        "title": "Wall-E"
+      }
+    ]
+    "###);
+
+    // Repoint the alias to a new index
+    let index = server.index("movies_v2");
+    let (task, _code) = index.add_documents(json!([{ "id": 3, "title": "Shazam!" }]), None).await;
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, code) =
+        server.update_aliases(json!([{ "alias": "movies", "indexUid": "movies_v2" }])).await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = alias.search_post(json!({ "q": "" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 3,
+        "title": "Shazam!"
+      }
+    ]
+    "###);
+
+    // Remove the alias
+    let (task, code) =
+        server.update_aliases(json!([{ "alias": "movies", "indexUid": null }])).await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = alias.search_post(json!({ "q": "" })).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""index_not_found""###);
+}
+
+#[actix_rt::test]
+async fn alias_conflicts_with_index_uid() {
+    let server = Server::new().await;
+    let index = server.index("movies");
+    let (task, _code) = index.create(None).await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (task, code) =
+        server.update_aliases(json!([{ "alias": "movies", "indexUid": "movies" }])).await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(task.uid()).await.failed();
+    snapshot!(json_string!(task["error"]), @r###"
+    {
+      "message": "Cannot use `movies` as an alias because an index with this uid already exists.",
+      "code": "index_alias_conflict",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#index_alias_conflict"
+    }
+    "###);
+
+    let (task, code) =
+        server.update_aliases(json!([{ "alias": "doggos", "indexUid": "dogs" }])).await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(task.uid()).await.failed();
+    snapshot!(task["error"]["code"], @r###""index_not_found""###);
+
+    let (response, code) = server
+        .update_aliases(json!([
+            { "alias": "films", "indexUid": "movies" },
+            { "alias": "films", "indexUid": null },
+        ]))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_index_alias_duplicate_found""###);
+
+    let (response, code) = server.update_aliases(json!([{ "indexUid": "movies" }])).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""missing_index_alias""###);
+}
+
+#[actix_rt::test]
+async fn alias_is_authorized_against_the_index_it_points_to() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let index = server.index("movies_v1");
+    let (task, _code) = index.add_documents(json!([{ "id": 1, "title": "Carol" }]), None).await;
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, _code) =
+        server.update_aliases(json!([{ "alias": "movies", "indexUid": "movies_v1" }])).await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (target_key, code) = server
+        .add_api_key(json!({ "indexes": ["movies_v1"], "actions": ["search"], "expiresAt": null }))
+        .await;
+    snapshot!(code, @"201 Created");
+    let (alias_key, code) = server
+        .add_api_key(json!({ "indexes": ["movies"], "actions": ["search"], "expiresAt": null }))
+        .await;
+    snapshot!(code, @"201 Created");
+
+    // A key granted the index can search it through the alias.
+    server.use_api_key(target_key["key"].as_str().unwrap());
+    let (response, code) = server.index("movies").search_post(json!({ "q": "" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1,
+        "title": "Carol"
+      }
+    ]
+    "###);
+    let (_response, code) =
+        server.multi_search(json!({ "queries": [{ "indexUid": "movies", "q": "" }] })).await;
+    snapshot!(code, @"200 OK");
+
+    // A key only granted the alias name can't reach the index it points to.
+    server.use_api_key(alias_key["key"].as_str().unwrap());
+    let (response, code) = server.index("movies").search_post(json!({ "q": "" })).await;
+    snapshot!(code, @"403 Forbidden");
+    snapshot!(response["code"], @r###""invalid_api_key""###);
+    let (response, code) =
+        server.multi_search(json!({ "queries": [{ "indexUid": "movies", "q": "" }] })).await;
+    snapshot!(code, @"403 Forbidden");
+    snapshot!(response["code"], @r###""invalid_api_key""###);
+}
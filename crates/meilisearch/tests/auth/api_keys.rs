@@ -1,6 +1,11 @@
+use std::net::SocketAddr;
 use std::{thread, time};
 
-use crate::common::{Server, Value};
+use actix_web::test;
+use meilisearch::Opt;
+use tempfile::TempDir;
+
+use crate::common::{default_settings, Server, Value};
 use crate::json;
 
 #[actix_rt::test]
@@ -421,7 +426,7 @@ async fn error_add_api_key_invalid_parameters_actions() {
     meili_snap::snapshot!(code, @"400 Bad Request");
     meili_snap::snapshot!(meili_snap::json_string!(response, { ".createdAt" => "[ignored]", ".updatedAt" => "[ignored]" }), @r###"
     {
-      "message": "Unknown value `doc.add` at `.actions[0]`: expected one of `*`, `search`, `documents.*`, `documents.add`, `documents.get`, `documents.delete`, `indexes.*`, `indexes.create`, `indexes.get`, `indexes.update`, `indexes.delete`, `indexes.swap`, `tasks.*`, `tasks.cancel`, `tasks.delete`, `tasks.get`, `settings.*`, `settings.get`, `settings.update`, `stats.*`, `stats.get`, `metrics.*`, `metrics.get`, `dumps.*`, `dumps.create`, `snapshots.*`, `snapshots.create`, `version`, `keys.create`, `keys.get`, `keys.update`, `keys.delete`, `experimental.get`, `experimental.update`, `network.get`, `network.update`",
+      "message": "Unknown value `doc.add` at `.actions[0]`: expected one of `*`, `search`, `documents.*`, `documents.add`, `documents.get`, `documents.delete`, `indexes.*`, `indexes.create`, `indexes.get`, `indexes.update`, `indexes.delete`, `indexes.swap`, `tasks.*`, `tasks.cancel`, `tasks.delete`, `tasks.get`, `settings.*`, `settings.get`, `settings.update`, `stats.*`, `stats.get`, `metrics.*`, `metrics.get`, `dumps.*`, `dumps.create`, `snapshots.*`, `snapshots.create`, `version`, `keys.create`, `keys.get`, `keys.update`, `keys.delete`, `experimental.get`, `experimental.update`, `network.get`, `network.update`, `auditLogs.get`, `tasks.own`",
       "code": "invalid_api_key_actions",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_api_key_actions"
@@ -1393,7 +1398,7 @@ async fn error_patch_api_key_indexes() {
     let (response, code) = server.patch_api_key(&uid, content).await;
     meili_snap::snapshot!(meili_snap::json_string!(response, { ".createdAt" => "[ignored]", ".updatedAt" => "[ignored]" }), @r###"
     {
-      "message": "Immutable field `indexes`: expected one of `description`, `name`",
+      "message": "Immutable field `indexes`: expected one of `description`, `name`, `rateLimits`, `allowedIps`, `allowedOrigins`",
       "code": "immutable_api_key_indexes",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_api_key_indexes"
@@ -1470,7 +1475,7 @@ async fn error_patch_api_key_actions() {
     let (response, code) = server.patch_api_key(&uid, content).await;
     meili_snap::snapshot!(meili_snap::json_string!(response, { ".createdAt" => "[ignored]", ".updatedAt" => "[ignored]" }), @r###"
     {
-      "message": "Immutable field `actions`: expected one of `description`, `name`",
+      "message": "Immutable field `actions`: expected one of `description`, `name`, `rateLimits`, `allowedIps`, `allowedOrigins`",
       "code": "immutable_api_key_actions",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_api_key_actions"
@@ -1539,7 +1544,7 @@ async fn error_patch_api_key_expiration_date() {
     let (response, code) = server.patch_api_key(&uid, content).await;
     meili_snap::snapshot!(meili_snap::json_string!(response, { ".createdAt" => "[ignored]", ".updatedAt" => "[ignored]" }), @r###"
     {
-      "message": "Immutable field `expiresAt`: expected one of `description`, `name`",
+      "message": "Immutable field `expiresAt`: expected one of `description`, `name`, `rateLimits`, `allowedIps`, `allowedOrigins`",
       "code": "immutable_api_key_expires_at",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#immutable_api_key_expires_at"
@@ -1779,3 +1784,433 @@ async fn error_access_api_key_routes_no_master_key_set() {
     "###);
     meili_snap::snapshot!(code, @"401 Unauthorized");
 }
+
+#[actix_rt::test]
+async fn api_key_rate_limits() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) = server
+        .add_api_key(json!({
+            "uid": "4bc0887a-0e41-4f3b-935d-0c451dcee9c8",
+            "indexes": ["*"],
+            "actions": ["*"],
+            "expiresAt": null,
+            "rateLimits": { "requestsPerSecond": 1, "maxPayloadBytesPerDay": 100 }
+        }))
+        .await;
+    meili_snap::snapshot!(code, @"201 Created");
+    meili_snap::snapshot!(meili_snap::json_string!(response["rateLimits"]), @r###"
+    {
+      "requestsPerSecond": 1,
+      "maxPayloadBytesPerDay": 100
+    }
+    "###);
+    let key = response["key"].as_str().unwrap().to_string();
+
+    let app = server.init_web_app().await;
+    let req = test::TestRequest::get()
+        .uri("/indexes")
+        .insert_header(("Authorization", format!("Bearer {key}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    meili_snap::snapshot!(res.status(), @"200 OK");
+
+    // the bucket is empty until the next second
+    let req = test::TestRequest::get()
+        .uri("/indexes")
+        .insert_header(("Authorization", format!("Bearer {key}")))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    meili_snap::snapshot!(res.status(), @"429 Too Many Requests");
+    meili_snap::snapshot!(res.headers().get("retry-after").unwrap().to_str().unwrap(), @"1");
+    let body = test::read_body(res).await;
+    let response: Value = serde_json::from_slice(&body).unwrap_or_default();
+    meili_snap::snapshot!(response, @r###"
+    {
+      "message": "Too many requests. The API key is limited to 1 requests per second.",
+      "code": "too_many_requests",
+      "type": "auth",
+      "link": "https://docs.meilisearch.com/errors#too_many_requests"
+    }
+    "###);
+
+    // a key can't be limited to no request at all
+    let (response, code) = server
+        .patch_api_key(
+            "4bc0887a-0e41-4f3b-935d-0c451dcee9c8",
+            json!({ "rateLimits": { "requestsPerSecond": 0 } }),
+        )
+        .await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(response, @r###"
+    {
+      "message": "Invalid value at `.rateLimits`: `requestsPerSecond` must be greater than `0`.",
+      "code": "invalid_api_key_rate_limits",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_api_key_rate_limits"
+    }
+    "###);
+
+    // the payload is counted in the quota of the day as it is received
+    let (response, code) = server
+        .patch_api_key(
+            "4bc0887a-0e41-4f3b-935d-0c451dcee9c8",
+            json!({ "rateLimits": { "maxPayloadBytesPerDay": 100 } }),
+        )
+        .await;
+    meili_snap::snapshot!(code, @"200 OK", "{response}");
+    let documents =
+        |size: usize| format!("[{{ \"id\": 1, \"description\": \"{}\" }}]", "a".repeat(size));
+
+    // an announced length above the quota is rejected before reading the payload
+    let req = test::TestRequest::post()
+        .uri("/indexes/dog/documents")
+        .insert_header(("Authorization", format!("Bearer {key}")))
+        .insert_header(("content-type", "application/json"))
+        .set_payload(documents(100))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    meili_snap::snapshot!(res.status(), @"429 Too Many Requests");
+    assert!(res.headers().contains_key("retry-after"));
+
+    let req = test::TestRequest::post()
+        .uri("/indexes/dog/documents")
+        .insert_header(("Authorization", format!("Bearer {key}")))
+        .insert_header(("content-type", "application/json"))
+        .set_payload(documents(10))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    meili_snap::snapshot!(res.status(), @"202 Accepted");
+
+    // a chunked payload, without length, is rejected once the quota is exceeded
+    let mut req = test::TestRequest::post()
+        .uri("/indexes/dog/documents")
+        .insert_header(("Authorization", format!("Bearer {key}")))
+        .insert_header(("content-type", "application/json"))
+        .set_payload(documents(100))
+        .to_request();
+    req.headers_mut().remove("content-length");
+    let res = test::call_service(&app, req).await;
+    meili_snap::snapshot!(res.status(), @"429 Too Many Requests");
+    let body = test::read_body(res).await;
+    let response: Value = serde_json::from_slice(&body).unwrap_or_default();
+    meili_snap::snapshot!(response, @r###"
+    {
+      "message": "Too many requests. The API key is limited to 100 bytes of payload per day.",
+      "code": "too_many_requests",
+      "type": "auth",
+      "link": "https://docs.meilisearch.com/errors#too_many_requests"
+    }
+    "###);
+
+    // the master key is never limited and can see the usage of the key
+    let (response, code) = server.get_api_key("4bc0887a-0e41-4f3b-935d-0c451dcee9c8").await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["usage"], { ".payloadBytesToday" => "[ignored]" }), @r###"
+    {
+      "availableRequests": null,
+      "payloadBytesToday": "[ignored]",
+      "concurrentSearches": 0
+    }
+    "###);
+    assert!(response["usage"]["payloadBytesToday"].as_u64().unwrap() > 100, "{response}");
+
+    // the limits can be removed
+    let (response, code) = server
+        .patch_api_key("4bc0887a-0e41-4f3b-935d-0c451dcee9c8", json!({ "rateLimits": null }))
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    assert!(response["rateLimits"].is_null(), "{response}");
+}
+
+#[actix_rt::test]
+async fn api_key_network_restrictions() {
+    let dir = TempDir::new().unwrap();
+    let mut options = default_settings(dir.path());
+    options.trusted_proxies = vec!["10.0.0.0/8".parse().unwrap()];
+    let mut server = Server::new_auth_with_options(options, dir).await;
+    server.use_api_key("MASTER_KEY");
+
+    let (response, code) = server
+        .add_api_key(json!({ "indexes": ["*"], "actions": ["*"], "expiresAt": null, "allowedIps": ["doggo"] }))
+        .await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(response["code"], @r###""invalid_api_key_allowed_ips""###);
+
+    let (response, code) = server
+        .add_api_key(json!({
+            "uid": "4bc0887a-0e41-4f3b-935d-0c451dcee9c8",
+            "indexes": ["*"],
+            "actions": ["*"],
+            "expiresAt": null,
+            "allowedIps": ["192.168.0.0/16"],
+            "allowedOrigins": ["https://admin.example.com"]
+        }))
+        .await;
+    meili_snap::snapshot!(code, @"201 Created");
+    meili_snap::snapshot!(meili_snap::json_string!([&response["allowedIps"], &response["allowedOrigins"]]), @r###"
+    [
+      [
+        "192.168.0.0/16"
+      ],
+      [
+        "https://admin.example.com"
+      ]
+    ]
+    "###);
+    let key = response["key"].as_str().unwrap().to_string();
+
+    let app = server.init_web_app().await;
+    let request = |peer: &str, forwarded_for: Option<&str>, origin: Option<&str>| {
+        let mut req = test::TestRequest::get()
+            .uri("/indexes")
+            .peer_addr(SocketAddr::new(peer.parse().unwrap(), 4242))
+            .insert_header(("Authorization", format!("Bearer {key}")));
+        if let Some(forwarded_for) = forwarded_for {
+            req = req.insert_header(("X-Forwarded-For", forwarded_for));
+        }
+        if let Some(origin) = origin {
+            req = req.insert_header(("Origin", origin));
+        }
+        req.to_request()
+    };
+    let origin = Some("https://admin.example.com");
+
+    let res = test::call_service(&app, request("192.168.1.4", None, origin)).await;
+    meili_snap::snapshot!(res.status(), @"200 OK");
+    // the address of the client is read from the header set by the trusted proxy
+    let res = test::call_service(&app, request("10.0.0.2", Some("192.168.1.4"), origin)).await;
+    meili_snap::snapshot!(res.status(), @"200 OK");
+    // even when the proxy adds the port of the client
+    let res =
+        test::call_service(&app, request("10.0.0.2", Some("192.168.1.4:51234"), origin)).await;
+    meili_snap::snapshot!(res.status(), @"200 OK");
+    // the client is unknown when the trusted proxy doesn't send its address
+    let res = test::call_service(&app, request("10.0.0.2", None, origin)).await;
+    meili_snap::snapshot!(res.status(), @"403 Forbidden");
+    let res = test::call_service(&app, request("10.0.0.2", Some("unknown"), origin)).await;
+    meili_snap::snapshot!(res.status(), @"403 Forbidden");
+    // but not from clients pretending to be a proxy
+    let res = test::call_service(&app, request("203.0.113.7", Some("192.168.1.4"), origin)).await;
+    meili_snap::snapshot!(res.status(), @"403 Forbidden");
+    let body = test::read_body(res).await;
+    let response: Value = serde_json::from_slice(&body).unwrap_or_default();
+    meili_snap::snapshot!(response, @r###"
+    {
+      "message": "The provided API key can't be used from this address or origin.",
+      "code": "api_key_origin_not_allowed",
+      "type": "auth",
+      "link": "https://docs.meilisearch.com/errors#api_key_origin_not_allowed"
+    }
+    "###);
+    let res =
+        test::call_service(&app, request("192.168.1.4", None, Some("https://example.com"))).await;
+    meili_snap::snapshot!(res.status(), @"403 Forbidden");
+    let res = test::call_service(&app, request("192.168.1.4", None, None)).await;
+    meili_snap::snapshot!(res.status(), @"403 Forbidden");
+
+    // the restrictions can be removed
+    let (response, code) = server
+        .patch_api_key(
+            "4bc0887a-0e41-4f3b-935d-0c451dcee9c8",
+            json!({ "allowedIps": null, "allowedOrigins": null }),
+        )
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    assert!(response["allowedIps"].is_null(), "{response}");
+    let res = test::call_service(&app, request("203.0.113.7", None, None)).await;
+    meili_snap::snapshot!(res.status(), @"200 OK");
+}
+
+#[actix_rt::test]
+async fn api_key_rotation() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let uid = "4bc0887a-0e41-4f3b-935d-0c451dcee9c8";
+    let (response, code) = server
+        .add_api_key(
+            json!({ "uid": uid, "indexes": ["*"], "actions": ["indexes.get"], "expiresAt": null }),
+        )
+        .await;
+    meili_snap::snapshot!(code, @"201 Created");
+    let first_key = response["key"].as_str().unwrap().to_string();
+
+    let (response, code) = server.rotate_api_key(uid, json!({ "gracePeriod": "doggo" })).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(response["code"], @r###""invalid_api_key_grace_period""###);
+
+    // the previous value is accepted during the grace period
+    let (response, code) = server.rotate_api_key(uid, json!({ "gracePeriod": 3600 })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response, { ".key" => "[key]", ".createdAt" => "[date]", ".updatedAt" => "[date]", ".previousKeyExpiresAt" => "[date]" }), @r###"
+    {
+      "name": null,
+      "description": null,
+      "key": "[key]",
+      "uid": "4bc0887a-0e41-4f3b-935d-0c451dcee9c8",
+      "actions": [
+        "indexes.get"
+      ],
+      "indexes": [
+        "*"
+      ],
+      "expiresAt": null,
+      "previousKeyExpiresAt": "[date]",
+      "createdAt": "[date]",
+      "updatedAt": "[date]"
+    }
+    "###);
+    let second_key = response["key"].as_str().unwrap().to_string();
+    assert_ne!(first_key, second_key);
+
+    server.use_api_key(&first_key);
+    let (_response, code) = server.list_indexes(None, None).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    server.use_api_key(&second_key);
+    let (_response, code) = server.list_indexes(None, None).await;
+    meili_snap::snapshot!(code, @"200 OK");
+
+    // without a body, hence without a grace period, only the new value is accepted
+    server.use_api_key("MASTER_KEY");
+    let (response, code) =
+        server.service.post_str(format!("/keys/{second_key}/rotate"), "", Vec::new()).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    assert!(response["previousKeyExpiresAt"].is_null(), "{response}");
+    let third_key = response["key"].as_str().unwrap().to_string();
+
+    for key in [&first_key, &second_key] {
+        server.use_api_key(key);
+        let (response, code) = server.list_indexes(None, None).await;
+        meili_snap::snapshot!(code, @"403 Forbidden");
+        meili_snap::snapshot!(response["code"], @r###""invalid_api_key""###);
+    }
+    server.use_api_key(&third_key);
+    let (_response, code) = server.list_indexes(None, None).await;
+    meili_snap::snapshot!(code, @"200 OK");
+}
+
+#[actix_rt::test]
+async fn master_key_rotation() {
+    let dir = TempDir::new().unwrap();
+    let options =
+        Opt { master_key: Some("PREVIOUS_MASTER_KEY".to_string()), ..default_settings(dir.path()) };
+    let mut server = Server::new_with_options(options.clone()).await.unwrap();
+    server.use_api_key("PREVIOUS_MASTER_KEY");
+
+    let (response, code) = server
+        .add_api_key(json!({ "indexes": ["*"], "actions": ["indexes.get"], "expiresAt": null }))
+        .await;
+    meili_snap::snapshot!(code, @"201 Created");
+    let uid = response["uid"].as_str().unwrap().to_string();
+    let previous_key = response["key"].as_str().unwrap().to_string();
+
+    let count = server
+        .service
+        .auth
+        .rotate_master_key("PREVIOUS_MASTER_KEY", ::time::Duration::days(1))
+        .unwrap();
+    // the default search and admin keys and the one we created
+    meili_snap::snapshot!(count, @"3");
+    drop(server);
+
+    let options = Opt { master_key: Some("NEW_MASTER_KEY".to_string()), ..options };
+    let mut server = Server::new_with_options(options).await.unwrap();
+    server.use_api_key("NEW_MASTER_KEY");
+    let (response, code) = server.get_api_key(&uid).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    let new_key = response["key"].as_str().unwrap().to_string();
+    assert_ne!(previous_key, new_key);
+
+    // both values are accepted during the transition
+    for key in [&previous_key, &new_key] {
+        server.use_api_key(key);
+        let (_response, code) = server.list_indexes(None, None).await;
+        meili_snap::snapshot!(code, @"200 OK");
+    }
+
+    // only the use of the previous value is reported
+    let used: Vec<_> = server
+        .service
+        .auth
+        .list_previous_key_values()
+        .unwrap()
+        .into_iter()
+        .filter(|previous| previous.last_used_at.is_some())
+        .map(|previous| previous.uid.to_string())
+        .collect();
+    assert_eq!(used, vec![uid]);
+
+    // the previous master key isn't accepted anymore
+    server.use_api_key("PREVIOUS_MASTER_KEY");
+    let (response, code) = server.list_indexes(None, None).await;
+    meili_snap::snapshot!(code, @"403 Forbidden");
+    meili_snap::snapshot!(response["code"], @r###""invalid_api_key""###);
+}
+
+#[actix_rt::test]
+async fn rotate_and_delete_key_during_master_key_rotation() {
+    let dir = TempDir::new().unwrap();
+    let options =
+        Opt { master_key: Some("PREVIOUS_MASTER_KEY".to_string()), ..default_settings(dir.path()) };
+    let mut server = Server::new_with_options(options.clone()).await.unwrap();
+    server.use_api_key("PREVIOUS_MASTER_KEY");
+
+    let content = json!({ "indexes": ["*"], "actions": ["indexes.get"], "expiresAt": null });
+    let (response, code) = server.add_api_key(content.clone()).await;
+    meili_snap::snapshot!(code, @"201 Created");
+    let rotated_uid = response["uid"].as_str().unwrap().to_string();
+    let rotated_previous_key = response["key"].as_str().unwrap().to_string();
+    let (response, code) = server.add_api_key(content).await;
+    meili_snap::snapshot!(code, @"201 Created");
+    let deleted_uid = response["uid"].as_str().unwrap().to_string();
+    let deleted_previous_key = response["key"].as_str().unwrap().to_string();
+
+    server
+        .service
+        .auth
+        .rotate_master_key("PREVIOUS_MASTER_KEY", ::time::Duration::days(1))
+        .unwrap();
+    drop(server);
+
+    let options = Opt { master_key: Some("NEW_MASTER_KEY".to_string()), ..options };
+    let mut server = Server::new_with_options(options).await.unwrap();
+
+    // the values derived from the previous master key are accepted during the transition
+    for key in [&rotated_previous_key, &deleted_previous_key] {
+        server.use_api_key(key);
+        let (_response, code) = server.list_indexes(None, None).await;
+        meili_snap::snapshot!(code, @"200 OK");
+    }
+
+    server.use_api_key("NEW_MASTER_KEY");
+    let (response, code) =
+        server.rotate_api_key(&rotated_uid, json!({ "gracePeriod": 3600 })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    let rotated_key = response["key"].as_str().unwrap().to_string();
+    let (_response, code) = server.delete_api_key(&deleted_uid).await;
+    meili_snap::snapshot!(code, @"204 No Content");
+
+    // the rotation and the deletion revoke them, regardless of the grace period
+    for key in [&rotated_previous_key, &deleted_previous_key] {
+        server.use_api_key(key);
+        let (response, code) = server.list_indexes(None, None).await;
+        meili_snap::snapshot!(code, @"403 Forbidden");
+        meili_snap::snapshot!(response["code"], @r###""invalid_api_key""###);
+    }
+    server.use_api_key(&rotated_key);
+    let (_response, code) = server.list_indexes(None, None).await;
+    meili_snap::snapshot!(code, @"200 OK");
+
+    let remaining: Vec<_> = server
+        .service
+        .auth
+        .list_previous_key_values()
+        .unwrap()
+        .into_iter()
+        .map(|previous| previous.uid.to_string())
+        .filter(|uid| *uid == rotated_uid || *uid == deleted_uid)
+        .collect();
+    assert!(remaining.is_empty(), "{remaining:?}");
+}
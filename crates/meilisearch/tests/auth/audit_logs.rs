@@ -0,0 +1,210 @@
+use std::num::NonZeroU64;
+
+use meili_snap::{json_string, snapshot};
+use tempfile::TempDir;
+
+use crate::common::{default_settings, Server};
+use crate::json;
+
+const KEY_UID: &str = "4bc0887a-0e41-4f3b-935d-0c451dcee9c8";
+
+async fn server_with_backoffice_key() -> (Server, String) {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let content = json!({
+        "name": "Backoffice",
+        "uid": KEY_UID,
+        "indexes": ["*"],
+        "actions": ["settings.update", "auditLogs.get"],
+        "expiresAt": null,
+    });
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(201, code, "{:?}", response);
+    let key = response["key"].as_str().unwrap().to_string();
+
+    (server, key)
+}
+
+#[actix_rt::test]
+async fn audit_log_records_the_key_and_outcome() {
+    let (mut server, key) = server_with_backoffice_key().await;
+    server.use_api_key(&key);
+
+    let index = server.index("movies");
+    let (response, code) =
+        index.update_settings(json!({ "filterableAttributes": ["genre"] })).await;
+    assert_eq!(202, code, "{:?}", response);
+
+    let (response, code) = server.service.get("/audit-logs?actions=settings.update").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".results[].uid" => "[uid]", ".results[].timestamp" => "[date]", ".from" => "[uid]" }), @r###"
+    {
+      "results": [
+        {
+          "uid": "[uid]",
+          "timestamp": "[date]",
+          "keyUid": "4bc0887a-0e41-4f3b-935d-0c451dcee9c8",
+          "keyName": "Backoffice",
+          "action": "settings.update",
+          "indexUid": "movies",
+          "method": "PATCH",
+          "route": "/indexes/movies/settings",
+          "statusCode": 202,
+          "outcome": "succeeded"
+        }
+      ],
+      "limit": 20,
+      "from": "[uid]",
+      "next": null
+    }
+    "###);
+
+    // the key is not allowed to delete the index, the failure is recorded as unauthenticated
+    let (_response, code) = index.delete().await;
+    snapshot!(code, @"403 Forbidden");
+
+    let (response, code) = server.service.get("/audit-logs?actions=indexes.delete").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".results[].uid" => "[uid]", ".results[].timestamp" => "[date]", ".from" => "[uid]" }), @r###"
+    {
+      "results": [
+        {
+          "uid": "[uid]",
+          "timestamp": "[date]",
+          "keyUid": "unauthenticated",
+          "keyName": null,
+          "action": "indexes.delete",
+          "indexUid": "movies",
+          "method": "DELETE",
+          "route": "/indexes/movies",
+          "statusCode": 403,
+          "outcome": "failed"
+        }
+      ],
+      "limit": 20,
+      "from": "[uid]",
+      "next": null
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn audit_log_pagination_and_filters() {
+    let (mut server, _key) = server_with_backoffice_key().await;
+
+    for index in ["movies", "books", "games"] {
+        let (response, code) = server.index(index).update_settings(json!({})).await;
+        assert_eq!(202, code, "{:?}", response);
+    }
+
+    let (response, code) =
+        server.service.get("/audit-logs?actions=settings.update&indexUids=movies,games").await;
+    snapshot!(code, @"200 OK");
+    let indexes: Vec<_> =
+        response["results"].as_array().unwrap().iter().map(|log| &log["indexUid"]).collect();
+    snapshot!(json_string!(indexes), @r###"
+    [
+      "games",
+      "movies"
+    ]
+    "###);
+
+    let (response, code) = server.service.get("/audit-logs?actions=settings.update&limit=1").await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(response["results"][0]["indexUid"], json!("games"));
+    let next = response["next"].as_u64().unwrap();
+
+    let (response, code) = server
+        .service
+        .get(format!("/audit-logs?actions=settings.update&limit=1&from={next}"))
+        .await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(response["results"][0]["indexUid"], json!("books"));
+
+    // the entries made with the master key are told apart from the other keys
+    let (response, code) = server.service.get(format!("/audit-logs?keyUids={KEY_UID}")).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"]), @"[]");
+    let (response, code) =
+        server.service.get("/audit-logs?keyUids=masterKey&actions=settings.update").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["results"].as_array().unwrap().len(), @"3");
+
+    let (response, code) = server.service.get("/audit-logs?keyUids=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_audit_log_key_uids""###);
+
+    let (response, code) = server.service.get("/audit-logs?actions=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_audit_log_actions""###);
+}
+
+#[actix_rt::test]
+async fn audit_log_skips_polling_and_hides_entries_without_index() {
+    let (mut server, _key) = server_with_backoffice_key().await;
+
+    let (response, code) = server.index("movies").update_settings(json!({})).await;
+    assert_eq!(202, code, "{:?}", response);
+    let (_response, code) = server.tasks().await;
+    assert_eq!(200, code);
+    let (_response, code) = server.stats().await;
+    assert_eq!(200, code);
+    let (_response, code) = server.service.get("/audit-logs").await;
+    assert_eq!(200, code);
+
+    // neither the polling of the tasks and stats nor the audit log itself are recorded
+    let (response, code) = server.service.get("/audit-logs").await;
+    snapshot!(code, @"200 OK");
+    let actions: Vec<_> =
+        response["results"].as_array().unwrap().iter().map(|log| &log["action"]).collect();
+    snapshot!(json_string!(actions), @r###"
+    [
+      "settings.update",
+      "keys.add"
+    ]
+    "###);
+
+    // a key restricted to some indexes can't see the entries without index, e.g. about the keys
+    let (response, code) = server
+        .add_api_key(json!({
+            "indexes": ["movies"],
+            "actions": ["auditLogs.get"],
+            "expiresAt": null,
+        }))
+        .await;
+    assert_eq!(201, code, "{:?}", response);
+    let key = response["key"].as_str().unwrap().to_string();
+    server.use_api_key(&key);
+
+    let (response, code) = server.service.get("/audit-logs").await;
+    snapshot!(code, @"200 OK");
+    let actions: Vec<_> =
+        response["results"].as_array().unwrap().iter().map(|log| &log["action"]).collect();
+    snapshot!(json_string!(actions), @r###"
+    [
+      "settings.update"
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn audit_log_max_entries() {
+    let dir = TempDir::new().unwrap();
+    let mut options = default_settings(dir.path());
+    options.audit_log_max_entries = NonZeroU64::new(2);
+    let mut server = Server::new_auth_with_options(options, dir).await;
+    server.use_api_key("MASTER_KEY");
+
+    let index = server.index("movies");
+    for genre in ["drama", "comedy", "horror"] {
+        let (response, code) =
+            index.update_settings(json!({ "filterableAttributes": [genre] })).await;
+        assert_eq!(202, code, "{:?}", response);
+    }
+
+    // only the most recent entries are kept
+    let (response, code) = server.service.get("/audit-logs?actions=settings.update").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["results"].as_array().unwrap().len(), @"2");
+}
@@ -1,7 +1,10 @@
 mod api_keys;
+mod audit_logs;
 mod authorization;
 mod errors;
+mod own_tasks;
 mod payload;
 mod tenant_token;
+mod trusted_issuers;
 
 mod tenant_token_multi_search;
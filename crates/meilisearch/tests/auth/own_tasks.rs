@@ -0,0 +1,65 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+const KEY_UID: &str = "8b9d1c3e-6a5f-4f0e-9d2c-7e4b1a0f3c5d";
+
+#[actix_rt::test]
+async fn key_with_tasks_own_only_sees_its_tasks() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let content = json!({
+        "uid": KEY_UID,
+        "indexes": ["*"],
+        "actions": ["settings.update", "tasks.own"],
+        "expiresAt": null,
+    });
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(201, code, "{:?}", response);
+    let key = response["key"].as_str().unwrap().to_string();
+
+    let (response, code) = server.index("movies").update_settings(json!({})).await;
+    assert_eq!(202, code, "{:?}", response);
+
+    server.use_api_key(&key);
+    let (response, code) = server.index("books").update_settings(json!({})).await;
+    assert_eq!(202, code, "{:?}", response);
+    let own_task = response["taskUid"].as_u64().unwrap();
+
+    let (response, code) = server.tasks().await;
+    snapshot!(code, @"200 OK");
+    let tasks: Vec<_> = response["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|task| json!({ "uid": task["uid"], "indexUid": task["indexUid"], "keyUid": task["keyUid"] }))
+        .collect();
+    snapshot!(json_string!(tasks), @r###"
+    [
+      {
+        "uid": 1,
+        "indexUid": "books",
+        "keyUid": "8b9d1c3e-6a5f-4f0e-9d2c-7e4b1a0f3c5d"
+      }
+    ]
+    "###);
+    assert_eq!(response["total"], json!(1));
+
+    // the task enqueued with the master key is hidden
+    let (_response, code) = server.get_task(0).await;
+    snapshot!(code, @"404 Not Found");
+    let (_response, code) = server.get_task(own_task).await;
+    snapshot!(code, @"200 OK");
+
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.tasks_filter(&format!("keyUids={KEY_UID}")).await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(response["total"], json!(1));
+    assert_eq!(response["results"][0]["uid"], json!(own_task));
+
+    let (response, code) = server.tasks_filter("keyUids=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_task_key_uids""###);
+}
@@ -350,6 +350,139 @@ async fn filter_search_authorized_filter_token() {
     compute_authorized_search!(tenant_tokens, "color = yellow", 1);
 }
 
+#[actix_rt::test]
+async fn search_attributes_restricted_by_token() {
+    let mut server = Server::new_auth().await;
+    server.use_admin_key("MASTER_KEY").await;
+    let index = server.index("sales");
+    let (task, _status_code) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _status_code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["color", "title"],
+            "sortableAttributes": ["color"]
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+    drop(index);
+
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server
+        .add_api_key(json!({"indexes": ["sales"], "actions": ["search"], "expiresAt": null}))
+        .await;
+    assert_eq!(code, 201);
+    let key = response["key"].as_str().unwrap();
+    let uid = response["uid"].as_str().unwrap();
+
+    let tenant_token = hashmap! {
+        "searchRules" => json!({"sales": {"hiddenAttributes": ["color"], "attributesToSearchOn": ["title"]}}),
+        "exp" => json!(null)
+    };
+    let web_token = generate_tenant_token(uid, key, tenant_token);
+    server.use_api_key(&web_token);
+    let index = server.index("sales");
+
+    // the hidden attribute can't be retrieved, highlighted, or searched on
+    index
+        .search(
+            json!({
+                "q": "captain",
+                "attributesToRetrieve": ["title", "color"],
+                "attributesToHighlight": ["*"],
+                "showMatchesPosition": true,
+                "facets": ["*"]
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{response}");
+                let hits = response["hits"].as_array().unwrap();
+                assert_eq!(hits.len(), 1, "{response}");
+                assert!(hits[0].get("color").is_none(), "{response}");
+                assert!(hits[0]["_formatted"].get("color").is_none(), "{response}");
+                assert_eq!(hits[0]["title"], json!("Captain Marvel"), "{response}");
+                assert_eq!(response["facetDistribution"], json!({}), "{response}");
+            },
+        )
+        .await;
+
+    // nor used in any other parameter of the search
+    let forbidden_queries = [
+        (
+            json!({ "q": "yellow", "attributesToSearchOn": ["color"] }),
+            "attributesToSearchOn",
+            "invalid_search_attributes_to_search_on",
+        ),
+        (json!({ "filter": "color = yellow" }), "filter", "invalid_search_filter"),
+        (
+            json!({ "filter": ["title = Gläss", ["color = yellow"]] }),
+            "filter",
+            "invalid_search_filter",
+        ),
+        (json!({ "sort": ["color:asc"] }), "sort", "invalid_search_sort"),
+        (json!({ "distinct": "color" }), "distinct", "invalid_search_distinct"),
+        (json!({ "facets": ["color"] }), "facets", "invalid_search_facets"),
+    ];
+    for (query, parameter, error_code) in forbidden_queries {
+        index
+            .search(query.clone(), |response, code| {
+                assert_eq!(code, 400, "{query}: {response}");
+                assert_eq!(
+                    response["message"],
+                    json!(format!("Attribute `color` is hidden by the search rules and cannot be used in `{parameter}`.")),
+                    "{query}: {response}"
+                );
+                assert_eq!(response["code"], json!(error_code), "{query}: {response}");
+            })
+            .await;
+    }
+}
+
+#[actix_rt::test]
+async fn get_documents_filtered_by_token() {
+    let mut server = Server::new_auth().await;
+    server.use_admin_key("MASTER_KEY").await;
+    let index = server.index("sales");
+    let (task, _status_code) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _status_code) =
+        index.update_settings(json!({"filterableAttributes": ["color"]})).await;
+    index.wait_task(task.uid()).await.succeeded();
+    drop(index);
+
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server
+        .add_api_key(json!({"indexes": ["sales"], "actions": ["documents.get"], "expiresAt": null}))
+        .await;
+    assert_eq!(code, 201);
+    let key = response["key"].as_str().unwrap();
+    let uid = response["uid"].as_str().unwrap();
+
+    let tenant_token = hashmap! {
+        "searchRules" => json!({"sales": {"filter": "color = yellow"}}),
+        "exp" => json!(null)
+    };
+    let web_token = generate_tenant_token(uid, key, tenant_token);
+    server.use_api_key(&web_token);
+    let index = server.index("sales");
+
+    let (response, code) = index.get_document(299537, None).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["title"], json!("Captain Marvel"), "{response}");
+
+    // a document filtered out by the token is reported as missing
+    let (response, code) = index.get_document(287947, None).await;
+    assert_eq!(code, 404, "{response}");
+    assert_eq!(response["code"], json!("document_not_found"), "{response}");
+
+    let (response, code) = index.get_all_documents_raw("").await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["total"], json!(2), "{response}");
+
+    let (response, code) = index.fetch_documents(json!({"filter": "color = red"})).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["total"], json!(1), "{response}");
+    assert_eq!(response["results"][0]["title"], json!("Escape Room"), "{response}");
+}
+
 /// Tests that those Tenant Token are incompatible with the REFUSED_KEYS defined above.
 #[actix_rt::test]
 async fn error_search_token_forbidden_parent_key() {
@@ -467,7 +600,7 @@ async fn error_access_forbidden_routes() {
     server.use_api_key(&web_token);
 
     for ((method, route), actions) in AUTHORIZATIONS.iter() {
-        if !actions.contains("search") {
+        if !actions.contains("search") && !actions.contains("documents.get") {
             let (mut response, code) = server.dummy_request(method, route).await;
             response["message"] = serde_json::json!(null);
             assert_eq!(response, INVALID_RESPONSE.clone());
@@ -566,3 +699,104 @@ async fn error_access_modified_token() {
     assert_eq!(response, INVALID_RESPONSE.clone());
     assert_eq!(code, 403);
 }
+
+#[actix_rt::test]
+async fn access_rotated_parent_key() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let content = json!({
+        "indexes": ["*"],
+        "actions": ["search"],
+        "expiresAt": null,
+    });
+
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(code, 201);
+    let key = response["key"].as_str().unwrap().to_string();
+    let uid = response["uid"].as_str().unwrap().to_string();
+
+    let tenant_token = hashmap! {
+        "searchRules" => json!(["*"]),
+        "exp" => json!((OffsetDateTime::now_utc() + Duration::hours(1)).unix_timestamp())
+    };
+    let web_token = generate_tenant_token(&uid, &key, tenant_token);
+
+    // the tokens signed with the previous value are accepted during the grace period
+    let (_response, code) = server.rotate_api_key(&uid, json!({ "gracePeriod": 3600 })).await;
+    assert_eq!(code, 200);
+    server.use_api_key(&web_token);
+    let (response, code) = server.dummy_request("POST", "/indexes/products/search").await;
+    assert_ne!(response, INVALID_RESPONSE.clone());
+    assert_ne!(code, 403);
+
+    server.use_api_key("MASTER_KEY");
+    let (_response, code) = server.rotate_api_key(&uid, json!({})).await;
+    assert_eq!(code, 200);
+    server.use_api_key(&web_token);
+    let (mut response, code) = server.dummy_request("POST", "/indexes/products/search").await;
+    response["message"] = serde_json::json!(null);
+    assert_eq!(response, INVALID_RESPONSE.clone());
+    assert_eq!(code, 403);
+}
+
+#[actix_rt::test]
+async fn search_suggestions_restricted_by_token() {
+    let mut server = Server::new_auth().await;
+    server.use_admin_key("MASTER_KEY").await;
+    let index = server.index("sales");
+    let documents = json!([
+        { "id": 0, "title": "quick fox", "color": "red", "secret": "quack" },
+        { "id": 1, "title": "quack duck", "color": "blue" },
+        { "id": 2, "title": "quick cat", "color": "red" },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _status_code) =
+        index.update_settings(json!({"filterableAttributes": ["color", "id"]})).await;
+    index.wait_task(task.uid()).await.succeeded();
+    drop(index);
+
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server
+        .add_api_key(json!({"indexes": ["sales"], "actions": ["search"], "expiresAt": null}))
+        .await;
+    assert_eq!(code, 201);
+    let key = response["key"].as_str().unwrap();
+    let uid = response["uid"].as_str().unwrap();
+
+    let tenant_token = hashmap! {
+        "searchRules" => json!({"sales": {"filter": "color = red", "hiddenAttributes": ["secret"]}}),
+        "exp" => json!(null)
+    };
+    let web_token = generate_tenant_token(uid, key, tenant_token);
+    server.use_api_key(&web_token);
+    let index = server.index("sales");
+
+    // `quack` is only in a document outside the filter and in a hidden attribute
+    index
+        .search(json!({ "q": "quock", "suggestions": true }), |response, code| {
+            assert_eq!(code, 200, "{response}");
+            assert_eq!(
+                response["suggestions"],
+                json!([{ "query": "quick", "estimatedTotalHits": 2 }]),
+                "{response}"
+            );
+        })
+        .await;
+
+    // the filter of the query restricts the suggestions too
+    index
+        .search(
+            json!({ "q": "quock", "suggestions": true, "filter": "id = 2" }),
+            |response, code| {
+                assert_eq!(code, 200, "{response}");
+                assert_eq!(
+                    response["suggestions"],
+                    json!([{ "query": "quick", "estimatedTotalHits": 1 }]),
+                    "{response}"
+                );
+            },
+        )
+        .await;
+}
@@ -294,6 +294,21 @@ impl Index<'_, Shared> {
 
 #[allow(dead_code)]
 impl<State> Index<'_, State> {
+    pub async fn clone_index(&self, new_uid: &str) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/clone", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, json!({ "uid": new_uid }), self.encoder).await
+    }
+
+    pub async fn export_index(&self) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/export", urlencode(self.uid.as_ref()));
+        self.service.post(url, json!(null)).await
+    }
+
+    pub async fn import_index(&self, dump_uid: &str) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/import", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, json!({ "dumpUid": dump_uid }), self.encoder).await
+    }
+
     pub async fn get(&self) -> (Value, StatusCode) {
         let url = format!("/indexes/{}", urlencode(self.uid.as_ref()));
         self.service.get(url).await
@@ -520,6 +535,11 @@ impl<State> Index<'_, State> {
         self.service.post_encoded(url, query, self.encoder).await
     }
 
+    pub async fn autocomplete(&self, query: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/autocomplete", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, query, self.encoder).await
+    }
+
     pub async fn get_distinct_attribute(&self) -> (Value, StatusCode) {
         let url =
             format!("/indexes/{}/settings/{}", urlencode(self.uid.as_ref()), "distinct-attribute");
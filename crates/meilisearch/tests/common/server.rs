@@ -111,6 +111,15 @@ impl Server<Owned> {
         self.service.patch(url, content).await
     }
 
+    pub async fn rotate_api_key(
+        &self,
+        key: impl AsRef<str>,
+        content: Value,
+    ) -> (Value, StatusCode) {
+        let url = format!("/keys/{}/rotate", key.as_ref());
+        self.service.post(url, content).await
+    }
+
     pub async fn delete_api_key(&self, key: impl AsRef<str>) -> (Value, StatusCode) {
         let url = format!("/keys/{}", key.as_ref());
         self.service.delete(url).await
@@ -388,6 +397,14 @@ impl<State> Server<State> {
         self.service.post("/swap-indexes", value).await
     }
 
+    pub async fn update_aliases(&self, value: Value) -> (Value, StatusCode) {
+        self.service.post("/aliases", value).await
+    }
+
+    pub async fn aliases(&self) -> (Value, StatusCode) {
+        self.service.get("/aliases").await
+    }
+
     pub async fn cancel_tasks(&self, value: &str) -> (Value, StatusCode) {
         self.service.post(format!("/tasks/cancel?{}", value), json!(null)).await
     }
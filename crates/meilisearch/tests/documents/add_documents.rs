@@ -2986,3 +2986,77 @@ async fn dry_register_file() {
     "###);
     snapshot!(code, @"404 Not Found");
 }
+
+#[actix_rt::test]
+async fn add_encrypted_documents() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt { encryption_key: Some("ab".repeat(32)), ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("pets");
+
+    let (response, code) = index
+        .raw_add_documents(
+            r#"[{ "id": 0, "doggo": "kefir" }]"#,
+            vec![("Content-Type", "application/json")],
+            "",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(response.uid()).await.succeeded();
+    let (response, code) = index
+        .raw_add_documents(
+            r#"{ "id": 1, "doggo": "intel" }"#,
+            vec![("Content-Type", "application/x-ndjson")],
+            "",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(response.uid()).await.succeeded();
+    let (response, code) = index
+        .raw_add_documents("id,doggo\n2,bouvier", vec![("Content-Type", "text/csv")], "")
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(response.uid()).await.succeeded();
+
+    let (documents, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 0,
+        "doggo": "kefir"
+      },
+      {
+        "id": 1,
+        "doggo": "intel"
+      },
+      {
+        "id": "2",
+        "doggo": "bouvier"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn add_encrypted_documents_larger_than_the_indexing_memory() {
+    let temp = tempfile::tempdir().unwrap();
+    let mut options =
+        Opt { encryption_key: Some("ab".repeat(32)), ..default_settings(temp.path()) };
+    options.indexer_options.max_indexing_memory = "1 KiB".parse().unwrap();
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("pets");
+
+    // the encrypted update files are decrypted in memory to be indexed
+    let (response, code) =
+        index.add_documents(json!([{ "id": 0, "doggo": "kefir".repeat(300) }]), None).await;
+    snapshot!(code, @"413 Payload Too Large");
+    snapshot!(response, @r###"
+    {
+      "message": "The update file of 1519 bytes can't be decrypted in memory as it exceeds the maximum indexing memory of 1024 bytes.",
+      "code": "payload_too_large",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#payload_too_large"
+    }
+    "###);
+}
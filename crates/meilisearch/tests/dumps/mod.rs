@@ -103,7 +103,9 @@ async fn import_dump_v1_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "ngramAttributes": [],
+      "queryRules": []
     }
     "###
     );
@@ -277,7 +279,9 @@ async fn import_dump_v1_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "ngramAttributes": [],
+      "queryRules": []
     }
     "###
     );
@@ -450,7 +454,9 @@ async fn import_dump_v1_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "ngramAttributes": [],
+      "queryRules": []
     }
     "###
     );
@@ -609,7 +615,9 @@ async fn import_dump_v2_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "ngramAttributes": [],
+      "queryRules": []
     }
     "###
     );
@@ -780,7 +788,9 @@ async fn import_dump_v2_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "ngramAttributes": [],
+      "queryRules": []
     }
     "###
     );
@@ -950,7 +960,9 @@ async fn import_dump_v2_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "ngramAttributes": [],
+      "queryRules": []
     }
     "###
     );
@@ -1109,7 +1121,9 @@ async fn import_dump_v3_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "ngramAttributes": [],
+      "queryRules": []
     }
     "###
     );
@@ -1280,7 +1294,9 @@ async fn import_dump_v3_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "ngramAttributes": [],
+      "queryRules": []
     }
     "###
     );
@@ -1450,7 +1466,9 @@ async fn import_dump_v3_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "ngramAttributes": [],
+      "queryRules": []
     }
     "###
     );
@@ -1609,7 +1627,9 @@ async fn import_dump_v4_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "ngramAttributes": [],
+      "queryRules": []
     }
     "###
     );
@@ -1780,7 +1800,9 @@ async fn import_dump_v4_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "ngramAttributes": [],
+      "queryRules": []
     }
     "###
     );
@@ -1950,7 +1972,9 @@ async fn import_dump_v4_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "ngramAttributes": [],
+      "queryRules": []
     }
     "###
     );
@@ -2240,7 +2264,9 @@ async fn import_dump_v6_containing_experimental_features() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "ngramAttributes": [],
+      "queryRules": []
     }
     "###);
 
@@ -2482,7 +2508,9 @@ async fn generate_and_import_dump_containing_vectors() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "ngramAttributes": [],
+      "queryRules": []
     }
     "###);
 
@@ -2546,3 +2574,48 @@ async fn generate_and_import_dump_containing_vectors() {
         })
         .await;
 }
+
+#[actix_rt::test]
+#[cfg_attr(target_os = "windows", ignore)]
+async fn generate_and_import_encrypted_dump() {
+    let temp = tempfile::tempdir().unwrap();
+    let mut opt = Opt { encryption_key: Some("ab".repeat(32)), ..default_settings(temp.path()) };
+    let server = Server::new_with_options(opt.clone()).await.unwrap();
+
+    let index = server.index("pets");
+    let (response, code) =
+        index.add_documents(json!([{ "id": 0, "doggo": "kefir" }]), Some("id")).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = server.create_dump().await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await.succeeded();
+
+    drop(server);
+    tokio::fs::remove_dir_all(&opt.db_path).await.unwrap();
+    let dump_name = format!("{}.dump", response["details"]["dumpUid"].as_str().unwrap());
+    let dump_path = opt.dump_dir.join(dump_name);
+    let content = std::fs::read(&dump_path).unwrap();
+    assert!(content.starts_with(b"MEILIENC"));
+    assert!(!content.windows(5).any(|window| window == b"kefir"));
+
+    // the dump can't be imported without the key
+    opt.import_dump = Some(dump_path);
+    let without_key =
+        Opt { encryption_key: None, db_path: temp.path().join("without-key"), ..opt.clone() };
+    assert!(Server::new_with_options(without_key).await.is_err());
+
+    let server = Server::new_with_options(opt).await.unwrap();
+    let (response, code) =
+        server.index("pets").get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 0,
+        "doggo": "kefir"
+      }
+    ]
+    "###);
+}
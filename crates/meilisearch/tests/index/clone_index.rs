@@ -0,0 +1,82 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::{shared_does_not_exists_index, GetAllDocumentsOptions, Server};
+use crate::json;
+
+#[actix_rt::test]
+async fn clone_index() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let (task, code) = index.add_documents(json!([{ "id": 1, "title": "Carol" }]), None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _code) = index.update_settings(json!({ "searchableAttributes": ["title"] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let copy = server.unique_index();
+    let (task, code) = index.clone_index(&copy.uid).await;
+    snapshot!(code, @"202 Accepted");
+    let task = index.wait_task(task.uid()).await.succeeded();
+    assert_eq!(task["type"], "indexClone");
+    assert_eq!(task["indexUid"], index.uid.as_str());
+    assert_eq!(task["details"]["newIndexUid"], copy.uid.as_str());
+
+    let (response, code) = copy.get().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["primaryKey"], @r###""id""###);
+    let (response, code) = copy.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "title": "Carol"
+      }
+    ]
+    "###);
+    let (response, _code) = copy.settings().await;
+    snapshot!(json_string!(response["searchableAttributes"]), @r###"
+    [
+      "title"
+    ]
+    "###);
+
+    // The clone lives its own life
+    let (task, _code) = copy.add_documents(json!([{ "id": 2, "title": "Wall-E" }]), None).await;
+    copy.wait_task(task.uid()).await.succeeded();
+    let (response, _code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(response["total"], @"1");
+    let (response, _code) = copy.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(response["total"], @"2");
+
+    // Cloning into an existing index fails
+    let (task, code) = index.clone_index(&copy.uid).await;
+    snapshot!(code, @"202 Accepted");
+    let task = index.wait_task(task.uid()).await.failed();
+    snapshot!(task["error"]["code"], @r###""index_already_exists""###);
+}
+
+#[actix_rt::test]
+async fn clone_unexisting_index() {
+    let index = shared_does_not_exists_index().await;
+    let (task, code) = index.clone_index("DOES_NOT_EXISTS_EITHER").await;
+    snapshot!(code, @"202 Accepted");
+    let task = index.wait_task(task.uid()).await.failed();
+    snapshot!(json_string!(task["error"]), @r###"
+    {
+      "message": "Index `DOES_NOT_EXISTS` not found.",
+      "code": "index_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#index_not_found"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn clone_index_bad_uid() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let (response, code) = index.clone_index("the\\best\\index").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_index_uid""###);
+}
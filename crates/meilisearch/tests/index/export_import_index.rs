@@ -0,0 +1,141 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::{GetAllDocumentsOptions, Server};
+use crate::json;
+
+#[actix_rt::test]
+async fn export_and_import_index() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let (task, code) = index.add_documents(json!([{ "id": 1, "title": "Carol" }]), None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _code) = index.update_settings(json!({ "searchableAttributes": ["title"] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, code) = index.export_index().await;
+    snapshot!(code, @"202 Accepted");
+    let task = index.wait_task(task.uid()).await.succeeded();
+    assert_eq!(task["type"], "indexExport");
+    let dump_uid = task["details"]["dumpUid"].as_str().unwrap();
+    assert!(dump_uid.starts_with(index.uid.as_str()));
+
+    // Import the dump in a new index
+    let copy = server.unique_index();
+    let (task, code) = copy.import_index(dump_uid).await;
+    snapshot!(code, @"202 Accepted");
+    let task = copy.wait_task(task.uid()).await.succeeded();
+    assert_eq!(task["type"], "indexImport");
+    assert_eq!(task["details"]["dumpUid"], dump_uid);
+    snapshot!(task["details"]["indexedDocuments"], @"1");
+
+    let (response, code) = copy.get().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["primaryKey"], @r###""id""###);
+    let (response, _code) = copy.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "title": "Carol"
+      }
+    ]
+    "###);
+    let (response, _code) = copy.settings().await;
+    snapshot!(json_string!(response["searchableAttributes"]), @r###"
+    [
+      "title"
+    ]
+    "###);
+
+    // Import the dump in an existing index, the documents with the same id are replaced
+    let (task, _code) = copy
+        .add_documents(
+            json!([{ "id": 1, "title": "Wall-E" }, { "id": 2, "title": "Shazam!" }]),
+            None,
+        )
+        .await;
+    copy.wait_task(task.uid()).await.succeeded();
+    let (task, _code) = copy.import_index(dump_uid).await;
+    copy.wait_task(task.uid()).await.succeeded();
+    let (response, _code) = copy.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "title": "Carol"
+      },
+      {
+        "id": 2,
+        "title": "Shazam!"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn import_unexisting_dump() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let (task, code) = index.import_index("does-not-exist").await;
+    snapshot!(code, @"202 Accepted");
+    let task = index.wait_task(task.uid()).await.failed();
+    snapshot!(json_string!(task["error"]), @r###"
+    {
+      "message": "Dump `does-not-exist` not found.",
+      "code": "dump_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#dump_not_found"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn import_index_bad_dump_uid() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let (response, code) = index.import_index("../data.ms/data").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.dumpUid`: `../data.ms/data` is not a valid dump uid. A dump uid can only contain alphanumeric characters, hyphens (-) and underscores (_).",
+      "code": "invalid_index_import_dump_uid",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_index_import_dump_uid"
+    }
+    "###);
+
+    let (response, code) =
+        index.service.post(format!("/indexes/{}/import", index.uid), json!({})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""missing_index_import_dump_uid""###);
+}
+
+#[actix_rt::test]
+async fn import_index_requires_access_to_all_indexes() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server
+        .add_api_key(json!({
+            "actions": ["documents.*", "dumps.create", "indexes.create"],
+            "indexes": ["tenant-*"],
+            "expiresAt": null,
+        }))
+        .await;
+    snapshot!(code, @"201 Created");
+    let key = response["key"].as_str().unwrap();
+
+    // the dump of any other tenant could be imported in an index of the key
+    server.use_api_key(key);
+    let index = server.index("tenant-copy");
+    let (response, code) = index.import_index("20240808-170555791").await;
+    snapshot!(code, @"403 Forbidden");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The provided API key is invalid.",
+      "code": "invalid_api_key",
+      "type": "auth",
+      "link": "https://docs.meilisearch.com/errors#invalid_api_key"
+    }
+    "###);
+}
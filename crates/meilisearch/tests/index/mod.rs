@@ -1,6 +1,8 @@
+mod clone_index;
 mod create_index;
 mod delete_index;
 mod errors;
+mod export_import_index;
 mod get_index;
 mod stats;
 mod update_index;
@@ -1,3 +1,4 @@
+mod aliases;
 mod auth;
 mod batches;
 mod common;
@@ -8,6 +9,7 @@ mod features;
 mod index;
 mod logs;
 mod network;
+mod s3;
 mod search;
 mod settings;
 mod similar;
@@ -0,0 +1,276 @@
+use index_scheduler::s3::{Error, S3Client, S3Options, MIN_MULTIPART_PART_SIZE};
+use sha2::{Digest, Sha256};
+use wiremock::matchers::{body_string, method, path, query_param};
+use wiremock::{Match, Mock, MockServer, Request, ResponseTemplate};
+
+const ACCESS_KEY: &str = "ACCESS_KEY";
+const SECRET_KEY: &str = "SECRET_KEY";
+const REGION: &str = "eu-west-3";
+
+fn client(server: &MockServer, retention: Option<usize>) -> S3Client {
+    S3Client::new(S3Options {
+        bucket_url: server.uri(),
+        bucket_region: REGION.to_string(),
+        bucket_name: "bucket".to_string(),
+        access_key: ACCESS_KEY.to_string(),
+        secret_key: SECRET_KEY.to_string(),
+        prefix: "backups".to_string(),
+        multipart_part_size: MIN_MULTIPART_PART_SIZE,
+        retention,
+    })
+}
+
+/// Matches the requests carrying a valid AWS Signature V4, recomputed from what the server received.
+struct ValidSignature;
+
+impl Match for ValidSignature {
+    fn matches(&self, request: &Request) -> bool {
+        let header = |name: &str| request.headers.get(name).and_then(|v| v.to_str().ok());
+        let (Some(host), Some(amz_date), Some(payload_hash), Some(authorization)) = (
+            header("host"),
+            header("x-amz-date"),
+            header("x-amz-content-sha256"),
+            header("authorization"),
+        ) else {
+            return false;
+        };
+        if payload_hash != hex(&Sha256::digest(&request.body)) {
+            return false;
+        }
+
+        let mut query: Vec<_> =
+            request.url.query().unwrap_or_default().split('&').filter(|s| !s.is_empty()).collect();
+        query.sort_unstable();
+        let canonical_request = format!(
+            "{}\n{}\n{}\nhost:{host}\nx-amz-content-sha256:{payload_hash}\nx-amz-date:{amz_date}\n\nhost;x-amz-content-sha256;x-amz-date\n{payload_hash}",
+            request.method.as_str(),
+            request.url.path(),
+            query.join("&"),
+        );
+        let date = &amz_date[..8];
+        let scope = format!("{date}/{REGION}/s3/aws4_request");
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+        let signing_key = [date, REGION, "s3", "aws4_request"]
+            .iter()
+            .fold(format!("AWS4{SECRET_KEY}").into_bytes(), |key, data| {
+                hmac_sha256(&key, data.as_bytes())
+            });
+        let signature = hex(&hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        authorization
+            == format!(
+                "AWS4-HMAC-SHA256 Credential={ACCESS_KEY}/{scope}, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature={signature}"
+            )
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut block = [0u8; 64];
+    block[..key.len()].copy_from_slice(key);
+    let inner: Vec<u8> = block.iter().map(|b| b ^ 0x36).chain(data.iter().copied()).collect();
+    let inner = Sha256::digest(inner);
+    let outer: Vec<u8> = block.iter().map(|b| b ^ 0x5c).chain(inner).collect();
+    Sha256::digest(outer).to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+async fn mount_create_upload(server: &MockServer, key: &str) {
+    Mock::given(method("POST"))
+        .and(path(format!("/bucket/{key}")))
+        .and(query_param("uploads", ""))
+        .and(ValidSignature)
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            "<InitiateMultipartUploadResult><UploadId>upload-id</UploadId></InitiateMultipartUploadResult>",
+        ))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+async fn mount_upload_part(server: &MockServer, key: &str, part_number: &str, status: u16) {
+    Mock::given(method("PUT"))
+        .and(path(format!("/bucket/{key}")))
+        .and(query_param("partNumber", part_number))
+        .and(query_param("uploadId", "upload-id"))
+        .and(ValidSignature)
+        .respond_with(
+            ResponseTemplate::new(status).insert_header("ETag", format!("\"etag-{part_number}\"")),
+        )
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+#[actix_rt::test]
+async fn multipart_upload_is_completed() {
+    let server = MockServer::start().await;
+    let key = "snapshots/data.ms.snapshot";
+    mount_create_upload(&server, key).await;
+    mount_upload_part(&server, key, "1", 200).await;
+    mount_upload_part(&server, key, "2", 200).await;
+    Mock::given(method("POST"))
+        .and(path(format!("/bucket/{key}")))
+        .and(query_param("uploadId", "upload-id"))
+        .and(body_string(
+            "<CompleteMultipartUpload><Part><PartNumber>1</PartNumber><ETag>\"etag-1\"</ETag></Part><Part><PartNumber>2</PartNumber><ETag>\"etag-2\"</ETag></Part></CompleteMultipartUpload>",
+        ))
+        .and(ValidSignature)
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            "<CompleteMultipartUploadResult><Key>snapshots/data.ms.snapshot</Key></CompleteMultipartUploadResult>",
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // one full part and a smaller last one
+    let content = vec![b'a'; MIN_MULTIPART_PART_SIZE as usize + 10];
+    let client = client(&server, None);
+    tokio::task::spawn_blocking(move || client.upload(key, content.as_slice()))
+        .await
+        .unwrap()
+        .unwrap();
+}
+
+#[actix_rt::test]
+async fn multipart_upload_is_aborted_on_failure() {
+    let server = MockServer::start().await;
+    let key = "dumps/20250101-000000.dump";
+    mount_create_upload(&server, key).await;
+    mount_upload_part(&server, key, "1", 200).await;
+    mount_upload_part(&server, key, "2", 500).await;
+    Mock::given(method("DELETE"))
+        .and(path(format!("/bucket/{key}")))
+        .and(query_param("uploadId", "upload-id"))
+        .and(ValidSignature)
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+    // the upload must never be completed
+    Mock::given(method("POST"))
+        .and(query_param("uploadId", "upload-id"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let content = vec![b'a'; MIN_MULTIPART_PART_SIZE as usize + 10];
+    let client = client(&server, None);
+    let error = tokio::task::spawn_blocking(move || client.upload(key, content.as_slice()))
+        .await
+        .unwrap()
+        .unwrap_err();
+    assert!(matches!(error, Error::Response { status: 500, .. }), "{error}");
+}
+
+#[actix_rt::test]
+async fn completion_error_in_a_success_response() {
+    let server = MockServer::start().await;
+    let key = "dumps/20250101-000000.dump";
+    mount_create_upload(&server, key).await;
+    mount_upload_part(&server, key, "1", 200).await;
+    Mock::given(method("POST"))
+        .and(path(format!("/bucket/{key}")))
+        .and(query_param("uploadId", "upload-id"))
+        .and(ValidSignature)
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string("<Error><Code>InternalError</Code></Error>"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server, None);
+    let error = tokio::task::spawn_blocking(move || client.upload(key, &b"dump"[..]))
+        .await
+        .unwrap()
+        .unwrap_err();
+    assert!(matches!(error, Error::InvalidResponse(_)), "{error}");
+}
+
+#[actix_rt::test]
+async fn retention_deletes_the_oldest_dumps() {
+    let server = MockServer::start().await;
+    let key = "backups/dumps/20250103-000000.dump";
+    mount_create_upload(&server, key).await;
+    mount_upload_part(&server, key, "1", 200).await;
+    Mock::given(method("POST"))
+        .and(path(format!("/bucket/{key}")))
+        .and(query_param("uploadId", "upload-id"))
+        .and(ValidSignature)
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/bucket/"))
+        .and(query_param("list-type", "2"))
+        .and(query_param("prefix", "backups/dumps/"))
+        .and(ValidSignature)
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            "<ListBucketResult><IsTruncated>false</IsTruncated>\
+             <Contents><Key>backups/dumps/20250102-000000.dump</Key></Contents>\
+             <Contents><Key>backups/dumps/20250103-000000.dump</Key></Contents>\
+             <Contents><Key>backups/dumps/20250101-000000.dump</Key></Contents>\
+             </ListBucketResult>",
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/bucket/backups/dumps/20250101-000000.dump"))
+        .and(ValidSignature)
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+    // the two most recent dumps are kept
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = client(&server, Some(2));
+    tokio::task::spawn_blocking(move || client.upload_dump("20250103-000000.dump", &b"dump"[..]))
+        .await
+        .unwrap()
+        .unwrap();
+}
+
+#[actix_rt::test]
+async fn download_an_object() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/bucket/dumps/a.dump"))
+        .and(ValidSignature)
+        .respond_with(ResponseTemplate::new(200).set_body_string("dump content"))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/bucket/dumps/missing.dump"))
+        .respond_with(
+            ResponseTemplate::new(404).set_body_string("<Error><Code>NoSuchKey</Code></Error>"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server, None);
+    let (content, missing) = tokio::task::spawn_blocking(move || {
+        let mut content = Vec::new();
+        client.download("dumps/a.dump", &mut content).unwrap();
+        (content, client.download("dumps/missing.dump", Vec::new()))
+    })
+    .await
+    .unwrap();
+    assert_eq!(content, b"dump content");
+    assert!(matches!(missing, Err(Error::ObjectNotFound(_))), "{missing:?}");
+}
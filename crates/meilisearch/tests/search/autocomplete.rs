@@ -0,0 +1,150 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::{Index, Server, Shared};
+use crate::json;
+
+async fn products_index(server: &Server<Shared>) -> Index<'_> {
+    let index = server.unique_index();
+
+    let (task, _code) = index.update_settings(json!({ "filterableAttributes": ["brand"] })).await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 0, "brand": "apple", "title": "iphone 15 pro" },
+        { "id": 1, "brand": "apple", "title": "iphone 15 pro max" },
+        { "id": 2, "brand": "apple", "title": "iphone 14" },
+        { "id": 3, "brand": "apple", "title": "ipad air" },
+        { "id": 4, "brand": "samsung", "title": "galaxy phone" },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    index
+}
+
+#[actix_rt::test]
+async fn autocomplete() {
+    let server = Server::new_shared();
+    let index = products_index(server).await;
+
+    let (response, code) = index.autocomplete(json!({"q": "iph", "limit": 3})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".processingTimeMs" => "[duration]" }), @r###"
+    {
+      "completions": [
+        {
+          "value": "iphone",
+          "count": 3
+        },
+        {
+          "value": "iphone 15",
+          "count": 2
+        },
+        {
+          "value": "iphone 15 pro",
+          "count": 2
+        }
+      ],
+      "query": "iph",
+      "processingTimeMs": "[duration]"
+    }
+    "###);
+
+    // the beginning of the query is kept as typed
+    let (response, code) = index.autocomplete(json!({"q": "Iphone 15 p"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["completions"]), @r###"
+    [
+      {
+        "value": "Iphone 15 pro",
+        "count": 2
+      },
+      {
+        "value": "Iphone 15 pro max",
+        "count": 1
+      }
+    ]
+    "###);
+
+    // a typo is tolerated on the last term
+    let (response, code) = index.autocomplete(json!({"q": "galaxu"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["completions"]), @r###"
+    [
+      {
+        "value": "galaxy",
+        "count": 1
+      },
+      {
+        "value": "galaxy phone",
+        "count": 1
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn autocomplete_with_filter() {
+    let server = Server::new_shared();
+    let index = products_index(server).await;
+
+    let (response, code) =
+        index.autocomplete(json!({"q": "i", "filter": "brand = apple", "limit": 2})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["completions"]), @r###"
+    [
+      {
+        "value": "iphone",
+        "count": 3
+      },
+      {
+        "value": "iphone 15",
+        "count": 2
+      }
+    ]
+    "###);
+
+    let (response, code) = index.autocomplete(json!({"q": "i", "filter": "brand = samsung"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["completions"]), @"[]");
+}
+
+#[actix_rt::test]
+async fn autocomplete_bad_parameters() {
+    let server = Server::new_shared();
+    let index = products_index(server).await;
+
+    let (response, code) = index.autocomplete(json!({"limit": 2})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `q`",
+      "code": "missing_autocomplete_q",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_autocomplete_q"
+    }
+    "###);
+
+    let (response, code) = index.autocomplete(json!({"q": "iph", "limit": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.limit`: expected a positive integer, but found a string: `\"doggo\"`",
+      "code": "invalid_autocomplete_limit",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_autocomplete_limit"
+    }
+    "###);
+
+    let (response, code) =
+        index.autocomplete(json!({"q": "iph", "filter": "title = iphone"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attribute `title` is not filterable. Available filterable attribute patterns are: `brand`.\n1:6 title = iphone",
+      "code": "invalid_autocomplete_filter",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_autocomplete_filter"
+    }
+    "###);
+}
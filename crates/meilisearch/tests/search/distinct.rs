@@ -304,3 +304,70 @@ async fn distinct_at_search_time() {
     snapshot!(response["totalPages"], @"1");
     snapshot!(response["totalHits"], @"3");
 }
+
+#[actix_rt::test]
+async fn collapse_search_with_pagination() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, Some(DOCUMENT_PRIMARY_KEY)).await;
+    let (task, _) =
+        index.update_settings_filterable_attributes(json!([DOCUMENT_DISTINCT_KEY])).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    /// The id of each hit along with the number of documents of its group and the ids of its inner hits.
+    fn get_groups(response: &Value) -> Vec<(u64, u64, Vec<u64>)> {
+        let hits_array = response["hits"]
+            .as_array()
+            .unwrap_or_else(|| panic!("{}", &serde_json::to_string_pretty(&response).unwrap()));
+        hits_array
+            .iter()
+            .map(|h| {
+                let inner_hits = &h["_innerHits"];
+                let ids = inner_hits["hits"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|h| h[DOCUMENT_PRIMARY_KEY].as_u64().unwrap())
+                    .collect();
+                (
+                    h[DOCUMENT_PRIMARY_KEY].as_u64().unwrap(),
+                    inner_hits["totalHits"].as_u64().unwrap(),
+                    ids,
+                )
+            })
+            .collect::<Vec<_>>()
+    }
+
+    let (response, code) = index
+        .search_post(json!({
+            "page": 1,
+            "hitsPerPage": 2,
+            "collapse": { "field": DOCUMENT_DISTINCT_KEY, "innerHits": 2 }
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(format!("{:?}", get_groups(&response)), @"[(1, 3, [1, 2]), (4, 2, [4, 5])]");
+    snapshot!(response["page"], @"1");
+    snapshot!(response["totalPages"], @"3");
+    snapshot!(response["totalHits"], @"6");
+
+    let (response, code) = index
+        .search_post(json!({
+            "page": 2,
+            "hitsPerPage": 2,
+            "collapse": { "field": DOCUMENT_DISTINCT_KEY, "innerHits": 2 }
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(format!("{:?}", get_groups(&response)), @"[(6, 2, [6, 7]), (8, 1, [8])]");
+    snapshot!(response["page"], @"2");
+    snapshot!(response["totalPages"], @"3");
+    snapshot!(response["totalHits"], @"6");
+
+    let (response, code) =
+        index.search_get(&format!("?collapseField={DOCUMENT_DISTINCT_KEY}&limit=1")).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(format!("{:?}", get_groups(&response)), @"[(1, 3, [1, 2, 3])]");
+}
@@ -636,6 +636,179 @@ async fn search_bad_matching_strategy() {
     "###);
 }
 
+#[actix_rt::test]
+async fn search_bad_query_syntax() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"querySyntax": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `doggo` at `.querySyntax`: expected one of `simple`, `advanced`",
+      "code": "invalid_search_query_syntax",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_query_syntax"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?querySyntax=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `doggo` for parameter `querySyntax`: expected one of `simple`, `advanced`",
+      "code": "invalid_search_query_syntax",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_query_syntax"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_suggestions() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"suggestions": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.suggestions`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_suggestions",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_suggestions"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?suggestions=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `suggestions`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_suggestions",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_suggestions"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_explain() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"explain": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.explain`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_explain",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_explain"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?explain=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `explain`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_explain",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_explain"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"explainDocuments": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.explainDocuments`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_search_explain_documents",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_explain_documents"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_collapse() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"collapse": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.collapse`: expected an object, but found a string: `\"doggo\"`",
+      "code": "invalid_search_collapse",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_collapse"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"collapse": {"innerHits": 2}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `field` inside `.collapse`",
+      "code": "invalid_search_collapse",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_collapse"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?collapseInnerHits=2").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`collapseField` is mandatory when `collapseInnerHits` is present",
+      "code": "invalid_search_collapse",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_collapse"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?collapseField=id&collapseInnerHits=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `collapseInnerHits`: could not parse `doggo` as a positive integer",
+      "code": "invalid_search_collapse",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_collapse"
+    }
+    "###);
+
+    // the index must exist for the search parameters to be checked together
+    let (task, _code) = index.create(None).await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) =
+        index.search_post(json!({"collapse": {"field": "id"}, "distinct": "id"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`collapse` cannot be used along `distinct`, the hits are already distinct by the collapse field.",
+      "code": "invalid_search_collapse",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_collapse"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"collapse": {"field": "id", "innerHits": 101}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`collapse.innerHits` cannot be greater than 100, received 101.",
+      "code": "invalid_search_collapse",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_collapse"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn filter_invalid_syntax_object() {
     test_settings_documents_indexing_swapping_and_search(
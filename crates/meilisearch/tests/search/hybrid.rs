@@ -157,6 +157,54 @@ async fn limit_offset() {
     assert_eq!(response["hits"].as_array().unwrap().len(), 1);
 }
 
+#[actix_rt::test]
+async fn collapse_estimated_total_hits() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings(json!({ "filterableAttributes": ["universe"], "embedders": {"default": {
+                "source": "userProvided",
+                "dimensions": 2}}} ))
+        .await;
+    assert_eq!(202, code, "{:?}", response);
+    index.wait_task(response.uid()).await.succeeded();
+
+    // the keyword and the semantic searches don't keep the same document of the marvel universe
+    let documents = json!([
+        {
+            "title": "Captain Marvel",
+            "universe": "marvel",
+            "id": "1",
+            "_vectors": {"default": [1.0, 0.0]},
+        },
+        {
+            "title": "Shazam!",
+            "universe": "marvel",
+            "id": "2",
+            "_vectors": {"default": [0.0, 1.0]},
+        },
+        {
+            "title": "Captain Planet",
+            "universe": "planeteers",
+            "id": "3",
+            "_vectors": {"default": [1.0, 1.0]},
+        }
+    ]);
+    let (response, code) = index.add_documents(documents, None).await;
+    assert_eq!(202, code, "{:?}", response);
+    index.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "vector": [0.0, 1.0], "hybrid": {"semanticRatio": 0.5, "embedder": "default"}, "collapse": {"field": "universe"}}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(response["hits"].as_array().unwrap().len(), 2);
+    snapshot!(response["estimatedTotalHits"], @"2");
+}
+
 #[actix_rt::test]
 async fn simple_search_hf() {
     let server = Server::new().await;
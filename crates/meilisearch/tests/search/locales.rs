@@ -1335,3 +1335,89 @@ async fn german_search() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn english_stemming_search() {
+    let server = Server::new().await;
+
+    let index = server.index("test");
+    let documents = json!([
+      {"id": 1, "title": "the children are playing"},
+      {"id": 2, "title": "a child played"},
+      {"id": 3, "title": "childhood memories"},
+    ]);
+    index.add_documents(documents, None).await;
+    let (response, _) = index
+        .update_settings(json!({
+            "searchableAttributes": ["title"],
+            "localizedAttributes": [
+                {"attributePatterns": ["title"], "locales": ["eng"], "stemming": true}
+            ]
+        }))
+        .await;
+    index.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = index.get_settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["localizedAttributes"]), @r###"
+    [
+      {
+        "attributePatterns": [
+          "title"
+        ],
+        "locales": [
+          "eng"
+        ],
+        "stemming": true
+      }
+    ]
+    "###);
+
+    // the exact match is ranked before the stem match
+    index
+        .search(json!({"q": "children", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(response, @r###"
+            {
+              "hits": [
+                {
+                  "id": 1
+                },
+                {
+                  "id": 2
+                }
+              ],
+              "query": "children",
+              "processingTimeMs": "[duration]",
+              "limit": 20,
+              "offset": 0,
+              "estimatedTotalHits": 2
+            }
+            "###);
+            snapshot!(code, @"200 OK");
+        })
+        .await;
+
+    // the stems are not used when searching in another locale
+    index
+        .search(
+            json!({"q": "children", "locales": ["fra"], "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                snapshot!(response, @r###"
+                {
+                  "hits": [
+                    {
+                      "id": 1
+                    }
+                  ],
+                  "query": "children",
+                  "processingTimeMs": "[duration]",
+                  "limit": 20,
+                  "offset": 0,
+                  "estimatedTotalHits": 1
+                }
+                "###);
+                snapshot!(code, @"200 OK");
+            },
+        )
+        .await;
+}
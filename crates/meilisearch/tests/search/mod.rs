@@ -1,6 +1,7 @@
 // This modules contains all the test concerning search. Each particular feature of the search
 // should be tested in its own module to isolate tests and keep the tests readable.
 
+mod autocomplete;
 mod distinct;
 mod errors;
 mod facet_search;
@@ -13,6 +14,7 @@ mod locales;
 mod matching_strategy;
 mod multi;
 mod pagination;
+mod query_rules;
 mod restrict_searchable;
 mod search_queue;
 
@@ -2086,3 +2088,189 @@ async fn test_exact_typos_terms() {
     )
     .await;
 }
+
+#[actix_rt::test]
+async fn search_with_suggestions() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 0, "title": "the quick brown fox" },
+        { "id": 1, "title": "the quick brown cat" },
+        { "id": 2, "title": "a quack duck" },
+        { "id": 3, "title": "brown bread" },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) =
+        index.search_post(json!({"q": "quikc browm", "suggestions": true})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "query": "quick brown",
+        "estimatedTotalHits": 2
+      }
+    ]
+    "###);
+
+    let (response, code) = index.search_get("?q=quock&suggestions=true").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "query": "quick",
+        "estimatedTotalHits": 2
+      },
+      {
+        "query": "quack",
+        "estimatedTotalHits": 1
+      }
+    ]
+    "###);
+
+    // the suggestions are only returned when asked for
+    let (response, code) = index.search_post(json!({"q": "quikc browm"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @"null");
+}
+
+#[actix_rt::test]
+async fn search_with_explain() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 0, "title": "quick brown fox" },
+        { "id": 1, "title": "quikc fox" },
+        { "id": 2, "title": "slow dog" },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({"q": "quikc", "explain": true, "explainDocuments": ["0", "1", "2"]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["explanation"], { ".rankingRules[].duration" => "[duration]" }), @r###"
+    {
+      "rankingRules": [
+        {
+          "name": "words",
+          "duration": "[duration]",
+          "iterations": 1,
+          "bucketCount": 1,
+          "bucketSizes": [
+            2
+          ]
+        },
+        {
+          "name": "typo",
+          "duration": "[duration]",
+          "iterations": 1,
+          "bucketCount": 1,
+          "bucketSizes": [
+            1
+          ]
+        },
+        {
+          "name": "proximity",
+          "duration": "[duration]",
+          "iterations": 0,
+          "bucketCount": 0,
+          "bucketSizes": []
+        },
+        {
+          "name": "fid",
+          "duration": "[duration]",
+          "iterations": 0,
+          "bucketCount": 0,
+          "bucketSizes": []
+        },
+        {
+          "name": "position",
+          "duration": "[duration]",
+          "iterations": 0,
+          "bucketCount": 0,
+          "bucketSizes": []
+        },
+        {
+          "name": "exact_attribute",
+          "duration": "[duration]",
+          "iterations": 0,
+          "bucketCount": 0,
+          "bucketSizes": []
+        },
+        {
+          "name": "exactness",
+          "duration": "[duration]",
+          "iterations": 0,
+          "bucketCount": 0,
+          "bucketSizes": []
+        }
+      ],
+      "queryGraph": [
+        {
+          "id": 2,
+          "positions": [
+            0,
+            0
+          ],
+          "original": "quikc",
+          "isPrefix": true,
+          "exact": "quikc",
+          "oneTypo": [
+            "quick"
+          ],
+          "successors": []
+        }
+      ],
+      "documents": {
+        "0": [
+          {
+            "rankingRule": "words",
+            "bucket": 0,
+            "bucketSize": 2
+          }
+        ],
+        "1": [
+          {
+            "rankingRule": "words",
+            "bucket": 0,
+            "bucketSize": 2
+          },
+          {
+            "rankingRule": "typo",
+            "bucket": 0,
+            "bucketSize": 1
+          }
+        ]
+      }
+    }
+    "###);
+
+    let (response, code) = index.search_get("?q=quikc&explain=true&explainDocuments=1").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["explanation"]["documents"]), @r###"
+    {
+      "1": [
+        {
+          "rankingRule": "words",
+          "bucket": 0,
+          "bucketSize": 2
+        },
+        {
+          "rankingRule": "typo",
+          "bucket": 0,
+          "bucketSize": 1
+        }
+      ]
+    }
+    "###);
+
+    // the explanation is only returned when asked for
+    let (response, code) = index.search_post(json!({"q": "quikc"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["explanation"]), @"null");
+}
@@ -3438,6 +3438,90 @@ async fn federation_null_weight() {
     "###);
 }
 
+#[actix_rt::test]
+async fn federation_federated_contains_collapse() {
+    let server = Server::new().await;
+
+    let index = server.index("fruits");
+
+    let documents = FRUITS_DOCUMENTS.clone();
+    let (value, _) = index.add_documents(documents, None).await;
+    index.wait_task(value.uid()).await.succeeded();
+
+    // fail when a federated query contains "collapse"
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "fruits", "q": "apple red"},
+        {"indexUid": "fruits", "q": "apple red", "collapse": {"field": "name"}},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    insta::assert_json_snapshot!(response, { ".processingTimeMs" => "[time]" }, @r###"
+    {
+      "message": "Inside `.queries[1]`: Using `collapse` is not allowed in federated queries.\n - Hint: remove `collapse` from query #1 or remove `federation` from the request",
+      "code": "invalid_multi_search_query_collapse",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_query_collapse"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn federation_federated_contains_suggestions() {
+    let server = Server::new().await;
+
+    let index = server.index("fruits");
+
+    let documents = FRUITS_DOCUMENTS.clone();
+    let (value, _) = index.add_documents(documents, None).await;
+    index.wait_task(value.uid()).await.succeeded();
+
+    // fail when a federated query contains "suggestions"
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "fruits", "q": "apple red"},
+        {"indexUid": "fruits", "q": "aple red", "suggestions": true},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    insta::assert_json_snapshot!(response, { ".processingTimeMs" => "[time]" }, @r###"
+    {
+      "message": "Inside `.queries[1]`: Using `suggestions` is not allowed in federated queries.\n - Hint: remove `suggestions` from query #1 or remove `federation` from the request",
+      "code": "invalid_multi_search_query_suggestions",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_query_suggestions"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn federation_federated_contains_explain() {
+    let server = Server::new().await;
+
+    let index = server.index("fruits");
+
+    let documents = FRUITS_DOCUMENTS.clone();
+    let (value, _) = index.add_documents(documents, None).await;
+    index.wait_task(value.uid()).await.succeeded();
+
+    // fail when a federated query contains "explain"
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "fruits", "q": "apple red"},
+        {"indexUid": "fruits", "q": "apple red", "explain": true},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    insta::assert_json_snapshot!(response, { ".processingTimeMs" => "[time]" }, @r###"
+    {
+      "message": "Inside `.queries[1]`: Using `explain` is not allowed in federated queries.\n - Hint: remove `explain` from query #1 or remove `federation` from the request",
+      "code": "invalid_multi_search_query_explain",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_query_explain"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn federation_federated_contains_pagination() {
     let server = Server::new().await;
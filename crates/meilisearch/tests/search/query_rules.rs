@@ -0,0 +1,253 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::{Index, Server, Shared};
+use crate::json;
+
+async fn products_index(server: &Server<Shared>) -> Index<'_> {
+    let index = server.unique_index();
+
+    let (task, _code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["in_stock"],
+            "queryRules": [
+                {
+                    "uid": "laptop-promo",
+                    "conditions": { "query": { "pattern": "laptop" } },
+                    "consequences": {
+                        "pin": [{ "id": "4", "position": 1 }],
+                        "hide": ["1"],
+                        "filter": "in_stock = true"
+                    }
+                },
+                {
+                    "uid": "notebook-synonym",
+                    "conditions": { "query": { "pattern": "notebook", "anchoring": "contains" } },
+                    "consequences": { "replaceQuery": "laptop" }
+                },
+                {
+                    "uid": "clearance",
+                    "conditions": { "filters": ["in_stock = false"] },
+                    "consequences": { "hide": ["2"] }
+                }
+            ]
+        }))
+        .await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 0, "title": "laptop pro", "in_stock": true },
+        { "id": 1, "title": "laptop air", "in_stock": true },
+        { "id": 2, "title": "laptop basic", "in_stock": false },
+        { "id": 3, "title": "laptop gamer", "in_stock": true },
+        { "id": 4, "title": "gaming mouse", "in_stock": true },
+        { "id": 5, "title": "mouse pad", "in_stock": false },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    index
+}
+
+#[actix_rt::test]
+async fn pin_hide_and_filter() {
+    let server = Server::new_shared();
+    let index = products_index(server).await;
+
+    index
+        .search(json!({"q": "Laptop", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 0
+              },
+              {
+                "id": 4
+              },
+              {
+                "id": 3
+              }
+            ]
+            "###);
+            snapshot!(response["estimatedTotalHits"], @"3");
+            snapshot!(json_string!(response["appliedRules"]), @r###"
+            [
+              "laptop-promo"
+            ]
+            "###);
+        })
+        .await;
+
+    // the pinned document keeps its position across pages
+    index
+        .search(
+            json!({"q": "laptop", "offset": 1, "limit": 1, "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 4
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    // the pinned document is not returned when it doesn't match the filter of the search
+    index
+        .search(
+            json!({"q": "laptop", "filter": "in_stock = false", "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @"[]");
+                snapshot!(json_string!(response["appliedRules"]), @r###"
+                [
+                  "laptop-promo",
+                  "clearance"
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn replace_query() {
+    let server = Server::new_shared();
+    let index = products_index(server).await;
+
+    index
+        .search(json!({"q": "cheap notebook", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 0
+              },
+              {
+                "id": 1
+              },
+              {
+                "id": 2
+              },
+              {
+                "id": 3
+              }
+            ]
+            "###);
+            // the query is reported as typed
+            snapshot!(response["query"], @r###""cheap notebook""###);
+            snapshot!(json_string!(response["appliedRules"]), @r###"
+            [
+              "notebook-synonym"
+            ]
+            "###);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn filter_conditions() {
+    let server = Server::new_shared();
+    let index = products_index(server).await;
+
+    index
+        .search(
+            json!({"filter": "in_stock  =  false", "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 5
+                  }
+                ]
+                "###);
+                snapshot!(json_string!(response["appliedRules"]), @r###"
+                [
+                  "clearance"
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    // no rule applies
+    index
+        .search(json!({"q": "mouse", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 5
+              },
+              {
+                "id": 4
+              }
+            ]
+            "###);
+            assert!(response.get("appliedRules").is_none());
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn federated_search() {
+    let server = Server::new_shared();
+    let index = products_index(server).await;
+
+    // the hidden and filtered out documents are not returned, the pinned ones are not inserted
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+            {"indexUid": index.uid, "q": "laptop", "attributesToRetrieve": ["id"]},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> = response["hits"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hit| hit["id"].as_u64().unwrap())
+        .collect();
+    snapshot!(format!("{ids:?}"), @"[0, 3]");
+
+    // the query is replaced
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+            {"indexUid": index.uid, "q": "cheap notebook", "attributesToRetrieve": ["id"]},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> = response["hits"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hit| hit["id"].as_u64().unwrap())
+        .collect();
+    snapshot!(format!("{ids:?}"), @"[0, 1, 2, 3]");
+}
+
+#[actix_rt::test]
+async fn autocomplete() {
+    let server = Server::new_shared();
+    let index = products_index(server).await;
+
+    // the words of the hidden and filtered out documents are not proposed
+    let (response, code) = index.autocomplete(json!({"q": "laptop"})).await;
+    snapshot!(code, @"200 OK");
+    let mut completions: Vec<_> = response["completions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|completion| completion["value"].as_str().unwrap())
+        .collect();
+    completions.sort();
+    snapshot!(format!("{completions:?}"), @r###"["laptop", "laptop gamer", "laptop pro"]"###);
+
+    let (response, code) =
+        index.autocomplete(json!({"q": "laptop b", "filter": "in_stock = false"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["completions"]), @"[]");
+}
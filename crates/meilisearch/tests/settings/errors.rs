@@ -365,3 +365,56 @@ async fn settings_bad_search_cutoff_ms() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn settings_bad_ngram_attributes() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.update_settings(json!({ "ngramAttributes": "doggo" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.ngramAttributes`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_settings_ngram_attributes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_ngram_attributes"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_query_rules() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.update_settings(json!({ "queryRules": "doggo" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.queryRules`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_settings_query_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_query_rules"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_query_rules_with_duplicated_uid() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let rule = json!({ "uid": "promo", "consequences": { "hide": ["1"] } });
+    let (task, code) = index.update_settings(json!({ "queryRules": [rule, rule] })).await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(task.uid()).await.failed();
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "Index `test`: Invalid query rule `promo`: another rule has the same uid",
+      "code": "invalid_settings_query_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_query_rules"
+    }
+    "###);
+}
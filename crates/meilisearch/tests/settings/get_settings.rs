@@ -161,6 +161,11 @@ test_setting_routes!(
         update_verb: put,
         default_value: true
     },
+    {
+        setting: ngram_attributes,
+        update_verb: put,
+        default_value: []
+    },
     {
         setting: prefix_search,
         update_verb: put,
@@ -171,6 +176,11 @@ test_setting_routes!(
         update_verb: put,
         default_value: "byWord"
     },
+    {
+        setting: query_rules,
+        update_verb: put,
+        default_value: []
+    },
     {
         setting: sortable_attributes,
         update_verb: put,
@@ -199,7 +209,7 @@ async fn get_settings() {
     let (response, code) = index.settings().await;
     assert_eq!(code, 200);
     let settings = response.as_object().unwrap();
-    assert_eq!(settings.keys().len(), 20);
+    assert_eq!(settings.keys().len(), 22);
     assert_eq!(settings["displayedAttributes"], json!(["*"]));
     assert_eq!(settings["searchableAttributes"], json!(["*"]));
     assert_eq!(settings["filterableAttributes"], json!([]));
@@ -232,6 +242,8 @@ async fn get_settings() {
     assert_eq!(settings["searchCutoffMs"], json!(null));
     assert_eq!(settings["prefixSearch"], json!("indexingTime"));
     assert_eq!(settings["facetSearch"], json!(true));
+    assert_eq!(settings["ngramAttributes"], json!([]));
+    assert_eq!(settings["queryRules"], json!([]));
     assert_eq!(settings["embedders"], json!({}));
 }
 
@@ -336,7 +348,9 @@ async fn secrets_are_hidden_in_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "ngramAttributes": [],
+      "queryRules": []
     }
     "###);
 
@@ -485,7 +499,7 @@ async fn error_set_invalid_ranking_rules() {
     meili_snap::snapshot!(code, @"400 Bad Request");
     meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
     {
-      "message": "Invalid value at `.rankingRules[0]`: `manyTheFish` ranking rule is invalid. Valid ranking rules are words, typo, sort, proximity, attribute, exactness and custom ranking rules.",
+      "message": "Invalid value at `.rankingRules[0]`: `manyTheFish` ranking rule is invalid. Valid ranking rules are words, typo, sort, proximity, attribute, exactness, bm25 and custom ranking rules.",
       "code": "invalid_settings_ranking_rules",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_settings_ranking_rules"
@@ -203,3 +203,55 @@ async fn perform_on_demand_snapshot() {
         server.index("doggo").settings(),
     );
 }
+
+#[actix_rt::test]
+#[cfg_attr(target_os = "windows", ignore)]
+async fn perform_incremental_snapshot() {
+    let temp = tempfile::tempdir().unwrap();
+    let snapshot_dir = tempfile::tempdir().unwrap();
+
+    let options = Opt {
+        snapshot_dir: snapshot_dir.path().to_owned(),
+        experimental_incremental_snapshots: true,
+        ..default_settings(temp.path())
+    };
+
+    let server = Server::new_with_options(options).await.unwrap();
+
+    let catto = server.index("catto");
+    let (task, _code) = catto.add_documents(json!([{ "id": 1, "name": "kefir" }]), None).await;
+    catto.wait_task(task.uid()).await.succeeded();
+    let doggo = server.index("doggo");
+    let (task, _code) = doggo.add_documents(json!([{ "id": 1, "name": "intel" }]), None).await;
+    doggo.wait_task(task.uid()).await.succeeded();
+
+    let (task, _code) = server.create_snapshot().await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    // Only doggo changes, catto must be taken from the first snapshot
+    let (task, _code) = doggo.add_documents(json!([{ "id": 2, "name": "bork" }]), None).await;
+    doggo.wait_task(task.uid()).await.succeeded();
+
+    let (task, _code) = server.create_snapshot().await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    let mut snapshots: Vec<String> = std::fs::read_dir(&snapshot_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path().file_name().unwrap().to_str().unwrap().to_string())
+        .filter(|name| name.starts_with("db-"))
+        .collect();
+    snapshots.sort();
+    // The first snapshot is still referenced by the second one
+    assert_eq!(snapshots.len(), 2, "{snapshots:?}");
+
+    let temp = tempfile::tempdir().unwrap();
+    let snapshot_path = snapshot_dir.path().to_owned().join("db.snapshot");
+    let options = Opt { import_snapshot: Some(snapshot_path), ..default_settings(temp.path()) };
+    let snapshot_server = Server::new_with_options(options).await.unwrap();
+
+    verify_snapshot!(server, snapshot_server, |server| =>
+        server.list_indexes(None, None),
+        server.index("catto").get_all_documents(GetAllDocumentsOptions::default()),
+        server.index("doggo").get_all_documents(GetAllDocumentsOptions::default()),
+    );
+}
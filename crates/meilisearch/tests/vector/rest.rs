@@ -2111,3 +2111,43 @@ async fn searchable_reindex() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn collapsed_hybrid_search_embeds_the_query_once() {
+    let (mock, setting) = create_mock().await;
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "filterableAttributes": ["breed"],
+          "embedders": {
+              "rest": setting,
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+    let documents = json!([
+      {"id": 0, "name": "kefir", "breed": "pug"},
+      {"id": 1, "name": "echo", "breed": "pug"},
+      {"id": 2, "name": "intel", "breed": "beagle"},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+    let indexing_requests = mock.received_requests().await.unwrap().len();
+
+    // each collapsed group is searched again with the vector of the query
+    let (response, code) = index
+        .search_post(json!({
+            "q": "intel",
+            "hybrid": {"semanticRatio": 0.5, "embedder": "rest"},
+            "collapse": {"field": "breed"},
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"].as_array().unwrap().len(), @"2");
+    let search_requests = mock.received_requests().await.unwrap().len() - indexing_requests;
+    snapshot!(search_requests, @"1");
+}
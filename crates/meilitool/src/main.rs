@@ -1,11 +1,13 @@
+use std::collections::HashMap;
 use std::fs::{read_dir, read_to_string, remove_file, File};
 use std::io::{BufWriter, Write as _};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 use anyhow::{bail, Context};
 use clap::{Parser, Subcommand, ValueEnum};
 use dump::{DumpWriter, IndexMetadata};
+use file_store::encryption::{EncryptWriter, EncryptionKey};
 use file_store::FileStore;
 use meilisearch_auth::{open_auth_store_env, AuthController};
 use meilisearch_types::batches::Batch;
@@ -21,6 +23,7 @@ use meilisearch_types::tasks::{Status, Task};
 use meilisearch_types::versioning::{get_version, parse_version};
 use meilisearch_types::Index;
 use serde_json::Value::Object;
+use time::format_description::well_known::Rfc3339;
 use time::macros::format_description;
 use time::OffsetDateTime;
 use upgrade::OfflineUpgrade;
@@ -29,6 +32,8 @@ use uuid_codec::UuidCodec;
 mod upgrade;
 mod uuid_codec;
 
+const PREVIOUS_MASTER_KEY_ENV: &str = "MEILI_PREVIOUS_MASTER_KEY";
+
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
@@ -70,6 +75,12 @@ enum Command {
         /// the processed ones is not particularly interesting.
         #[arg(long)]
         skip_enqueued_tasks: bool,
+
+        /// The file containing the key the update files are encrypted with, if any.
+        ///
+        /// The dump is encrypted with the same key.
+        #[arg(long)]
+        encryption_key_path: Option<PathBuf>,
     },
 
     /// Exports the documents of an index in NDJSON format from a Meilisearch index to stdout.
@@ -137,6 +148,48 @@ enum Command {
         #[arg(long, value_delimiter = ',')]
         index_part: Vec<IndexPart>,
     },
+
+    /// Keeps the API keys derived from the previous master key valid while the master key is changed.
+    ///
+    /// Run this command before restarting Meilisearch with the new master key. During the transition,
+    /// the clients can keep using the previous values of the keys while they are given the new ones,
+    /// which are listed by the `GET /keys` route once Meilisearch uses the new master key.
+    ///
+    /// The previous master key is read from the `MEILI_PREVIOUS_MASTER_KEY` environment variable or,
+    /// when it is not set, from the first line of the standard input, so that it never appears in the
+    /// list of processes or in the shell history.
+    ///
+    /// Only the hashes of the previous values are stored, which means the tenant tokens signed with
+    /// them will be rejected and must be signed again with the new values.
+    RotateMasterKey {
+        /// The number of days the previous values of the API keys are kept valid.
+        #[arg(long, default_value_t = 7)]
+        transition_days: u32,
+    },
+
+    /// Reports the API keys that are still used with a value derived from the previous master key.
+    ///
+    /// Outputs one line of JSON per API key to stdout, with when it was last used with its previous
+    /// value and until when this value is valid. This command can be executed on a running Meilisearch
+    /// database.
+    MasterKeyRotationReport,
+
+    /// Encrypts the update files with a new key.
+    ///
+    /// Make sure to run this command when Meilisearch is not running, and to restart it with the new
+    /// encryption key. If it is interrupted, it can be run again with the same keys: the update files
+    /// already encrypted with the new key are skipped. Omitting the previous key encrypts the update files written in plaintext, and
+    /// omitting the new key decrypts them. The dumps and snapshots already created are not modified
+    /// and can still be imported with the previous key.
+    RotateEncryptionKey {
+        /// The file containing the key the update files are currently encrypted with.
+        #[arg(long)]
+        previous_encryption_key_path: Option<PathBuf>,
+
+        /// The file containing the key to encrypt the update files with.
+        #[arg(long)]
+        new_encryption_key_path: Option<PathBuf>,
+    },
 }
 
 #[derive(Clone, ValueEnum)]
@@ -152,8 +205,9 @@ fn main() -> anyhow::Result<()> {
 
     match command {
         Command::ClearTaskQueue => clear_task_queue(db_path),
-        Command::ExportADump { dump_dir, skip_enqueued_tasks } => {
-            export_a_dump(db_path, dump_dir, skip_enqueued_tasks, detected_version)
+        Command::ExportADump { dump_dir, skip_enqueued_tasks, encryption_key_path } => {
+            let encryption_key = read_encryption_key(encryption_key_path.as_deref())?;
+            export_a_dump(db_path, dump_dir, skip_enqueued_tasks, encryption_key, detected_version)
         }
         Command::ExportDocuments { index_name, ignore_vectors, offset } => {
             export_documents(db_path, index_name, ignore_vectors, offset)
@@ -166,6 +220,16 @@ fn main() -> anyhow::Result<()> {
         Command::HairDryer { index_name, index_part } => {
             hair_dryer(db_path, &index_name, &index_part)
         }
+        Command::RotateMasterKey { transition_days } => {
+            let previous_master_key = read_previous_master_key()?;
+            rotate_master_key(db_path, &previous_master_key, transition_days)
+        }
+        Command::MasterKeyRotationReport => master_key_rotation_report(db_path),
+        Command::RotateEncryptionKey { previous_encryption_key_path, new_encryption_key_path } => {
+            let previous_key = read_encryption_key(previous_encryption_key_path.as_deref())?;
+            let new_key = read_encryption_key(new_encryption_key_path.as_deref())?;
+            rotate_encryption_key(db_path, previous_key, new_key)
+        }
     }
 }
 
@@ -259,6 +323,7 @@ fn export_a_dump(
     db_path: PathBuf,
     dump_dir: PathBuf,
     skip_enqueued_tasks: bool,
+    encryption_key: Option<EncryptionKey>,
     detected_version: (u32, u32, u32),
 ) -> Result<(), anyhow::Error> {
     let started_at = OffsetDateTime::now_utc();
@@ -280,8 +345,8 @@ fn export_a_dump(
     };
 
     let dump = DumpWriter::new(instance_uid).context("While creating a new dump")?;
-    let file_store =
-        FileStore::new(db_path.join("update_files")).context("While opening the FileStore")?;
+    let file_store = FileStore::new_with_encryption(db_path.join("update_files"), encryption_key)
+        .context("While opening the FileStore")?;
 
     let index_scheduler_path = db_path.join("tasks");
     let env = unsafe {
@@ -334,10 +399,10 @@ fn export_a_dump(
         // 3.1. Dump the `content_file` associated with the task if there is one and the task is not finished yet.
         if let Some(content_file_uuid) = content_file {
             if status == Status::Enqueued {
-                let content_file = file_store.get_update(content_file_uuid)?;
-
                 if (detected_version.0, detected_version.1, detected_version.2) < (1, 12, 0) {
                     eprintln!("Dumping the enqueued tasks reading them in obkv format...");
+                    // these versions predate the encryption of the update files
+                    let content_file = file_store.get_update(content_file_uuid)?;
                     let reader =
                         DocumentsBatchReader::from_reader(content_file).with_context(|| {
                             format!("While reading content file {:?}", content_file_uuid)
@@ -351,6 +416,7 @@ fn export_a_dump(
                     }
                 } else {
                     eprintln!("Dumping the enqueued tasks reading them in JSON stream format...");
+                    let content_file = file_store.get_update_reader(content_file_uuid)?;
                     for document in
                         serde_json::de::Deserializer::from_reader(content_file).into_iter()
                     {
@@ -434,14 +500,137 @@ fn export_a_dump(
     )).unwrap();
 
     let path = dump_dir.join(format!("{}.dump", dump_uid));
-    let file = File::create(&path)?;
-    dump.persist_to(BufWriter::new(file))?;
+    let file = BufWriter::new(File::create(&path)?);
+    match file_store.encryption_key() {
+        Some(key) => {
+            let mut writer = EncryptWriter::new(file, key)?;
+            dump.persist_to(&mut writer)?;
+            writer.finish()?;
+        }
+        None => dump.persist_to(file)?,
+    }
 
     eprintln!("Dump exported at path {:?}", path.display());
 
     Ok(())
 }
 
+fn read_encryption_key(path: Option<&Path>) -> anyhow::Result<Option<EncryptionKey>> {
+    path.map(|path| {
+        EncryptionKey::from_path(path)
+            .with_context(|| format!("While reading the encryption key at {}", path.display()))
+    })
+    .transpose()
+}
+
+fn rotate_encryption_key(
+    db_path: PathBuf,
+    previous_key: Option<EncryptionKey>,
+    new_key: Option<EncryptionKey>,
+) -> anyhow::Result<()> {
+    let file_store = FileStore::new_with_encryption(db_path.join("update_files"), new_key)
+        .context("While opening the FileStore")?;
+
+    eprintln!("Encrypting the update files with the new key...");
+    // the files are replaced while we iterate, so we list them first.
+    let uuids: Vec<_> = file_store.all_uuids()?.collect::<Result<_, _>>()?;
+    let (mut count, mut skipped) = (0, 0);
+    for uuid in uuids {
+        // the files already encrypted with the new key come from an interrupted rotation.
+        let reencrypted = file_store
+            .reencrypt(uuid, previous_key.as_ref())
+            .with_context(|| format!("While encrypting the update file {uuid}"))?;
+        if reencrypted {
+            count += 1;
+        } else {
+            skipped += 1;
+        }
+    }
+    eprintln!(
+        "Encrypted {count} update files, {skipped} already were. You can now restart Meilisearch with the new key."
+    );
+
+    Ok(())
+}
+
+fn open_auth_controller(db_path: &Path) -> anyhow::Result<AuthController> {
+    let auth_path = db_path.join("auth");
+    let auth_env = open_auth_store_env(&auth_path).context("While opening the auth store")?;
+    AuthController::new(auth_env, &None)
+        .with_context(|| format!("While opening the auth store at {}", db_path.display()))
+}
+
+fn read_previous_master_key() -> anyhow::Result<String> {
+    let key = match std::env::var(PREVIOUS_MASTER_KEY_ENV) {
+        Ok(key) => key,
+        Err(std::env::VarError::NotPresent) => {
+            eprintln!("Reading the previous master key from the standard input...");
+            let mut key = String::new();
+            std::io::stdin()
+                .read_line(&mut key)
+                .context("While reading the previous master key from the standard input")?;
+            key.trim_end_matches(['\r', '\n']).to_string()
+        }
+        Err(e) => {
+            return Err(e).with_context(|| format!("While reading `{PREVIOUS_MASTER_KEY_ENV}`"))
+        }
+    };
+
+    if key.is_empty() {
+        bail!("The previous master key is empty. Set it with `{PREVIOUS_MASTER_KEY_ENV}` or write it on the standard input.");
+    }
+
+    Ok(key)
+}
+
+fn rotate_master_key(
+    db_path: PathBuf,
+    previous_master_key: &str,
+    transition_days: u32,
+) -> anyhow::Result<()> {
+    let auth = open_auth_controller(&db_path)?;
+
+    eprintln!("Recording the values of the keys derived from the previous master key...");
+    let count = auth
+        .rotate_master_key(previous_master_key, time::Duration::days(transition_days.into()))
+        .context("While recording the previous values of the keys")?;
+    eprintln!(
+        "Recorded {count} previous key values, valid for {transition_days} days. \
+        You can now restart Meilisearch with the new master key."
+    );
+
+    Ok(())
+}
+
+fn master_key_rotation_report(db_path: PathBuf) -> anyhow::Result<()> {
+    let auth = open_auth_controller(&db_path)?;
+    let now = OffsetDateTime::now_utc();
+    let names: HashMap<_, _> =
+        auth.list_keys()?.into_iter().map(|key| (key.uid, key.name)).collect();
+
+    let mut stdout = BufWriter::new(std::io::stdout());
+    let mut count = 0;
+    for previous in auth.list_previous_key_values()? {
+        let Some(last_used_at) = previous.last_used_at else { continue };
+        let name = names.get(&previous.uid).cloned().flatten();
+        let line = serde_json::json!({
+            "uid": previous.uid,
+            "name": name,
+            "lastUsedAt": last_used_at.format(&Rfc3339)?,
+            "validUntil": previous.valid_until.format(&Rfc3339)?,
+            "expired": previous.valid_until <= now,
+        });
+        serde_json::to_writer(&mut stdout, &line)?;
+        writeln!(&mut stdout)?;
+        count += 1;
+    }
+    stdout.flush()?;
+
+    eprintln!("{count} keys have been used with a value derived from the previous master key.");
+
+    Ok(())
+}
+
 fn compact_index(db_path: PathBuf, index_name: &str) -> anyhow::Result<()> {
     let index_scheduler_path = db_path.join("tasks");
     let env = unsafe {
@@ -8,7 +8,7 @@ use crate::{AscDesc, Member};
 
 #[derive(Error, Debug)]
 pub enum CriterionError {
-    #[error("`{name}` ranking rule is invalid. Valid ranking rules are words, typo, sort, proximity, attribute, exactness and custom ranking rules.")]
+    #[error("`{name}` ranking rule is invalid. Valid ranking rules are words, typo, sort, proximity, attribute, exactness, bm25 and custom ranking rules.")]
     InvalidName { name: String },
     #[error("`{name}` is a reserved keyword and thus can't be used as a ranking rule")]
     ReservedName { name: String },
@@ -41,6 +41,9 @@ pub enum Criterion {
     Sort,
     /// Sorted by the similarity of the matched words with the query words.
     Exactness,
+    /// Sorted by decreasing BM25 score, the frequency of the query terms in the documents
+    /// weighted by their rarity in the index and normalized by the length of the documents.
+    Bm25,
     /// Sorted by the increasing value of the field specified.
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
@@ -68,6 +71,7 @@ impl FromStr for Criterion {
             "attribute" => Ok(Criterion::Attribute),
             "sort" => Ok(Criterion::Sort),
             "exactness" => Ok(Criterion::Exactness),
+            "bm25" => Ok(Criterion::Bm25),
             text => match AscDesc::from_str(text)? {
                 AscDesc::Asc(Member::Field(field)) => Ok(Criterion::Asc(field)),
                 AscDesc::Desc(Member::Field(field)) => Ok(Criterion::Desc(field)),
@@ -101,6 +105,7 @@ impl fmt::Display for Criterion {
             Attribute => f.write_str("attribute"),
             Sort => f.write_str("sort"),
             Exactness => f.write_str("exactness"),
+            Bm25 => f.write_str("bm25"),
             Asc(attr) => write!(f, "{}:asc", attr),
             Desc(attr) => write!(f, "{}:desc", attr),
         }
@@ -124,6 +129,7 @@ mod tests {
             ("attribute", Criterion::Attribute),
             ("sort", Criterion::Sort),
             ("exactness", Criterion::Exactness),
+            ("bm25", Criterion::Bm25),
             ("price:asc", Criterion::Asc(S("price"))),
             ("price:desc", Criterion::Desc(S("price"))),
             ("price:asc:desc", Criterion::Desc(S("price:asc"))),
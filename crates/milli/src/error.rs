@@ -217,6 +217,30 @@ and can not be more than 511 bytes.", .document_id.to_string()
         hidden_fields: bool,
         matching_rule_index: Option<usize>,
     },
+    #[error("Attribute `{}` is not filterable and thus, cannot be used to collapse the search results. {}",
+        .field,
+        match (.valid_patterns.is_empty(), .matching_rule_index) {
+            // No rules match and no filterable attributes
+            (true, None) => "This index does not have configured filterable attributes.".to_string(),
+
+            // No rules match but there are some filterable attributes
+            (false, None) => format!("Available filterable attributes patterns are: `{}{}`.",
+                    valid_patterns.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
+                    .hidden_fields.then_some(", <..hidden-attributes>").unwrap_or(""),
+                ),
+
+            // A rule matched but filtering isn't enabled
+            (_, Some(rule_index)) => format!("Note: this attribute matches rule #{} in filterableAttributes, but this rule does not enable filtering.\nHint: enable filtering in rule #{} by adding appropriate filter features.\nHint: prepend another rule matching {} with filter features before rule #{}",
+                    rule_index, rule_index, .field, rule_index
+                ),
+        }
+    )]
+    InvalidCollapseAttribute {
+        field: String,
+        valid_patterns: BTreeSet<String>,
+        hidden_fields: bool,
+        matching_rule_index: Option<usize>,
+    },
     #[error("Attribute `{}` is not facet-searchable. {}",
         .field,
         match (.valid_patterns.is_empty(), .matching_rule_index) {
@@ -251,6 +275,22 @@ and can not be more than 511 bytes.", .document_id.to_string()
         valid_fields: BTreeSet<String>,
         hidden_fields: bool,
     },
+    #[error("Attribute `{}` used in the search query is not searchable. Available searchable attributes are: `{}{}`.",
+        .field,
+        .valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
+        .hidden_fields.then_some(", <..hidden-attributes>").unwrap_or(""),
+    )]
+    InvalidSearchQueryAttribute {
+        field: String,
+        valid_fields: BTreeSet<String>,
+        hidden_fields: bool,
+    },
+    #[error("Invalid advanced search query: {0}")]
+    InvalidAdvancedQuery(String),
+    #[error("The slop `{slop}` is too large, the words of a phrase or around a `NEAR` operator can be at most {max} positions away from each other.")]
+    InvalidPhraseSlop { slop: u8, max: u8 },
+    #[error("Invalid query rule `{uid}`: {reason}")]
+    InvalidQueryRule { uid: String, reason: String },
     #[error("An LMDB environment is already opened")]
     EnvAlreadyOpened,
     #[error("You must specify where `sort` is listed in the rankingRules setting to use the sort parameter at search time.")]
@@ -0,0 +1,31 @@
+use std::borrow::Cow;
+
+use heed::BoxedError;
+
+use super::SliceTooShortError;
+use crate::{try_split_array_at, FieldId};
+
+pub struct FieldIdLengthCodec;
+
+impl<'a> heed::BytesDecode<'a> for FieldIdLengthCodec {
+    type DItem = (FieldId, u16);
+
+    fn bytes_decode(bytes: &'a [u8]) -> Result<Self::DItem, BoxedError> {
+        let (field_id_bytes, bytes) = try_split_array_at(bytes).ok_or(SliceTooShortError)?;
+        let field_id = u16::from_be_bytes(field_id_bytes);
+        let (length_bytes, _nothing) = try_split_array_at(bytes).ok_or(SliceTooShortError)?;
+        let length = u16::from_be_bytes(length_bytes);
+        Ok((field_id, length))
+    }
+}
+
+impl<'a> heed::BytesEncode<'a> for FieldIdLengthCodec {
+    type EItem = (FieldId, u16);
+
+    fn bytes_encode((field_id, length): &Self::EItem) -> Result<Cow<'a, [u8]>, BoxedError> {
+        let mut bytes = Vec::with_capacity(2 + 2);
+        bytes.extend_from_slice(&field_id.to_be_bytes());
+        bytes.extend_from_slice(&length.to_be_bytes());
+        Ok(Cow::Owned(bytes))
+    }
+}
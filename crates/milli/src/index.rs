@@ -3,6 +3,7 @@ use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs::File;
 use std::path::Path;
 
+use charabia::Language;
 use heed::types::*;
 use heed::{CompactionOption, Database, DatabaseStat, RoTxn, RwTxn, Unspecified, WithoutTls};
 use indexmap::IndexMap;
@@ -21,16 +22,18 @@ use crate::heed_codec::facet::{
     FieldIdCodec, OrderedF64Codec,
 };
 use crate::heed_codec::version::VersionCodec;
-use crate::heed_codec::{BEU16StrCodec, FstSetCodec, StrBEU16Codec, StrRefCodec};
+use crate::heed_codec::{
+    BEU16StrCodec, FieldIdLengthCodec, FstSetCodec, StrBEU16Codec, StrRefCodec,
+};
 use crate::order_by_map::OrderByMap;
 use crate::proximity::ProximityPrecision;
 use crate::vector::{ArroyStats, ArroyWrapper, Embedding, EmbeddingConfig};
 use crate::{
-    default_criteria, CboRoaringBitmapCodec, Criterion, DocumentId, ExternalDocumentsIds,
-    FacetDistribution, FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldIdWordCountCodec,
-    FieldidsWeightsMap, FilterableAttributesRule, GeoPoint, LocalizedAttributesRule, ObkvCodec,
-    Result, RoaringBitmapCodec, RoaringBitmapLenCodec, Search, U8StrStrCodec, Weight, BEU16, BEU32,
-    BEU64,
+    default_criteria, CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, Criterion, DocumentId,
+    ExternalDocumentsIds, FacetDistribution, FieldDistribution, FieldId, FieldIdMapMissingEntry,
+    FieldIdWordCountCodec, FieldidsWeightsMap, FilterableAttributesRule, GeoPoint,
+    LocalizedAttributesRule, ObkvCodec, PatternMatch, QueryRule, Result, RoaringBitmapCodec,
+    RoaringBitmapLenCodec, Search, U8StrStrCodec, Weight, BEU16, BEU32, BEU64,
 };
 
 pub const DEFAULT_MIN_WORD_LEN_ONE_TYPO: u8 = 5;
@@ -68,6 +71,7 @@ pub mod main_key {
     pub const TWO_TYPOS_WORD_LEN: &str = "two-typos-word-len";
     pub const EXACT_WORDS: &str = "exact-words";
     pub const EXACT_ATTRIBUTES: &str = "exact-attributes";
+    pub const NGRAM_ATTRIBUTES: &str = "ngram-attributes";
     pub const MAX_VALUES_PER_FACET: &str = "max-values-per-facet";
     pub const SORT_FACET_VALUES_BY: &str = "sort-facet-values-by";
     pub const PAGINATION_MAX_TOTAL_HITS: &str = "pagination-max-total-hits";
@@ -75,9 +79,11 @@ pub mod main_key {
     pub const EMBEDDING_CONFIGS: &str = "embedding_configs";
     pub const SEARCH_CUTOFF: &str = "search_cutoff";
     pub const LOCALIZED_ATTRIBUTES_RULES: &str = "localized_attributes_rules";
+    pub const QUERY_RULES: &str = "query_rules";
     pub const FACET_SEARCH: &str = "facet_search";
     pub const PREFIX_SEARCH: &str = "prefix_search";
     pub const DOCUMENTS_STATS: &str = "documents_stats";
+    pub const FIELD_LENGTHS: &str = "field_lengths";
     pub const DISABLED_TYPOS_TERMS: &str = "disabled_typos_terms";
 }
 
@@ -94,7 +100,13 @@ pub mod db_name {
     pub const WORD_FIELD_ID_DOCIDS: &str = "word-field-id-docids";
     pub const WORD_PREFIX_POSITION_DOCIDS: &str = "word-prefix-position-docids";
     pub const WORD_PREFIX_FIELD_ID_DOCIDS: &str = "word-prefix-field-id-docids";
+    pub const NGRAM_FIELD_ID_DOCIDS: &str = "ngram-field-id-docids";
+    pub const STEM_FIELD_ID_DOCIDS: &str = "stem-field-id-docids";
+    pub const NGRAM_FIELD_ID_WORDS: &str = "ngram-field-id-words";
+    pub const STEM_FIELD_ID_WORDS: &str = "stem-field-id-words";
     pub const FIELD_ID_WORD_COUNT_DOCIDS: &str = "field-id-word-count-docids";
+    pub const FIELD_ID_LENGTH_DOCIDS: &str = "field-id-length-docids";
+    pub const WORD_FREQUENCY_DOCIDS: &str = "word-frequency-docids";
     pub const FACET_ID_F64_DOCIDS: &str = "facet-id-f64-docids";
     pub const FACET_ID_EXISTS_DOCIDS: &str = "facet-id-exists-docids";
     pub const FACET_ID_IS_NULL_DOCIDS: &str = "facet-id-is-null-docids";
@@ -108,7 +120,7 @@ pub mod db_name {
     pub const VECTOR_ARROY: &str = "vector-arroy";
     pub const DOCUMENTS: &str = "documents";
 }
-const NUMBER_OF_DBS: u32 = 25;
+const NUMBER_OF_DBS: u32 = 31;
 
 #[derive(Clone)]
 pub struct Index {
@@ -143,10 +155,27 @@ pub struct Index {
 
     /// Maps the field id and the word count with the docids that corresponds to it.
     pub field_id_word_count_docids: Database<FieldIdWordCountCodec, CboRoaringBitmapCodec>,
+    /// Maps the field id and the number of words in the field with the docids that corresponds to it.
+    pub field_id_length_docids: Database<FieldIdLengthCodec, CboRoaringBitmapCodec>,
+    /// Maps the word and the number of times it appears in the searchable fields of a document
+    /// with the docids that corresponds to it. Words appearing only once are not stored.
+    pub word_frequency_docids: Database<StrBEU16Codec, CboRoaringBitmapCodec>,
     /// Maps the word prefix and a position with all the docids where the prefix appears at the position.
     pub word_prefix_position_docids: Database<StrBEU16Codec, CboRoaringBitmapCodec>,
     /// Maps the word prefix and a field id with all the docids where the prefix appears inside the field
     pub word_prefix_fid_docids: Database<StrBEU16Codec, CboRoaringBitmapCodec>,
+    /// Maps the character n-grams of the words of the n-gram attributes and a field id
+    /// with all the docids where a word containing the n-gram appears inside the field.
+    pub ngram_fid_docids: Database<StrBEU16Codec, CboRoaringBitmapCodec>,
+    /// Maps the stems of the words of the stemmed attributes and a field id
+    /// with all the docids where a word with this stem appears inside the field.
+    pub stem_fid_docids: Database<StrBEU16Codec, CboRoaringBitmapCodec>,
+    /// Maps the character n-grams of the words of the n-gram attributes and a field id
+    /// with the words of the field containing the n-gram.
+    pub ngram_fid_words: Database<StrBEU16Codec, FstSetCodec>,
+    /// Maps the stems of the words of the stemmed attributes and a field id
+    /// with the words of the field with this stem.
+    pub stem_fid_words: Database<StrBEU16Codec, FstSetCodec>,
 
     /// Maps the facet field id and the docids for which this field exists
     pub facet_id_exists_docids: Database<FieldIdCodec, CboRoaringBitmapCodec>,
@@ -206,10 +235,17 @@ impl Index {
         let word_fid_docids = env.create_database(&mut wtxn, Some(WORD_FIELD_ID_DOCIDS))?;
         let field_id_word_count_docids =
             env.create_database(&mut wtxn, Some(FIELD_ID_WORD_COUNT_DOCIDS))?;
+        let field_id_length_docids =
+            env.create_database(&mut wtxn, Some(FIELD_ID_LENGTH_DOCIDS))?;
+        let word_frequency_docids = env.create_database(&mut wtxn, Some(WORD_FREQUENCY_DOCIDS))?;
         let word_prefix_position_docids =
             env.create_database(&mut wtxn, Some(WORD_PREFIX_POSITION_DOCIDS))?;
         let word_prefix_fid_docids =
             env.create_database(&mut wtxn, Some(WORD_PREFIX_FIELD_ID_DOCIDS))?;
+        let ngram_fid_docids = env.create_database(&mut wtxn, Some(NGRAM_FIELD_ID_DOCIDS))?;
+        let stem_fid_docids = env.create_database(&mut wtxn, Some(STEM_FIELD_ID_DOCIDS))?;
+        let ngram_fid_words = env.create_database(&mut wtxn, Some(NGRAM_FIELD_ID_WORDS))?;
+        let stem_fid_words = env.create_database(&mut wtxn, Some(STEM_FIELD_ID_WORDS))?;
         let facet_id_f64_docids = env.create_database(&mut wtxn, Some(FACET_ID_F64_DOCIDS))?;
         let facet_id_string_docids =
             env.create_database(&mut wtxn, Some(FACET_ID_STRING_DOCIDS))?;
@@ -246,7 +282,13 @@ impl Index {
             word_fid_docids,
             word_prefix_position_docids,
             word_prefix_fid_docids,
+            ngram_fid_docids,
+            stem_fid_docids,
+            ngram_fid_words,
+            stem_fid_words,
             field_id_word_count_docids,
+            field_id_length_docids,
+            word_frequency_docids,
             facet_id_f64_docids,
             facet_id_string_docids,
             facet_id_normalized_string_strings,
@@ -499,6 +541,44 @@ impl Index {
             .get(rtxn, main_key::DOCUMENTS_STATS)
     }
 
+    /* field lengths */
+
+    /// Computes and writes the total number of words of each field over all the documents,
+    /// from the `field_id_length_docids` database.
+    pub(crate) fn update_field_lengths(&self, wtxn: &mut RwTxn<'_>) -> Result<()> {
+        let mut lengths = BTreeMap::<FieldId, u64>::new();
+        let iter = self.field_id_length_docids.remap_data_type::<CboRoaringBitmapLenCodec>();
+        for result in iter.iter(wtxn)? {
+            let ((fid, length), count) = result?;
+            *lengths.entry(fid).or_default() += length as u64 * count;
+        }
+
+        if lengths.is_empty() {
+            self.delete_field_lengths(wtxn)?;
+        } else {
+            self.main.remap_types::<Str, SerdeJson<BTreeMap<FieldId, u64>>>().put(
+                wtxn,
+                main_key::FIELD_LENGTHS,
+                &lengths,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Returns the total number of words of each field over all the documents,
+    /// it is only maintained when the bm25 ranking rule is used.
+    pub fn field_lengths(&self, rtxn: &RoTxn<'_>) -> heed::Result<BTreeMap<FieldId, u64>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<BTreeMap<FieldId, u64>>>()
+            .get(rtxn, main_key::FIELD_LENGTHS)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn delete_field_lengths(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::FIELD_LENGTHS)
+    }
+
     /* primary key */
 
     /// Writes the documents primary key, this is the field name that is used to store the id.
@@ -1578,6 +1658,37 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(txn, main_key::EXACT_ATTRIBUTES)
     }
 
+    /// Returns the n-gram attributes: attributes whose words can be matched by their infixes.
+    pub fn ngram_attributes<'t>(&self, txn: &'t RoTxn<'t>) -> Result<Vec<&'t str>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeBincode<Vec<&str>>>()
+            .get(txn, main_key::NGRAM_ATTRIBUTES)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the list of n-gram attributes field ids.
+    pub fn ngram_attributes_ids(&self, txn: &RoTxn<'_>) -> Result<HashSet<FieldId>> {
+        let attrs = self.ngram_attributes(txn)?;
+        let fid_map = self.fields_ids_map(txn)?;
+        Ok(attrs.iter().filter_map(|attr| fid_map.id(attr)).collect())
+    }
+
+    /// Writes the n-gram attributes to the database.
+    pub(crate) fn put_ngram_attributes(&self, txn: &mut RwTxn<'_>, attrs: &[&str]) -> Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<&[&str]>>().put(
+            txn,
+            main_key::NGRAM_ATTRIBUTES,
+            &attrs,
+        )?;
+        Ok(())
+    }
+
+    /// Clears the n-gram attributes from the store.
+    pub(crate) fn delete_ngram_attributes(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::NGRAM_ATTRIBUTES)
+    }
+
     pub fn max_values_per_facet(&self, txn: &RoTxn<'_>) -> heed::Result<Option<u64>> {
         self.main.remap_types::<Str, BEU64>().get(txn, main_key::MAX_VALUES_PER_FACET)
     }
@@ -1719,6 +1830,58 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(txn, main_key::LOCALIZED_ATTRIBUTES_RULES)
     }
 
+    /// Returns the query rules, in the order they are evaluated.
+    pub fn query_rules(&self, rtxn: &RoTxn<'_>) -> heed::Result<Vec<QueryRule>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<Vec<QueryRule>>>()
+            .get(rtxn, main_key::QUERY_RULES)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_query_rules(
+        &self,
+        txn: &mut RwTxn<'_>,
+        rules: &[QueryRule],
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<&[QueryRule]>>().put(
+            txn,
+            main_key::QUERY_RULES,
+            &rules,
+        )
+    }
+
+    pub(crate) fn delete_query_rules(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::QUERY_RULES)
+    }
+
+    /// Returns the searchable fields whose words are stemmed, with the language of their stemmer.
+    ///
+    /// The first localized attributes rule matching a field decides if it is stemmed.
+    pub fn stemmed_fields_ids(&self, rtxn: &RoTxn<'_>) -> Result<BTreeMap<FieldId, Language>> {
+        let Some(rules) = self.localized_attributes_rules(rtxn)? else {
+            return Ok(BTreeMap::new());
+        };
+        if !rules.iter().any(|rule| rule.stemming) {
+            return Ok(BTreeMap::new());
+        }
+
+        let fields_ids_map = self.fields_ids_map(rtxn)?;
+        let mut stemmed_fields = BTreeMap::new();
+        for field_id in self.searchable_fields_ids(rtxn)? {
+            let Some(name) = fields_ids_map.name(field_id) else { continue };
+            let language = rules
+                .iter()
+                .find(|rule| rule.match_str(name) == PatternMatch::Match)
+                .and_then(|rule| rule.stemming_language());
+            if let Some(language) = language {
+                stemmed_fields.insert(field_id, language);
+            }
+        }
+
+        Ok(stemmed_fields)
+    }
+
     /// Put the embedding configs:
     /// 1. The name of the embedder
     /// 2. The configuration option for this embedder
@@ -1821,7 +1984,13 @@ impl Index {
             word_fid_docids,
             word_prefix_position_docids,
             word_prefix_fid_docids,
+            ngram_fid_docids,
+            stem_fid_docids,
+            ngram_fid_words,
+            stem_fid_words,
             field_id_word_count_docids,
+            field_id_length_docids,
+            word_frequency_docids,
             facet_id_f64_docids,
             facet_id_string_docids,
             facet_id_normalized_string_strings,
@@ -1872,10 +2041,17 @@ impl Index {
         );
         sizes
             .insert("word_prefix_fid_docids", word_prefix_fid_docids.stat(rtxn).map(compute_size)?);
+        sizes.insert("ngram_fid_docids", ngram_fid_docids.stat(rtxn).map(compute_size)?);
+        sizes.insert("stem_fid_docids", stem_fid_docids.stat(rtxn).map(compute_size)?);
+        sizes.insert("ngram_fid_words", ngram_fid_words.stat(rtxn).map(compute_size)?);
+        sizes.insert("stem_fid_words", stem_fid_words.stat(rtxn).map(compute_size)?);
         sizes.insert(
             "field_id_word_count_docids",
             field_id_word_count_docids.stat(rtxn).map(compute_size)?,
         );
+        sizes
+            .insert("field_id_length_docids", field_id_length_docids.stat(rtxn).map(compute_size)?);
+        sizes.insert("word_frequency_docids", word_frequency_docids.stat(rtxn).map(compute_size)?);
         sizes.insert("facet_id_f64_docids", facet_id_f64_docids.stat(rtxn).map(compute_size)?);
         sizes
             .insert("facet_id_string_docids", facet_id_string_docids.stat(rtxn).map(compute_size)?);
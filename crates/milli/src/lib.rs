@@ -24,8 +24,10 @@ mod localized_attributes_rules;
 pub mod order_by_map;
 pub mod prompt;
 pub mod proximity;
+mod query_rules;
 pub mod score_details;
 mod search;
+mod stemming;
 mod thread_pool_no_abort;
 pub mod update;
 pub mod vector;
@@ -47,8 +49,9 @@ pub use filter_parser::{Condition, FilterCondition, Span, Token};
 use fxhash::{FxHasher32, FxHasher64};
 pub use grenad::CompressionType;
 pub use search::new::{
-    execute_search, filtered_universe, DefaultSearchLogger, GeoSortStrategy, SearchContext,
-    SearchLogger, VisualSearchLogger,
+    execute_search, filtered_universe, DefaultSearchLogger, DocumentBucket, ExplainSearchLogger,
+    GeoSortStrategy, QueryNodeExplanation, QueryTermDerivations, RankingRuleExplanation,
+    SearchContext, SearchExplanation, SearchLogger, VisualSearchLogger,
 };
 use serde_json::Value;
 pub use thread_pool_no_abort::{PanicCatched, ThreadPoolNoAbort, ThreadPoolNoAbortBuilder};
@@ -70,17 +73,24 @@ pub use self::filterable_attributes_rules::{
 };
 pub use self::heed_codec::{
     BEU16StrCodec, BEU32StrCodec, BoRoaringBitmapCodec, BoRoaringBitmapLenCodec,
-    CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, FieldIdWordCountCodec, ObkvCodec,
-    RoaringBitmapCodec, RoaringBitmapLenCodec, StrBEU32Codec, U8StrStrCodec,
+    CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, FieldIdLengthCodec, FieldIdWordCountCodec,
+    ObkvCodec, RoaringBitmapCodec, RoaringBitmapLenCodec, StrBEU32Codec, U8StrStrCodec,
     UncheckedU8StrStrCodec,
 };
 pub use self::index::Index;
 pub use self::localized_attributes_rules::LocalizedAttributesRule;
+pub use self::query_rules::{
+    PinnedDocument, QueryAnchoring, QueryPattern, QueryRule, QueryRuleConditions,
+    QueryRuleConsequences,
+};
+pub use self::search::autocomplete::{Autocomplete, QueryCompletion};
 pub use self::search::facet::{FacetValueHit, SearchForFacetValues};
 pub use self::search::similar::Similar;
+pub use self::search::suggestions::{QuerySuggestion, QuerySuggestions};
 pub use self::search::{
-    FacetDistribution, Filter, FormatOptions, MatchBounds, MatcherBuilder, MatchingWords, OrderBy,
-    Search, SearchResult, SemanticSearch, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    Collapse, CollapsedGroup, FacetDistribution, Filter, FormatOptions, MatchBounds,
+    MatcherBuilder, MatchingWords, OrderBy, QuerySyntax, Search, SearchResult, SemanticSearch,
+    TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
 };
 pub use self::update::ChannelCongestion;
 
@@ -16,16 +16,25 @@ use crate::{AttributePatterns, FieldId};
 /// The pattern `attribute_name*` matches any attribute name that starts with `attribute_name`.
 /// The pattern `*attribute_name` matches any attribute name that ends with `attribute_name`.
 /// The pattern `*attribute_name*` matches any attribute name that contains `attribute_name`.
+///
+/// When `stemming` is enabled, the words of the matching attributes are also indexed by their stem
+/// in the first supported locale of the rule.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub struct LocalizedAttributesRule {
     pub attribute_patterns: AttributePatterns,
     #[schema(value_type = Vec<String>)]
     pub locales: Vec<Language>,
+    #[serde(default)]
+    pub stemming: bool,
 }
 
 impl LocalizedAttributesRule {
     pub fn new(attribute_patterns: Vec<String>, locales: Vec<Language>) -> Self {
-        Self { attribute_patterns: AttributePatterns::from(attribute_patterns), locales }
+        Self {
+            attribute_patterns: AttributePatterns::from(attribute_patterns),
+            locales,
+            stemming: false,
+        }
     }
 
     pub fn match_str(&self, str: &str) -> PatternMatch {
@@ -35,6 +44,17 @@ impl LocalizedAttributesRule {
     pub fn locales(&self) -> &[Language] {
         &self.locales
     }
+
+    /// Returns the language in which the words of the matching attributes must be stemmed.
+    pub fn stemming_language(&self) -> Option<Language> {
+        if !self.stemming {
+            return None;
+        }
+        self.locales
+            .iter()
+            .copied()
+            .find(|&language| crate::stemming::is_stemming_supported(language))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
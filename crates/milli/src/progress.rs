@@ -200,6 +200,8 @@ make_enum_progress! {
         ExactWordDocids,
         WordPositionDocids,
         FieldIdWordCountDocids,
+        FieldIdLengthDocids,
+        WordFrequencyDocids,
     }
 }
 
@@ -0,0 +1,150 @@
+use charabia::Language;
+use deserr::Deserr;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{Filter, Result, UserError};
+
+/// A merchandising rule: when its conditions match a search, its consequences are applied.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryRule {
+    /// The identifier of the rule, reported in the search responses it is applied to.
+    pub uid: String,
+    pub conditions: QueryRuleConditions,
+    pub consequences: QueryRuleConsequences,
+}
+
+/// The conditions a search must fulfill for a rule to apply, a rule without condition always applies.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryRuleConditions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<QueryPattern>,
+    /// Filter expressions that must all be part of the filter of the search.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub filters: Vec<String>,
+    /// The search must be restricted to one of these locales.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locales: Vec<Language>,
+}
+
+/// How the query of a search must match a pattern.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct QueryPattern {
+    pub pattern: String,
+    #[serde(default)]
+    #[deserr(default)]
+    pub anchoring: QueryAnchoring,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug, Default, Deserr, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum QueryAnchoring {
+    /// The query is the pattern.
+    #[default]
+    Exact,
+    /// The query starts with the pattern.
+    Prefix,
+    /// The query contains the words of the pattern.
+    Contains,
+}
+
+impl QueryPattern {
+    /// Whether the query matches the pattern, case and spaces are ignored.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = normalize(query);
+        let pattern = normalize(&self.pattern);
+        match self.anchoring {
+            QueryAnchoring::Exact => query == pattern,
+            QueryAnchoring::Prefix => query.starts_with(&pattern),
+            QueryAnchoring::Contains => format!(" {query} ").contains(&format!(" {pattern} ")),
+        }
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().map(str::to_lowercase).collect::<Vec<_>>().join(" ")
+}
+
+/// What happens to a search when a rule applies.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Default, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct QueryRuleConsequences {
+    /// Documents returned at a fixed position, whether they match the query or not.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[deserr(default)]
+    pub pin: Vec<PinnedDocument>,
+    /// Documents never returned.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[deserr(default)]
+    pub hide: Vec<String>,
+    /// A filter expression added to the filter of the search.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub filter: Option<String>,
+    /// A query searched instead of the query of the search.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub replace_query: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct PinnedDocument {
+    /// The external id of the document.
+    pub id: String,
+    /// The position of the document in the hits, starting from 0.
+    pub position: usize,
+}
+
+/// Checks that the uids of the rules are unique and that their filters are valid.
+pub(crate) fn validate_query_rules(rules: &[QueryRule]) -> Result<()> {
+    for (i, rule) in rules.iter().enumerate() {
+        let invalid =
+            |reason: String| UserError::InvalidQueryRule { uid: rule.uid.clone(), reason };
+
+        if rules[..i].iter().any(|other| other.uid == rule.uid) {
+            return Err(invalid("another rule has the same uid".to_string()).into());
+        }
+
+        let filters = rule.conditions.filters.iter().chain(&rule.consequences.filter);
+        for filter in filters {
+            if let Err(error) = Filter::from_str(filter) {
+                return Err(invalid(error.to_string()).into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{QueryAnchoring, QueryPattern};
+
+    #[test]
+    fn query_patterns() {
+        let pattern =
+            |pattern: &str, anchoring| QueryPattern { pattern: pattern.into(), anchoring };
+
+        let exact = pattern("gaming laptop", QueryAnchoring::Exact);
+        assert!(exact.matches("gaming laptop"));
+        assert!(exact.matches("  Gaming   LAPTOP "));
+        assert!(!exact.matches("gaming laptops"));
+
+        let prefix = pattern("lap", QueryAnchoring::Prefix);
+        assert!(prefix.matches("laptop stand"));
+        assert!(!prefix.matches("gaming laptop"));
+
+        let contains = pattern("laptop", QueryAnchoring::Contains);
+        assert!(contains.matches("gaming laptop"));
+        assert!(contains.matches("laptop"));
+        assert!(!contains.matches("laptops"));
+    }
+}
@@ -17,6 +17,7 @@ pub enum ScoreDetails {
     Sort(Sort),
     Vector(Vector),
     GeoSort(GeoSort),
+    Bm25(Bm25),
 
     /// Returned when we don't have the time to finish applying all the subsequent ranking-rules
     Skipped,
@@ -34,6 +35,7 @@ enum RankOrValue<'a> {
     Sort(&'a Sort),
     GeoSort(&'a GeoSort),
     Score(f64),
+    Bm25(f64),
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -62,6 +64,7 @@ impl ScoreDetails {
             ScoreDetails::Sort(_) => None,
             ScoreDetails::GeoSort(_) => None,
             ScoreDetails::Vector(_) => None,
+            ScoreDetails::Bm25(_) => None,
             ScoreDetails::Skipped => Some(Rank { rank: 0, max_rank: 1 }),
         }
     }
@@ -92,7 +95,7 @@ impl ScoreDetails {
                 RankOrValue::Rank(r) => ScoreValue::Score(r.local_score()),
                 RankOrValue::Sort(s) => ScoreValue::Sort(s),
                 RankOrValue::GeoSort(g) => ScoreValue::GeoSort(g),
-                RankOrValue::Score(s) => ScoreValue::Score(s),
+                RankOrValue::Score(s) | RankOrValue::Bm25(s) => ScoreValue::Score(s),
             })
     }
 
@@ -117,6 +120,7 @@ impl ScoreDetails {
                     WeightedScoreValue::GeoSort { asc: g.ascending, distance: g.distance() }
                 }
                 RankOrValue::Score(s) => WeightedScoreValue::VectorSort(s * weight),
+                RankOrValue::Bm25(s) => WeightedScoreValue::WeightedScore(s * weight),
             })
     }
 
@@ -134,6 +138,7 @@ impl ScoreDetails {
             ScoreDetails::Vector(vector) => {
                 RankOrValue::Score(vector.similarity.as_ref().map(|s| *s as f64).unwrap_or(0.0f64))
             }
+            ScoreDetails::Bm25(bm25) => RankOrValue::Bm25(bm25.local_score()),
             ScoreDetails::Skipped => RankOrValue::Rank(Rank { rank: 0, max_rank: 1 }),
         }
     }
@@ -291,6 +296,15 @@ impl ScoreDetails {
                     details_map.insert("vectorSort".into(), details);
                     order += 1;
                 }
+                ScoreDetails::Bm25(bm25) => {
+                    let details = serde_json::json!({
+                        "order": order,
+                        "bm25Score": bm25.score,
+                        "score": bm25.local_score(),
+                    });
+                    details_map.insert("bm25".into(), details);
+                    order += 1;
+                }
                 ScoreDetails::Skipped => {
                     details_map
                         .insert("skipped".to_string(), serde_json::json!({ "order": order }));
@@ -545,6 +559,23 @@ pub struct Vector {
     pub similarity: Option<f32>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Bm25 {
+    pub score: f64,
+    /// The score of a document containing all the query terms infinitely many times.
+    pub max_score: f64,
+}
+
+impl Bm25 {
+    pub fn local_score(&self) -> f64 {
+        if self.max_score > 0.0 {
+            self.score / self.max_score
+        } else {
+            0.0
+        }
+    }
+}
+
 impl GeoSort {
     pub fn distance(&self) -> Option<f64> {
         self.value.map(|value| distance_between_two_points(&self.target_point, &value))
@@ -0,0 +1,418 @@
+use std::cmp::Reverse;
+
+use charabia::{Language, Token, TokenizerBuilder};
+use fst::automaton::Str;
+use fst::{IntoStreamer, Streamer};
+use heed::types::Bytes;
+use heed::BytesEncode;
+use roaring::RoaringBitmap;
+
+use super::fst_utils::{Intersection, StartsWith};
+use super::{build_dfa, get_first, word_docids_in_fields};
+use crate::heed_codec::U8StrStrCodec;
+use crate::{filtered_universe, FieldId, Filter, Index, Result};
+
+/// The default maximum number of completions returned.
+pub const DEFAULT_AUTOCOMPLETE_LIMIT: usize = 10;
+/// The maximum number of words of the index that are read to complete the last term,
+/// it bounds the cost of completing very short prefixes.
+const MAX_SCANNED_WORDS: usize = 1000;
+/// The maximum number of words that can follow a completed term, each of them starts a continuation.
+const MAX_CONTINUATIONS_PER_WORD: usize = 3;
+/// The maximum number of words added after the completed term.
+const MAX_CONTINUATION_WORDS: usize = 3;
+
+/// A completion of the query, made of words that exist in the index.
+#[derive(Debug, Clone, serde::Serialize, PartialEq, Eq)]
+pub struct QueryCompletion {
+    pub value: String,
+    /// The number of documents containing all the words of the completion.
+    pub count: u64,
+}
+
+/// Computes the completions of a query that is being typed.
+///
+/// The last term of the query is completed with the words of the index it is a prefix of,
+/// with some typos tolerated, and the completed term is then followed by the words that
+/// are the most often found right after it. The completions are ranked by the number of
+/// documents, among the filtered ones, containing all their words.
+pub struct Autocomplete<'a> {
+    query: String,
+    filter: Option<Filter<'a>>,
+    limit: usize,
+    locales: Option<Vec<Language>>,
+    fields_ids: Option<Vec<FieldId>>,
+    excluded_documents: RoaringBitmap,
+    rtxn: &'a heed::RoTxn<'a>,
+    index: &'a Index,
+}
+
+struct Completion {
+    words: Vec<String>,
+    typos: u8,
+    docids: RoaringBitmap,
+}
+
+impl<'a> Autocomplete<'a> {
+    pub fn new(query: impl Into<String>, index: &'a Index, rtxn: &'a heed::RoTxn<'a>) -> Self {
+        Self {
+            query: query.into(),
+            filter: None,
+            limit: DEFAULT_AUTOCOMPLETE_LIMIT,
+            locales: None,
+            fields_ids: None,
+            excluded_documents: RoaringBitmap::new(),
+            rtxn,
+            index,
+        }
+    }
+
+    pub fn filter(&mut self, filter: Filter<'a>) -> &mut Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = limit;
+        self
+    }
+
+    pub fn locales(&mut self, locales: Vec<Language>) -> &mut Self {
+        self.locales = Some(locales);
+        self
+    }
+
+    /// Only completes the query with the words of these fields.
+    ///
+    /// The continuations can't be restricted to some fields and are not proposed.
+    pub fn fields_ids(&mut self, fields_ids: Vec<FieldId>) -> &mut Self {
+        self.fields_ids = Some(fields_ids);
+        self
+    }
+
+    /// The words of these documents are not proposed and they are not counted.
+    pub fn excluded_documents(&mut self, documents: RoaringBitmap) -> &mut Self {
+        self.excluded_documents = documents;
+        self
+    }
+
+    pub fn execute(&self) -> Result<Vec<QueryCompletion>> {
+        if self.limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut tokbuilder = TokenizerBuilder::new();
+        let stop_words = self.index.stop_words(self.rtxn)?;
+        if let Some(ref stop_words) = stop_words {
+            tokbuilder.stop_words(stop_words);
+        }
+        if let Some(locales) = self.locales.as_deref().filter(|locales| !locales.is_empty()) {
+            tokbuilder.allow_list(locales);
+        }
+        let tokenizer = tokbuilder.build();
+        let mut tokens: Vec<Token> = tokenizer.tokenize(&self.query).collect();
+
+        // The last term is a prefix unless the user already typed a separator after it.
+        let prefix = match tokens.last() {
+            Some(token) if !token.is_separator() => tokens.pop(),
+            _ => None,
+        };
+        let words: Vec<_> = tokens.iter().filter(|token| token.is_word()).collect();
+
+        let mut universe = filtered_universe(self.index, self.rtxn, &self.filter)?;
+        universe -= &self.excluded_documents;
+        for word in &words {
+            universe &= self.word_docids(word.lemma())?;
+        }
+        if universe.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (base, mut completions) = match &prefix {
+            Some(prefix) if !prefix.lemma().is_empty() => {
+                let base = self.query[..prefix.byte_start].to_string();
+                let mut completions = Vec::new();
+                for completion in self.complete_prefix(prefix.lemma(), &universe)? {
+                    self.push_continuations(&completion.words[0], &completion, &mut completions)?;
+                    completions.push(completion);
+                }
+                (base, completions)
+            }
+            // the query itself is not a completion, only its continuations are
+            None => match words.last() {
+                Some(word) => {
+                    let base = format!("{} ", self.query.trim_end());
+                    let query = Completion { words: Vec::new(), typos: 0, docids: universe };
+                    let mut completions = Vec::new();
+                    self.push_continuations(word.lemma(), &query, &mut completions)?;
+                    (base, completions)
+                }
+                None => return Ok(Vec::new()),
+            },
+            Some(_) => return Ok(Vec::new()),
+        };
+
+        completions.sort_by_key(|completion| {
+            (Reverse(completion.docids.len()), completion.typos, completion.words.len())
+        });
+
+        Ok(completions
+            .into_iter()
+            .take(self.limit)
+            .map(|completion| QueryCompletion {
+                value: format!("{base}{}", completion.words.join(" ")),
+                count: completion.docids.len(),
+            })
+            .collect())
+    }
+
+    /// Returns the words of the index starting with the prefix, with some typos tolerated,
+    /// that are contained in the documents of the universe.
+    fn complete_prefix(&self, prefix: &str, universe: &RoaringBitmap) -> Result<Vec<Completion>> {
+        let max_typos = if !self.index.authorize_typos(self.rtxn)? {
+            0
+        } else if prefix.len() < self.index.min_word_len_one_typo(self.rtxn)? as usize {
+            0
+        } else if prefix.len() < self.index.min_word_len_two_typos(self.rtxn)? as usize {
+            1
+        } else {
+            2
+        };
+
+        // Without typos, the prefix database tells us right away if there is nothing to complete.
+        if max_typos == 0
+            && self.fields_ids.is_none()
+            && self.index.words_prefixes_fst(self.rtxn)?.contains(prefix)
+        {
+            let prefix_docids =
+                self.index.word_prefix_docids.get(self.rtxn, prefix)?.unwrap_or_default();
+            if prefix_docids.is_disjoint(universe) {
+                return Ok(Vec::new());
+            }
+        }
+
+        let fst = self.index.words_fst(self.rtxn)?;
+        let dfa = build_dfa(prefix, max_typos, true);
+        let starts = StartsWith(Str::new(get_first(prefix)));
+        let mut stream = fst.search_with_state(Intersection(starts, &dfa)).into_stream();
+
+        let mut completions = Vec::new();
+        let mut scanned = 0;
+        while let Some((word, state)) = stream.next() {
+            scanned += 1;
+            if scanned > MAX_SCANNED_WORDS {
+                break;
+            }
+            let typos = dfa.distance(state.1).to_u8();
+            let word = std::str::from_utf8(word)?;
+            let docids = self.word_docids(word)? & universe;
+            if !docids.is_empty() {
+                completions.push(Completion { words: vec![word.to_owned()], typos, docids });
+            }
+        }
+
+        completions.sort_by_key(|completion| (Reverse(completion.docids.len()), completion.typos));
+        completions.truncate(self.limit);
+        Ok(completions)
+    }
+
+    /// Returns the documents containing the word in the fields the completions are restricted to.
+    fn word_docids(&self, word: &str) -> Result<RoaringBitmap> {
+        word_docids_in_fields(self.index, self.rtxn, word, self.fields_ids.as_deref())
+    }
+
+    /// Pushes the continuations of the completion, starting with the words that are the most
+    /// often found right after the given word and following with the most frequent next words.
+    fn push_continuations(
+        &self,
+        word: &str,
+        completion: &Completion,
+        output: &mut Vec<Completion>,
+    ) -> Result<()> {
+        for (next_word, docids) in
+            self.next_words(word, &completion.docids)?.into_iter().take(MAX_CONTINUATIONS_PER_WORD)
+        {
+            let mut words = completion.words.clone();
+            words.push(next_word);
+            let mut continuation = Completion { words, typos: completion.typos, docids };
+
+            for _ in 1..MAX_CONTINUATION_WORDS {
+                let last_word = continuation.words.last().unwrap();
+                let Some((next_word, docids)) =
+                    self.next_words(last_word, &continuation.docids)?.into_iter().next()
+                else {
+                    break;
+                };
+                let mut words = continuation.words.clone();
+                words.push(next_word);
+                let next = Completion { words, typos: continuation.typos, docids };
+                output.push(std::mem::replace(&mut continuation, next));
+            }
+            output.push(continuation);
+        }
+        Ok(())
+    }
+
+    /// Returns the words found right after the given word in the candidates,
+    /// ordered by the number of candidates containing both words.
+    fn next_words(
+        &self,
+        word: &str,
+        candidates: &RoaringBitmap,
+    ) -> Result<Vec<(String, RoaringBitmap)>> {
+        if self.fields_ids.is_some() {
+            return Ok(Vec::new());
+        }
+
+        let key = U8StrStrCodec::bytes_encode(&(1, word, "")).unwrap().into_owned();
+        let iter = self
+            .index
+            .word_pair_proximity_docids
+            .remap_key_type::<Bytes>()
+            .prefix_iter(self.rtxn, &key)?
+            .remap_key_type::<U8StrStrCodec>();
+
+        let mut next_words = Vec::new();
+        for result in iter {
+            let ((_, _, next_word), docids) = result?;
+            let docids = docids & candidates;
+            if !docids.is_empty() {
+                next_words.push((next_word.to_owned(), docids));
+            }
+        }
+
+        next_words.sort_by_key(|(_, docids)| Reverse(docids.len()));
+        Ok(next_words)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+
+    use crate::index::tests::TempIndex;
+    use crate::{Autocomplete, Filter, FilterableAttributesRule};
+
+    #[test]
+    fn complete_the_last_term() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S("brand"))]);
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "brand": "apple", "title": "iphone 15 pro" },
+                { "id": 1, "brand": "apple", "title": "iphone 15 pro max" },
+                { "id": 2, "brand": "apple", "title": "iphone 14" },
+                { "id": 3, "brand": "apple", "title": "ipad air" },
+                { "id": 4, "brand": "samsung", "title": "galaxy phone" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let complete = |query: &str, filter: Option<&str>| {
+            let mut autocomplete = Autocomplete::new(query, &index, &rtxn);
+            if let Some(filter) = filter.and_then(|filter| Filter::from_str(filter).unwrap()) {
+                autocomplete.filter(filter);
+            }
+            autocomplete
+                .limit(4)
+                .execute()
+                .unwrap()
+                .into_iter()
+                .map(|completion| (completion.value, completion.count))
+                .collect::<Vec<_>>()
+        };
+
+        insta::assert_debug_snapshot!(complete("iph", None), @r###"
+        [
+            (
+                "iphone",
+                3,
+            ),
+            (
+                "iphone 15",
+                2,
+            ),
+            (
+                "iphone 15 pro",
+                2,
+            ),
+            (
+                "iphone 14",
+                1,
+            ),
+        ]
+        "###);
+
+        // the words before the last term must all be in the completed documents
+        insta::assert_debug_snapshot!(complete("iphone 15 p", None), @r###"
+        [
+            (
+                "iphone 15 pro",
+                2,
+            ),
+            (
+                "iphone 15 pro max",
+                1,
+            ),
+        ]
+        "###);
+
+        // after a separator, only the continuations of the query are proposed
+        insta::assert_debug_snapshot!(complete("iphone ", None), @r###"
+        [
+            (
+                "iphone 15",
+                2,
+            ),
+            (
+                "iphone 15 pro",
+                2,
+            ),
+            (
+                "iphone 14",
+                1,
+            ),
+            (
+                "iphone 15 pro max",
+                1,
+            ),
+        ]
+        "###);
+
+        insta::assert_debug_snapshot!(complete("i", Some("brand = samsung")), @"[]");
+
+        // one typo is tolerated on prefixes of five letters or more
+        insta::assert_debug_snapshot!(complete("galaxu", None), @r###"
+        [
+            (
+                "galaxy",
+                1,
+            ),
+            (
+                "galaxy phone",
+                1,
+            ),
+        ]
+        "###);
+
+        // the words of the other fields are ignored
+        insta::assert_debug_snapshot!(complete("app", None), @r###"
+        [
+            (
+                "apple",
+                4,
+            ),
+        ]
+        "###);
+        let title = index.fields_ids_map(&rtxn).unwrap().id("title").unwrap();
+        let completions =
+            Autocomplete::new("app", &index, &rtxn).fields_ids(vec![title]).execute().unwrap();
+        assert!(completions.is_empty());
+    }
+}
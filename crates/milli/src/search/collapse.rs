@@ -0,0 +1,86 @@
+use roaring::RoaringBitmap;
+
+use super::Search;
+use crate::score_details::ScoreDetails;
+use crate::search::new::distinct_single_docid;
+use crate::{DocumentId, Result};
+
+/// Groups the documents of a search by the values of a field.
+#[derive(Debug, Clone)]
+pub struct Collapse {
+    pub field: String,
+    /// The maximum number of documents returned for each group.
+    pub inner_hits: usize,
+}
+
+/// The best documents of the group of a hit, in the order of the search.
+#[derive(Debug, Default)]
+pub struct CollapsedGroup {
+    /// The number of documents of the group matching the search.
+    pub total_hits: u64,
+    pub documents_ids: Vec<DocumentId>,
+    pub document_scores: Vec<Vec<ScoreDetails>>,
+}
+
+impl Search<'_> {
+    /// Returns the group of each of the given documents when the search is collapsed.
+    ///
+    /// The group of a document is made of the documents sharing a value of the collapse field with it,
+    /// they are sorted by running the search again on them, as a hybrid search if a semantic ratio is given.
+    /// The query of a hybrid search is embedded again for each group unless its vector is given.
+    pub fn collapsed_groups(
+        &self,
+        documents_ids: &[DocumentId],
+        semantic_ratio: Option<f32>,
+    ) -> Result<Vec<CollapsedGroup>> {
+        let Some(Collapse { field, inner_hits }) = &self.collapse else {
+            return Ok(Vec::new());
+        };
+        let field_id = self.index.fields_ids_map(self.rtxn)?.id(field);
+
+        let mut groups = Vec::with_capacity(documents_ids.len());
+        for &docid in documents_ids {
+            // like with distinct, a document without any value for the field is alone in its group
+            let mut group = RoaringBitmap::from_iter([docid]);
+            if let Some(field_id) = field_id {
+                distinct_single_docid(self.index, self.rtxn, field_id, docid, &mut group)?;
+            }
+
+            let search = Search {
+                query: self.query.clone(),
+                filter: self.filter.clone(),
+                offset: 0,
+                limit: *inner_hits,
+                sort_criteria: self.sort_criteria.clone(),
+                distinct: None,
+                searchable_attributes: self.searchable_attributes,
+                geo_param: self.geo_param,
+                terms_matching_strategy: self.terms_matching_strategy,
+                query_syntax: self.query_syntax,
+                scoring_strategy: self.scoring_strategy,
+                words_limit: self.words_limit,
+                exhaustive_number_hits: self.exhaustive_number_hits,
+                rtxn: self.rtxn,
+                index: self.index,
+                semantic: self.semantic.clone(),
+                time_budget: self.time_budget.clone(),
+                ranking_score_threshold: self.ranking_score_threshold,
+                locales: self.locales.clone(),
+                excluded_documents: self.excluded_documents.clone(),
+                collapse: None,
+                collapsed_group: Some(group),
+            };
+            let result = match semantic_ratio {
+                Some(semantic_ratio) => search.execute_hybrid(semantic_ratio)?.0,
+                None => search.execute()?,
+            };
+
+            groups.push(CollapsedGroup {
+                total_hits: result.candidates.len(),
+                documents_ids: result.documents_ids,
+                document_scores: result.document_scores,
+            });
+        }
+        Ok(groups)
+    }
+}
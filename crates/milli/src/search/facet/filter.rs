@@ -228,6 +228,11 @@ impl<'a> Filter<'a> {
     pub fn use_contains_operator(&self) -> Option<&Token> {
         self.condition.use_contains_operator()
     }
+
+    /// Returns the fields the conditions of the filter apply to.
+    pub fn fields(&self) -> impl Iterator<Item = &str> + '_ {
+        self.condition.fids(MAX_FILTER_DEPTH).map(|token| token.value())
+    }
 }
 
 impl<'a> Filter<'a> {
@@ -4,8 +4,9 @@ use itertools::Itertools;
 use roaring::RoaringBitmap;
 
 use crate::score_details::{ScoreDetails, ScoreValue, ScoringStrategy};
-use crate::search::SemanticSearch;
-use crate::{MatchingWords, Result, Search, SearchResult};
+use crate::search::new::distinct_single_docid;
+use crate::search::{Collapse, SemanticSearch};
+use crate::{FieldId, Index, MatchingWords, Result, Search, SearchResult};
 
 struct ScoreWithRatioResult {
     matching_words: MatchingWords,
@@ -91,7 +92,8 @@ impl ScoreWithRatioResult {
         keyword_results: Self,
         from: usize,
         length: usize,
-    ) -> (SearchResult, u32) {
+        collapse: Option<(&Index, &heed::RoTxn<'_>, FieldId)>,
+    ) -> Result<(SearchResult, u32)> {
         #[derive(Clone, Copy)]
         enum ResultSource {
             Semantic,
@@ -107,6 +109,7 @@ impl ScoreWithRatioResult {
         );
 
         let mut documents_seen = RoaringBitmap::new();
+        let mut skipped = 0;
         for ((docid, (main_score, _sub_score)), source) in vector_results
             .document_scores
             .into_iter()
@@ -121,13 +124,25 @@ impl ScoreWithRatioResult {
                     compare_scores(left, right).is_ge()
                 },
             )
+        {
             // remove documents we already saw
-            .filter(|((docid, _), _)| documents_seen.insert(*docid))
+            if !documents_seen.insert(docid) {
+                continue;
+            }
+            // and the documents of the groups we already saw when collapsing
+            if let Some((index, rtxn, field_id)) = collapse {
+                distinct_single_docid(index, rtxn, field_id, docid, &mut documents_seen)?;
+            }
             // start skipping **after** the filter
-            .skip(from)
+            if skipped < from {
+                skipped += 1;
+                continue;
+            }
             // take **after** skipping
-            .take(length)
-        {
+            if documents_ids.len() == length {
+                break;
+            }
+
             if let ResultSource::Semantic = source {
                 semantic_hit_count += 1;
             }
@@ -136,10 +151,25 @@ impl ScoreWithRatioResult {
             document_scores.push(main_score);
         }
 
-        (
+        // both searches keep one document by group, but not necessarily the same one
+        let mut candidates = vector_results.candidates | keyword_results.candidates;
+        if let Some((index, rtxn, field_id)) = collapse {
+            let mut excluded = RoaringBitmap::new();
+            let mut remaining = RoaringBitmap::new();
+            for docid in &candidates {
+                if excluded.contains(docid) {
+                    continue;
+                }
+                distinct_single_docid(index, rtxn, field_id, docid, &mut excluded)?;
+                remaining.push(docid);
+            }
+            candidates = remaining;
+        }
+
+        Ok((
             SearchResult {
                 matching_words: keyword_results.matching_words,
-                candidates: vector_results.candidates | keyword_results.candidates,
+                candidates,
                 documents_ids,
                 document_scores,
                 degraded: vector_results.degraded | keyword_results.degraded,
@@ -147,7 +177,7 @@ impl ScoreWithRatioResult {
                     | keyword_results.used_negative_operator,
             },
             semantic_hit_count,
-        )
+        ))
     }
 }
 
@@ -166,6 +196,7 @@ impl Search<'_> {
             searchable_attributes: self.searchable_attributes,
             geo_param: self.geo_param,
             terms_matching_strategy: self.terms_matching_strategy,
+            query_syntax: self.query_syntax,
             scoring_strategy: ScoringStrategy::Detailed,
             words_limit: self.words_limit,
             exhaustive_number_hits: self.exhaustive_number_hits,
@@ -175,6 +206,9 @@ impl Search<'_> {
             time_budget: self.time_budget.clone(),
             ranking_score_threshold: self.ranking_score_threshold,
             locales: self.locales.clone(),
+            excluded_documents: self.excluded_documents.clone(),
+            collapse: self.collapse.clone(),
+            collapsed_group: self.collapsed_group.clone(),
         };
 
         let semantic = search.semantic.take();
@@ -226,8 +260,21 @@ impl Search<'_> {
         let keyword_results = ScoreWithRatioResult::new(keyword_results, 1.0 - semantic_ratio);
         let vector_results = ScoreWithRatioResult::new(vector_results, semantic_ratio);
 
-        let (merge_results, semantic_hit_count) =
-            ScoreWithRatioResult::merge(vector_results, keyword_results, self.offset, self.limit);
+        let collapse = match &self.collapse {
+            Some(Collapse { field, .. }) => self
+                .index
+                .fields_ids_map(self.rtxn)?
+                .id(field)
+                .map(|field_id| (self.index, self.rtxn, field_id)),
+            None => None,
+        };
+        let (merge_results, semantic_hit_count) = ScoreWithRatioResult::merge(
+            vector_results,
+            keyword_results,
+            self.offset,
+            self.limit,
+            collapse,
+        )?;
         assert!(merge_results.documents_ids.len() <= self.limit);
         Ok((merge_results, Some(semantic_hit_count)))
     }
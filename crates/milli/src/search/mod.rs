@@ -1,3 +1,4 @@
+use std::collections::BTreeSet;
 use std::fmt;
 use std::sync::Arc;
 
@@ -6,15 +7,19 @@ use levenshtein_automata::{LevenshteinAutomatonBuilder as LevBuilder, DFA};
 use once_cell::sync::Lazy;
 use roaring::bitmap::RoaringBitmap;
 
+pub use self::collapse::{Collapse, CollapsedGroup};
 pub use self::facet::{FacetDistribution, Filter, OrderBy, DEFAULT_VALUES_PER_FACET};
 pub use self::new::matches::{FormatOptions, MatchBounds, MatcherBuilder, MatchingWords};
-use self::new::{execute_vector_search, PartialSearchResult, VectorStoreStats};
+use self::new::{
+    execute_vector_search, ExplainSearchLoggers, PartialSearchResult, SearchExplanation,
+    VectorStoreStats,
+};
 use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::vector::Embedder;
 use crate::{
-    execute_search, filtered_universe, AscDesc, DefaultSearchLogger, DocumentId, Error, Index,
-    Result, SearchContext, TimeBudget, UserError,
+    execute_search, filtered_universe, AscDesc, DefaultSearchLogger, DocumentId, Error, FieldId,
+    Index, Result, SearchContext, SearchLogger, TimeBudget, UserError,
 };
 
 // Building these factories is not free.
@@ -22,11 +27,14 @@ static LEVDIST0: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(0, true));
 static LEVDIST1: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(1, true));
 static LEVDIST2: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(2, true));
 
+pub mod autocomplete;
+mod collapse;
 pub mod facet;
 mod fst_utils;
 pub mod hybrid;
 pub mod new;
 pub mod similar;
+pub mod suggestions;
 
 #[derive(Debug, Clone)]
 pub struct SemanticSearch {
@@ -47,6 +55,7 @@ pub struct Search<'a> {
     searchable_attributes: Option<&'a [String]>,
     geo_param: new::GeoSortParameter,
     terms_matching_strategy: TermsMatchingStrategy,
+    query_syntax: QuerySyntax,
     scoring_strategy: ScoringStrategy,
     words_limit: usize,
     exhaustive_number_hits: bool,
@@ -56,6 +65,10 @@ pub struct Search<'a> {
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
     locales: Option<Vec<Language>>,
+    excluded_documents: RoaringBitmap,
+    collapse: Option<Collapse>,
+    /// Restricts the search to the documents of a collapsed group, without applying any distinct.
+    collapsed_group: Option<RoaringBitmap>,
 }
 
 impl<'a> Search<'a> {
@@ -70,6 +83,7 @@ impl<'a> Search<'a> {
             searchable_attributes: None,
             geo_param: new::GeoSortParameter::default(),
             terms_matching_strategy: TermsMatchingStrategy::default(),
+            query_syntax: QuerySyntax::default(),
             scoring_strategy: Default::default(),
             exhaustive_number_hits: false,
             words_limit: 10,
@@ -79,6 +93,9 @@ impl<'a> Search<'a> {
             locales: None,
             time_budget: TimeBudget::max(),
             ranking_score_threshold: None,
+            excluded_documents: RoaringBitmap::new(),
+            collapse: None,
+            collapsed_group: None,
         }
     }
 
@@ -118,6 +135,13 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Keeps one document per value of the field, like [`Search::distinct`],
+    /// and lets [`Search::collapsed_groups`] return the best documents of each value.
+    pub fn collapse(&mut self, field: String, inner_hits: usize) -> &mut Search<'a> {
+        self.collapse = Some(Collapse { field, inner_hits });
+        self
+    }
+
     pub fn searchable_attributes(&mut self, searchable: &'a [String]) -> &mut Search<'a> {
         self.searchable_attributes = Some(searchable);
         self
@@ -128,6 +152,11 @@ impl<'a> Search<'a> {
         self
     }
 
+    pub fn query_syntax(&mut self, value: QuerySyntax) -> &mut Search<'a> {
+        self.query_syntax = value;
+        self
+    }
+
     pub fn scoring_strategy(&mut self, value: ScoringStrategy) -> &mut Search<'a> {
         self.scoring_strategy = value;
         self
@@ -177,16 +206,40 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Removes these documents from the candidates, whether they match the query and filter or not.
+    pub fn excluded_documents(&mut self, documents: RoaringBitmap) -> &mut Search<'a> {
+        self.excluded_documents = documents;
+        self
+    }
+
     pub fn execute_for_candidates(&self, has_vector_search: bool) -> Result<RoaringBitmap> {
         if has_vector_search {
             let ctx = SearchContext::new(self.index, self.rtxn)?;
-            filtered_universe(ctx.index, ctx.txn, &self.filter)
+            let universe = filtered_universe(ctx.index, ctx.txn, &self.filter)?;
+            Ok(universe - &self.excluded_documents)
         } else {
             Ok(self.execute()?.candidates)
         }
     }
 
     pub fn execute(&self) -> Result<SearchResult> {
+        self.execute_with_explanation(None).map(|(result, _)| result)
+    }
+
+    /// Executes the search and explains how the ranking rules sorted the documents,
+    /// following the buckets the tracked documents landed in.
+    pub fn execute_explained(
+        &self,
+        tracked_documents: RoaringBitmap,
+    ) -> Result<(SearchResult, SearchExplanation)> {
+        self.execute_with_explanation(Some(tracked_documents))
+            .map(|(result, explanation)| (result, explanation.unwrap_or_default()))
+    }
+
+    fn execute_with_explanation(
+        &self,
+        tracked_documents: Option<RoaringBitmap>,
+    ) -> Result<(SearchResult, Option<SearchExplanation>)> {
         let mut ctx = SearchContext::new(self.index, self.rtxn)?;
 
         if let Some(searchable_attributes) = self.searchable_attributes {
@@ -194,34 +247,60 @@ impl<'a> Search<'a> {
         }
 
         if let Some(distinct) = &self.distinct {
-            let filterable_fields = ctx.index.filterable_attributes_rules(ctx.txn)?;
-            // check if the distinct field is in the filterable fields
-            let matched_rule = matching_features(distinct, &filterable_fields);
-            let is_filterable = matched_rule.is_some_and(|(_, features)| features.is_filterable());
-
-            if !is_filterable {
-                // if not, remove the hidden fields from the filterable fields to generate the error message
-                let matching_patterns =
-                    filtered_matching_patterns(&filterable_fields, &|features| {
-                        features.is_filterable()
-                    });
-                let (valid_patterns, hidden_fields) =
-                    ctx.index.remove_hidden_fields(ctx.txn, matching_patterns)?;
-
-                // Get the matching rule index if any rule matched the attribute
-                let matching_rule_index = matched_rule.map(|(rule_index, _)| rule_index);
-
-                // and return the error
-                return Err(Error::UserError(UserError::InvalidDistinctAttribute {
-                    field: distinct.clone(),
-                    valid_patterns,
-                    hidden_fields,
-                    matching_rule_index,
-                }));
-            }
+            check_filterable_field(
+                &ctx,
+                distinct,
+                |field, valid_patterns, hidden_fields, matching_rule_index| {
+                    UserError::InvalidDistinctAttribute {
+                        field,
+                        valid_patterns,
+                        hidden_fields,
+                        matching_rule_index,
+                    }
+                },
+            )?;
+        }
+
+        if let Some(Collapse { field, .. }) = &self.collapse {
+            check_filterable_field(
+                &ctx,
+                field,
+                |field, valid_patterns, hidden_fields, matching_rule_index| {
+                    UserError::InvalidCollapseAttribute {
+                        field,
+                        valid_patterns,
+                        hidden_fields,
+                        matching_rule_index,
+                    }
+                },
+            )?;
         }
 
-        let universe = filtered_universe(ctx.index, ctx.txn, &self.filter)?;
+        // the collapse field takes the place of the distinct attribute,
+        // and all the documents of a collapsed group are returned
+        let distinct = match (&self.collapsed_group, &self.collapse, &self.distinct) {
+            (Some(_), _, _) => None,
+            (None, Some(Collapse { field, .. }), _) => Some(field.clone()),
+            (None, None, Some(distinct)) => Some(distinct.clone()),
+            (None, None, None) => ctx.index.distinct_field(ctx.txn)?.map(ToOwned::to_owned),
+        };
+
+        let mut explain_loggers = tracked_documents.map(ExplainSearchLoggers::new);
+        let (mut default_placeholder_logger, mut default_query_graph_logger) =
+            (DefaultSearchLogger, DefaultSearchLogger);
+        let (placeholder_search_logger, query_graph_logger): (
+            &mut dyn SearchLogger<_>,
+            &mut dyn SearchLogger<_>,
+        ) = match &mut explain_loggers {
+            Some(explain_loggers) => explain_loggers.search_loggers(),
+            None => (&mut default_placeholder_logger, &mut default_query_graph_logger),
+        };
+
+        let mut universe =
+            filtered_universe(ctx.index, ctx.txn, &self.filter)? - &self.excluded_documents;
+        if let Some(collapsed_group) = &self.collapsed_group {
+            universe &= collapsed_group;
+        }
         let PartialSearchResult {
             located_query_terms,
             candidates,
@@ -237,7 +316,7 @@ impl<'a> Search<'a> {
                     self.scoring_strategy,
                     universe,
                     &self.sort_criteria,
-                    &self.distinct,
+                    &distinct,
                     self.geo_param,
                     self.offset,
                     self.limit,
@@ -252,17 +331,18 @@ impl<'a> Search<'a> {
                 &mut ctx,
                 self.query.as_deref(),
                 self.terms_matching_strategy,
+                self.query_syntax,
                 self.scoring_strategy,
                 self.exhaustive_number_hits,
                 universe,
                 &self.sort_criteria,
-                &self.distinct,
+                &distinct,
                 self.geo_param,
                 self.offset,
                 self.limit,
                 Some(self.words_limit),
-                &mut DefaultSearchLogger,
-                &mut DefaultSearchLogger,
+                placeholder_search_logger,
+                query_graph_logger,
                 self.time_budget.clone(),
                 self.ranking_score_threshold,
                 self.locales.as_ref(),
@@ -275,20 +355,26 @@ impl<'a> Search<'a> {
             tracing::debug!("Vector store stats: total_time={total_time:.02?}, total_queries={total_queries}, total_results={total_results}");
         }
 
+        let explanation = match explain_loggers {
+            Some(explain_loggers) => Some(explain_loggers.finish(&mut ctx)?),
+            None => None,
+        };
+
         // consume context and located_query_terms to build MatchingWords.
         let matching_words = match located_query_terms {
             Some(located_query_terms) => MatchingWords::new(ctx, located_query_terms),
             None => MatchingWords::default(),
         };
 
-        Ok(SearchResult {
+        let result = SearchResult {
             matching_words,
             candidates,
             document_scores,
             documents_ids,
             degraded,
             used_negative_operator,
-        })
+        };
+        Ok((result, explanation))
     }
 }
 
@@ -304,6 +390,7 @@ impl fmt::Debug for Search<'_> {
             searchable_attributes,
             geo_param: _,
             terms_matching_strategy,
+            query_syntax,
             scoring_strategy,
             words_limit,
             exhaustive_number_hits,
@@ -313,6 +400,9 @@ impl fmt::Debug for Search<'_> {
             time_budget,
             ranking_score_threshold,
             locales,
+            excluded_documents,
+            collapse,
+            collapsed_group,
         } = self;
         f.debug_struct("Search")
             .field("query", query)
@@ -324,6 +414,7 @@ impl fmt::Debug for Search<'_> {
             .field("distinct", distinct)
             .field("searchable_attributes", searchable_attributes)
             .field("terms_matching_strategy", terms_matching_strategy)
+            .field("query_syntax", query_syntax)
             .field("scoring_strategy", scoring_strategy)
             .field("exhaustive_number_hits", exhaustive_number_hits)
             .field("words_limit", words_limit)
@@ -334,10 +425,45 @@ impl fmt::Debug for Search<'_> {
             .field("time_budget", time_budget)
             .field("ranking_score_threshold", ranking_score_threshold)
             .field("locales", locales)
+            .field("excluded_documents", excluded_documents)
+            .field("collapse", collapse)
+            .field("collapsed_group", collapsed_group)
             .finish()
     }
 }
 
+/// Returns the error built by `error` if the field is not filterable.
+fn check_filterable_field(
+    ctx: &SearchContext<'_>,
+    field: &str,
+    error: fn(String, BTreeSet<String>, bool, Option<usize>) -> UserError,
+) -> Result<()> {
+    let filterable_fields = ctx.index.filterable_attributes_rules(ctx.txn)?;
+    // check if the field is in the filterable fields
+    let matched_rule = matching_features(field, &filterable_fields);
+    let is_filterable = matched_rule.is_some_and(|(_, features)| features.is_filterable());
+
+    if !is_filterable {
+        // if not, remove the hidden fields from the filterable fields to generate the error message
+        let matching_patterns =
+            filtered_matching_patterns(&filterable_fields, &|features| features.is_filterable());
+        let (valid_patterns, hidden_fields) =
+            ctx.index.remove_hidden_fields(ctx.txn, matching_patterns)?;
+
+        // Get the matching rule index if any rule matched the attribute
+        let matching_rule_index = matched_rule.map(|(rule_index, _)| rule_index);
+
+        // and return the error
+        return Err(Error::UserError(error(
+            field.to_string(),
+            valid_patterns,
+            hidden_fields,
+            matching_rule_index,
+        )));
+    }
+    Ok(())
+}
+
 #[derive(Default, Debug)]
 pub struct SearchResult {
     pub matching_words: MatchingWords,
@@ -364,6 +490,15 @@ impl Default for TermsMatchingStrategy {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuerySyntax {
+    // quoted phrases and negative words only
+    #[default]
+    Simple,
+    // field restrictions, `OR` and parenthesised groups on top of the simple syntax
+    Advanced,
+}
+
 fn get_first(s: &str) -> &str {
     match s.chars().next() {
         Some(c) => &s[..c.len_utf8()],
@@ -385,6 +520,27 @@ pub fn build_dfa(word: &str, typos: u8, is_prefix: bool) -> DFA {
     }
 }
 
+/// Returns the documents containing the word, only in the given fields if there are some.
+fn word_docids_in_fields(
+    index: &Index,
+    rtxn: &heed::RoTxn<'_>,
+    word: &str,
+    fields_ids: Option<&[FieldId]>,
+) -> Result<RoaringBitmap> {
+    match fields_ids {
+        Some(fields_ids) => {
+            let mut docids = RoaringBitmap::new();
+            for &fid in fields_ids {
+                if let Some(fid_docids) = index.word_fid_docids.get(rtxn, &(word, fid))? {
+                    docids |= fid_docids;
+                }
+            }
+            Ok(docids)
+        }
+        None => Ok(index.word_docids.get(rtxn, word)?.unwrap_or_default()),
+    }
+}
+
 #[cfg(test)]
 mod test {
     #[allow(unused_imports)]
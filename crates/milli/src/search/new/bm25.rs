@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+
+use heed::types::Bytes;
+use heed::BytesDecode;
+use roaring::RoaringBitmap;
+
+use super::query_graph::{QueryGraph, QueryNodeData};
+use super::ranking_rules::{RankingRule, RankingRuleOutput};
+use super::resolve_query_graph::compute_query_term_subset_docids;
+use crate::heed_codec::StrBEU16Codec;
+use crate::score_details::{self, ScoreDetails};
+use crate::{CboRoaringBitmapCodec, DocumentId, Result, SearchContext, SearchLogger};
+
+/// Controls how fast the score of a term saturates with its frequency in a document.
+const K1: f64 = 1.2;
+/// Controls how much the length of a document penalizes its score.
+const B: f64 = 0.75;
+
+/// A ranking rule sorting the documents by decreasing [BM25](https://en.wikipedia.org/wiki/Okapi_BM25) score.
+///
+/// The frequency of a term in a document is the number of times its words appear in the
+/// searchable attributes of the document, and the length of a document is its number of words.
+/// Documents with the same score are returned in the same bucket.
+pub struct Bm25 {
+    query: Option<QueryGraph>,
+    max_score: f64,
+    buckets: std::vec::IntoIter<(f64, RoaringBitmap)>,
+}
+
+impl Bm25 {
+    pub fn new() -> Self {
+        Self { query: None, max_score: 0.0, buckets: Vec::new().into_iter() }
+    }
+}
+
+impl<'ctx> RankingRule<'ctx, QueryGraph> for Bm25 {
+    fn id(&self) -> String {
+        "bm25".to_owned()
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::bm25")]
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<QueryGraph>,
+        universe: &RoaringBitmap,
+        query: &QueryGraph,
+    ) -> Result<()> {
+        let number_of_documents = ctx.index.number_of_documents(ctx.txn)? as f64;
+
+        // the idf, the frequencies and the documents of the universe matching each term
+        let mut terms = Vec::new();
+        let mut matching_docids = RoaringBitmap::new();
+        let mut max_score = 0.0;
+        for (_, node) in query.nodes.iter() {
+            let QueryNodeData::Term(term) = &node.data else { continue };
+            let term = &term.term_subset;
+
+            let term_docids = compute_query_term_subset_docids(ctx, None, term)?;
+            if term_docids.is_empty() {
+                continue;
+            }
+            let document_frequency = term_docids.len() as f64;
+            let idf = (1.0
+                + (number_of_documents - document_frequency + 0.5) / (document_frequency + 0.5))
+                .ln();
+            max_score += idf * (K1 + 1.0);
+
+            // The words of the term appearing more than once are in the word frequency docids,
+            // the documents matching the term in other ways (prefix, phrase...) count it once.
+            let mut frequencies: HashMap<DocumentId, u32> = HashMap::new();
+            for word in term.all_single_words_except_prefix_db(ctx)? {
+                let Some(word_docids) = ctx.word_docids(Some(universe), word)? else { continue };
+                for docid in &word_docids {
+                    *frequencies.entry(docid).or_default() += 1;
+                }
+
+                let word = ctx.word_interner.get(word.interned()).as_str();
+                let mut prefix = Vec::with_capacity(word.len() + 1);
+                prefix.extend_from_slice(word.as_bytes());
+                prefix.push(0);
+                let iter = ctx
+                    .index
+                    .word_frequency_docids
+                    .remap_types::<Bytes, CboRoaringBitmapCodec>()
+                    .prefix_iter(ctx.txn, &prefix)?;
+                for result in iter {
+                    let (key, docids) = result?;
+                    let (_word, frequency) =
+                        StrBEU16Codec::bytes_decode(key).map_err(heed::Error::Decoding)?;
+                    for docid in &(docids & &word_docids) {
+                        *frequencies.entry(docid).or_default() += u32::from(frequency) - 1;
+                    }
+                }
+            }
+
+            let term_docids = term_docids & universe;
+            matching_docids |= &term_docids;
+            terms.push((idf, frequencies, term_docids));
+        }
+
+        let document_lengths = DocumentLengths::new(ctx, &matching_docids, number_of_documents)?;
+        let mut scores: HashMap<DocumentId, f64> = HashMap::new();
+        for (idf, frequencies, term_docids) in terms {
+            for docid in &term_docids {
+                let frequency = frequencies.get(&docid).copied().unwrap_or(1).max(1) as f64;
+                let length_norm = document_lengths.norm(docid);
+                let score = idf * frequency * (K1 + 1.0) / (frequency + K1 * length_norm);
+                *scores.entry(docid).or_default() += score;
+            }
+        }
+
+        let mut scores: Vec<_> = scores.into_iter().collect();
+        scores.sort_unstable_by(|(_, left), (_, right)| right.total_cmp(left));
+
+        let mut buckets: Vec<(f64, RoaringBitmap)> = Vec::new();
+        for (docid, score) in scores {
+            match buckets.last_mut() {
+                Some((bucket_score, docids)) if *bucket_score == score => {
+                    docids.insert(docid);
+                }
+                _ => buckets.push((score, RoaringBitmap::from_iter([docid]))),
+            }
+        }
+
+        self.query = Some(query.clone());
+        self.max_score = max_score;
+        self.buckets = buckets.into_iter();
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::bm25")]
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<QueryGraph>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<QueryGraph>>> {
+        let query = self.query.as_ref().unwrap().clone();
+        let max_score = self.max_score;
+
+        for (score, docids) in self.buckets.by_ref() {
+            let candidates = docids & universe;
+            if !candidates.is_empty() {
+                return Ok(Some(RankingRuleOutput {
+                    query,
+                    candidates,
+                    score: ScoreDetails::Bm25(score_details::Bm25 { score, max_score }),
+                }));
+            }
+        }
+
+        // the remaining documents don't contain any of the terms of the query
+        Ok(Some(RankingRuleOutput {
+            query,
+            candidates: universe.clone(),
+            score: ScoreDetails::Bm25(score_details::Bm25 { score: 0.0, max_score }),
+        }))
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::bm25")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<QueryGraph>,
+    ) {
+        self.query = None;
+        self.buckets = Vec::new().into_iter();
+    }
+}
+
+/// The number of words of the scored documents, compared to the average in the index.
+struct DocumentLengths {
+    lengths: HashMap<DocumentId, u32>,
+    average_length: f64,
+}
+
+impl DocumentLengths {
+    fn new(
+        ctx: &SearchContext<'_>,
+        documents: &RoaringBitmap,
+        number_of_documents: f64,
+    ) -> Result<Self> {
+        // the total lengths of the fields are computed at indexing time
+        let total_length: u64 = ctx.index.field_lengths(ctx.txn)?.values().sum();
+        let average_length =
+            if number_of_documents > 0.0 { total_length as f64 / number_of_documents } else { 0.0 };
+
+        let mut lengths: HashMap<DocumentId, u32> = HashMap::new();
+        if average_length > 0.0 && !documents.is_empty() {
+            for result in ctx.index.field_id_length_docids.iter(ctx.txn)? {
+                let ((_fid, length), docids) = result?;
+                for docid in &(docids & documents) {
+                    *lengths.entry(docid).or_default() += u32::from(length);
+                }
+            }
+        }
+
+        Ok(Self { lengths, average_length })
+    }
+
+    /// The length normalization of the document, 1 for a document of average length.
+    fn norm(&self, docid: DocumentId) -> f64 {
+        // the lengths are unknown in indexes that were not reindexed since they are computed
+        if self.average_length == 0.0 {
+            return 1.0;
+        }
+        let length = self.lengths.get(&docid).copied().unwrap_or_default() as f64;
+        1.0 - B + B * length / self.average_length
+    }
+}
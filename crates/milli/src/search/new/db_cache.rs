@@ -12,7 +12,8 @@ use super::interner::Interned;
 use super::Word;
 use crate::heed_codec::{BytesDecodeOwned, StrBEU16Codec};
 use crate::proximity::ProximityPrecision;
-use crate::update::MergeCboRoaringBitmaps;
+use crate::stemming::stem;
+use crate::update::{word_ngrams, MergeCboRoaringBitmaps};
 use crate::{
     CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, Result, SearchContext, U8StrStrCodec,
 };
@@ -45,6 +46,10 @@ pub struct DatabaseCache<'ctx> {
     pub word_prefix_fid_docids: FxHashMap<(Interned<String>, u16), Option<Cow<'ctx, [u8]>>>,
     pub word_fids: FxHashMap<Interned<String>, Vec<u16>>,
     pub word_prefix_fids: FxHashMap<Interned<String>, Vec<u16>>,
+
+    pub ngram_fids: Option<Vec<u16>>,
+    pub word_infix_fid_docids: FxHashMap<(Interned<String>, u16), Option<RoaringBitmap>>,
+    pub word_stem_fid_docids: FxHashMap<(Interned<String>, u16), Option<Cow<'ctx, [u8]>>>,
 }
 impl<'ctx> DatabaseCache<'ctx> {
     fn get_value<'v, K1, KC>(
@@ -572,6 +577,111 @@ impl<'ctx> SearchContext<'ctx> {
         )
     }
 
+    /// Returns the documents in which the given word appears as an infix of a word of the field.
+    ///
+    /// The documents are the intersection of the documents of every n-gram of the word,
+    /// so there may be false positives for words containing many n-grams.
+    pub fn get_db_word_infix_fid_docids(
+        &mut self,
+        universe: Option<&RoaringBitmap>,
+        word: Interned<String>,
+        fid: u16,
+    ) -> Result<Option<RoaringBitmap>> {
+        // if the requested fid isn't in the restricted list, return None.
+        if self.restricted_fids.as_ref().is_some_and(|fids| !fids.contains(&fid)) {
+            return Ok(None);
+        }
+
+        let docids = match self.db_cache.word_infix_fid_docids.get(&(word, fid)) {
+            Some(docids) => docids.clone(),
+            None => {
+                let mut docids: Option<RoaringBitmap> = None;
+                for ngram in word_ngrams(self.word_interner.get(word)) {
+                    let ngram_docids = self
+                        .index
+                        .ngram_fid_docids
+                        .get(self.txn, &(ngram, fid))?
+                        .unwrap_or_default();
+                    match docids.as_mut() {
+                        Some(docids) => *docids &= ngram_docids,
+                        None => docids = Some(ngram_docids),
+                    }
+                }
+                let docids = docids.filter(|docids| !docids.is_empty());
+                self.db_cache.word_infix_fid_docids.insert((word, fid), docids.clone());
+                docids
+            }
+        };
+
+        Ok(match universe {
+            Some(universe) => docids.map(|docids| docids & universe),
+            None => docids,
+        })
+    }
+
+    /// Returns the n-gram fields in which the given word appears as an infix.
+    pub fn get_db_word_infix_fids(&mut self, word: Interned<String>) -> Result<Vec<u16>> {
+        let ngram_fids = match &self.db_cache.ngram_fids {
+            Some(ngram_fids) => ngram_fids.clone(),
+            None => {
+                let mut ngram_fids: Vec<_> =
+                    self.index.ngram_attributes_ids(self.txn)?.into_iter().collect();
+                ngram_fids.sort_unstable();
+                self.db_cache.ngram_fids = Some(ngram_fids.clone());
+                ngram_fids
+            }
+        };
+
+        let mut fids = vec![];
+        for fid in ngram_fids {
+            if self.get_db_word_infix_fid_docids(None, word, fid)?.is_some() {
+                fids.push(fid);
+            }
+        }
+        Ok(fids)
+    }
+
+    /// Returns the documents in which a word sharing the stem of the given word appears inside the field.
+    pub fn get_db_word_stem_fid_docids(
+        &mut self,
+        universe: Option<&RoaringBitmap>,
+        word: Interned<String>,
+        fid: u16,
+    ) -> Result<Option<RoaringBitmap>> {
+        // if the requested fid isn't in the restricted list, return None.
+        if self.restricted_fids.as_ref().is_some_and(|fids| !fids.contains(&fid)) {
+            return Ok(None);
+        }
+
+        let word_stem = self
+            .stemmed_fids
+            .get(&fid)
+            .and_then(|&language| stem(language, self.word_interner.get(word)));
+        let Some(word_stem) = word_stem else { return Ok(None) };
+
+        DatabaseCache::get_value(
+            self.txn,
+            (word, fid),
+            &(word_stem.as_str(), fid),
+            &mut self.db_cache.word_stem_fid_docids,
+            universe,
+            self.index.stem_fid_docids.remap_data_type::<Bytes>(),
+        )
+    }
+
+    /// Returns the stemmed fields in which a word sharing the stem of the given word appears.
+    pub fn get_db_word_stem_fids(&mut self, word: Interned<String>) -> Result<Vec<u16>> {
+        let stemmed_fids: Vec<_> = self.stemmed_fids.keys().copied().collect();
+
+        let mut fids = vec![];
+        for fid in stemmed_fids {
+            if self.get_db_word_stem_fid_docids(None, word, fid)?.is_some() {
+                fids.push(fid);
+            }
+        }
+        Ok(fids)
+    }
+
     pub fn get_db_word_fids(&mut self, word: Interned<String>) -> Result<Vec<u16>> {
         let fids = match self.db_cache.word_fids.entry(word) {
             Entry::Occupied(fids) => fids.get().clone(),
@@ -15,3 +15,9 @@ pub const MAX_SYNONYM_PHRASE_COUNT: usize = 50;
 ///
 /// This limit is meant to gracefully handle the case where a word would have very long phrases as synonyms.
 pub const MAX_SYNONYM_WORD_COUNT: usize = 100;
+
+/// Maximum number of groups of operands nested in each other in a query written with the advanced syntax.
+pub const MAX_NESTED_GROUPS: usize = 16;
+
+/// Maximum distance between the words of a phrase, the largest one stored in the word pair proximity database.
+pub const MAX_PHRASE_SLOP: u8 = crate::proximity::MAX_DISTANCE as u8 - 1;
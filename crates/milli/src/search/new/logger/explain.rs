@@ -0,0 +1,255 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use roaring::RoaringBitmap;
+
+use crate::search::new::query_graph::QueryNodeData;
+use crate::search::new::query_term::QueryTermDerivations;
+use crate::search::new::ranking_rules::{BoxRankingRule, PlaceholderQuery, RankingRuleQueryTrait};
+use crate::search::new::{QueryGraph, RankingRule, SearchContext, SearchLogger};
+use crate::{DocumentId, Result};
+
+/// The maximum number of bucket sizes kept for each ranking rule.
+const MAX_BUCKET_SIZES: usize = 100;
+
+/// How the ranking rules sorted the documents of a search.
+#[derive(Debug, Clone, Default)]
+pub struct SearchExplanation {
+    pub ranking_rules: Vec<RankingRuleExplanation>,
+    /// The nodes of the query graph, empty for a placeholder search.
+    pub query_graph: Vec<QueryNodeExplanation>,
+    /// The buckets the tracked documents landed in, from the first ranking rule to the last.
+    pub documents: BTreeMap<DocumentId, Vec<DocumentBucket>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RankingRuleExplanation {
+    pub name: String,
+    /// The time spent computing the buckets of the ranking rule, without its children.
+    pub duration: Duration,
+    /// The number of times the ranking rule sorted a bucket of its parent.
+    pub iterations: usize,
+    pub bucket_count: usize,
+    /// The sizes of the first buckets, in the order they were computed.
+    pub bucket_sizes: Vec<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct QueryNodeExplanation {
+    pub id: u16,
+    /// The positions of the words of the query the node stands for.
+    pub positions: (u16, u16),
+    pub derivations: QueryTermDerivations,
+    /// The ids of the nodes that can follow this one, the end of the query is not listed.
+    pub successors: Vec<u16>,
+}
+
+/// The bucket a document landed in during an iteration of a ranking rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentBucket {
+    /// The index of the ranking rule.
+    pub ranking_rule: usize,
+    /// The rank of the bucket among the buckets of the iteration, starting from 0.
+    pub bucket: usize,
+    pub bucket_size: u64,
+}
+
+/// A [`SearchLogger`] gathering how the ranking rules sorted the documents.
+///
+/// The time spent between two events is attributed to the ranking rule logging the second one.
+pub struct ExplainSearchLogger<Q> {
+    tracked_documents: RoaringBitmap,
+    initial_query: Option<Q>,
+    ranking_rules: Vec<RankingRuleExplanation>,
+    /// The number of buckets computed by each ranking rule during its current iteration.
+    current_buckets: Vec<usize>,
+    documents: BTreeMap<DocumentId, Vec<DocumentBucket>>,
+    last_event: Instant,
+}
+
+impl<Q> ExplainSearchLogger<Q> {
+    /// Creates a logger following the buckets the given documents land in.
+    pub fn new(tracked_documents: RoaringBitmap) -> Self {
+        Self {
+            tracked_documents,
+            initial_query: None,
+            ranking_rules: Vec::new(),
+            current_buckets: Vec::new(),
+            documents: BTreeMap::new(),
+            last_event: Instant::now(),
+        }
+    }
+
+    /// Whether the search went through this logger.
+    pub fn is_used(&self) -> bool {
+        self.initial_query.is_some()
+    }
+
+    fn record_time(&mut self, ranking_rule_idx: usize) {
+        let now = Instant::now();
+        if let Some(ranking_rule) = self.ranking_rules.get_mut(ranking_rule_idx) {
+            ranking_rule.duration += now - self.last_event;
+        }
+        self.last_event = now;
+    }
+
+    fn into_explanation(self, query_graph: Vec<QueryNodeExplanation>) -> SearchExplanation {
+        SearchExplanation {
+            ranking_rules: self.ranking_rules,
+            query_graph,
+            documents: self.documents,
+        }
+    }
+}
+
+impl ExplainSearchLogger<QueryGraph> {
+    pub fn finish(mut self, ctx: &mut SearchContext<'_>) -> Result<SearchExplanation> {
+        let mut nodes = Vec::new();
+        if let Some(query_graph) = self.initial_query.take() {
+            for (id, node) in query_graph.nodes.iter() {
+                let QueryNodeData::Term(term) = &node.data else { continue };
+                let successors = node
+                    .successors
+                    .iter()
+                    .filter(|successor| {
+                        matches!(query_graph.nodes.get(*successor).data, QueryNodeData::Term(_))
+                    })
+                    .map(|successor| successor.into_raw())
+                    .collect();
+                nodes.push(QueryNodeExplanation {
+                    id: id.into_raw(),
+                    positions: (*term.positions.start(), *term.positions.end()),
+                    derivations: term.term_subset.derivations(ctx)?,
+                    successors,
+                });
+            }
+        }
+        Ok(self.into_explanation(nodes))
+    }
+}
+
+impl ExplainSearchLogger<PlaceholderQuery> {
+    pub fn finish(self) -> SearchExplanation {
+        self.into_explanation(Vec::new())
+    }
+}
+
+/// The loggers explaining a search, whether it is a placeholder search or not.
+pub struct ExplainSearchLoggers {
+    placeholder: ExplainSearchLogger<PlaceholderQuery>,
+    query_graph: ExplainSearchLogger<QueryGraph>,
+}
+
+impl ExplainSearchLoggers {
+    pub fn new(tracked_documents: RoaringBitmap) -> Self {
+        Self {
+            placeholder: ExplainSearchLogger::new(tracked_documents.clone()),
+            query_graph: ExplainSearchLogger::new(tracked_documents),
+        }
+    }
+
+    pub fn search_loggers(
+        &mut self,
+    ) -> (&mut dyn SearchLogger<PlaceholderQuery>, &mut dyn SearchLogger<QueryGraph>) {
+        (&mut self.placeholder, &mut self.query_graph)
+    }
+
+    /// Builds the explanation of the search, the query graph must be explained before the context is consumed.
+    pub fn finish(self, ctx: &mut SearchContext<'_>) -> Result<SearchExplanation> {
+        if self.query_graph.is_used() {
+            self.query_graph.finish(ctx)
+        } else {
+            Ok(self.placeholder.finish())
+        }
+    }
+}
+
+impl<Q: RankingRuleQueryTrait> SearchLogger<Q> for ExplainSearchLogger<Q> {
+    fn initial_query(&mut self, query: &Q) {
+        self.initial_query = Some(query.clone());
+        self.last_event = Instant::now();
+    }
+
+    fn initial_universe(&mut self, _universe: &RoaringBitmap) {}
+
+    fn query_for_initial_universe(&mut self, _query: &Q) {}
+
+    fn ranking_rules(&mut self, rr: &[BoxRankingRule<'_, Q>]) {
+        self.ranking_rules = rr
+            .iter()
+            .map(|rr| RankingRuleExplanation {
+                name: rr.id(),
+                duration: Duration::ZERO,
+                iterations: 0,
+                bucket_count: 0,
+                bucket_sizes: Vec::new(),
+            })
+            .collect();
+        self.current_buckets = vec![0; rr.len()];
+    }
+
+    fn start_iteration_ranking_rule(
+        &mut self,
+        ranking_rule_idx: usize,
+        _ranking_rule: &dyn RankingRule<'_, Q>,
+        _query: &Q,
+        _universe: &RoaringBitmap,
+    ) {
+        self.last_event = Instant::now();
+        if let Some(ranking_rule) = self.ranking_rules.get_mut(ranking_rule_idx) {
+            ranking_rule.iterations += 1;
+            self.current_buckets[ranking_rule_idx] = 0;
+        }
+    }
+
+    fn next_bucket_ranking_rule(
+        &mut self,
+        ranking_rule_idx: usize,
+        _ranking_rule: &dyn RankingRule<'_, Q>,
+        _universe: &RoaringBitmap,
+        candidates: &RoaringBitmap,
+    ) {
+        self.record_time(ranking_rule_idx);
+        let Some(ranking_rule) = self.ranking_rules.get_mut(ranking_rule_idx) else { return };
+
+        let bucket_size = candidates.len();
+        ranking_rule.bucket_count += 1;
+        if ranking_rule.bucket_sizes.len() < MAX_BUCKET_SIZES {
+            ranking_rule.bucket_sizes.push(bucket_size);
+        }
+
+        let bucket = self.current_buckets[ranking_rule_idx];
+        self.current_buckets[ranking_rule_idx] += 1;
+        for docid in &(candidates & &self.tracked_documents) {
+            let buckets = self.documents.entry(docid).or_default();
+            // the buckets of a previous iteration of the ranking rule are not the ones the document landed in
+            buckets.retain(|bucket| bucket.ranking_rule < ranking_rule_idx);
+            buckets.push(DocumentBucket { ranking_rule: ranking_rule_idx, bucket, bucket_size });
+        }
+    }
+
+    fn skip_bucket_ranking_rule(
+        &mut self,
+        ranking_rule_idx: usize,
+        _ranking_rule: &dyn RankingRule<'_, Q>,
+        _candidates: &RoaringBitmap,
+    ) {
+        self.record_time(ranking_rule_idx);
+    }
+
+    fn end_iteration_ranking_rule(
+        &mut self,
+        ranking_rule_idx: usize,
+        _ranking_rule: &dyn RankingRule<'_, Q>,
+        _universe: &RoaringBitmap,
+    ) {
+        self.record_time(ranking_rule_idx);
+    }
+
+    fn add_to_results(&mut self, _docids: &[u32]) {
+        self.last_event = Instant::now();
+    }
+
+    fn log_internal_state(&mut self, _rr: &dyn Any) {}
+}
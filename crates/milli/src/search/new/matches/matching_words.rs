@@ -125,15 +125,18 @@ impl<'a> Iterator for MatchesIter<'a, '_> {
                 // create a PartialMatch struct to make it compute the first match
                 // instead of duplicating the code.
                 let ids = &located_phrase.positions;
-                // collect the references of words from the interner.
+                // collect the references of words from the interner,
+                // the stop words of a phrase with a slop are ignored.
                 let words = phrase
                     .words
                     .iter()
+                    .filter(|word| phrase.slop == 0 || word.is_some())
                     .map(|word| {
                         word.map(|word| self.matching_words.word_interner.get(word).as_str())
                     })
                     .collect();
-                let partial = PartialMatch { matching_words: words, ids };
+                let partial =
+                    PartialMatch { matching_words: words, ids, slop: phrase.slop, skipped: None };
 
                 partial.match_token(self.token).or_else(|| self.next())
             }
@@ -161,6 +164,10 @@ pub enum MatchType<'a> {
 pub struct PartialMatch<'a> {
     matching_words: Vec<Option<&'a str>>,
     ids: &'a RangeInclusive<WordId>,
+    /// The maximum distance between two matching words of a phrase, `0` for an exact phrase.
+    slop: u8,
+    /// The number of words seen since the last matching word, `None` until a word matched.
+    skipped: Option<u8>,
 }
 
 impl<'a> PartialMatch<'a> {
@@ -169,6 +176,10 @@ impl<'a> PartialMatch<'a> {
     /// - Partial if the given token matches the partial match but doesn't complete it
     /// - Full if the given token completes the partial match
     pub fn match_token(self, token: &Token<'_>) -> Option<MatchType<'a>> {
+        if self.slop > 0 {
+            return self.match_token_with_slop(token);
+        }
+
         let Self { mut matching_words, ids, .. } = self;
 
         let is_matching = match matching_words.first()? {
@@ -182,7 +193,7 @@ impl<'a> PartialMatch<'a> {
         // return a new Partial match allowing the highlighter to continue.
         if is_matching && matching_words.len() > 1 {
             matching_words.remove(0);
-            Some(MatchType::Partial(Self { matching_words, ids }))
+            Some(MatchType::Partial(Self { matching_words, ids, slop: 0, skipped: None }))
         // if there is no remaining word to match in the phrase and the current token is matching,
         // return a Full match.
         } else if is_matching {
@@ -196,6 +207,37 @@ impl<'a> PartialMatch<'a> {
             None
         }
     }
+
+    /// The words of a phrase with a slop can match in any order, as long as
+    /// at most `slop - 1` other words are found between two of them.
+    fn match_token_with_slop(self, token: &Token<'_>) -> Option<MatchType<'a>> {
+        let Self { mut matching_words, ids, slop, skipped } = self;
+
+        match matching_words.iter().position(|word| *word == Some(token.lemma())) {
+            Some(index) => {
+                matching_words.remove(index);
+                if matching_words.is_empty() {
+                    Some(MatchType::Full {
+                        char_count: token.char_end - token.char_start,
+                        byte_len: token.byte_end - token.byte_start,
+                        ids,
+                    })
+                } else {
+                    Some(MatchType::Partial(Self { matching_words, ids, slop, skipped: Some(0) }))
+                }
+            }
+            // the first word of the match must be one of the words of the phrase.
+            None => match skipped {
+                Some(skipped) if skipped + 1 < slop => Some(MatchType::Partial(Self {
+                    matching_words,
+                    ids,
+                    slop,
+                    skipped: Some(skipped + 1),
+                })),
+                _ => None,
+            },
+        }
+    }
 }
 
 impl fmt::Debug for MatchingWords {
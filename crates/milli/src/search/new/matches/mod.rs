@@ -508,6 +508,7 @@ mod tests {
                 &mut ctx,
                 Some(query),
                 crate::TermsMatchingStrategy::default(),
+                crate::QuerySyntax::default(),
                 crate::score_details::ScoringStrategy::Skip,
                 false,
                 universe,
@@ -801,6 +802,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn format_highlight_phrase_with_slop() {
+        let temp_index = TempIndex::new();
+
+        let text = "The groundbreaking invention had the power to split the world between those who embraced progress and those who resisted change!";
+        temp_index
+            .add_documents(documents!([
+                { "id": 1, "text": text }
+            ]))
+            .unwrap();
+
+        let rtxn = temp_index.read_txn().unwrap();
+
+        let format_options = FormatOptions { highlight: true, crop: None };
+
+        // the words of the phrase may be separated by other words.
+        let builder = MatcherBuilder::new_test(&rtxn, &temp_index, "\"invention power\"~3");
+        let mut matcher = builder.build(text, None);
+        insta::assert_snapshot!(
+            matcher.format(format_options),
+            @"The groundbreaking <em>invention had the power</em> to split the world between those who embraced progress and those who resisted change!"
+        );
+
+        // and may appear in any order.
+        let builder = MatcherBuilder::new_test(&rtxn, &temp_index, "\"world split\"~2");
+        let mut matcher = builder.build(text, None);
+        insta::assert_snapshot!(
+            matcher.format(format_options),
+            @"The groundbreaking invention had the power to <em>split the world</em> between those who embraced progress and those who resisted change!"
+        );
+
+        // but not further away than the slop.
+        let builder = MatcherBuilder::new_test(&rtxn, &temp_index, "\"invention power\"~2");
+        let mut matcher = builder.build(text, None);
+        insta::assert_snapshot!(
+            matcher.format(format_options),
+            @"The groundbreaking invention had the power to split the world between those who embraced progress and those who resisted change!"
+        );
+    }
+
     #[test]
     fn format_highlight_crop_phrase_query() {
         //! testing: https://github.com/meilisearch/meilisearch/issues/3975
@@ -14,6 +14,7 @@ mod ranking_rules;
 mod resolve_query_graph;
 mod small_bitmap;
 
+mod bm25;
 mod exact_attribute;
 mod sort;
 mod vector_sort;
@@ -21,10 +22,11 @@ mod vector_sort;
 #[cfg(test)]
 mod tests;
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::ops::AddAssign;
 use std::time::Duration;
 
+use bm25::Bm25;
 use bucket_sort::{bucket_sort, BucketSortOutput};
 use charabia::{Language, TokenizerBuilder};
 use db_cache::DatabaseCache;
@@ -32,19 +34,29 @@ use exact_attribute::ExactAttribute;
 use graph_based_ranking_rule::{Exactness, Fid, Position, Proximity, Typo};
 use heed::RoTxn;
 use interner::{DedupInterner, Interner};
+pub use logger::explain::{
+    DocumentBucket, ExplainSearchLogger, ExplainSearchLoggers, QueryNodeExplanation,
+    RankingRuleExplanation, SearchExplanation,
+};
 pub use logger::visual::VisualSearchLogger;
 pub use logger::{DefaultSearchLogger, SearchLogger};
 use query_graph::{QueryGraph, QueryNode};
+pub(crate) use query_term::typos_allowed_by_settings;
+pub use query_term::QueryTermDerivations;
 use query_term::{
-    located_query_terms_from_tokens, ExtractedTokens, LocatedQueryTerm, Phrase, QueryTerm,
+    located_query_clauses_from_advanced_query, located_query_terms_from_tokens, ExtractedClauses,
+    ExtractedTokens, LocatedQueryTerm, Phrase, QueryClause, QueryTerm, QueryTermSubset,
 };
 use ranking_rules::{
     BoxRankingRule, PlaceholderQuery, RankingRule, RankingRuleOutput, RankingRuleQueryTrait,
 };
-use resolve_query_graph::{compute_query_graph_docids, PhraseDocIdsCache};
+use resolve_query_graph::{
+    compute_query_graph_docids, compute_query_term_subset_docids, PhraseDocIdsCache,
+};
 use roaring::RoaringBitmap;
 use sort::Sort;
 
+pub(crate) use self::distinct::distinct_single_docid;
 use self::distinct::facet_string_values;
 use self::geo_sort::GeoSort;
 pub use self::geo_sort::Parameter as GeoSortParameter;
@@ -59,8 +71,8 @@ use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::search::new::distinct::apply_distinct_rule;
 use crate::vector::Embedder;
 use crate::{
-    AscDesc, DocumentId, FieldId, Filter, Index, Member, Result, TermsMatchingStrategy, TimeBudget,
-    UserError, Weight,
+    AscDesc, DocumentId, FieldId, Filter, Index, Member, QuerySyntax, Result,
+    TermsMatchingStrategy, TimeBudget, UserError, Weight,
 };
 
 /// A structure used throughout the execution of a search query.
@@ -75,6 +87,8 @@ pub struct SearchContext<'ctx> {
     pub restricted_fids: Option<RestrictedFids>,
     pub prefix_search: PrefixSearch,
     pub vector_store_stats: Option<VectorStoreStats>,
+    /// The searchable fields whose words are stemmed, with the language of their stemmer
+    pub stemmed_fids: BTreeMap<FieldId, Language>,
 }
 
 impl<'ctx> SearchContext<'ctx> {
@@ -93,6 +107,7 @@ impl<'ctx> SearchContext<'ctx> {
         }
 
         let prefix_search = index.prefix_search(txn)?.unwrap_or_default();
+        let stemmed_fids = index.stemmed_fields_ids(txn)?;
 
         Ok(Self {
             index,
@@ -105,6 +120,7 @@ impl<'ctx> SearchContext<'ctx> {
             restricted_fids: None,
             prefix_search,
             vector_store_stats: None,
+            stemmed_fids,
         })
     }
 
@@ -294,6 +310,50 @@ fn resolve_negative_phrases(
     Ok(negative_bitmap)
 }
 
+#[tracing::instrument(level = "trace", skip_all, target = "search::query")]
+fn resolve_negative_clauses(
+    ctx: &mut SearchContext<'_>,
+    negative_clauses: &[QueryClause],
+) -> Result<RoaringBitmap> {
+    let mut negative_bitmap = RoaringBitmap::new();
+    for clause in negative_clauses {
+        negative_bitmap |= resolve_query_clause(ctx, clause)?;
+    }
+    Ok(negative_bitmap)
+}
+
+/// Return the documents matching the exact words of a clause of an advanced query.
+fn resolve_query_clause(
+    ctx: &mut SearchContext<'_>,
+    clause: &QueryClause,
+) -> Result<RoaringBitmap> {
+    match clause {
+        QueryClause::Term(term) => {
+            let mut term_subset = QueryTermSubset::full(term.value);
+            term_subset.keep_only_exact_term(ctx);
+            compute_query_term_subset_docids(ctx, None, &term_subset)
+        }
+        QueryClause::And(clauses) => {
+            let mut docids: Option<RoaringBitmap> = None;
+            for clause in clauses {
+                let clause_docids = resolve_query_clause(ctx, clause)?;
+                docids = Some(match docids {
+                    Some(docids) => docids & clause_docids,
+                    None => clause_docids,
+                });
+            }
+            Ok(docids.unwrap_or_default())
+        }
+        QueryClause::Or(clauses) => {
+            let mut docids = RoaringBitmap::new();
+            for clause in clauses {
+                docids |= resolve_query_clause(ctx, clause)?;
+            }
+            Ok(docids)
+        }
+    }
+}
+
 /// Return the list of initialised ranking rules to be used for a placeholder search.
 fn get_ranking_rules_for_placeholder_search<'ctx>(
     ctx: &SearchContext<'ctx>,
@@ -312,7 +372,8 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
             | crate::Criterion::Typo
             | crate::Criterion::Attribute
             | crate::Criterion::Proximity
-            | crate::Criterion::Exactness => continue,
+            | crate::Criterion::Exactness
+            | crate::Criterion::Bm25 => continue,
             crate::Criterion::Sort => {
                 if sort {
                     continue;
@@ -373,7 +434,8 @@ fn get_ranking_rules_for_vector<'ctx>(
             | crate::Criterion::Typo
             | crate::Criterion::Proximity
             | crate::Criterion::Attribute
-            | crate::Criterion::Exactness => {
+            | crate::Criterion::Exactness
+            | crate::Criterion::Bm25 => {
                 if !vector {
                     let vector_candidates = ctx.index.documents_ids(ctx.txn)?;
                     let vector_sort = VectorSort::new(
@@ -437,6 +499,7 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
     let mut sort = false;
     let mut attribute = false;
     let mut exactness = false;
+    let mut bm25 = false;
     let mut sorted_fields = HashSet::new();
     let mut geo_sorted = false;
 
@@ -448,12 +511,13 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
     let mut ranking_rules: Vec<BoxRankingRule<'ctx, QueryGraph>> = vec![];
     let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
     for rr in settings_ranking_rules {
-        // Add Words before any of: typo, proximity, attribute
+        // Add Words before any of: typo, proximity, attribute, exactness, bm25
         match rr {
             crate::Criterion::Typo
             | crate::Criterion::Attribute
             | crate::Criterion::Proximity
-            | crate::Criterion::Exactness => {
+            | crate::Criterion::Exactness
+            | crate::Criterion::Bm25 => {
                 if !words {
                     ranking_rules.push(Box::new(Words::new(terms_matching_strategy)));
                     words = true;
@@ -513,6 +577,13 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
                 ranking_rules.push(Box::new(Exactness::new()));
                 exactness = true;
             }
+            crate::Criterion::Bm25 => {
+                if bm25 {
+                    continue;
+                }
+                ranking_rules.push(Box::new(Bm25::new()));
+                bm25 = true;
+            }
             crate::Criterion::Asc(field_name) => {
                 if sorted_fields.contains(&field_name) {
                     continue;
@@ -666,6 +737,7 @@ pub fn execute_search(
     ctx: &mut SearchContext<'_>,
     query: Option<&str>,
     terms_matching_strategy: TermsMatchingStrategy,
+    query_syntax: QuerySyntax,
     scoring_strategy: ScoringStrategy,
     exhaustive_number_hits: bool,
     mut universe: RoaringBitmap,
@@ -685,7 +757,7 @@ pub fn execute_search(
 
     let mut used_negative_operator = false;
     let mut located_query_terms = None;
-    let query_terms = if let Some(query) = query {
+    let query_graph = if let Some(query) = query {
         let span = tracing::trace_span!(target: "search::tokens", "tokenizer_builder");
         let entered = span.enter();
 
@@ -716,6 +788,8 @@ pub fn execute_search(
             Some(locales) => {
                 if !locales.is_empty() {
                     tokbuilder.allow_list(locales);
+                    // only stem the query words with the stemmers of the requested locales
+                    ctx.stemmed_fids.retain(|_, language| locales.contains(language));
                 }
             }
             None => {
@@ -753,33 +827,51 @@ pub fn execute_search(
         let tokenizer = tokbuilder.build();
         drop(entered);
 
-        let span = tracing::trace_span!(target: "search::tokens", "tokenize");
-        let entered = span.enter();
-        let tokens = tokenizer.tokenize(query);
-        drop(entered);
-
-        let ExtractedTokens { query_terms, negative_words, negative_phrases } =
-            located_query_terms_from_tokens(ctx, tokens, words_limit)?;
-        used_negative_operator = !negative_words.is_empty() || !negative_phrases.is_empty();
-
-        let ignored_documents = resolve_negative_words(ctx, Some(&universe), &negative_words)?;
-        let ignored_phrases = resolve_negative_phrases(ctx, &negative_phrases)?;
-
-        universe -= ignored_documents;
-        universe -= ignored_phrases;
-
-        if query_terms.is_empty() {
-            // Do a placeholder search instead
-            None
-        } else {
-            Some(query_terms)
+        match query_syntax {
+            QuerySyntax::Simple => {
+                let span = tracing::trace_span!(target: "search::tokens", "tokenize");
+                let entered = span.enter();
+                let tokens = tokenizer.tokenize(query);
+                drop(entered);
+
+                let ExtractedTokens { query_terms, negative_words, negative_phrases } =
+                    located_query_terms_from_tokens(ctx, tokens, words_limit)?;
+                used_negative_operator = !negative_words.is_empty() || !negative_phrases.is_empty();
+
+                let ignored_documents =
+                    resolve_negative_words(ctx, Some(&universe), &negative_words)?;
+                let ignored_phrases = resolve_negative_phrases(ctx, &negative_phrases)?;
+
+                universe -= ignored_documents;
+                universe -= ignored_phrases;
+
+                if query_terms.is_empty() {
+                    // Do a placeholder search instead
+                    None
+                } else {
+                    Some(QueryGraph::from_query(ctx, &query_terms)?)
+                }
+            }
+            QuerySyntax::Advanced => {
+                let ExtractedClauses { clauses, negative_clauses } =
+                    located_query_clauses_from_advanced_query(ctx, &tokenizer, query, words_limit)?;
+                used_negative_operator = !negative_clauses.is_empty();
+
+                universe -= resolve_negative_clauses(ctx, &negative_clauses)?;
+
+                if clauses.is_empty() {
+                    // Do a placeholder search instead
+                    None
+                } else {
+                    Some(QueryGraph::from_advanced_query(ctx, &clauses)?)
+                }
+            }
         }
     } else {
         None
     };
 
-    let bucket_sort_output = if let Some(query_terms) = query_terms {
-        let (graph, new_located_query_terms) = QueryGraph::from_query(ctx, &query_terms)?;
+    let bucket_sort_output = if let Some((graph, new_located_query_terms)) = query_graph {
         located_query_terms = Some(new_located_query_terms);
 
         let ranking_rules = get_ranking_rules_for_query_graph_search(
@@ -829,12 +921,7 @@ pub fn execute_search(
     // The candidates is the universe unless the exhaustive number of hits
     // is requested and a distinct attribute is set.
     if exhaustive_number_hits {
-        let distinct_field = match distinct.as_deref() {
-            Some(distinct) => Some(distinct),
-            None => ctx.index.distinct_field(ctx.txn)?,
-        };
-
-        if let Some(f) = distinct_field {
+        if let Some(f) = distinct.as_deref() {
             if let Some(distinct_fid) = fields_ids_map.id(f) {
                 all_candidates = apply_distinct_rule(ctx, distinct_fid, &all_candidates)?.remaining;
             }
@@ -7,7 +7,8 @@ use roaring::RoaringBitmap;
 
 use super::interner::{FixedSizeInterner, Interned};
 use super::query_term::{
-    self, number_of_typos_allowed, LocatedQueryTerm, LocatedQueryTermSubset, QueryTermSubset,
+    self, number_of_typos_allowed, LocatedQueryTerm, LocatedQueryTermSubset, QueryClause,
+    QueryTermSubset,
 };
 use super::small_bitmap::SmallBitmap;
 use super::SearchContext;
@@ -178,6 +179,101 @@ impl QueryGraph {
         Ok((graph, new_located_query_terms))
     }
 
+    /// Build the query graph from the clauses of a query written with the advanced syntax,
+    /// return the list of the located query terms it contains, including the ngrams.
+    ///
+    /// Each clause is given its own term id. The alternatives of a disjunction are parallel
+    /// paths between the nodes of the surrounding clauses, and the ngrams are only built
+    /// between consecutive plain words.
+    pub fn from_advanced_query(
+        ctx: &mut SearchContext<'_>,
+        clauses: &[QueryClause],
+    ) -> Result<(QueryGraph, Vec<LocatedQueryTerm>)> {
+        let nbr_typos = number_of_typos_allowed(ctx)?;
+
+        let mut nodes_data: Vec<QueryNodeData> = vec![QueryNodeData::Start, QueryNodeData::End];
+        let mut edges = vec![];
+        let mut located_query_terms = vec![];
+        // The nodes each clause starts and ends with
+        let mut clauses_bounds: Vec<(Vec<u16>, Vec<u16>)> = vec![];
+        let mut plain_words = vec![];
+
+        for (term_id, clause) in clauses.iter().enumerate() {
+            let term_id = term_id as u8;
+            let mandatory = clause.is_mandatory(ctx);
+            let bounds = add_clause(
+                &mut nodes_data,
+                &mut edges,
+                &mut located_query_terms,
+                clause,
+                term_id,
+                mandatory,
+            );
+            clauses_bounds.push(bounds);
+
+            match clause {
+                QueryClause::Term(term) if !mandatory => plain_words.push(term.clone()),
+                _ => {
+                    plain_words.clear();
+                    continue;
+                }
+            }
+
+            for ngram_len in [2, 3] {
+                let Some(words) =
+                    plain_words.len().checked_sub(ngram_len).map(|s| &plain_words[s..])
+                else {
+                    continue;
+                };
+                if let Some(ngram) = query_term::make_ngram(ctx, words, &nbr_typos)? {
+                    let first_term_id = term_id + 1 - ngram_len as u8;
+                    let ngram_idx = add_node(
+                        &mut nodes_data,
+                        QueryNodeData::Term(LocatedQueryTermSubset {
+                            term_subset: QueryTermSubset::full(ngram.value),
+                            positions: ngram.positions.clone(),
+                            term_ids: first_term_id..=term_id,
+                        }),
+                    );
+                    located_query_terms.push(ngram);
+                    clauses_bounds[first_term_id as usize].0.push(ngram_idx);
+                    clauses_bounds[term_id as usize].1.push(ngram_idx);
+                }
+            }
+        }
+
+        let mut previous_exits = vec![0];
+        for (entries, exits) in clauses_bounds {
+            edges.extend(previous_exits.iter().flat_map(|&p| entries.iter().map(move |&e| (p, e))));
+            previous_exits = exits;
+        }
+        edges.extend(previous_exits.into_iter().map(|p| (p, 1)));
+
+        let nodes_len = nodes_data.len() as u16;
+        let mut nodes = FixedSizeInterner::new(
+            nodes_len,
+            QueryNode {
+                data: QueryNodeData::Deleted,
+                predecessors: SmallBitmap::new(nodes_len),
+                successors: SmallBitmap::new(nodes_len),
+            },
+        );
+        for (node_idx, node_data) in nodes_data.into_iter().enumerate() {
+            let node = nodes.get_mut(Interned::from_raw(node_idx as u16));
+            node.data = node_data;
+        }
+        for (from, to) in edges {
+            let (from, to) = (Interned::from_raw(from), Interned::from_raw(to));
+            nodes.get_mut(from).successors.insert(to);
+            nodes.get_mut(to).predecessors.insert(from);
+        }
+
+        let graph =
+            QueryGraph { root_node: Interned::from_raw(0), end_node: Interned::from_raw(1), nodes };
+
+        Ok((graph, located_query_terms))
+    }
+
     /// Remove the given nodes, connecting all their predecessors to all their successors.
     pub fn remove_nodes_keep_edges(&mut self, nodes: &[Interned<QueryNode>]) {
         for &node_id in nodes {
@@ -420,6 +516,63 @@ fn add_node(nodes_data: &mut Vec<QueryNodeData>, node_data: QueryNodeData) -> u1
     new_node_idx
 }
 
+/// Add the nodes of a clause of an advanced query and the edges between them,
+/// return the nodes the clause starts and ends with.
+fn add_clause(
+    nodes_data: &mut Vec<QueryNodeData>,
+    edges: &mut Vec<(u16, u16)>,
+    located_query_terms: &mut Vec<LocatedQueryTerm>,
+    clause: &QueryClause,
+    term_id: u8,
+    mandatory: bool,
+) -> (Vec<u16>, Vec<u16>) {
+    match clause {
+        QueryClause::Term(term) => {
+            let mut term_subset = QueryTermSubset::full(term.value);
+            if mandatory {
+                term_subset.make_mandatory();
+            }
+            let node_idx = add_node(
+                nodes_data,
+                QueryNodeData::Term(LocatedQueryTermSubset {
+                    term_subset,
+                    positions: term.positions.clone(),
+                    term_ids: term_id..=term_id,
+                }),
+            );
+            located_query_terms.push(term.clone());
+            (vec![node_idx], vec![node_idx])
+        }
+        QueryClause::And(clauses) => {
+            let mut entries = None;
+            let mut previous_exits: Vec<u16> = vec![];
+            for clause in clauses {
+                let (clause_entries, clause_exits) =
+                    add_clause(nodes_data, edges, located_query_terms, clause, term_id, mandatory);
+                for &p in &previous_exits {
+                    edges.extend(clause_entries.iter().map(|&e| (p, e)));
+                }
+                if entries.is_none() {
+                    entries = Some(clause_entries);
+                }
+                previous_exits = clause_exits;
+            }
+            (entries.unwrap_or_default(), previous_exits)
+        }
+        QueryClause::Or(clauses) => {
+            let mut entries = vec![];
+            let mut exits = vec![];
+            for clause in clauses {
+                let (clause_entries, clause_exits) =
+                    add_clause(nodes_data, edges, located_query_terms, clause, term_id, mandatory);
+                entries.extend(clause_entries);
+                exits.extend(clause_exits);
+            }
+            (entries, exits)
+        }
+    }
+}
+
 impl QueryGraph {
     /*
     Build a query graph from a list of paths
@@ -14,6 +14,8 @@ use crate::search::new::interner::{DedupInterner, Interned};
 use crate::search::new::query_term::{Lazy, TwoTypoTerm};
 use crate::search::new::{limits, SearchContext};
 use crate::search::{build_dfa, get_first};
+use crate::stemming::stem;
+use crate::update::NGRAM_LENGTH;
 use crate::{Result, MAX_WORD_LENGTH};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -179,6 +181,7 @@ pub fn partially_initialized_term_from_word(
                 original: ctx.word_interner.insert(word.to_owned()),
                 ngram_words: None,
                 is_prefix: false,
+                restricted_fids: None,
                 max_levenshtein_distance: 0,
                 zero_typo: <_>::default(),
                 one_typo: Lazy::Init(<_>::default()),
@@ -234,17 +237,34 @@ pub fn partially_initialized_term_from_word(
             }
             synonym_word_count += words.len();
             let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
-            Some(ctx.phrase_interner.insert(Phrase { words }))
+            Some(ctx.phrase_interner.insert(Phrase { words, slop: 0 }))
         })
         .collect();
-    let zero_typo =
-        ZeroTypoTerm { phrase: None, exact: zero_typo, prefix_of, synonyms, use_prefix_db };
+    // The words of the n-gram attributes can also be matched by one of their infixes
+    let infix_of = (!is_ngram
+        && word.chars().count() >= NGRAM_LENGTH
+        && !ctx.index.ngram_attributes(ctx.txn)?.is_empty())
+    .then_some(word_interned);
+    // The words of the stemmed attributes can also be matched by their stem
+    let stem_of = (!is_ngram
+        && ctx.stemmed_fids.values().any(|&language| stem(language, word).is_some()))
+    .then_some(word_interned);
+    let zero_typo = ZeroTypoTerm {
+        phrase: None,
+        exact: zero_typo,
+        prefix_of,
+        synonyms,
+        use_prefix_db,
+        infix_of,
+        stem_of,
+    };
 
     Ok(QueryTerm {
         original: word_interned,
         ngram_words: None,
         max_levenshtein_distance: max_typo,
         is_prefix,
+        restricted_fids: None,
         zero_typo,
         one_typo: Lazy::Uninit,
         two_typo: Lazy::Uninit,
@@ -253,7 +273,7 @@ pub fn partially_initialized_term_from_word(
 
 fn find_split_words(ctx: &mut SearchContext<'_>, word: &str) -> Result<Option<Interned<Phrase>>> {
     if let Some((l, r)) = split_best_frequency(ctx, word)? {
-        Ok(Some(ctx.phrase_interner.insert(Phrase { words: vec![Some(l), Some(r)] })))
+        Ok(Some(ctx.phrase_interner.insert(Phrase { words: vec![Some(l), Some(r)], slop: 0 })))
     } else {
         Ok(None)
     }
@@ -306,7 +326,7 @@ impl Interned<QueryTerm> {
         let split_words = if let Some((ngram_words, split_words)) =
             self_mut.ngram_words.as_ref().zip(split_words.as_ref())
         {
-            let Phrase { words } = ctx.phrase_interner.get(*split_words);
+            let Phrase { words, .. } = ctx.phrase_interner.get(*split_words);
             if ngram_words.iter().ne(words.iter().flatten()) {
                 Some(*split_words)
             } else {
@@ -1,5 +1,6 @@
 mod compute_derivations;
 mod ntypo_subset;
+mod parse_advanced_query;
 mod parse_query;
 mod phrase;
 
@@ -9,6 +10,10 @@ use std::ops::RangeInclusive;
 
 use either::Either;
 pub use ntypo_subset::NTypoTermSubset;
+pub use parse_advanced_query::{
+    located_query_clauses_from_advanced_query, ExtractedClauses, QueryClause,
+};
+pub(crate) use parse_query::typos_allowed_by_settings;
 pub use parse_query::{
     located_query_terms_from_tokens, make_ngram, number_of_typos_allowed, ExtractedTokens,
 };
@@ -16,7 +21,7 @@ pub use phrase::Phrase;
 
 use super::interner::{DedupInterner, Interned};
 use super::{limits, SearchContext, Word};
-use crate::Result;
+use crate::{FieldId, Result};
 
 /// A set of word derivations attached to a location in the search query.
 #[derive(Clone, PartialEq, Eq, Hash)]
@@ -32,6 +37,10 @@ pub struct QueryTermSubset {
     zero_typo_subset: NTypoTermSubset,
     one_typo_subset: NTypoTermSubset,
     two_typo_subset: NTypoTermSubset,
+    /// `true` if the term can match the words of the n-gram attributes it is an infix of
+    infix_subset: bool,
+    /// `true` if the term can match the words of the stemmed attributes that share its stem
+    stem_subset: bool,
     /// `true` if the term cannot be deleted through the term matching strategy
     ///
     /// Note that there are other reasons for which a term cannot be deleted, such as
@@ -46,6 +55,8 @@ pub struct QueryTerm {
     ngram_words: Option<Vec<Interned<String>>>,
     max_levenshtein_distance: u8,
     is_prefix: bool,
+    /// The only fields the term can match in, when the query restricts it
+    restricted_fids: Option<Vec<FieldId>>,
     zero_typo: ZeroTypoTerm,
     // May not be computed yet
     one_typo: Lazy<OneTypoTerm>,
@@ -66,6 +77,10 @@ struct ZeroTypoTerm {
     synonyms: BTreeSet<Interned<Phrase>>,
     /// A prefix in the prefix databases matching the original word
     use_prefix_db: Option<Interned<String>>,
+    /// The original word, to look up as an infix in the n-gram databases
+    infix_of: Option<Interned<String>>,
+    /// The original word, to look up by its stem in the stem databases
+    stem_of: Option<Interned<String>>,
 }
 #[derive(Default, Clone, PartialEq, Eq, Hash)]
 struct OneTypoTerm {
@@ -148,6 +163,8 @@ impl QueryTermSubset {
             zero_typo_subset: NTypoTermSubset::Nothing,
             one_typo_subset: NTypoTermSubset::Nothing,
             two_typo_subset: NTypoTermSubset::Nothing,
+            infix_subset: false,
+            stem_subset: false,
             mandatory: false,
         }
     }
@@ -157,6 +174,8 @@ impl QueryTermSubset {
             zero_typo_subset: NTypoTermSubset::All,
             one_typo_subset: NTypoTermSubset::All,
             two_typo_subset: NTypoTermSubset::All,
+            infix_subset: true,
+            stem_subset: true,
             mandatory: false,
         }
     }
@@ -166,12 +185,16 @@ impl QueryTermSubset {
         self.zero_typo_subset.union(&other.zero_typo_subset);
         self.one_typo_subset.union(&other.one_typo_subset);
         self.two_typo_subset.union(&other.two_typo_subset);
+        self.infix_subset |= other.infix_subset;
+        self.stem_subset |= other.stem_subset;
     }
     pub fn intersect(&mut self, other: &Self) {
         assert!(self.original == other.original);
         self.zero_typo_subset.intersect(&other.zero_typo_subset);
         self.one_typo_subset.intersect(&other.one_typo_subset);
         self.two_typo_subset.intersect(&other.two_typo_subset);
+        self.infix_subset &= other.infix_subset;
+        self.stem_subset &= other.stem_subset;
     }
 
     pub fn use_prefix_db(&self, ctx: &SearchContext<'_>) -> Option<Word> {
@@ -196,6 +219,22 @@ impl QueryTermSubset {
             }
         })
     }
+    /// The word to look up as an infix of the words of the n-gram attributes, if any
+    pub fn use_infix(&self, ctx: &SearchContext<'_>) -> Option<Interned<String>> {
+        if self.infix_subset {
+            ctx.term_interner.get(self.original).zero_typo.infix_of
+        } else {
+            None
+        }
+    }
+    /// The word to look up by its stem in the stemmed attributes, if any
+    pub fn use_stem(&self, ctx: &SearchContext<'_>) -> Option<Interned<String>> {
+        if self.stem_subset {
+            ctx.term_interner.get(self.original).zero_typo.stem_of
+        } else {
+            None
+        }
+    }
     pub fn all_single_words_except_prefix_db(
         &self,
         ctx: &mut SearchContext<'_>,
@@ -214,6 +253,8 @@ impl QueryTermSubset {
                     prefix_of,
                     synonyms: _,
                     use_prefix_db: _,
+                    infix_of: _,
+                    stem_of: _,
                 } = &original.zero_typo;
                 result.extend(zero_typo.iter().copied().map(|w| {
                     if original.ngram_words.is_some() {
@@ -237,6 +278,8 @@ impl QueryTermSubset {
                     prefix_of,
                     synonyms: _,
                     use_prefix_db: _,
+                    infix_of: _,
+                    stem_of: _,
                 } = &original.zero_typo;
                 if let Some(zero_typo) = zero_typo {
                     if words.contains(zero_typo) {
@@ -298,8 +341,15 @@ impl QueryTermSubset {
         }
         let original = ctx.term_interner.get_mut(self.original);
 
-        let ZeroTypoTerm { phrase, exact: _, prefix_of: _, synonyms, use_prefix_db: _ } =
-            &original.zero_typo;
+        let ZeroTypoTerm {
+            phrase,
+            exact: _,
+            prefix_of: _,
+            synonyms,
+            use_prefix_db: _,
+            infix_of: _,
+            stem_of: _,
+        } = &original.zero_typo;
         result.extend(phrase.iter().copied());
         result.extend(synonyms.iter().copied());
 
@@ -337,6 +387,10 @@ impl QueryTermSubset {
         }
         None
     }
+    /// The fields the term can only match in, if the query restricted it to some fields
+    pub fn restricted_fids(&self, ctx: &SearchContext<'_>) -> Option<Vec<FieldId>> {
+        ctx.term_interner.get(self.original).restricted_fids.clone()
+    }
     pub fn max_typo_cost(&self, ctx: &SearchContext<'_>) -> u8 {
         let t = ctx.term_interner.get(self.original);
         match t.max_levenshtein_distance {
@@ -378,6 +432,8 @@ impl QueryTermSubset {
                     };
                     self.clear_one_typo_subset();
                     self.clear_two_typo_subset();
+                    self.clear_infix_subset();
+                    self.clear_stem_subset();
                 }
                 ExactTerm::Word(w) => {
                     self.zero_typo_subset = NTypoTermSubset::Subset {
@@ -386,6 +442,8 @@ impl QueryTermSubset {
                     };
                     self.clear_one_typo_subset();
                     self.clear_two_typo_subset();
+                    self.clear_infix_subset();
+                    self.clear_stem_subset();
                 }
             }
         }
@@ -399,20 +457,118 @@ impl QueryTermSubset {
     pub fn clear_two_typo_subset(&mut self) {
         self.two_typo_subset = NTypoTermSubset::Nothing;
     }
+    pub fn clear_infix_subset(&mut self) {
+        self.infix_subset = false;
+    }
+    pub fn clear_stem_subset(&mut self) {
+        self.stem_subset = false;
+    }
     pub fn description(&self, ctx: &SearchContext<'_>) -> String {
         let t = ctx.term_interner.get(self.original);
         ctx.word_interner.get(t.original).to_owned()
     }
+
+    /// Returns the words and phrases of the subset, grouped by kind of derivation.
+    pub fn derivations(&self, ctx: &mut SearchContext<'_>) -> Result<QueryTermDerivations> {
+        if !self.one_typo_subset.is_empty() || !self.two_typo_subset.is_empty() {
+            self.original.compute_fully_if_needed(ctx)?;
+        }
+
+        let ctx = &*ctx;
+        let term = ctx.term_interner.get(self.original);
+        let word = |word: &Interned<String>| ctx.word_interner.get(*word).clone();
+        let ZeroTypoTerm {
+            phrase: _,
+            exact,
+            prefix_of,
+            synonyms,
+            use_prefix_db,
+            infix_of,
+            stem_of,
+        } = &term.zero_typo;
+        let zero_typo = &self.zero_typo_subset;
+
+        let mut derivations = QueryTermDerivations {
+            original: word(&term.original),
+            is_prefix: term.is_prefix,
+            exact: exact.filter(|w| zero_typo.contains_word(*w)).as_ref().map(word),
+            prefix_of: prefix_of
+                .iter()
+                .filter(|w| zero_typo.contains_word(**w))
+                .map(word)
+                .collect(),
+            use_prefix_db: use_prefix_db.is_some_and(|w| zero_typo.contains_word(w)),
+            synonyms: synonyms
+                .iter()
+                .filter(|p| zero_typo.contains_phrase(**p))
+                .map(|p| p.description(ctx))
+                .collect(),
+            ngram_words: term.ngram_words.iter().flatten().map(word).collect(),
+            infix: self.infix_subset && infix_of.is_some(),
+            stem: self.stem_subset && stem_of.is_some(),
+            ..Default::default()
+        };
+
+        if let Lazy::Init(OneTypoTerm { split_words, one_typo }) = &term.one_typo {
+            let subset = &self.one_typo_subset;
+            derivations.split_words =
+                split_words.filter(|p| subset.contains_phrase(*p)).map(|p| p.description(ctx));
+            derivations.one_typo =
+                one_typo.iter().filter(|w| subset.contains_word(**w)).map(word).collect();
+        }
+        if let Lazy::Init(TwoTypoTerm { two_typos }) = &term.two_typo {
+            let subset = &self.two_typo_subset;
+            derivations.two_typos =
+                two_typos.iter().filter(|w| subset.contains_word(**w)).map(word).collect();
+        }
+
+        Ok(derivations)
+    }
+}
+
+/// The words and phrases a query term can match, grouped by kind of derivation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryTermDerivations {
+    /// The term as written in the query
+    pub original: String,
+    pub is_prefix: bool,
+    /// The normalized term
+    pub exact: Option<String>,
+    /// The words starting with the term
+    pub prefix_of: Vec<String>,
+    /// `true` if the documents are found through the prefix databases
+    pub use_prefix_db: bool,
+    pub synonyms: Vec<String>,
+    /// The term split into two words
+    pub split_words: Option<String>,
+    pub one_typo: Vec<String>,
+    pub two_typos: Vec<String>,
+    /// The words of the query concatenated into this term
+    pub ngram_words: Vec<String>,
+    /// `true` if the term is looked up as an infix of the words of the n-gram attributes
+    pub infix: bool,
+    /// `true` if the term is looked up by its stem in the stemmed attributes
+    pub stem: bool,
 }
 
 impl ZeroTypoTerm {
     fn is_empty(&self) -> bool {
-        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, use_prefix_db } = self;
+        let ZeroTypoTerm {
+            phrase,
+            exact: zero_typo,
+            prefix_of,
+            synonyms,
+            use_prefix_db,
+            infix_of,
+            stem_of,
+        } = self;
         phrase.is_none()
             && zero_typo.is_none()
             && prefix_of.is_empty()
             && synonyms.is_empty()
             && use_prefix_db.is_none()
+            && infix_of.is_none()
+            && stem_of.is_none()
     }
 }
 impl OneTypoTerm {
@@ -489,8 +645,15 @@ impl QueryTerm {
         let mut words = BTreeSet::new();
         let mut phrases = BTreeSet::new();
 
-        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, use_prefix_db: _ } =
-            &self.zero_typo;
+        let ZeroTypoTerm {
+            phrase,
+            exact: zero_typo,
+            prefix_of,
+            synonyms,
+            use_prefix_db: _,
+            infix_of: _,
+            stem_of: _,
+        } = &self.zero_typo;
         words.extend(zero_typo.iter().copied());
         words.extend(prefix_of.iter().copied());
         phrases.extend(phrase.iter().copied());
@@ -0,0 +1,727 @@
+use charabia::{TokenKind, Tokenizer};
+
+use super::compute_derivations::partially_initialized_term_from_word;
+use super::parse_query::PhraseBuilder;
+use super::{number_of_typos_allowed, LocatedQueryTerm};
+use crate::search::new::limits;
+use crate::{FieldId, Result, SearchContext, UserError};
+
+/// An operand of a search query written with the advanced query syntax.
+///
+/// On top of the quoted phrases and `-negative` operands of the simple syntax, the advanced
+/// syntax understands `field:word` and `field:"a phrase"` restrictions, `OR` between operands,
+/// groups of operands in parentheses and `NEAR/N` between two words,
+/// e.g. `title:(rust OR go) -"hello world" breach NEAR/5 contract`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryExpr {
+    Word {
+        field: Option<String>,
+        word: String,
+        is_prefix: bool,
+    },
+    Phrase {
+        field: Option<String>,
+        phrase: String,
+        /// The maximum distance between two consecutive words of the phrase, see [`Phrase::slop`].
+        ///
+        /// [`Phrase::slop`]: super::Phrase::slop
+        slop: u8,
+    },
+    /// Operands written in parentheses, all of which must match.
+    And(Vec<QueryExpr>),
+    /// Operands separated by `OR`, at least one of which must match.
+    Or(Vec<QueryExpr>),
+    /// An operand prefixed by `-`, which must not match.
+    Not(Box<QueryExpr>),
+}
+
+impl QueryExpr {
+    /// The field and the word of this operand if it is a single word.
+    fn as_word(&self) -> Option<(Option<&str>, &str)> {
+        match self {
+            QueryExpr::Word { field, word, .. } => Some((field.as_deref(), word)),
+            _ => None,
+        }
+    }
+
+    /// Restrict the words and phrases of this operand that aren't already restricted to a field.
+    fn restricted_to(self, field: &str) -> Self {
+        match self {
+            QueryExpr::Word { field: None, word, is_prefix } => {
+                QueryExpr::Word { field: Some(field.to_string()), word, is_prefix }
+            }
+            QueryExpr::Phrase { field: None, phrase, slop } => {
+                QueryExpr::Phrase { field: Some(field.to_string()), phrase, slop }
+            }
+            QueryExpr::And(exprs) => {
+                QueryExpr::And(exprs.into_iter().map(|e| e.restricted_to(field)).collect())
+            }
+            QueryExpr::Or(exprs) => {
+                QueryExpr::Or(exprs.into_iter().map(|e| e.restricted_to(field)).collect())
+            }
+            QueryExpr::Not(expr) => QueryExpr::Not(Box::new(expr.restricted_to(field))),
+            expr => expr,
+        }
+    }
+}
+
+/// Parse a search query written with the advanced query syntax into its top-level operands.
+///
+/// Like the simple syntax, the parser is lenient: an unclosed quote or parenthesis runs until
+/// the end of the query, and a closing parenthesis without an opening one is ignored.
+pub fn parse_advanced_query(query: &str) -> Result<Vec<QueryExpr>> {
+    let mut parser = Parser { query, offset: 0 };
+    let mut operands = Vec::new();
+    loop {
+        operands.extend(parser.parse_sequence(0)?);
+        if !parser.eat(')') {
+            break;
+        }
+    }
+    Ok(operands)
+}
+
+struct Parser<'a> {
+    query: &'a str,
+    offset: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.query[self.offset..].chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.offset += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.query[self.offset..];
+        self.offset += rest.len() - rest.trim_start().len();
+    }
+
+    /// A `-` is a negation when it is directly followed by an operand.
+    fn at_negation(&self) -> bool {
+        let mut chars = self.query[self.offset..].chars();
+        chars.next() == Some('-')
+            && chars.next().is_some_and(|c| !c.is_whitespace() && c != '-' && c != ')')
+    }
+
+    /// An `OR` keyword must be written in uppercase and be followed by another operand.
+    fn eat_or_keyword(&mut self) -> bool {
+        let Some(rest) = self.query[self.offset..].strip_prefix("OR") else { return false };
+        match rest.chars().next() {
+            Some(c) if c.is_whitespace() || c == '(' || c == '"' => {
+                if rest.trim_start().is_empty() || rest.trim_start().starts_with(')') {
+                    return false;
+                }
+                self.offset += "OR".len();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// A `NEAR/N` keyword must be written in uppercase and be followed by another operand.
+    fn eat_near_keyword(&mut self) -> Option<u8> {
+        let rest = self.query[self.offset..].strip_prefix("NEAR/")?;
+        let len = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let slop = rest[..len].parse().ok()?;
+        let rest = &rest[len..];
+        match rest.chars().next() {
+            Some(c) if c.is_whitespace() => {
+                if rest.trim_start().is_empty() || rest.trim_start().starts_with(')') {
+                    return None;
+                }
+                self.offset += "NEAR/".len() + len;
+                Some(slop)
+            }
+            _ => None,
+        }
+    }
+
+    /// A `~N` right after the closing quote of a phrase is the slop of the phrase.
+    fn eat_phrase_slop(&mut self) -> u8 {
+        let Some(rest) = self.query[self.offset..].strip_prefix('~') else { return 0 };
+        let len = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let ends_operand = rest[len..].chars().next().is_none_or(|c| c.is_whitespace() || c == ')');
+        match rest[..len].parse() {
+            Ok(slop) if ends_operand => {
+                self.offset += '~'.len_utf8() + len;
+                slop
+            }
+            _ => 0,
+        }
+    }
+
+    /// Parse the operands until the end of the query or of the group, `depth` being the number
+    /// of groups the operands are nested in.
+    fn parse_sequence(&mut self, depth: usize) -> Result<Vec<QueryExpr>> {
+        let mut operands = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None | Some(')') => break,
+                Some(_) => match self.parse_or(depth)? {
+                    Some(QueryExpr::Not(_)) if depth > 0 => {
+                        return Err(invalid_query(
+                            "The `-` operator cannot be used inside of parentheses.",
+                        ))
+                    }
+                    Some(operand) => operands.push(operand),
+                    None => (),
+                },
+            }
+        }
+        Ok(operands)
+    }
+
+    fn parse_or(&mut self, depth: usize) -> Result<Option<QueryExpr>> {
+        let Some(first) = self.parse_near(depth)? else { return Ok(None) };
+        let mut operands = vec![first];
+        loop {
+            let offset = self.offset;
+            self.skip_whitespace();
+            if !self.eat_or_keyword() {
+                self.offset = offset;
+                break;
+            }
+            self.skip_whitespace();
+            if let Some(operand) = self.parse_near(depth)? {
+                operands.push(operand);
+            }
+        }
+
+        if operands.len() == 1 {
+            Ok(operands.pop())
+        } else if operands.iter().any(|operand| matches!(operand, QueryExpr::Not(_))) {
+            Err(invalid_query("The `-` operator cannot be combined with `OR`."))
+        } else {
+            Ok(Some(QueryExpr::Or(operands)))
+        }
+    }
+
+    fn parse_near(&mut self, depth: usize) -> Result<Option<QueryExpr>> {
+        let Some(first) = self.parse_operand(depth)? else { return Ok(None) };
+        let mut operands = vec![first];
+        let mut slops = Vec::new();
+        loop {
+            let offset = self.offset;
+            self.skip_whitespace();
+            let Some(slop) = self.eat_near_keyword() else {
+                self.offset = offset;
+                break;
+            };
+            self.skip_whitespace();
+            match self.parse_operand(depth)? {
+                Some(operand) => {
+                    operands.push(operand);
+                    slops.push(slop);
+                }
+                None => break,
+            }
+        }
+
+        if operands.len() == 1 {
+            return Ok(operands.pop());
+        }
+
+        // `a NEAR/N b NEAR/M c` is understood as `a NEAR/N b` and `b NEAR/M c`.
+        let mut phrases = Vec::new();
+        for (pair, slop) in operands.windows(2).zip(slops) {
+            let [left, right] = pair else { unreachable!() };
+            let (Some((left_field, left)), Some((right_field, right))) =
+                (left.as_word(), right.as_word())
+            else {
+                return Err(invalid_query(
+                    "The `NEAR` operator can only be used between two words.",
+                ));
+            };
+            if left_field.is_some() && right_field.is_some() && left_field != right_field {
+                return Err(invalid_query(
+                    "The `NEAR` operator cannot be used between words of different attributes.",
+                ));
+            }
+            phrases.push(QueryExpr::Phrase {
+                field: left_field.or(right_field).map(String::from),
+                phrase: format!("{left} {right}"),
+                slop,
+            });
+        }
+        Ok(match phrases.len() {
+            1 => phrases.pop(),
+            _ => Some(QueryExpr::And(phrases)),
+        })
+    }
+
+    fn parse_operand(&mut self, depth: usize) -> Result<Option<QueryExpr>> {
+        match self.peek() {
+            None | Some(')') => Ok(None),
+            Some('-') if self.at_negation() => {
+                self.eat('-');
+                Ok(self.parse_operand(depth)?.map(|operand| QueryExpr::Not(Box::new(operand))))
+            }
+            Some('(') => {
+                if depth >= limits::MAX_NESTED_GROUPS {
+                    return Err(invalid_query(&format!(
+                        "The groups of operands cannot be nested more than {} times.",
+                        limits::MAX_NESTED_GROUPS
+                    )));
+                }
+                self.eat('(');
+                let mut operands = self.parse_sequence(depth + 1)?;
+                self.eat(')');
+                Ok(match operands.len() {
+                    0 => None,
+                    1 => operands.pop(),
+                    _ => Some(QueryExpr::And(operands)),
+                })
+            }
+            Some('"') => {
+                self.eat('"');
+                let rest = &self.query[self.offset..];
+                let phrase = rest.split('"').next().unwrap_or_default();
+                self.offset += phrase.len();
+                let slop = if self.eat('"') { self.eat_phrase_slop() } else { 0 };
+                Ok(Some(QueryExpr::Phrase { field: None, phrase: phrase.to_string(), slop }))
+            }
+            Some(_) => {
+                let rest = &self.query[self.offset..];
+                let len = rest
+                    .find(|c: char| c.is_whitespace() || matches!(c, '(' | ')' | '"'))
+                    .unwrap_or(rest.len());
+                let word = &rest[..len];
+                self.offset += len;
+                let is_prefix = self.offset == self.query.len();
+
+                match word.split_once(':') {
+                    Some((field, ""))
+                        if !field.is_empty() && matches!(self.peek(), Some('(' | '"')) =>
+                    {
+                        Ok(self.parse_operand(depth)?.map(|operand| operand.restricted_to(field)))
+                    }
+                    Some((field, word)) if !field.is_empty() && !word.is_empty() => {
+                        Ok(Some(QueryExpr::Word {
+                            field: Some(field.to_string()),
+                            word: word.to_string(),
+                            is_prefix,
+                        }))
+                    }
+                    _ => {
+                        Ok(Some(QueryExpr::Word { field: None, word: word.to_string(), is_prefix }))
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn invalid_query(message: &str) -> crate::Error {
+    UserError::InvalidAdvancedQuery(message.to_string()).into()
+}
+
+/// A query term, or a combination of query terms, of a query written with the advanced syntax.
+#[derive(Clone)]
+pub enum QueryClause {
+    Term(LocatedQueryTerm),
+    And(Vec<QueryClause>),
+    Or(Vec<QueryClause>),
+}
+
+impl QueryClause {
+    /// Clauses using an operator of the advanced syntax can't be removed by the terms matching
+    /// strategy, only the plain words of the query can.
+    pub fn is_mandatory(&self, ctx: &SearchContext<'_>) -> bool {
+        match self {
+            QueryClause::Term(term) => ctx.term_interner.get(term.value).restricted_fids.is_some(),
+            QueryClause::And(_) | QueryClause::Or(_) => true,
+        }
+    }
+
+    /// All the query terms of this clause.
+    pub fn terms(&self) -> Vec<&LocatedQueryTerm> {
+        match self {
+            QueryClause::Term(term) => vec![term],
+            QueryClause::And(clauses) | QueryClause::Or(clauses) => {
+                clauses.iter().flat_map(QueryClause::terms).collect()
+            }
+        }
+    }
+
+    /// Make the last terms of this clause end at the given position.
+    fn stretch_to(&mut self, end: u16) {
+        match self {
+            QueryClause::Term(term) => term.positions = *term.positions.start()..=end,
+            QueryClause::And(clauses) => {
+                if let Some(last) = clauses.last_mut() {
+                    last.stretch_to(end);
+                }
+            }
+            QueryClause::Or(clauses) => clauses.iter_mut().for_each(|c| c.stretch_to(end)),
+        }
+    }
+
+    fn end_position(&self) -> u16 {
+        match self {
+            QueryClause::Term(term) => *term.positions.end(),
+            QueryClause::And(clauses) => clauses.last().map_or(0, QueryClause::end_position),
+            QueryClause::Or(clauses) => {
+                clauses.iter().map(QueryClause::end_position).max().unwrap_or_default()
+            }
+        }
+    }
+}
+
+/// Extraction of the content of a query written with the advanced syntax.
+pub struct ExtractedClauses {
+    /// The clauses to search for in the database, in the order of the query.
+    pub clauses: Vec<QueryClause>,
+    /// The clauses that must not match the results.
+    pub negative_clauses: Vec<QueryClause>,
+}
+
+/// Parse a query written with the advanced syntax and convert its operands into query clauses.
+#[tracing::instrument(level = "trace", skip_all, target = "search::query")]
+pub fn located_query_clauses_from_advanced_query(
+    ctx: &mut SearchContext<'_>,
+    tokenizer: &Tokenizer<'_>,
+    query: &str,
+    words_limit: Option<usize>,
+) -> Result<ExtractedClauses> {
+    let operands = parse_advanced_query(query)?;
+
+    let nbr_typos = number_of_typos_allowed(ctx)?;
+    let mut builder = ClauseBuilder {
+        tokenizer,
+        nbr_typos: &nbr_typos,
+        allow_prefix_search: ctx.is_prefix_search_allowed(),
+        position: 0,
+        words_left: words_limit.unwrap_or(usize::MAX).min(limits::MAX_TOKEN_COUNT),
+        negative: false,
+    };
+
+    let mut clauses = Vec::new();
+    let mut negative_clauses = Vec::new();
+    for operand in operands {
+        match operand {
+            QueryExpr::Not(operand) => {
+                builder.negative = true;
+                negative_clauses.extend(builder.build(ctx, *operand)?);
+                builder.negative = false;
+            }
+            operand => clauses.extend(builder.build(ctx, operand)?),
+        }
+    }
+
+    Ok(ExtractedClauses { clauses, negative_clauses })
+}
+
+struct ClauseBuilder<'a, 't, F> {
+    tokenizer: &'a Tokenizer<'t>,
+    nbr_typos: &'a F,
+    allow_prefix_search: bool,
+    /// The position of the next word of the query
+    position: u16,
+    /// The number of words that can still be added to the query
+    words_left: usize,
+    /// Negative clauses are only used to exclude documents, they match the exact words
+    /// and don't count in the positions and words limit of the query.
+    negative: bool,
+}
+
+impl<F: Fn(&str) -> u8> ClauseBuilder<'_, '_, F> {
+    fn build(
+        &mut self,
+        ctx: &mut SearchContext<'_>,
+        expr: QueryExpr,
+    ) -> Result<Option<QueryClause>> {
+        let clause = match expr {
+            QueryExpr::Word { field, word, is_prefix } => {
+                let restricted_fids = field.map(|field| fids_of_field(ctx, &field)).transpose()?;
+                let tokens: Vec<_> = self
+                    .tokenizer
+                    .tokenize(&word)
+                    .filter(|token| {
+                        matches!(token.kind, TokenKind::Word | TokenKind::StopWord)
+                            && !token.lemma().is_empty()
+                    })
+                    .collect();
+
+                let mut terms = Vec::new();
+                for (i, token) in tokens.iter().enumerate() {
+                    if self.words_left == 0 && !self.negative {
+                        break;
+                    }
+                    let position = self.next_position();
+                    // Like in the simple syntax, only the last word of the query is a prefix
+                    // and stop words are ignored unless they are that last word.
+                    let is_prefix = is_prefix && i == tokens.len() - 1 && !self.negative;
+                    if matches!(token.kind, TokenKind::StopWord) && !is_prefix {
+                        continue;
+                    }
+
+                    let word = token.lemma();
+                    let max_typo = if self.negative { 0 } else { (self.nbr_typos)(word) };
+                    let mut term = partially_initialized_term_from_word(
+                        ctx,
+                        word,
+                        max_typo,
+                        is_prefix && self.allow_prefix_search,
+                        false,
+                    )?;
+                    term.restricted_fids = restricted_fids.clone();
+                    terms.push(QueryClause::Term(LocatedQueryTerm {
+                        value: ctx.term_interner.push(term),
+                        positions: position..=position,
+                    }));
+                    if !self.negative {
+                        self.words_left -= 1;
+                    }
+                }
+
+                match terms.len() {
+                    0 | 1 => terms.pop(),
+                    _ => Some(QueryClause::And(terms)),
+                }
+            }
+            QueryExpr::Phrase { field, phrase, slop } => {
+                if self.words_left == 0 && !self.negative {
+                    return Ok(None);
+                }
+                let restricted_fids = field.map(|field| fids_of_field(ctx, &field)).transpose()?;
+                let mut builder = PhraseBuilder::empty();
+                builder.set_slop(slop)?;
+                for token in self.tokenizer.tokenize(&phrase) {
+                    if matches!(token.kind, TokenKind::Word | TokenKind::StopWord)
+                        && !token.lemma().is_empty()
+                    {
+                        let position = self.next_position();
+                        builder.push_word(ctx, &token, position);
+                    }
+                }
+
+                let term = builder.build(ctx);
+                if let Some(term) = &term {
+                    ctx.term_interner.get_mut(term.value).restricted_fids = restricted_fids;
+                    if !self.negative {
+                        self.words_left -= 1;
+                    }
+                }
+                term.map(QueryClause::Term)
+            }
+            QueryExpr::And(exprs) => {
+                let mut clauses = Vec::new();
+                for expr in exprs {
+                    clauses.extend(self.build(ctx, expr)?);
+                }
+                match clauses.len() {
+                    0 | 1 => clauses.pop(),
+                    _ => Some(QueryClause::And(clauses)),
+                }
+            }
+            QueryExpr::Or(exprs) => {
+                // All the alternatives start at the same position of the query and are stretched
+                // to end at the same position, so that the proximity with the surrounding words
+                // is computed for each of them.
+                let start = self.position;
+                let mut end = start;
+                let mut clauses = Vec::new();
+                for expr in exprs {
+                    self.position = start;
+                    if let Some(clause) = self.build(ctx, expr)? {
+                        end = end.max(self.position);
+                        clauses.push(clause);
+                    }
+                }
+                self.position = end;
+                for clause in &mut clauses {
+                    if clause.end_position() + 1 < end {
+                        clause.stretch_to(end - 1);
+                    }
+                }
+                match clauses.len() {
+                    0 | 1 => clauses.pop(),
+                    _ => Some(QueryClause::Or(clauses)),
+                }
+            }
+            QueryExpr::Not(_) => {
+                return Err(invalid_query("The `-` operator cannot be used inside of parentheses."))
+            }
+        };
+
+        Ok(clause)
+    }
+
+    fn next_position(&mut self) -> u16 {
+        let position = self.position;
+        if !self.negative {
+            self.position = self.position.saturating_add(1);
+        }
+        position
+    }
+}
+
+/// Return the ids of the searchable fields matching the field name written in the query,
+/// including its nested fields, among the ones the search is restricted to.
+///
+/// A field outside of the `attributesToSearchOn` is rejected like an unknown one, without
+/// revealing that it exists.
+fn fids_of_field(ctx: &SearchContext<'_>, field: &str) -> Result<Vec<FieldId>> {
+    let searchable_fields_weights = ctx.index.searchable_fields_and_weights(ctx.txn)?;
+    let searchable_fields: Vec<_> = searchable_fields_weights
+        .iter()
+        .filter(|(_, fid, _)| ctx.restricted_fids.as_ref().is_none_or(|r| r.contains(fid)))
+        .collect();
+    let fids: Vec<_> = searchable_fields
+        .iter()
+        .filter(|(name, _, _)| {
+            name == field || name.strip_prefix(field).is_some_and(|nested| nested.starts_with('.'))
+        })
+        .map(|(_, fid, _)| *fid)
+        .collect();
+
+    // The field may not exist yet if no document has it, as long as the searchable
+    // attributes aren't restricted it is simply not matching anything.
+    if fids.is_empty()
+        && (ctx.restricted_fids.is_some()
+            || ctx.index.user_defined_searchable_fields(ctx.txn)?.is_some())
+    {
+        let (valid_fields, hidden_fields) = ctx
+            .index
+            .remove_hidden_fields(ctx.txn, searchable_fields.iter().map(|(name, _, _)| name))?;
+        return Err(UserError::InvalidSearchQueryAttribute {
+            field: field.to_string(),
+            valid_fields,
+            hidden_fields,
+        }
+        .into());
+    }
+
+    Ok(fids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(field: Option<&str>, word: &str) -> QueryExpr {
+        QueryExpr::Word { field: field.map(String::from), word: word.to_string(), is_prefix: false }
+    }
+
+    #[test]
+    fn parse() {
+        let query = parse_advanced_query("title:rust (async OR tokio) -\"hello world\"").unwrap();
+        assert_eq!(
+            query,
+            vec![
+                word(Some("title"), "rust"),
+                QueryExpr::Or(vec![word(None, "async"), word(None, "tokio")]),
+                QueryExpr::Not(Box::new(QueryExpr::Phrase {
+                    field: None,
+                    phrase: "hello world".to_string(),
+                    slop: 0,
+                })),
+            ]
+        );
+
+        // the field applies to the whole group and `OR` binds tighter than juxtaposition
+        let query = parse_advanced_query("title:(a OR \"b c\") d OR e").unwrap();
+        assert_eq!(
+            query,
+            vec![
+                QueryExpr::Or(vec![
+                    word(Some("title"), "a"),
+                    QueryExpr::Phrase {
+                        field: Some("title".to_string()),
+                        phrase: "b c".to_string(),
+                        slop: 0,
+                    },
+                ]),
+                QueryExpr::Or(vec![
+                    word(None, "d"),
+                    QueryExpr::Word { field: None, word: "e".to_string(), is_prefix: true },
+                ]),
+            ]
+        );
+
+        // a lowercase `or`, a dangling `OR` and unbalanced parentheses are plain words
+        let query = parse_advanced_query("a or OR ) (b c").unwrap();
+        assert_eq!(
+            query,
+            vec![
+                word(None, "a"),
+                word(None, "or"),
+                word(None, "OR"),
+                QueryExpr::And(vec![
+                    word(None, "b"),
+                    QueryExpr::Word { field: None, word: "c".to_string(), is_prefix: true },
+                ]),
+            ]
+        );
+
+        // a phrase with a slop and words near each other
+        let query =
+            parse_advanced_query("\"breach contract\"~5 a NEAR/3 title:b NEAR/2 c").unwrap();
+        assert_eq!(
+            query,
+            vec![
+                QueryExpr::Phrase { field: None, phrase: "breach contract".to_string(), slop: 5 },
+                QueryExpr::And(vec![
+                    QueryExpr::Phrase {
+                        field: Some("title".to_string()),
+                        phrase: "a b".to_string(),
+                        slop: 3,
+                    },
+                    QueryExpr::Phrase {
+                        field: Some("title".to_string()),
+                        phrase: "b c".to_string(),
+                        slop: 2,
+                    },
+                ]),
+            ]
+        );
+
+        // a dangling `NEAR/N` is a plain word
+        let query = parse_advanced_query("a NEAR/3").unwrap();
+        assert_eq!(
+            query,
+            vec![
+                word(None, "a"),
+                QueryExpr::Word { field: None, word: "NEAR/3".to_string(), is_prefix: true },
+            ]
+        );
+
+        assert!(parse_advanced_query("(a -b)").is_err());
+        assert!(parse_advanced_query("a NEAR/3 \"b c\"").is_err());
+        assert!(parse_advanced_query("title:a NEAR/3 body:b").is_err());
+        assert!(parse_advanced_query("a OR -b").is_err());
+    }
+
+    #[test]
+    fn parse_nested_groups() {
+        let nested = |depth: usize| format!("{}a{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(
+            parse_advanced_query(&nested(limits::MAX_NESTED_GROUPS)).unwrap(),
+            vec![word(None, "a")]
+        );
+
+        // the groups are rejected before being deep enough to overflow the stack
+        for query in [
+            nested(limits::MAX_NESTED_GROUPS + 1),
+            "(".repeat(100_000),
+            "title:(".repeat(100_000),
+            "-(a OR (".repeat(100_000),
+        ] {
+            let error = parse_advanced_query(&query).unwrap_err();
+            assert!(
+                matches!(error, crate::Error::UserError(UserError::InvalidAdvancedQuery(_))),
+                "{error}"
+            );
+        }
+    }
+}
@@ -1,13 +1,15 @@
 use std::collections::BTreeSet;
+use std::iter::Peekable;
 
 use charabia::normalizer::NormalizedTokenIter;
-use charabia::{SeparatorKind, TokenKind};
+use charabia::{SeparatorKind, Token, TokenKind};
+use heed::RoTxn;
 
 use super::compute_derivations::partially_initialized_term_from_word;
 use super::{LocatedQueryTerm, ZeroTypoTerm};
 use crate::search::new::query_term::{Lazy, Phrase, QueryTerm};
-use crate::search::new::Word;
-use crate::{Result, SearchContext, MAX_WORD_LENGTH};
+use crate::search::new::{limits, Word};
+use crate::{Index, Result, SearchContext, UserError, MAX_WORD_LENGTH};
 
 #[derive(Clone)]
 /// Extraction of the content of a query.
@@ -142,9 +144,17 @@ pub fn located_query_terms_from_tokens(
                     }
 
                     // Consume the closing quote and the phrase
-                    if let Some(phrase) = phrase {
+                    if let Some(mut phrase) = phrase {
                         // Per the check above, quote_count > 0
                         quote_count -= 1;
+                        // A closing quote followed by `~N` allows the words of the phrase
+                        // to be at most N positions away from each other.
+                        if quote_count == 0 {
+                            let after_quote = token.lemma().split_once('"').map_or("", |(_, s)| s);
+                            if let Some(slop) = parse_phrase_slop(after_quote, &mut peekable) {
+                                phrase.set_slop(slop)?;
+                            }
+                        }
                         if let Some(located_query_term) = phrase.build(ctx) {
                             // we were evaluating a negative operator so we
                             // put the phrase in the negative phrases
@@ -191,14 +201,40 @@ pub fn located_query_terms_from_tokens(
     Ok(ExtractedTokens { query_terms, negative_words, negative_phrases })
 }
 
+/// Parse the `~N` slop written right after the closing quote of a phrase, consuming its tokens.
+fn parse_phrase_slop<'t>(
+    after_quote: &str,
+    tokens: &mut Peekable<impl Iterator<Item = Token<'t>>>,
+) -> Option<u8> {
+    let has_tilde = if after_quote.is_empty() {
+        tokens.next_if(|token| token.is_separator() && token.lemma() == "~").is_some()
+    } else {
+        after_quote == "~"
+    };
+    if !has_tilde {
+        return None;
+    }
+    tokens
+        .next_if(|token| token.is_word() && token.lemma().parse::<u8>().is_ok())
+        .and_then(|token| token.lemma().parse().ok())
+}
+
 pub fn number_of_typos_allowed<'ctx>(
     ctx: &SearchContext<'ctx>,
 ) -> Result<impl Fn(&str) -> u8 + 'ctx> {
-    let authorize_typos = ctx.index.authorize_typos(ctx.txn)?;
-    let min_len_one_typo = ctx.index.min_word_len_one_typo(ctx.txn)?;
-    let min_len_two_typos = ctx.index.min_word_len_two_typos(ctx.txn)?;
+    typos_allowed_by_settings(ctx.index, ctx.txn)
+}
+
+/// Returns the number of typos the typo tolerance settings of the index allow on a query word.
+pub(crate) fn typos_allowed_by_settings<'t>(
+    index: &Index,
+    txn: &'t RoTxn<'t>,
+) -> Result<impl Fn(&str) -> u8 + 't> {
+    let authorize_typos = index.authorize_typos(txn)?;
+    let min_len_one_typo = index.min_word_len_one_typo(txn)?;
+    let min_len_two_typos = index.min_word_len_two_typos(txn)?;
 
-    let exact_words = ctx.index.exact_words(ctx.txn)?;
+    let exact_words = index.exact_words(txn)?;
 
     Ok(Box::new(move |word: &str| {
         if !authorize_typos
@@ -263,7 +299,7 @@ pub fn make_ngram(
     term.zero_typo.synonyms.extend(
         index_synonyms.get(&words).cloned().unwrap_or_default().into_iter().map(|words| {
             let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
-            ctx.phrase_interner.insert(Phrase { words })
+            ctx.phrase_interner.insert(Phrase { words, slop: 0 })
         }),
     );
 
@@ -271,6 +307,7 @@ pub fn make_ngram(
         original: ngram_str_interned,
         ngram_words: Some(words_interned),
         is_prefix,
+        restricted_fids: None,
         max_levenshtein_distance: max_nbr_typos,
         zero_typo: term.zero_typo,
         one_typo: Lazy::Uninit,
@@ -282,15 +319,16 @@ pub fn make_ngram(
     Ok(Some(term))
 }
 
-struct PhraseBuilder {
+pub(super) struct PhraseBuilder {
     words: Vec<Option<crate::search::new::Interned<String>>>,
+    slop: u8,
     start: u16,
     end: u16,
 }
 
 impl PhraseBuilder {
-    fn empty() -> Self {
-        Self { words: Default::default(), start: u16::MAX, end: u16::MAX }
+    pub(super) fn empty() -> Self {
+        Self { words: Default::default(), slop: 0, start: u16::MAX, end: u16::MAX }
     }
 
     fn is_empty(&self) -> bool {
@@ -298,7 +336,7 @@ impl PhraseBuilder {
     }
 
     // precondition: token has kind Word or StopWord
-    fn push_word(
+    pub(super) fn push_word(
         &mut self,
         ctx: &mut SearchContext<'_>,
         token: &charabia::Token<'_>,
@@ -317,25 +355,38 @@ impl PhraseBuilder {
         }
     }
 
-    fn build(self, ctx: &mut SearchContext<'_>) -> Option<LocatedQueryTerm> {
+    /// The slop can't exceed the distances stored in the word pair proximity database.
+    pub(super) fn set_slop(&mut self, slop: u8) -> Result<()> {
+        if slop > limits::MAX_PHRASE_SLOP {
+            return Err(UserError::InvalidPhraseSlop { slop, max: limits::MAX_PHRASE_SLOP }.into());
+        }
+        self.slop = slop;
+        Ok(())
+    }
+
+    pub(super) fn build(self, ctx: &mut SearchContext<'_>) -> Option<LocatedQueryTerm> {
         if self.is_empty() {
             return None;
         }
         Some(LocatedQueryTerm {
             value: ctx.term_interner.push({
-                let phrase = ctx.phrase_interner.insert(Phrase { words: self.words });
+                let phrase =
+                    ctx.phrase_interner.insert(Phrase { words: self.words, slop: self.slop });
                 let phrase_desc = phrase.description(ctx);
                 QueryTerm {
                     original: ctx.word_interner.insert(phrase_desc),
                     ngram_words: None,
                     max_levenshtein_distance: 0,
                     is_prefix: false,
+                    restricted_fids: None,
                     zero_typo: ZeroTypoTerm {
                         phrase: Some(phrase),
                         exact: None,
                         prefix_of: BTreeSet::default(),
                         synonyms: BTreeSet::default(),
                         use_prefix_db: None,
+                        infix_of: None,
+                        stem_of: None,
                     },
                     one_typo: Lazy::Uninit,
                     two_typo: Lazy::Uninit,
@@ -8,6 +8,10 @@ use crate::SearchContext;
 #[derive(Default, Clone, PartialEq, Eq, Hash)]
 pub struct Phrase {
     pub words: Vec<Option<Interned<String>>>,
+    /// The maximum distance between two consecutive words of the phrase, in any order.
+    ///
+    /// `0` means that the words must appear side-by-side and in the same order as in the phrase.
+    pub slop: u8,
 }
 impl Interned<Phrase> {
     pub fn description(self, ctx: &SearchContext<'_>) -> String {
@@ -75,6 +75,16 @@ impl RankingRuleGraphTrait for FidGraph {
             all_fields.extend(fields);
         }
 
+        if let Some(infix) = term.term_subset.use_infix(ctx) {
+            let fields = ctx.get_db_word_infix_fids(infix)?;
+            all_fields.extend(fields);
+        }
+
+        if let Some(word) = term.term_subset.use_stem(ctx) {
+            let fields = ctx.get_db_word_stem_fids(word)?;
+            all_fields.extend(fields);
+        }
+
         let weights_map = ctx.index.fieldids_weights_map(ctx.txn)?;
 
         let mut edges = vec![];
@@ -5,7 +5,9 @@ use super::{ComputedCondition, RankingRuleGraphTrait};
 use crate::score_details::{Rank, ScoreDetails};
 use crate::search::new::interner::{DedupInterner, Interned};
 use crate::search::new::query_term::LocatedQueryTermSubset;
-use crate::search::new::resolve_query_graph::compute_query_term_subset_docids_within_position;
+use crate::search::new::resolve_query_graph::{
+    compute_query_term_subset_docids_within_position, compute_query_term_subset_unlocated_docids,
+};
 use crate::search::new::SearchContext;
 use crate::Result;
 
@@ -28,6 +30,10 @@ impl RankingRuleGraphTrait for PositionGraph {
     ) -> Result<ComputedCondition> {
         let PositionCondition { term, positions } = condition;
         let mut docids = RoaringBitmap::new();
+        if positions.is_empty() {
+            docids |=
+                compute_query_term_subset_unlocated_docids(ctx, Some(universe), &term.term_subset)?;
+        }
         // TODO use MultiOps to do the big union
         for position in positions {
             // maybe compute_query_term_subset_docids_within_position should accept a universe as argument
@@ -108,7 +114,10 @@ impl RankingRuleGraphTrait for PositionGraph {
             ));
         }
 
-        if !max_cost_exists {
+        // infix and stem matches have no known position, they are given the max cost
+        let has_unlocated_matches =
+            term.term_subset.use_infix(ctx).is_some() || term.term_subset.use_stem(ctx).is_some();
+        if !max_cost_exists || has_unlocated_matches {
             // artificial empty condition for computing max cost
             edges.push((
                 max_cost,
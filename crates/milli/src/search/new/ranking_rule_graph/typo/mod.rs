@@ -52,8 +52,11 @@ impl RankingRuleGraphTrait for TypoGraph {
         // 3-gram -> equivalent to 2 typos
         let base_cost = if term.term_ids.len() == 1 { 0 } else { term.term_ids.len() as u32 };
 
-        for nbr_typos in 0..=term.term_subset.max_typo_cost(ctx) {
+        let max_typo_cost = term.term_subset.max_typo_cost(ctx);
+        for nbr_typos in 0..=max_typo_cost {
             let mut term = term.clone();
+            term.term_subset.clear_infix_subset();
+            term.term_subset.clear_stem_subset();
             match nbr_typos {
                 0 => {
                     term.term_subset.clear_one_typo_subset();
@@ -75,6 +78,35 @@ impl RankingRuleGraphTrait for TypoGraph {
                 conditions_interner.insert(TypoCondition { term, nbr_typos }),
             ));
         }
+
+        // Stem matches on the stemmed attributes are ranked after all the typo derivations
+        let mut nbr_typos = max_typo_cost;
+        if term.term_subset.use_stem(ctx).is_some() {
+            let mut term = term.clone();
+            term.term_subset.clear_zero_typo_subset();
+            term.term_subset.clear_one_typo_subset();
+            term.term_subset.clear_two_typo_subset();
+            term.term_subset.clear_infix_subset();
+            nbr_typos += 1;
+            edges.push((
+                nbr_typos as u32 + base_cost,
+                conditions_interner.insert(TypoCondition { term, nbr_typos }),
+            ));
+        }
+
+        // Infix matches on the n-gram attributes are ranked last
+        if term.term_subset.use_infix(ctx).is_some() {
+            let mut term = term.clone();
+            term.term_subset.clear_zero_typo_subset();
+            term.term_subset.clear_one_typo_subset();
+            term.term_subset.clear_two_typo_subset();
+            term.term_subset.clear_stem_subset();
+            nbr_typos += 1;
+            edges.push((
+                nbr_typos as u32 + base_cost,
+                conditions_interner.insert(TypoCondition { term, nbr_typos }),
+            ));
+        }
         Ok(edges)
     }
 
@@ -10,6 +10,7 @@ use super::query_graph::QueryNodeData;
 use super::query_term::{Phrase, QueryTermSubset};
 use super::small_bitmap::SmallBitmap;
 use super::{QueryGraph, SearchContext, Word};
+use crate::proximity::MAX_DISTANCE;
 use crate::search::new::query_term::LocatedQueryTermSubset;
 use crate::Result;
 
@@ -36,6 +37,16 @@ pub fn compute_query_term_subset_docids(
     term: &QueryTermSubset,
 ) -> Result<RoaringBitmap> {
     let mut docids = RoaringBitmap::new();
+    if let Some(fids) = term.restricted_fids(ctx) {
+        for fid in fids {
+            docids |= compute_query_term_subset_docids_within_field_id(ctx, universe, term, fid)?;
+        }
+        return match universe {
+            Some(universe) => Ok(docids & universe),
+            None => Ok(docids),
+        };
+    }
+
     // TODO use the MultiOps trait to do large intersections
     for word in term.all_single_words_except_prefix_db(ctx)? {
         if let Some(word_docids) = ctx.word_docids(universe, word)? {
@@ -52,12 +63,42 @@ pub fn compute_query_term_subset_docids(
         }
     }
 
+    docids |= compute_query_term_subset_unlocated_docids(ctx, universe, term)?;
+
     match universe {
         Some(universe) => Ok(docids & universe),
         None => Ok(docids),
     }
 }
 
+/// Returns the documents matched by the infix and stem derivations of the term,
+/// which are not indexed by position.
+pub fn compute_query_term_subset_unlocated_docids(
+    ctx: &mut SearchContext<'_>,
+    universe: Option<&RoaringBitmap>,
+    term: &QueryTermSubset,
+) -> Result<RoaringBitmap> {
+    let mut docids = RoaringBitmap::new();
+
+    if let Some(infix) = term.use_infix(ctx) {
+        for fid in ctx.get_db_word_infix_fids(infix)? {
+            if let Some(infix_docids) = ctx.get_db_word_infix_fid_docids(universe, infix, fid)? {
+                docids |= infix_docids;
+            }
+        }
+    }
+
+    if let Some(word) = term.use_stem(ctx) {
+        for fid in ctx.get_db_word_stem_fids(word)? {
+            if let Some(stem_docids) = ctx.get_db_word_stem_fid_docids(universe, word, fid)? {
+                docids |= stem_docids;
+            }
+        }
+    }
+
+    Ok(docids)
+}
+
 pub fn compute_query_term_subset_docids_within_field_id(
     ctx: &mut SearchContext<'_>,
     universe: Option<&RoaringBitmap>,
@@ -65,6 +106,10 @@ pub fn compute_query_term_subset_docids_within_field_id(
     fid: u16,
 ) -> Result<RoaringBitmap> {
     let mut docids = RoaringBitmap::new();
+    if term.restricted_fids(ctx).is_some_and(|fids| !fids.contains(&fid)) {
+        return Ok(docids);
+    }
+
     for word in term.all_single_words_except_prefix_db(ctx)? {
         if let Some(word_fid_docids) = ctx.get_db_word_fid_docids(universe, word.interned(), fid)? {
             docids |= word_fid_docids;
@@ -89,6 +134,18 @@ pub fn compute_query_term_subset_docids_within_field_id(
         }
     }
 
+    if let Some(infix) = term.use_infix(ctx) {
+        if let Some(infix_docids) = ctx.get_db_word_infix_fid_docids(universe, infix, fid)? {
+            docids |= infix_docids;
+        }
+    }
+
+    if let Some(word) = term.use_stem(ctx) {
+        if let Some(stem_docids) = ctx.get_db_word_stem_fid_docids(universe, word, fid)? {
+            docids |= stem_docids;
+        }
+    }
+
     Ok(docids)
 }
 
@@ -188,7 +245,7 @@ pub fn compute_phrase_docids(
     ctx: &mut SearchContext<'_>,
     phrase: Interned<Phrase>,
 ) -> Result<RoaringBitmap> {
-    let Phrase { words } = ctx.phrase_interner.get(phrase).clone();
+    let Phrase { words, slop } = ctx.phrase_interner.get(phrase).clone();
 
     if words.is_empty() {
         return Ok(RoaringBitmap::new());
@@ -210,6 +267,21 @@ pub fn compute_phrase_docids(
         return Ok(RoaringBitmap::new());
     };
 
+    if slop > 0 {
+        // The stop words of a phrase with a slop are ignored, the remaining words must each
+        // be close to the next one.
+        let words: Vec<_> = words.iter().flatten().copied().collect();
+        for pair in words.windows(2) {
+            let [left, right] = pair else { unreachable!() };
+            candidates =
+                compute_words_within_distance_docids(ctx, *left, *right, slop, &candidates)?;
+            if candidates.is_empty() {
+                break;
+            }
+        }
+        return Ok(candidates);
+    }
+
     let winsize = words.len().min(3);
 
     for win in words.windows(winsize) {
@@ -266,3 +338,33 @@ pub fn compute_phrase_docids(
     }
     Ok(candidates)
 }
+
+/// Compute the candidates in which the two words are at most `distance` positions away
+/// from each other, in any order.
+///
+/// The slop of the phrases is limited to the distances stored in the word pair proximity
+/// database, see [`super::limits::MAX_PHRASE_SLOP`].
+fn compute_words_within_distance_docids(
+    ctx: &mut SearchContext<'_>,
+    left: Interned<String>,
+    right: Interned<String>,
+    distance: u8,
+    candidates: &RoaringBitmap,
+) -> Result<RoaringBitmap> {
+    let mut docids = RoaringBitmap::new();
+
+    // The word pair proximity database stores the exact distance of the words
+    // that are less than `MAX_DISTANCE` positions away in the order of its key.
+    let max_proximity = (distance as u32).min(MAX_DISTANCE - 1) as u8;
+    for proximity in 1..=max_proximity {
+        for (word1, word2) in [(left, right), (right, left)] {
+            if let Some(pair_docids) =
+                ctx.get_db_word_pair_proximity_docids(None, word1, word2, proximity)?
+            {
+                docids |= pair_docids;
+            }
+        }
+    }
+    docids &= candidates;
+    Ok(docids)
+}
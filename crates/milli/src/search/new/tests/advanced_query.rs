@@ -0,0 +1,132 @@
+/*!
+This module tests the advanced query syntax:
+- a word or a phrase can be restricted to a single searchable attribute with `attribute:`
+- the `OR` operator matches documents containing either of its operands
+- parentheses group terms, the group itself is mandatory
+- the `-` operator excludes the documents containing the negated term
+- an attribute that is not searchable, or not one of the `attributesToSearchOn`, is rejected
+  without revealing whether it exists
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{Error, QuerySyntax, Search, SearchResult, TermsMatchingStrategy, UserError};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned(), "description".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+        {
+            "id": 0,
+            "title": "The Hidden World",
+            "description": "A dragon lives here",
+        },
+        {
+            "id": 1,
+            "title": "Dragon Tales",
+            "description": "Stories about a cat",
+        },
+        {
+            "id": 2,
+            "title": "Cat and Dog",
+            "description": "A hidden dragon",
+        },
+        {
+            "id": 3,
+            "title": "Dog House",
+            "description": "Nothing to see",
+        },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search_ids(index: &TempIndex, query: &str) -> crate::Result<Vec<u32>> {
+    search_ids_on(index, query, None)
+}
+
+fn search_ids_on(
+    index: &TempIndex,
+    query: &str,
+    attributes_to_search_on: Option<Vec<String>>,
+) -> crate::Result<Vec<u32>> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    if let Some(attributes_to_search_on) = &attributes_to_search_on {
+        s.searchable_attributes(attributes_to_search_on);
+    }
+    s.query_syntax(QuerySyntax::Advanced);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { mut documents_ids, .. } = s.execute()?;
+    documents_ids.sort_unstable();
+    Ok(documents_ids)
+}
+
+#[test]
+fn test_attribute_restriction() {
+    let index = create_index();
+
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "dragon").unwrap()), @"[0, 1, 2]");
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "title:dragon").unwrap()), @"[1]");
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "description:\"hidden dragon\"").unwrap()), @"[2]");
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "title:(hidden OR tales)").unwrap()), @"[0, 1]");
+}
+
+#[test]
+fn test_disjunction_and_group() {
+    let index = create_index();
+
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "cat OR dog").unwrap()), @"[1, 2, 3]");
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "(cat OR dog) house").unwrap()), @"[3]");
+}
+
+#[test]
+fn test_negation() {
+    let index = create_index();
+
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "dragon -hidden").unwrap()), @"[1]");
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "dragon -title:hidden").unwrap()), @"[1, 2]");
+}
+
+#[test]
+fn test_invalid_queries() {
+    let index = create_index();
+
+    let error = search_ids(&index, "unknown:dragon").unwrap_err();
+    assert!(matches!(error, Error::UserError(UserError::InvalidSearchQueryAttribute { .. })));
+
+    let error = search_ids(&index, "(dragon -hidden)").unwrap_err();
+    assert!(matches!(error, Error::UserError(UserError::InvalidAdvancedQuery(_))));
+}
+
+#[test]
+fn test_attribute_restriction_outside_attributes_to_search_on() {
+    let index = create_index();
+    let title = Some(vec!["title".to_owned()]);
+
+    insta::assert_snapshot!(format!("{:?}", search_ids_on(&index, "title:dragon", title.clone()).unwrap()), @"[1]");
+    insta::assert_snapshot!(format!("{:?}", search_ids_on(&index, "dragon", title.clone()).unwrap()), @"[1]");
+
+    // the attributes outside of the `attributesToSearchOn` are rejected like the unknown ones
+    let error = search_ids_on(&index, "description:dragon", title.clone()).unwrap_err();
+    insta::assert_snapshot!(error, @"Attribute `description` used in the search query is not searchable. Available searchable attributes are: `title`.");
+    let error = search_ids_on(&index, "unknown:dragon", title.clone()).unwrap_err();
+    insta::assert_snapshot!(error, @"Attribute `unknown` used in the search query is not searchable. Available searchable attributes are: `title`.");
+
+    // and the hidden attributes aren't listed
+    index
+        .update_settings(|s| {
+            s.set_displayed_fields(vec!["id".to_owned(), "description".to_owned()])
+        })
+        .unwrap();
+    let error = search_ids_on(&index, "description:dragon", title).unwrap_err();
+    insta::assert_snapshot!(error, @"Attribute `description` used in the search query is not searchable. Available searchable attributes are: `, <..hidden-attributes>`.");
+}
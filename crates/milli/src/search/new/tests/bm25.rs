@@ -0,0 +1,171 @@
+/*!
+This module tests the `bm25` ranking rule:
+- documents where the query terms appear more often are ranked first
+- shorter documents are ranked before longer ones containing the terms as often
+- the word frequencies and the field lengths are kept up to date by both
+  the document additions and the settings updates
+- they are only indexed when the `bm25` ranking rule is used
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{db_snap, Criterion, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Bm25]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "text": "rust rust rust guide",
+            },
+            {
+                "id": 1,
+                "text": "rust guide for beginners who want to learn programming",
+            },
+            {
+                "id": 2,
+                "text": "rust",
+            },
+            {
+                "id": 3,
+                "text": "python guide",
+            },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search_ids(index: &TempIndex, query: &str) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    documents_ids
+}
+
+fn field_lengths(index: &TempIndex) -> String {
+    let txn = index.read_txn().unwrap();
+    format!("{:?}", index.field_lengths(&txn).unwrap())
+}
+
+#[test]
+fn test_bm25_sorts_by_frequency_and_length() {
+    let index = create_index();
+
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "rust")), @"[0, 2, 1]");
+    // the documents are sorted by bm25 within the buckets of the words ranking rule
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "guide rust")), @"[0, 1, 3]");
+
+    index
+        .update_settings(|s| {
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "rust")), @"[0, 1, 2]");
+}
+
+#[test]
+fn test_bm25_statistics_update() {
+    let index = create_index();
+
+    db_snap!(index, word_frequency_docids, @r###"
+    rust             3      [0, ]
+    "###);
+    db_snap!(index, field_id_length_docids, @r###"
+    1   1      [2, ]
+    1   2      [3, ]
+    1   4      [0, ]
+    1   9      [1, ]
+    "###);
+    insta::assert_snapshot!(field_lengths(&index), @"{1: 16}");
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "text": "rust guide",
+            },
+        ]))
+        .unwrap();
+
+    db_snap!(index, word_frequency_docids, @"");
+    db_snap!(index, field_id_length_docids, @r###"
+    1   1      [2, ]
+    1   2      [0, 3, ]
+    1   9      [1, ]
+    "###);
+    insta::assert_snapshot!(field_lengths(&index), @"{1: 14}");
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "rust")), @"[2, 0, 1]");
+
+    index
+        .update_settings(|s| {
+            s.set_stop_words(["for", "who", "to"].iter().map(|word| word.to_string()).collect());
+        })
+        .unwrap();
+
+    db_snap!(index, field_id_length_docids, @r###"
+    1   1      [2, ]
+    1   2      [0, 3, ]
+    1   6      [1, ]
+    "###);
+    insta::assert_snapshot!(field_lengths(&index), @"{1: 11}");
+}
+
+#[test]
+fn test_bm25_statistics_follow_the_ranking_rule() {
+    let index = create_index();
+
+    index
+        .update_settings(|s| {
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    db_snap!(index, word_frequency_docids, @"");
+    db_snap!(index, field_id_length_docids, @"");
+    insta::assert_snapshot!(field_lengths(&index), @"{}");
+
+    // the statistics are not indexed without the rule
+    index
+        .add_documents(documents!([
+            {
+                "id": 4,
+                "text": "go go guide",
+            },
+        ]))
+        .unwrap();
+
+    db_snap!(index, word_frequency_docids, @"");
+    db_snap!(index, field_id_length_docids, @"");
+    insta::assert_snapshot!(field_lengths(&index), @"{}");
+
+    // adding the rule back reindexes them
+    index
+        .update_settings(|s| {
+            s.set_criteria(vec![Criterion::Words, Criterion::Bm25]);
+        })
+        .unwrap();
+
+    db_snap!(index, word_frequency_docids, @r###"
+    go               2      [4, ]
+    rust             3      [0, ]
+    "###);
+    db_snap!(index, field_id_length_docids, @r###"
+    1   1      [2, ]
+    1   2      [3, ]
+    1   3      [4, ]
+    1   4      [0, ]
+    1   9      [1, ]
+    "###);
+    insta::assert_snapshot!(field_lengths(&index), @"{1: 19}");
+}
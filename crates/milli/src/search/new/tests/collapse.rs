@@ -0,0 +1,106 @@
+/*!
+This module tests the collapse of the search results by the values of a field:
+1. only the best document of each group is returned, and the pagination counts the groups
+2. the documents of each group are returned in the order of the search, ignoring the distinct attribute
+3. a document without any value for the field is alone in its group
+*/
+
+use big_s::S;
+use maplit::hashset;
+
+use crate::index::tests::TempIndex;
+use crate::{
+    AscDesc, CollapsedGroup, Criterion, Error, Filter, FilterableAttributesRule, Member, Search,
+    SearchResult, UserError,
+};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_filterable_fields(vec![
+                FilterableAttributesRule::Field("product_id".to_owned()),
+                FilterableAttributesRule::Field("price".to_owned()),
+            ]);
+            s.set_sortable_fields(hashset! { S("price") });
+            s.set_distinct_field("product_id".to_owned());
+            s.set_criteria(vec![Criterion::Sort]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "product_id": "a", "price": 30 },
+            { "id": 1, "product_id": "b", "price": 10 },
+            { "id": 2, "product_id": "a", "price": 20 },
+            { "id": 3, "product_id": "c", "price": 50 },
+            { "id": 4, "product_id": "b", "price": 40 },
+            { "id": 5, "product_id": "a", "price": 60 },
+            { "id": 6, "product_id": "b", "price": 15 },
+            { "id": 7, "price": 5 },
+        ]))
+        .unwrap();
+    index
+}
+
+fn collapsed_search(search: &Search<'_>) -> (Vec<u32>, Vec<(u64, Vec<u32>)>) {
+    let SearchResult { documents_ids, .. } = search.execute().unwrap();
+    let groups = search
+        .collapsed_groups(&documents_ids, None)
+        .unwrap()
+        .into_iter()
+        .map(|CollapsedGroup { total_hits, documents_ids, .. }| (total_hits, documents_ids))
+        .collect();
+    (documents_ids, groups)
+}
+
+#[test]
+fn test_collapse() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.sort_criteria(vec![AscDesc::Asc(Member::Field(S("price")))]);
+    s.collapse(S("product_id"), 2);
+
+    let (documents_ids, groups) = collapsed_search(&s);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[7, 1, 2, 3]");
+    insta::assert_snapshot!(format!("{groups:?}"), @"[(1, [7]), (3, [1, 6]), (3, [2, 0]), (1, [3])]");
+
+    // the pagination applies to the groups
+    s.offset(1);
+    s.limit(2);
+    let (documents_ids, groups) = collapsed_search(&s);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 2]");
+    insta::assert_snapshot!(format!("{groups:?}"), @"[(3, [1, 6]), (3, [2, 0])]");
+}
+
+#[test]
+fn test_collapse_filter() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.sort_criteria(vec![AscDesc::Desc(Member::Field(S("price")))]);
+    s.filter(Filter::from_str("price < 50").unwrap().unwrap());
+    s.collapse(S("product_id"), 5);
+
+    // the groups only contain the documents matching the filter
+    let (documents_ids, groups) = collapsed_search(&s);
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[4, 0, 7]");
+    insta::assert_snapshot!(format!("{groups:?}"), @"[(3, [4, 6, 1]), (2, [0, 2]), (1, [7])]");
+}
+
+#[test]
+fn test_collapse_not_filterable() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.collapse(S("id"), 2);
+
+    let error = s.execute().unwrap_err();
+    assert!(matches!(error, Error::UserError(UserError::InvalidCollapseAttribute { .. })));
+}
@@ -0,0 +1,190 @@
+/*!
+This module tests the infix matches on the n-gram attributes:
+- a query word of at least 3 characters matches the words of the n-gram attributes
+  that contain it as an infix or a suffix
+- the infix matches are ranked after the exact, prefix and typo matches
+- the n-grams database is recomputed when the n-gram attributes are updated,
+  and kept up to date when documents are added, updated or deleted
+*/
+
+use roaring::RoaringBitmap;
+
+use crate::index::tests::TempIndex;
+use crate::update::compute_ngram_fid_docids;
+use crate::{FieldId, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned(), "description".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+        {
+            "id": 0,
+            "title": "hotdog stand",
+            "description": "mustard",
+        },
+        {
+            "id": 1,
+            "title": "dog house",
+            "description": "wood",
+        },
+        {
+            "id": 2,
+            "title": "the doggo",
+            "description": "fluffy",
+        },
+        {
+            "id": 3,
+            "title": "cat tree",
+            "description": "bulldog",
+        },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search_ids(index: &TempIndex, query: &str) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    documents_ids
+}
+
+#[test]
+fn test_infix_ranked_after_exact_and_prefix() {
+    let index = create_index();
+
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "dog")), @"[1, 2]");
+
+    index
+        .update_settings(|s| {
+            s.set_ngram_attributes(["title".to_owned()].into_iter().collect());
+        })
+        .unwrap();
+
+    // `hotdog` contains `dog` but `bulldog` is not in an n-gram attribute
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "dog")), @"[1, 2, 0]");
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "otdo")), @"[0]");
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "tand")), @"[0]");
+    // too short to be matched as an infix
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "og")), @"[]");
+}
+
+#[test]
+fn test_ngram_attributes_update() {
+    let index = create_index();
+
+    index
+        .update_settings(|s| {
+            s.set_ngram_attributes(
+                ["title".to_owned(), "description".to_owned()].into_iter().collect(),
+            );
+        })
+        .unwrap();
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "dog")), @"[1, 2, 0, 3]");
+
+    index
+        .add_documents(documents!([
+        {
+            "id": 4,
+            "title": "underdog",
+            "description": "story",
+        },
+        ]))
+        .unwrap();
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "dog")), @"[1, 2, 0, 4, 3]");
+
+    index
+        .update_settings(|s| {
+            s.reset_ngram_attributes();
+        })
+        .unwrap();
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "dog")), @"[1, 2]");
+
+    let rtxn = index.read_txn().unwrap();
+    assert!(index.ngram_fid_docids.is_empty(&rtxn).unwrap());
+}
+
+fn ngram_fid_docids(index: &TempIndex) -> Vec<(String, FieldId, RoaringBitmap)> {
+    let rtxn = index.read_txn().unwrap();
+    index
+        .ngram_fid_docids
+        .iter(&rtxn)
+        .unwrap()
+        .map(|result| {
+            let ((ngram, fid), docids) = result.unwrap();
+            (ngram.to_owned(), fid, docids)
+        })
+        .collect()
+}
+
+fn ngram_fid_words(index: &TempIndex) -> Vec<(String, FieldId, Vec<String>)> {
+    let rtxn = index.read_txn().unwrap();
+    index
+        .ngram_fid_words
+        .iter(&rtxn)
+        .unwrap()
+        .map(|result| {
+            let ((ngram, fid), words) = result.unwrap();
+            (ngram.to_owned(), fid, words.stream().into_strs().unwrap())
+        })
+        .collect()
+}
+
+#[test]
+fn test_ngram_fid_docids_incremental_update() {
+    let index = create_index();
+
+    index
+        .update_settings(|s| {
+            s.set_ngram_attributes(["title".to_owned()].into_iter().collect());
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+        {
+            "id": 1,
+            "title": "kennel",
+            "description": "wood",
+        },
+        {
+            "id": 4,
+            "title": "underdog",
+            "description": "story",
+        },
+        ]))
+        .unwrap();
+    index.delete_document("0");
+
+    // `hotdog` and `dog house` are gone, `the doggo` and `underdog` still contain `dog`
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "dog")), @"[2, 4]");
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "otdo")), @"[]");
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "nne")), @"[1]");
+
+    // only the words still in the documents are kept to recompute the n-grams
+    let dog_words: Vec<_> = ngram_fid_words(&index)
+        .into_iter()
+        .filter(|(ngram, _, _)| ngram == "dog")
+        .map(|(_, _, words)| words)
+        .collect();
+    insta::assert_snapshot!(format!("{dog_words:?}"), @r###"[["doggo", "underdog"]]"###);
+
+    // the incremental updates give the same databases as a full rebuild
+    let incremental = ngram_fid_docids(&index);
+    let incremental_words = ngram_fid_words(&index);
+    let mut wtxn = index.write_txn().unwrap();
+    compute_ngram_fid_docids(&mut wtxn, &index, &index.indexer_config.grenad_parameters()).unwrap();
+    wtxn.commit().unwrap();
+    assert_eq!(incremental, ngram_fid_docids(&index));
+    assert_eq!(incremental_words, ngram_fid_words(&index));
+}
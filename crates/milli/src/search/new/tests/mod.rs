@@ -1,17 +1,23 @@
+pub mod advanced_query;
 pub mod attribute_fid;
 pub mod attribute_position;
+pub mod bm25;
+pub mod collapse;
 pub mod cutoff;
 pub mod distinct;
 pub mod exactness;
 pub mod geo_sort;
+pub mod infix;
 pub mod integration;
 #[cfg(feature = "all-tokenizations")]
 #[cfg(not(feature = "chinese-pinyin"))]
 pub mod language;
 pub mod ngram_split_words;
+pub mod phrase_slop;
 pub mod proximity;
 pub mod proximity_typo;
 pub mod sort;
+pub mod stemming;
 pub mod stop_words;
 pub mod typo;
 pub mod typo_proximity;
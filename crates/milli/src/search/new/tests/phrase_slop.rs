@@ -0,0 +1,155 @@
+/*!
+This module tests the phrases written with a slop:
+- `"a b"~N` matches the documents in which `a` and `b` are at most N positions away, in any order
+- the words must be close to each other in the same attribute
+- a slop larger than the distances stored in the word pair proximity database is rejected
+- `a NEAR/N b` of the advanced query syntax is equivalent to `"a b"~N`
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{Error, QuerySyntax, Search, SearchResult, TermsMatchingStrategy, UserError};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+        {
+            "id": 0,
+            "text": "breach of the contract",
+        },
+        {
+            "id": 1,
+            "text": "the contract was in breach",
+        },
+        {
+            "id": 2,
+            "text": "breach of a long and complicated contract",
+        },
+        {
+            "id": 3,
+            "text": "breach contract",
+        },
+        {
+            "id": 4,
+            "text": "contract",
+        },
+        ]))
+        .unwrap();
+    index
+}
+
+fn create_multi_field_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned(), "text".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+        {
+            "id": 0,
+            "title": "breach",
+            "text": "of the contract",
+        },
+        {
+            "id": 1,
+            "title": "the contract",
+            "text": "was in breach",
+        },
+        {
+            "id": 2,
+            "text": "breach of contract",
+        },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search_ids(index: &TempIndex, query: &str, query_syntax: QuerySyntax) -> Vec<u32> {
+    try_search_ids(index, query, query_syntax).unwrap()
+}
+
+fn try_search_ids(
+    index: &TempIndex,
+    query: &str,
+    query_syntax: QuerySyntax,
+) -> crate::Result<Vec<u32>> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.query_syntax(query_syntax);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { mut documents_ids, .. } = s.execute()?;
+    documents_ids.sort_unstable();
+    Ok(documents_ids)
+}
+
+#[test]
+fn test_phrase_slop() {
+    let index = create_index();
+
+    let ids = search_ids(&index, "\"breach contract\"", QuerySyntax::Simple);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[3]");
+
+    let ids = search_ids(&index, "\"breach contract\"~0", QuerySyntax::Simple);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[3]");
+
+    let ids = search_ids(&index, "\"breach contract\"~2", QuerySyntax::Simple);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[3]");
+
+    let ids = search_ids(&index, "\"breach contract\"~3", QuerySyntax::Simple);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[0, 1, 3]");
+}
+
+#[test]
+fn test_near_operator() {
+    let index = create_index();
+
+    let ids = search_ids(&index, "breach NEAR/3 contract", QuerySyntax::Advanced);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[0, 1, 3]");
+
+    let ids = search_ids(&index, "\"breach contract\"~1", QuerySyntax::Advanced);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[3]");
+}
+
+#[test]
+fn test_phrase_slop_across_attributes() {
+    let index = create_multi_field_index();
+
+    // the words are close to each other, but in different attributes
+    let ids = search_ids(&index, "\"breach contract\"~3", QuerySyntax::Simple);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[2]");
+}
+
+#[test]
+fn test_phrase_slop_too_large() {
+    let index = create_index();
+
+    // the distances larger than the ones stored in the word pair proximity database can't
+    // be resolved without reading the documents
+    for (query, query_syntax) in [
+        ("\"breach contract\"~4", QuerySyntax::Simple),
+        ("\"breach contract\"~4", QuerySyntax::Advanced),
+        ("breach NEAR/6 contract", QuerySyntax::Advanced),
+    ] {
+        let error = try_search_ids(&index, query, query_syntax).unwrap_err();
+        assert!(
+            matches!(error, Error::UserError(UserError::InvalidPhraseSlop { max: 3, .. })),
+            "{error}"
+        );
+    }
+    let error = try_search_ids(&index, "\"breach contract\"~6", QuerySyntax::Simple).unwrap_err();
+    insta::assert_snapshot!(error, @"The slop `6` is too large, the words of a phrase or around a `NEAR` operator can be at most 3 positions away from each other.");
+}
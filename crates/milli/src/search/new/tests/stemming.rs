@@ -0,0 +1,183 @@
+/*!
+This module tests the stemming of the localized attributes:
+- the words of the attributes whose localized attributes rule enables stemming
+  are also matched by the query words sharing their stem
+- the stem matches are ranked after the exact and typo matches
+- the stems are not used when the query locales don't include the language of the stemmer
+- the stems database is recomputed when the localized attributes rules are updated,
+  and kept up to date when documents are added or deleted
+- the words are stemmed with the stemmer of the locale of their attribute
+*/
+
+use charabia::Language;
+use roaring::RoaringBitmap;
+
+use crate::index::tests::TempIndex;
+use crate::update::compute_stem_fid_docids;
+use crate::{FieldId, LocalizedAttributesRule, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned(), "description".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+        {
+            "id": 0,
+            "title": "the children run",
+            "description": "park",
+        },
+        {
+            "id": 1,
+            "title": "a child running",
+            "description": "street",
+        },
+        {
+            "id": 2,
+            "title": "runner up",
+            "description": "running",
+        },
+        {
+            "id": 3,
+            "title": "the runs of the child",
+            "description": "beach",
+        },
+        ]))
+        .unwrap();
+    index
+}
+
+fn enable_stemming(index: &TempIndex, stemming: bool) {
+    let mut rule = LocalizedAttributesRule::new(vec!["title".to_owned()], vec![Language::Eng]);
+    rule.stemming = stemming;
+    index
+        .update_settings(|s| {
+            s.set_localized_attributes_rules(vec![rule]);
+        })
+        .unwrap();
+}
+
+fn search_ids(index: &TempIndex, query: &str, locales: Option<Vec<Language>>) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    if let Some(locales) = locales {
+        s.locales(locales);
+    }
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    documents_ids
+}
+
+#[test]
+fn test_stems_ranked_after_exact_matches() {
+    let index = create_index();
+    enable_stemming(&index, false);
+
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "running", None)), @"[1, 2]");
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "children", None)), @"[0]");
+
+    enable_stemming(&index, true);
+
+    // `runner` doesn't share the stem of `running` and the description is not stemmed
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "running", None)), @"[1, 2, 0, 3]");
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "children", None)), @"[0, 1, 3]");
+    // irregular forms are reduced to their lemma
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "ran", None)), @"[0, 1, 3]");
+}
+
+#[test]
+fn test_stems_follow_query_locales() {
+    let index = create_index();
+    enable_stemming(&index, true);
+
+    let english = search_ids(&index, "children", Some(vec![Language::Eng]));
+    insta::assert_snapshot!(format!("{english:?}"), @"[0, 1, 3]");
+    let french = search_ids(&index, "children", Some(vec![Language::Fra]));
+    insta::assert_snapshot!(format!("{french:?}"), @"[0]");
+}
+
+#[test]
+fn test_stems_database_update() {
+    let index = create_index();
+    enable_stemming(&index, true);
+
+    index
+        .add_documents(documents!([
+        {
+            "id": 4,
+            "title": "childs play",
+            "description": "toys",
+        },
+        ]))
+        .unwrap();
+
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "children", None)), @"[0, 1, 3, 4]");
+
+    enable_stemming(&index, false);
+
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "children", None)), @"[0]");
+    let rtxn = index.read_txn().unwrap();
+    assert!(index.stem_fid_docids.is_empty(&rtxn).unwrap());
+}
+
+fn stem_fid_docids(index: &TempIndex) -> Vec<(String, FieldId, RoaringBitmap)> {
+    let rtxn = index.read_txn().unwrap();
+    index
+        .stem_fid_docids
+        .iter(&rtxn)
+        .unwrap()
+        .map(|result| {
+            let ((stem, fid), docids) = result.unwrap();
+            (stem.to_owned(), fid, docids)
+        })
+        .collect()
+}
+
+#[test]
+fn test_french_stems() {
+    let index = TempIndex::new();
+
+    let mut rule = LocalizedAttributesRule::new(vec!["title".to_owned()], vec![Language::Fra]);
+    rule.stemming = true;
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned()]);
+            s.set_localized_attributes_rules(vec![rule]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+        { "id": 0, "title": "le cheval mange" },
+        { "id": 1, "title": "les chevaux mangeaient" },
+        { "id": 2, "title": "un jardin" },
+        { "id": 3, "title": "des jardins" },
+        ]))
+        .unwrap();
+    index.delete_document("3");
+
+    // `chevaux` and `cheval` share the `cheval` stem, `mangeaient` and `mange` the `mang` stem
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "chevaux", None)), @"[1, 0]");
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "mangeaient", None)), @"[1, 0]");
+    insta::assert_snapshot!(format!("{:?}", search_ids(&index, "jardins", None)), @"[2]");
+    let french = search_ids(&index, "chevaux", Some(vec![Language::Fra]));
+    insta::assert_snapshot!(format!("{french:?}"), @"[1, 0]");
+    // the English stemmer doesn't reduce `chevaux`
+    let english = search_ids(&index, "chevaux", Some(vec![Language::Eng]));
+    insta::assert_snapshot!(format!("{english:?}"), @"[1]");
+
+    // the incremental updates give the same database as a full rebuild
+    let incremental = stem_fid_docids(&index);
+    let mut wtxn = index.write_txn().unwrap();
+    compute_stem_fid_docids(&mut wtxn, &index, &index.indexer_config.grenad_parameters()).unwrap();
+    wtxn.commit().unwrap();
+    assert_eq!(incremental, stem_fid_docids(&index));
+}
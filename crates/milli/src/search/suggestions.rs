@@ -0,0 +1,299 @@
+use std::cmp::Reverse;
+
+use charabia::{Language, TokenizerBuilder};
+use fst::automaton::Str;
+use fst::{IntoStreamer, Streamer};
+use roaring::RoaringBitmap;
+
+use super::fst_utils::{Intersection, StartsWith};
+use super::new::typos_allowed_by_settings;
+use super::{build_dfa, get_first, word_docids_in_fields};
+use crate::{FieldId, Index, Result};
+
+/// The default maximum number of suggestions returned.
+pub const DEFAULT_SUGGESTIONS_LIMIT: usize = 5;
+/// The maximum number of words of the query that are corrected, the following ones are ignored.
+const MAX_QUERY_WORDS: usize = 10;
+/// The maximum number of alternative words kept for each word of the query.
+const MAX_CANDIDATES_PER_WORD: usize = 5;
+/// The maximum number of partial queries kept while combining the alternative words.
+const MAX_PARTIAL_QUERIES: usize = 20;
+
+/// An alternative query string, made of words that exist in the index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuerySuggestion {
+    pub query: String,
+    /// The number of documents containing all the words of the suggestion.
+    pub estimated_total_hits: u64,
+}
+
+/// Computes spelling corrections of a query from the words of the index.
+///
+/// Every word of the query is replaced by the words of the index that are at most
+/// as many typos away as the search would tolerate, the most frequent ones first.
+/// The alternative queries are then ranked by the number of documents, among the universe,
+/// containing all their words.
+pub struct QuerySuggestions<'a> {
+    query: String,
+    limit: usize,
+    locales: Option<Vec<Language>>,
+    universe: Option<RoaringBitmap>,
+    fields_ids: Option<Vec<FieldId>>,
+    rtxn: &'a heed::RoTxn<'a>,
+    index: &'a Index,
+}
+
+struct Candidate {
+    word: String,
+    typos: u8,
+    docids: RoaringBitmap,
+}
+
+struct PartialQuery {
+    words: Vec<String>,
+    typos: u32,
+    docids: Option<RoaringBitmap>,
+}
+
+impl PartialQuery {
+    fn hits(&self) -> u64 {
+        self.docids.as_ref().map_or(0, RoaringBitmap::len)
+    }
+}
+
+impl<'a> QuerySuggestions<'a> {
+    pub fn new(query: impl Into<String>, index: &'a Index, rtxn: &'a heed::RoTxn<'a>) -> Self {
+        Self {
+            query: query.into(),
+            limit: DEFAULT_SUGGESTIONS_LIMIT,
+            locales: None,
+            universe: None,
+            fields_ids: None,
+            rtxn,
+            index,
+        }
+    }
+
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = limit;
+        self
+    }
+
+    pub fn locales(&mut self, locales: Vec<Language>) -> &mut Self {
+        self.locales = Some(locales);
+        self
+    }
+
+    /// Only counts the documents of the universe, the words of the other documents are not proposed.
+    pub fn universe(&mut self, universe: RoaringBitmap) -> &mut Self {
+        self.universe = Some(universe);
+        self
+    }
+
+    /// Only proposes the words of these fields.
+    pub fn fields_ids(&mut self, fields_ids: Vec<FieldId>) -> &mut Self {
+        self.fields_ids = Some(fields_ids);
+        self
+    }
+
+    pub fn execute(&self) -> Result<Vec<QuerySuggestion>> {
+        let words = self.query_words()?;
+        if words.is_empty() || self.limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut partial_queries = vec![PartialQuery { words: Vec::new(), typos: 0, docids: None }];
+        for word in &words {
+            let candidates = self.word_candidates(word)?;
+            let mut next_partial_queries = Vec::new();
+            for partial in &partial_queries {
+                for candidate in &candidates {
+                    let docids = match &partial.docids {
+                        Some(docids) => docids & &candidate.docids,
+                        None => candidate.docids.clone(),
+                    };
+                    if docids.is_empty() {
+                        continue;
+                    }
+                    let mut words = partial.words.clone();
+                    words.push(candidate.word.clone());
+                    next_partial_queries.push(PartialQuery {
+                        words,
+                        typos: partial.typos + candidate.typos as u32,
+                        docids: Some(docids),
+                    });
+                }
+            }
+            next_partial_queries.sort_by_key(|partial| (Reverse(partial.hits()), partial.typos));
+            next_partial_queries.truncate(MAX_PARTIAL_QUERIES);
+            partial_queries = next_partial_queries;
+        }
+
+        Ok(partial_queries
+            .into_iter()
+            // the query itself is not a suggestion
+            .filter(|partial| partial.typos != 0)
+            .take(self.limit)
+            .map(|partial| QuerySuggestion {
+                estimated_total_hits: partial.hits(),
+                query: partial.words.join(" "),
+            })
+            .collect())
+    }
+
+    /// Returns the normalized words of the query, without the stop words.
+    fn query_words(&self) -> Result<Vec<String>> {
+        let mut tokbuilder = TokenizerBuilder::new();
+        let stop_words = self.index.stop_words(self.rtxn)?;
+        if let Some(ref stop_words) = stop_words {
+            tokbuilder.stop_words(stop_words);
+        }
+        if let Some(locales) = self.locales.as_deref().filter(|locales| !locales.is_empty()) {
+            tokbuilder.allow_list(locales);
+        }
+        let tokenizer = tokbuilder.build();
+
+        Ok(tokenizer
+            .tokenize(&self.query)
+            .filter(|token| token.is_word())
+            .map(|token| token.lemma().to_string())
+            .take(MAX_QUERY_WORDS)
+            .collect())
+    }
+
+    /// Returns the words of the index that are a few typos away from the given word,
+    /// ordered by number of typos and then by frequency.
+    ///
+    /// The typos follow the typo tolerance settings of the index like the search does: the exact
+    /// words, the exact attributes and, when disabled, the numbers only match without typos.
+    fn word_candidates(&self, word: &str) -> Result<Vec<Candidate>> {
+        let max_typos = typos_allowed_by_settings(self.index, self.rtxn)?(word);
+        let disabled_typos_terms = self.index.disabled_typos_terms(self.rtxn)?;
+        let exact_attributes_ids = self.index.exact_attributes_ids(self.rtxn)?;
+        let typo_fields_ids: Option<Vec<FieldId>> = self.fields_ids.as_ref().map(|fields_ids| {
+            fields_ids.iter().copied().filter(|fid| !exact_attributes_ids.contains(fid)).collect()
+        });
+
+        let fst = self.index.words_fst(self.rtxn)?;
+        let dfa = build_dfa(word, max_typos, false);
+        let starts = StartsWith(Str::new(get_first(word)));
+        let mut stream = fst.search_with_state(Intersection(starts, &dfa)).into_stream();
+
+        let mut candidates = Vec::new();
+        while let Some((derived_word, state)) = stream.next() {
+            let typos = dfa.distance(state.1).to_u8();
+            let derived_word = std::str::from_utf8(derived_word)?;
+            let mut docids = if typos == 0 {
+                self.exact_word_docids(derived_word)?
+            } else if disabled_typos_terms.is_exact(derived_word) {
+                continue;
+            } else {
+                // the words of the exact attributes are not in the `word_docids` database
+                word_docids_in_fields(
+                    self.index,
+                    self.rtxn,
+                    derived_word,
+                    typo_fields_ids.as_deref(),
+                )?
+            };
+            if let Some(universe) = &self.universe {
+                docids &= universe;
+            }
+            if !docids.is_empty() {
+                candidates.push(Candidate { word: derived_word.to_owned(), typos, docids });
+            }
+        }
+
+        candidates.sort_by_key(|candidate| (candidate.typos, Reverse(candidate.docids.len())));
+        candidates.truncate(MAX_CANDIDATES_PER_WORD);
+        Ok(candidates)
+    }
+
+    /// Returns the documents containing the word in any of the fields, exact attributes included.
+    fn exact_word_docids(&self, word: &str) -> Result<RoaringBitmap> {
+        match &self.fields_ids {
+            Some(fields_ids) => {
+                word_docids_in_fields(self.index, self.rtxn, word, Some(fields_ids))
+            }
+            None => {
+                let mut docids = self.index.word_docids.get(self.rtxn, word)?.unwrap_or_default();
+                if let Some(exact_docids) = self.index.exact_word_docids.get(self.rtxn, word)? {
+                    docids |= exact_docids;
+                }
+                Ok(docids)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::index::tests::TempIndex;
+    use crate::search::suggestions::QuerySuggestion;
+    use crate::QuerySuggestions;
+
+    #[test]
+    fn suggest_corrected_queries() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "the quick brown fox" },
+                { "id": 1, "title": "the quick brown cat" },
+                { "id": 2, "title": "a quack duck" },
+                { "id": 3, "title": "brown bread" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let suggest = |query: &str| QuerySuggestions::new(query, &index, &rtxn).execute().unwrap();
+
+        let suggestion = |query: &str, hits| QuerySuggestion {
+            query: query.to_owned(),
+            estimated_total_hits: hits,
+        };
+
+        assert_eq!(suggest("quikc browm"), vec![suggestion("quick brown", 2)]);
+        // `quack` and `quick` are both one typo away from `quock`
+        assert_eq!(suggest("quock"), vec![suggestion("quick", 2), suggestion("quack", 1)]);
+        // the query is already correct
+        assert_eq!(suggest("brown"), vec![]);
+        // there is no document with both words
+        assert_eq!(suggest("duck browm"), vec![]);
+    }
+
+    #[test]
+    fn suggest_with_the_typo_tolerance_settings() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "quick brown fox", "code": "12345", "tag": "purple" },
+                { "id": 1, "title": "quack duck", "code": "67890", "tag": "yellow" },
+            ]))
+            .unwrap();
+
+        let suggest = |index: &TempIndex, query: &str| {
+            let rtxn = index.read_txn().unwrap();
+            let suggestions = QuerySuggestions::new(query, index, &rtxn).execute().unwrap();
+            suggestions.into_iter().map(|suggestion| suggestion.query).collect::<Vec<_>>()
+        };
+
+        assert_eq!(suggest(&index, "12346"), vec!["12345"]);
+        assert_eq!(suggest(&index, "purpel"), vec!["purple"]);
+        assert_eq!(suggest(&index, "quock"), vec!["quack", "quick"]);
+
+        index.update_settings(|s| s.set_disable_on_numbers(true)).unwrap();
+        assert_eq!(suggest(&index, "12346"), Vec::<String>::new());
+
+        index.update_settings(|s| s.set_exact_attributes(["tag".to_owned()].into())).unwrap();
+        assert_eq!(suggest(&index, "purpel"), Vec::<String>::new());
+
+        index.update_settings(|s| s.set_exact_words(["quock".to_owned()].into())).unwrap();
+        assert_eq!(suggest(&index, "quock"), Vec::<String>::new());
+        assert_eq!(suggest(&index, "quikc"), vec!["quick"]);
+
+        index.update_settings(|s| s.set_autorize_typos(false)).unwrap();
+        assert_eq!(suggest(&index, "quikc"), Vec::<String>::new());
+    }
+}
@@ -92,6 +92,8 @@ Create a snapshot test of the given database.
     - `word_prefix_pair_proximity_docids`
     - `word_position_docids`
     - `field_id_word_count_docids`
+    - `field_id_length_docids`
+    - `word_frequency_docids`
     - `word_prefix_position_docids`
     - `facet_id_f64_docids`
     - `facet_id_string_docids`
@@ -234,6 +236,16 @@ pub fn snap_field_id_word_count_docids(index: &Index) -> String {
         &format!("{field_id:<3} {word_count:<6} {}", display_bitmap(&b))
     })
 }
+pub fn snap_field_id_length_docids(index: &Index) -> String {
+    make_db_snap_from_iter!(index, field_id_length_docids, |((field_id, length), b)| {
+        &format!("{field_id:<3} {length:<6} {}", display_bitmap(&b))
+    })
+}
+pub fn snap_word_frequency_docids(index: &Index) -> String {
+    make_db_snap_from_iter!(index, word_frequency_docids, |((word, frequency), b)| {
+        &format!("{word:<16} {frequency:<6} {}", display_bitmap(&b))
+    })
+}
 pub fn snap_word_prefix_position_docids(index: &Index) -> String {
     make_db_snap_from_iter!(index, word_prefix_position_docids, |((word_prefix, position), b)| {
         &format!("{word_prefix:<4} {position:<6} {}", display_bitmap(&b))
@@ -455,6 +467,12 @@ macro_rules! full_snap_of_db {
     ($index:ident, field_id_word_count_docids) => {{
         $crate::snapshot_tests::snap_field_id_word_count_docids(&$index)
     }};
+    ($index:ident, field_id_length_docids) => {{
+        $crate::snapshot_tests::snap_field_id_length_docids(&$index)
+    }};
+    ($index:ident, word_frequency_docids) => {{
+        $crate::snapshot_tests::snap_word_frequency_docids(&$index)
+    }};
     ($index:ident, word_prefix_position_docids) => {{
         $crate::snapshot_tests::snap_word_prefix_position_docids(&$index)
     }};
@@ -0,0 +1,163 @@
+//! Stemming of the words of the attributes whose localized attributes rule enables it.
+//!
+//! The words are reduced to their stem with the Snowball stemmer of their language,
+//! the common irregular forms of the English words are lemmatized beforehand.
+
+use charabia::Language;
+use rust_stemmers::{Algorithm, Stemmer};
+
+/// Returns the Snowball stemming algorithm of a language, if there is one.
+fn algorithm(language: Language) -> Option<Algorithm> {
+    let algorithm = match language {
+        Language::Ara => Algorithm::Arabic,
+        Language::Dan => Algorithm::Danish,
+        Language::Nld => Algorithm::Dutch,
+        Language::Eng => Algorithm::English,
+        Language::Fin => Algorithm::Finnish,
+        Language::Fra => Algorithm::French,
+        Language::Deu => Algorithm::German,
+        Language::Ell => Algorithm::Greek,
+        Language::Hun => Algorithm::Hungarian,
+        Language::Ita => Algorithm::Italian,
+        Language::Nob => Algorithm::Norwegian,
+        Language::Por => Algorithm::Portuguese,
+        Language::Ron => Algorithm::Romanian,
+        Language::Rus => Algorithm::Russian,
+        Language::Spa => Algorithm::Spanish,
+        Language::Swe => Algorithm::Swedish,
+        Language::Tam => Algorithm::Tamil,
+        Language::Tur => Algorithm::Turkish,
+        _ => return None,
+    };
+    Some(algorithm)
+}
+
+/// Returns `true` if the words of this language can be stemmed.
+pub fn is_stemming_supported(language: Language) -> bool {
+    algorithm(language).is_some()
+}
+
+/// Returns the stem of a normalized word in the given language.
+///
+/// Returns `None` if the language is not supported or if the word can't be stemmed,
+/// a word that is its own stem is returned as is.
+pub fn stem(language: Language, word: &str) -> Option<String> {
+    let algorithm = algorithm(language)?;
+    if word.is_empty() || !word.chars().all(char::is_alphabetic) {
+        return None;
+    }
+
+    let word = match language {
+        Language::Eng => english::lemmatize(word),
+        _ => word,
+    };
+
+    Some(Stemmer::create(algorithm).stem(word).into_owned())
+}
+
+mod english {
+    /// The irregular forms that the Snowball stemmer is unable to reduce, with their lemma.
+    const IRREGULAR_FORMS: &[(&str, &str)] = &[
+        ("children", "child"),
+        ("feet", "foot"),
+        ("geese", "goose"),
+        ("men", "man"),
+        ("mice", "mouse"),
+        ("people", "person"),
+        ("teeth", "tooth"),
+        ("women", "woman"),
+        ("began", "begin"),
+        ("begun", "begin"),
+        ("bought", "buy"),
+        ("brought", "bring"),
+        ("came", "come"),
+        ("did", "do"),
+        ("done", "do"),
+        ("found", "find"),
+        ("gave", "give"),
+        ("given", "give"),
+        ("gone", "go"),
+        ("knew", "know"),
+        ("known", "know"),
+        ("made", "make"),
+        ("ran", "run"),
+        ("sang", "sing"),
+        ("sung", "sing"),
+        ("saw", "see"),
+        ("seen", "see"),
+        ("spoke", "speak"),
+        ("spoken", "speak"),
+        ("taught", "teach"),
+        ("thought", "think"),
+        ("took", "take"),
+        ("taken", "take"),
+        ("went", "go"),
+        ("wrote", "write"),
+        ("written", "write"),
+    ];
+
+    /// Returns the lemma of an irregular form, or the word itself.
+    pub fn lemmatize(word: &str) -> &str {
+        IRREGULAR_FORMS
+            .iter()
+            .find_map(|(form, lemma)| (*form == word).then_some(*lemma))
+            .unwrap_or(word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use charabia::Language;
+
+    use super::stem;
+
+    #[test]
+    fn english_stems() {
+        let stem = |word| stem(Language::Eng, word).unwrap();
+
+        assert_eq!(stem("caresses"), "caress");
+        assert_eq!(stem("ponies"), "poni");
+        assert_eq!(stem("cats"), "cat");
+        assert_eq!(stem("agreed"), "agre");
+        assert_eq!(stem("plastered"), "plaster");
+        assert_eq!(stem("motoring"), "motor");
+        assert_eq!(stem("sing"), "sing");
+        assert_eq!(stem("hopping"), "hop");
+        assert_eq!(stem("falling"), "fall");
+        assert_eq!(stem("filing"), "file");
+        assert_eq!(stem("happy"), "happi");
+        assert_eq!(stem("relational"), "relat");
+        assert_eq!(stem("generalization"), "general");
+        assert_eq!(stem("connection"), "connect");
+        assert_eq!(stem("connected"), "connect");
+        assert_eq!(stem("running"), "run");
+        assert_eq!(stem("runs"), "run");
+        assert_eq!(stem("run"), "run");
+    }
+
+    #[test]
+    fn english_irregular_forms() {
+        let stem = |word| stem(Language::Eng, word).unwrap();
+
+        assert_eq!(stem("children"), stem("child"));
+        assert_eq!(stem("mice"), stem("mouse"));
+        assert_eq!(stem("ran"), stem("running"));
+    }
+
+    #[test]
+    fn french_stems() {
+        let stem = |word| stem(Language::Fra, word).unwrap();
+
+        assert_eq!(stem("chevaux"), "cheval");
+        assert_eq!(stem("cheval"), "cheval");
+        assert_eq!(stem("mangeaient"), "mang");
+        assert_eq!(stem("mange"), "mang");
+        assert_eq!(stem("jardins"), "jardin");
+    }
+
+    #[test]
+    fn unsupported_words() {
+        assert_eq!(stem(Language::Eng, "b52"), None);
+        assert_eq!(stem(Language::Jpn, "sushi"), None);
+    }
+}
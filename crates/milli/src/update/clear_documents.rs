@@ -34,8 +34,14 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
             word_position_docids,
             word_fid_docids,
             field_id_word_count_docids,
+            field_id_length_docids,
+            word_frequency_docids,
             word_prefix_position_docids,
             word_prefix_fid_docids,
+            ngram_fid_docids,
+            stem_fid_docids,
+            ngram_fid_words,
+            stem_fid_words,
             facet_id_f64_docids,
             facet_id_string_docids,
             facet_id_normalized_string_strings,
@@ -62,6 +68,7 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         self.index.put_field_distribution(self.wtxn, &FieldDistribution::default())?;
         self.index.delete_geo_rtree(self.wtxn)?;
         self.index.delete_geo_faceted_documents_ids(self.wtxn)?;
+        self.index.delete_field_lengths(self.wtxn)?;
 
         // Remove all user-provided bits from the configs
         let mut configs = self.index.embedding_configs(self.wtxn)?;
@@ -80,8 +87,14 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         word_position_docids.clear(self.wtxn)?;
         word_fid_docids.clear(self.wtxn)?;
         field_id_word_count_docids.clear(self.wtxn)?;
+        field_id_length_docids.clear(self.wtxn)?;
+        word_frequency_docids.clear(self.wtxn)?;
         word_prefix_position_docids.clear(self.wtxn)?;
         word_prefix_fid_docids.clear(self.wtxn)?;
+        ngram_fid_docids.clear(self.wtxn)?;
+        stem_fid_docids.clear(self.wtxn)?;
+        ngram_fid_words.clear(self.wtxn)?;
+        stem_fid_words.clear(self.wtxn)?;
         facet_id_f64_docids.clear(self.wtxn)?;
         facet_id_normalized_string_strings.clear(self.wtxn)?;
         facet_id_string_fst.clear(self.wtxn)?;
@@ -136,11 +149,14 @@ mod tests {
         assert!(index.field_distribution(&rtxn).unwrap().is_empty());
         assert!(index.geo_rtree(&rtxn).unwrap().is_none());
         assert!(index.geo_faceted_documents_ids(&rtxn).unwrap().is_empty());
+        assert!(index.field_lengths(&rtxn).unwrap().is_empty());
 
         assert!(index.word_docids.is_empty(&rtxn).unwrap());
         assert!(index.word_prefix_docids.is_empty(&rtxn).unwrap());
         assert!(index.word_pair_proximity_docids.is_empty(&rtxn).unwrap());
         assert!(index.field_id_word_count_docids.is_empty(&rtxn).unwrap());
+        assert!(index.field_id_length_docids.is_empty(&rtxn).unwrap());
+        assert!(index.word_frequency_docids.is_empty(&rtxn).unwrap());
         assert!(index.facet_id_f64_docids.is_empty(&rtxn).unwrap());
         assert!(index.facet_id_string_docids.is_empty(&rtxn).unwrap());
         assert!(index.field_id_docid_facet_f64s.is_empty(&rtxn).unwrap());
@@ -0,0 +1,217 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+
+use heed::types::Bytes;
+use heed::{BytesDecode, BytesEncode, Database, RwTxn};
+use itertools::{merge_join_by, EitherOrBoth};
+use roaring::RoaringBitmap;
+use tracing::debug;
+
+use crate::heed_codec::{FstSetCodec, StrBEU16Codec};
+use crate::index::db_name;
+use crate::update::index_documents::{create_sorter, MergeCboRoaringBitmaps};
+use crate::update::GrenadParameters;
+use crate::{CboRoaringBitmapCodec, FieldId, Index, Result, SerializationError};
+
+/// Builds a database of the words derived from the words of some fields, like their
+/// n-grams or their stem, from the `word_fid_docids` database.
+///
+/// The derived database is keyed by the derived word and the field id, a document
+/// appears under a derived word when one of its words in the field derives it.
+/// The words deriving each entry are kept in a second database, so that an entry
+/// can be recomputed without going through all the words of the index.
+pub(crate) struct DerivedWordFidDocids<F> {
+    database: Database<StrBEU16Codec, CboRoaringBitmapCodec>,
+    words_database: Database<StrBEU16Codec, FstSetCodec>,
+    db_name: &'static str,
+    fields_ids: BTreeSet<FieldId>,
+    derive: F,
+}
+
+impl<F> DerivedWordFidDocids<F>
+where
+    F: Fn(FieldId, &str) -> Vec<String>,
+{
+    /// `derive` returns the words derived from a word of the field,
+    /// it is only called for the words of the given fields.
+    pub fn new(
+        database: Database<StrBEU16Codec, CboRoaringBitmapCodec>,
+        words_database: Database<StrBEU16Codec, FstSetCodec>,
+        db_name: &'static str,
+        fields_ids: BTreeSet<FieldId>,
+        derive: F,
+    ) -> Self {
+        DerivedWordFidDocids { database, words_database, db_name, fields_ids, derive }
+    }
+
+    /// Rebuilds the whole derived database, it is emptied when there are no fields.
+    pub fn compute(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        index: &Index,
+        grenad_parameters: &GrenadParameters,
+    ) -> Result<()> {
+        self.database.clear(wtxn)?;
+        self.words_database.clear(wtxn)?;
+        if self.fields_ids.is_empty() {
+            return Ok(());
+        }
+
+        debug!("Computing and writing the {} into LMDB on disk...", self.db_name);
+
+        let mut sorter = create_sorter(
+            grenad::SortAlgorithm::Unstable,
+            MergeCboRoaringBitmaps,
+            grenad_parameters.chunk_compression_type,
+            grenad_parameters.chunk_compression_level,
+            grenad_parameters.max_nb_chunks,
+            grenad_parameters.max_memory,
+            true,
+        );
+
+        // the entries are sorted by derived word, field id and then deriving word.
+        let mut key = Vec::new();
+        for result in index.word_fid_docids.remap_data_type::<Bytes>().iter(wtxn)? {
+            let ((word, fid), docids) = result?;
+            if !self.fields_ids.contains(&fid) {
+                continue;
+            }
+
+            for derived in (self.derive)(fid, word) {
+                key.clear();
+                key.extend_from_slice(
+                    &StrBEU16Codec::bytes_encode(&(derived.as_str(), fid))
+                        .map_err(heed::Error::Encoding)?,
+                );
+                key.extend_from_slice(word.as_bytes());
+                sorter.insert(&key, docids)?;
+            }
+        }
+
+        // the derived word entry being built, with its documents and deriving words.
+        let mut entry: Option<(Vec<u8>, RoaringBitmap, fst::SetBuilder<Vec<u8>>)> = None;
+        let mut iter = sorter.into_stream_merger_iter()?;
+        while let Some((key, docids)) = iter.next()? {
+            let (derived_key, word) = self.split_key(key)?;
+            match entry.take() {
+                Some(previous) if previous.0 != derived_key => self.write_entry(wtxn, previous)?,
+                previous => entry = previous,
+            }
+            let (_, entry_docids, words) = entry.get_or_insert_with(|| {
+                (derived_key.to_vec(), RoaringBitmap::new(), fst::SetBuilder::memory())
+            });
+            *entry_docids |= CboRoaringBitmapCodec::deserialize_from(docids)?;
+            words.insert(word)?;
+        }
+        if let Some(entry) = entry {
+            self.write_entry(wtxn, entry)?;
+        }
+
+        Ok(())
+    }
+
+    /// Updates the derived database with the changes made to the `word_fid_docids`
+    /// database in this write transaction.
+    ///
+    /// The entries of the derived words of the words whose documents changed are recomputed
+    /// from all the words deriving them, as a document can derive a word through several words.
+    pub fn update(&self, wtxn: &mut RwTxn<'_>, index: &Index) -> Result<()> {
+        if self.fields_ids.is_empty() {
+            return Ok(());
+        }
+
+        // the words that gained or lost documents by derived word and field,
+        // associated with whether they are still in the field.
+        let mut modified = BTreeMap::<(String, FieldId), BTreeMap<String, bool>>::new();
+        let rtxn = index.read_txn()?;
+        let previous = index.word_fid_docids.remap_types::<Bytes, Bytes>().iter(&rtxn)?;
+        let current = index.word_fid_docids.remap_types::<Bytes, Bytes>().iter(wtxn)?;
+        for eob in merge_join_by(previous, current, |lhs, rhs| match (lhs, rhs) {
+            (Ok((l, _)), Ok((r, _))) => l.cmp(r),
+            (Err(_), _) | (_, Err(_)) => Ordering::Equal,
+        }) {
+            let (key, present) = match eob {
+                EitherOrBoth::Both(lhs, rhs) => {
+                    let (key, lhs_bytes) = lhs?;
+                    let (_, rhs_bytes) = rhs?;
+                    if lhs_bytes == rhs_bytes {
+                        continue;
+                    }
+                    (key, true)
+                }
+                EitherOrBoth::Left(result) => (result?.0, false),
+                EitherOrBoth::Right(result) => (result?.0, true),
+            };
+            let (word, fid) = StrBEU16Codec::bytes_decode(key).map_err(|_| {
+                SerializationError::Decoding { db_name: Some(db_name::WORD_FIELD_ID_DOCIDS) }
+            })?;
+            if self.fields_ids.contains(&fid) {
+                for derived in (self.derive)(fid, word) {
+                    modified.entry((derived, fid)).or_default().insert(word.to_string(), present);
+                }
+            }
+        }
+        drop(rtxn);
+
+        if modified.is_empty() {
+            return Ok(());
+        }
+
+        debug!("Updating the {} of {} derived words...", self.db_name, modified.len());
+
+        for ((derived, fid), modified_words) in modified {
+            let key = (derived.as_str(), fid);
+            let mut words = BTreeSet::new();
+            if let Some(previous_words) = self.words_database.get(wtxn, &key)? {
+                words.extend(previous_words.stream().into_strs()?);
+            }
+            for (word, present) in modified_words {
+                if present {
+                    words.insert(word);
+                } else {
+                    words.remove(&word);
+                }
+            }
+
+            let mut docids = RoaringBitmap::new();
+            for word in &words {
+                if let Some(word_docids) = index.word_fid_docids.get(wtxn, &(word.as_str(), fid))? {
+                    docids |= word_docids;
+                }
+            }
+
+            if words.is_empty() {
+                self.database.delete(wtxn, &key)?;
+                self.words_database.delete(wtxn, &key)?;
+            } else {
+                self.database.put(wtxn, &key, &docids)?;
+                self.words_database.put(wtxn, &key, &fst::Set::from_iter(words)?)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Splits a key of the sorter of [`Self::compute`] into the
+    /// key of the derived database and the deriving word.
+    fn split_key<'a>(&self, key: &'a [u8]) -> Result<(&'a [u8], &'a [u8])> {
+        let footer_len = std::mem::size_of::<FieldId>();
+        match key.iter().position(|&byte| byte == 0).map(|nul| nul + 1 + footer_len) {
+            Some(end) if end <= key.len() => Ok(key.split_at(end)),
+            _ => Err(SerializationError::Decoding { db_name: Some(self.db_name) }.into()),
+        }
+    }
+
+    fn write_entry(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        (key, docids, words): (Vec<u8>, RoaringBitmap, fst::SetBuilder<Vec<u8>>),
+    ) -> Result<()> {
+        let key = StrBEU16Codec::bytes_decode(&key)
+            .map_err(|_| SerializationError::Decoding { db_name: Some(self.db_name) })?;
+        self.database.put(wtxn, &key, &docids)?;
+        let words = words.into_set();
+        self.words_database.put(wtxn, &key, &words)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use std::fs::File;
+use std::io::{self, BufReader};
+
+use obkv::KvReaderU16;
+
+use super::helpers::{
+    create_sorter, create_writer, sorter_into_reader, try_split_array_at, writer_into_reader,
+    GrenadParameters, MergeDeladdCboRoaringBitmaps,
+};
+use crate::error::SerializationError;
+use crate::index::db_name::DOCID_WORD_POSITIONS;
+use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
+use crate::update::settings::InnerIndexSettingsDiff;
+use crate::Result;
+
+/// Extracts the field id length, its number of words, and the documents ids where
+/// this field id has this length.
+///
+/// Returns a grenad reader with the list of extracted field id lengths
+/// and documents ids from the given chunk of docid word positions.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::extract")]
+pub fn extract_fid_length_docids<R: io::Read + io::Seek>(
+    docid_word_positions: grenad::Reader<R>,
+    indexer: GrenadParameters,
+    settings_diff: &InnerIndexSettingsDiff,
+) -> Result<grenad::Reader<BufReader<File>>> {
+    // the lengths are only used by the bm25 ranking rule
+    if !settings_diff.new.bm25 {
+        let writer = create_writer(
+            indexer.chunk_compression_type,
+            indexer.chunk_compression_level,
+            tempfile::tempfile()?,
+        );
+        return writer_into_reader(writer);
+    }
+
+    let max_memory = indexer.max_memory_by_thread();
+
+    let mut fid_length_docids_sorter = create_sorter(
+        grenad::SortAlgorithm::Unstable,
+        MergeDeladdCboRoaringBitmaps,
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        indexer.max_nb_chunks,
+        max_memory,
+        true,
+    );
+
+    let mut key_buffer = Vec::new();
+    let mut value_buffer = Vec::new();
+    let mut cursor = docid_word_positions.into_cursor()?;
+    while let Some((key, value)) = cursor.move_on_next()? {
+        let (document_id_bytes, fid_bytes) = try_split_array_at(key)
+            .ok_or(SerializationError::Decoding { db_name: Some(DOCID_WORD_POSITIONS) })?;
+        let document_id = u32::from_be_bytes(document_id_bytes);
+
+        let del_add_reader = KvReaderDelAdd::from_slice(value);
+        // the lengths were not indexed before the bm25 rule was added
+        let deletion = del_add_reader
+            .get(DelAdd::Deletion)
+            .filter(|_| settings_diff.old.bm25)
+            .map(|deletion| KvReaderU16::from_slice(deletion).iter().count());
+        let addition = del_add_reader
+            .get(DelAdd::Addition)
+            .map(|addition| KvReaderU16::from_slice(addition).iter().count());
+
+        if deletion != addition {
+            for (del_add, length) in [(DelAdd::Deletion, deletion), (DelAdd::Addition, addition)] {
+                let Some(length) = length else { continue };
+                let length = u16::try_from(length).unwrap_or(u16::MAX);
+                value_buffer.clear();
+                let mut value_writer = KvWriterDelAdd::new(&mut value_buffer);
+                value_writer.insert(del_add, document_id.to_ne_bytes()).unwrap();
+                key_buffer.clear();
+                key_buffer.extend_from_slice(fid_bytes);
+                key_buffer.extend_from_slice(&length.to_be_bytes());
+                fid_length_docids_sorter.insert(&key_buffer, value_writer.into_inner().unwrap())?;
+            }
+        }
+    }
+
+    sorter_into_reader(fid_length_docids_sorter, indexer)
+}
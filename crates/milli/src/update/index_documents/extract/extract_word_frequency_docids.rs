@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader};
+
+use obkv::KvReaderU16;
+
+use super::helpers::{
+    create_sorter, create_writer, sorter_into_reader, try_split_array_at, writer_into_reader,
+    GrenadParameters, MergeDeladdCboRoaringBitmaps,
+};
+use crate::error::SerializationError;
+use crate::index::db_name::DOCID_WORD_POSITIONS;
+use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
+use crate::update::settings::InnerIndexSettingsDiff;
+use crate::Result;
+
+/// Extracts the number of times each word appears in the searchable fields of a document
+/// and the documents ids where the word appears this many times.
+///
+/// Words appearing only once in a document are not extracted.
+///
+/// Returns a grenad reader with the list of extracted words and frequencies
+/// and documents ids from the given chunk of docid word positions.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::extract")]
+pub fn extract_word_frequency_docids<R: io::Read + io::Seek>(
+    docid_word_positions: grenad::Reader<R>,
+    indexer: GrenadParameters,
+    settings_diff: &InnerIndexSettingsDiff,
+) -> Result<grenad::Reader<BufReader<File>>> {
+    // the frequencies are only used by the bm25 ranking rule
+    if !settings_diff.new.bm25 {
+        let writer = create_writer(
+            indexer.chunk_compression_type,
+            indexer.chunk_compression_level,
+            tempfile::tempfile()?,
+        );
+        return writer_into_reader(writer);
+    }
+
+    let max_memory = indexer.max_memory_by_thread();
+
+    let mut word_frequency_docids_sorter = create_sorter(
+        grenad::SortAlgorithm::Unstable,
+        MergeDeladdCboRoaringBitmaps,
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        indexer.max_nb_chunks,
+        max_memory,
+        true,
+    );
+
+    // the frequencies of the words of the current document, before and after the update
+    let mut frequencies: HashMap<Vec<u8>, (usize, usize)> = HashMap::new();
+    let mut current_document_id = None;
+    let mut key_buffer = Vec::new();
+    let mut value_buffer = Vec::new();
+    let mut cursor = docid_word_positions.into_cursor()?;
+    while let Some((key, value)) = cursor.move_on_next()? {
+        let (document_id_bytes, _fid_bytes) = try_split_array_at(key)
+            .ok_or(SerializationError::Decoding { db_name: Some(DOCID_WORD_POSITIONS) })?;
+        let document_id = u32::from_be_bytes(document_id_bytes);
+
+        if let Some(current_document_id) =
+            current_document_id.filter(|current| *current != document_id)
+        {
+            flush_document_frequencies(
+                current_document_id,
+                &mut frequencies,
+                &mut word_frequency_docids_sorter,
+                &mut key_buffer,
+                &mut value_buffer,
+            )?;
+        }
+        current_document_id = Some(document_id);
+
+        let del_add_reader = KvReaderDelAdd::from_slice(value);
+        // the frequencies were not indexed before the bm25 rule was added
+        let deletion = del_add_reader.get(DelAdd::Deletion).filter(|_| settings_diff.old.bm25);
+        if let Some(deletion) = deletion {
+            for (_position, word) in KvReaderU16::from_slice(deletion).iter() {
+                frequencies.entry(word.to_vec()).or_default().0 += 1;
+            }
+        }
+        if let Some(addition) = del_add_reader.get(DelAdd::Addition) {
+            for (_position, word) in KvReaderU16::from_slice(addition).iter() {
+                frequencies.entry(word.to_vec()).or_default().1 += 1;
+            }
+        }
+    }
+
+    if let Some(current_document_id) = current_document_id {
+        flush_document_frequencies(
+            current_document_id,
+            &mut frequencies,
+            &mut word_frequency_docids_sorter,
+            &mut key_buffer,
+            &mut value_buffer,
+        )?;
+    }
+
+    sorter_into_reader(word_frequency_docids_sorter, indexer)
+}
+
+fn flush_document_frequencies(
+    document_id: u32,
+    frequencies: &mut HashMap<Vec<u8>, (usize, usize)>,
+    sorter: &mut grenad::Sorter<MergeDeladdCboRoaringBitmaps>,
+    key_buffer: &mut Vec<u8>,
+    value_buffer: &mut Vec<u8>,
+) -> Result<()> {
+    for (word, (deletion, addition)) in frequencies.drain() {
+        if deletion == addition {
+            continue;
+        }
+        for (del_add, frequency) in [(DelAdd::Deletion, deletion), (DelAdd::Addition, addition)] {
+            // words appearing once are not stored, their documents are in the word docids
+            if frequency <= 1 {
+                continue;
+            }
+            let frequency = u16::try_from(frequency).unwrap_or(u16::MAX);
+            value_buffer.clear();
+            let mut value_writer = KvWriterDelAdd::new(&mut *value_buffer);
+            value_writer.insert(del_add, document_id.to_ne_bytes()).unwrap();
+            key_buffer.clear();
+            key_buffer.extend_from_slice(&word);
+            key_buffer.push(0);
+            key_buffer.extend_from_slice(&frequency.to_be_bytes());
+            sorter.insert(&*key_buffer, value_writer.into_inner().unwrap())?;
+        }
+    }
+
+    Ok(())
+}
@@ -2,10 +2,12 @@ mod extract_docid_word_positions;
 mod extract_facet_number_docids;
 mod extract_facet_string_docids;
 mod extract_fid_docid_facet_values;
+mod extract_fid_length_docids;
 mod extract_fid_word_count_docids;
 mod extract_geo_points;
 mod extract_vector_points;
 mod extract_word_docids;
+mod extract_word_frequency_docids;
 mod extract_word_pair_proximity_docids;
 mod extract_word_position_docids;
 
@@ -20,12 +22,14 @@ use self::extract_docid_word_positions::extract_docid_word_positions;
 use self::extract_facet_number_docids::extract_facet_number_docids;
 use self::extract_facet_string_docids::extract_facet_string_docids;
 use self::extract_fid_docid_facet_values::{extract_fid_docid_facet_values, ExtractedFacetValues};
+use self::extract_fid_length_docids::extract_fid_length_docids;
 use self::extract_fid_word_count_docids::extract_fid_word_count_docids;
 use self::extract_geo_points::extract_geo_points;
 use self::extract_vector_points::{
     extract_embeddings, extract_vector_points, ExtractedVectorPoints,
 };
 use self::extract_word_docids::extract_word_docids;
+use self::extract_word_frequency_docids::extract_word_frequency_docids;
 use self::extract_word_pair_proximity_docids::extract_word_pair_proximity_docids;
 use self::extract_word_position_docids::extract_word_position_docids;
 use super::helpers::{as_cloneable_grenad, CursorClonableMmap, GrenadParameters};
@@ -93,6 +97,22 @@ pub(crate) fn data_from_obkv_documents(
                             extract_fid_word_count_docids,
                             TypedChunk::FieldIdWordCountDocids,
                         );
+                        run_extraction_task::<_, _, grenad::Reader<BufReader<File>>>(
+                            docid_word_positions_chunk.clone(),
+                            indexer,
+                            settings_diff.clone(),
+                            lmdb_writer_sx.clone(),
+                            extract_fid_length_docids,
+                            TypedChunk::FieldIdLengthDocids,
+                        );
+                        run_extraction_task::<_, _, grenad::Reader<BufReader<File>>>(
+                            docid_word_positions_chunk.clone(),
+                            indexer,
+                            settings_diff.clone(),
+                            lmdb_writer_sx.clone(),
+                            extract_word_frequency_docids,
+                            TypedChunk::WordFrequencyDocids,
+                        );
                         run_extraction_task::<
                             _,
                             _,
@@ -36,7 +36,8 @@ use crate::progress::Progress;
 use crate::thread_pool_no_abort::ThreadPoolNoAbortBuilder;
 pub use crate::update::index_documents::helpers::CursorClonableMmap;
 use crate::update::{
-    IndexerConfig, UpdateIndexingStep, WordPrefixDocids, WordPrefixIntegerDocids, WordsPrefixesFst,
+    update_ngram_fid_docids, update_stem_fid_docids, IndexerConfig, UpdateIndexingStep,
+    WordPrefixDocids, WordPrefixIntegerDocids, WordsPrefixesFst,
 };
 use crate::vector::{ArroyWrapper, EmbeddingConfigs};
 use crate::{CboRoaringBitmapCodec, Index, Result, UserError};
@@ -480,6 +481,8 @@ where
             let stats = DatabaseStats::new(self.index.documents.remap_data_type(), self.wtxn)?;
             self.index.put_documents_stats(self.wtxn, stats)?;
         }
+        // The total lengths of the fields are used by the bm25 ranking rule.
+        self.index.update_field_lengths(self.wtxn)?;
         // We write the field distribution into the main database
         self.index.put_field_distribution(self.wtxn, &field_distribution)?;
 
@@ -535,6 +538,11 @@ where
             .map_err(InternalError::from)??;
         }
 
+        if word_fid_docids.is_some() {
+            update_ngram_fid_docids(self.wtxn, self.index)?;
+            update_stem_fid_docids(self.wtxn, self.index)?;
+        }
+
         self.execute_prefix_databases(
             word_docids.map(MergerBuilder::build),
             exact_word_docids.map(MergerBuilder::build),
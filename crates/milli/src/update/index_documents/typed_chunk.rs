@@ -71,6 +71,8 @@ pub(crate) enum TypedChunk {
     FieldIdDocidFacetNumbers(grenad::Reader<CursorClonableMmap>),
     Documents(grenad::Reader<CursorClonableMmap>),
     FieldIdWordCountDocids(grenad::Reader<BufReader<File>>),
+    FieldIdLengthDocids(grenad::Reader<BufReader<File>>),
+    WordFrequencyDocids(grenad::Reader<BufReader<File>>),
     WordDocids {
         word_docids_reader: grenad::Reader<BufReader<File>>,
         exact_word_docids_reader: grenad::Reader<BufReader<File>>,
@@ -103,6 +105,8 @@ impl TypedChunk {
             | (FieldIdDocidFacetNumbers(_), FieldIdDocidFacetNumbers(_))
             | (Documents(_), Documents(_))
             | (FieldIdWordCountDocids(_), FieldIdWordCountDocids(_))
+            | (FieldIdLengthDocids(_), FieldIdLengthDocids(_))
+            | (WordFrequencyDocids(_), WordFrequencyDocids(_))
             | (WordDocids { .. }, WordDocids { .. })
             | (WordPositionDocids(_), WordPositionDocids(_))
             | (WordPairProximityDocids(_), WordPairProximityDocids(_))
@@ -255,6 +259,52 @@ pub(crate) fn write_typed_chunk_into_index(
             )?;
             is_merged_database = true;
         }
+        TypedChunk::FieldIdLengthDocids(_) => {
+            let span = tracing::trace_span!(target: "indexing::write_db", "field_id_length_docids");
+            let _entered = span.enter();
+
+            let mut builder = MergerBuilder::new(MergeDeladdCboRoaringBitmaps);
+            for typed_chunk in typed_chunks {
+                let TypedChunk::FieldIdLengthDocids(chunk) = typed_chunk else {
+                    unreachable!();
+                };
+
+                builder.push(chunk.into_cursor()?);
+            }
+            let merger = builder.build();
+
+            write_entries_into_database(
+                merger,
+                &index.field_id_length_docids,
+                wtxn,
+                deladd_serialize_add_side,
+                merge_deladd_cbo_roaring_bitmaps_into_cbo_roaring_bitmap,
+            )?;
+            is_merged_database = true;
+        }
+        TypedChunk::WordFrequencyDocids(_) => {
+            let span = tracing::trace_span!(target: "indexing::write_db", "word_frequency_docids");
+            let _entered = span.enter();
+
+            let mut builder = MergerBuilder::new(MergeDeladdCboRoaringBitmaps);
+            for typed_chunk in typed_chunks {
+                let TypedChunk::WordFrequencyDocids(chunk) = typed_chunk else {
+                    unreachable!();
+                };
+
+                builder.push(chunk.into_cursor()?);
+            }
+            let merger = builder.build();
+
+            write_entries_into_database(
+                merger,
+                &index.word_frequency_docids,
+                wtxn,
+                deladd_serialize_add_side,
+                merge_deladd_cbo_roaring_bitmaps_into_cbo_roaring_bitmap,
+            )?;
+            is_merged_database = true;
+        }
         TypedChunk::WordDocids { .. } => {
             let span = tracing::trace_span!(target: "indexing::write_db", "word_docids");
             let _entered = span.enter();
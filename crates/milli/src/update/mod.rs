@@ -6,7 +6,11 @@ pub use self::facet::incremental::FacetsUpdateIncrementalInner;
 pub use self::index_documents::*;
 pub use self::indexer_config::IndexerConfig;
 pub use self::new::ChannelCongestion;
+pub use self::ngram_fid_docids::{
+    compute_ngram_fid_docids, update_ngram_fid_docids, word_ngrams, NGRAM_LENGTH,
+};
 pub use self::settings::{validate_embedding_settings, Setting, Settings};
+pub use self::stem_fid_docids::{compute_stem_fid_docids, update_stem_fid_docids};
 pub use self::update_step::UpdateIndexingStep;
 pub use self::word_prefix_docids::WordPrefixDocids;
 pub use self::words_prefix_integer_docids::WordPrefixIntegerDocids;
@@ -16,11 +20,14 @@ mod available_ids;
 mod clear_documents;
 mod concurrent_available_ids;
 pub(crate) mod del_add;
+mod derived_fid_docids;
 pub(crate) mod facet;
 mod index_documents;
 mod indexer_config;
 pub mod new;
+mod ngram_fid_docids;
 pub(crate) mod settings;
+mod stem_fid_docids;
 mod update_step;
 pub mod upgrade;
 mod word_prefix_docids;
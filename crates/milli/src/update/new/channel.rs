@@ -387,10 +387,12 @@ pub enum Database {
     ExternalDocumentsIds,
     ExactWordDocids,
     FidWordCountDocids,
+    FidLengthDocids,
     WordDocids,
     WordFidDocids,
     WordPairProximityDocids,
     WordPositionDocids,
+    WordFrequencyDocids,
     FacetIdIsNullDocids,
     FacetIdIsEmptyDocids,
     FacetIdExistsDocids,
@@ -411,6 +413,8 @@ impl Database {
             Database::WordFidDocids => index.word_fid_docids.remap_types(),
             Database::WordPositionDocids => index.word_position_docids.remap_types(),
             Database::FidWordCountDocids => index.field_id_word_count_docids.remap_types(),
+            Database::FidLengthDocids => index.field_id_length_docids.remap_types(),
+            Database::WordFrequencyDocids => index.word_frequency_docids.remap_types(),
             Database::WordPairProximityDocids => index.word_pair_proximity_docids.remap_types(),
             Database::FacetIdIsNullDocids => index.facet_id_is_null_docids.remap_types(),
             Database::FacetIdIsEmptyDocids => index.facet_id_is_empty_docids.remap_types(),
@@ -432,6 +436,8 @@ impl Database {
             Database::WordFidDocids => db_name::WORD_FIELD_ID_DOCIDS,
             Database::WordPositionDocids => db_name::WORD_POSITION_DOCIDS,
             Database::FidWordCountDocids => db_name::FIELD_ID_WORD_COUNT_DOCIDS,
+            Database::FidLengthDocids => db_name::FIELD_ID_LENGTH_DOCIDS,
+            Database::WordFrequencyDocids => db_name::WORD_FREQUENCY_DOCIDS,
             Database::WordPairProximityDocids => db_name::WORD_PAIR_PROXIMITY_DOCIDS,
             Database::FacetIdIsNullDocids => db_name::FACET_ID_IS_NULL_DOCIDS,
             Database::FacetIdIsEmptyDocids => db_name::FACET_ID_IS_EMPTY_DOCIDS,
@@ -749,10 +755,12 @@ where
 
 pub enum ExactWordDocids {}
 pub enum FidWordCountDocids {}
+pub enum FidLengthDocids {}
 pub enum WordDocids {}
 pub enum WordFidDocids {}
 pub enum WordPairProximityDocids {}
 pub enum WordPositionDocids {}
+pub enum WordFrequencyDocids {}
 
 pub trait DatabaseType {
     const DATABASE: Database;
@@ -766,6 +774,10 @@ impl DatabaseType for FidWordCountDocids {
     const DATABASE: Database = Database::FidWordCountDocids;
 }
 
+impl DatabaseType for FidLengthDocids {
+    const DATABASE: Database = Database::FidLengthDocids;
+}
+
 impl DatabaseType for WordDocids {
     const DATABASE: Database = Database::WordDocids;
 }
@@ -782,6 +794,10 @@ impl DatabaseType for WordPositionDocids {
     const DATABASE: Database = Database::WordPositionDocids;
 }
 
+impl DatabaseType for WordFrequencyDocids {
+    const DATABASE: Database = Database::WordFrequencyDocids;
+}
+
 #[derive(Clone, Copy)]
 pub struct WordDocidsSender<'a, 'b, D> {
     sender: &'a ExtractorBbqueueSender<'b>,
@@ -17,7 +17,9 @@ use crate::update::new::ref_cell_ext::RefCellExt as _;
 use crate::update::new::steps::IndexingStep;
 use crate::update::new::thread_local::{FullySend, MostlySend, ThreadLocal};
 use crate::update::new::DocumentChange;
-use crate::{bucketed_position, DocumentId, FieldId, Result, MAX_POSITION_PER_ATTRIBUTE};
+use crate::{
+    bucketed_position, Criterion, DocumentId, FieldId, Result, MAX_POSITION_PER_ATTRIBUTE,
+};
 
 const MAX_COUNTED_WORDS: usize = 30;
 
@@ -27,22 +29,36 @@ pub struct WordDocidsBalancedCaches<'extractor> {
     exact_word_docids: BalancedCaches<'extractor>,
     word_position_docids: BalancedCaches<'extractor>,
     fid_word_count_docids: BalancedCaches<'extractor>,
+    fid_length_docids: BalancedCaches<'extractor>,
+    word_frequency_docids: BalancedCaches<'extractor>,
     fid_word_count: HashMap<FieldId, (Option<usize>, Option<usize>)>,
+    word_frequency: HashMap<String, (Option<usize>, Option<usize>)>,
     current_docid: Option<DocumentId>,
+    /// The field lengths and word frequencies are only used by the bm25 ranking rule.
+    bm25: bool,
 }
 
 unsafe impl MostlySend for WordDocidsBalancedCaches<'_> {}
 
 impl<'extractor> WordDocidsBalancedCaches<'extractor> {
-    pub fn new_in(buckets: usize, max_memory: Option<usize>, alloc: &'extractor Bump) -> Self {
+    pub fn new_in(
+        buckets: usize,
+        max_memory: Option<usize>,
+        bm25: bool,
+        alloc: &'extractor Bump,
+    ) -> Self {
         Self {
             word_fid_docids: BalancedCaches::new_in(buckets, max_memory, alloc),
             word_docids: BalancedCaches::new_in(buckets, max_memory, alloc),
             exact_word_docids: BalancedCaches::new_in(buckets, max_memory, alloc),
             word_position_docids: BalancedCaches::new_in(buckets, max_memory, alloc),
             fid_word_count_docids: BalancedCaches::new_in(buckets, max_memory, alloc),
+            fid_length_docids: BalancedCaches::new_in(buckets, max_memory, alloc),
+            word_frequency_docids: BalancedCaches::new_in(buckets, max_memory, alloc),
             fid_word_count: HashMap::new(),
+            word_frequency: HashMap::new(),
             current_docid: None,
+            bm25,
         }
     }
 
@@ -79,13 +95,21 @@ impl<'extractor> WordDocidsBalancedCaches<'extractor> {
         self.word_position_docids.insert_add_u32(&buffer, docid)?;
 
         if self.current_docid.is_some_and(|id| docid != id) {
-            self.flush_fid_word_count(&mut buffer)?;
+            self.flush_document_counts(&mut buffer)?;
         }
 
         self.fid_word_count
             .entry(field_id)
             .and_modify(|(_current_count, new_count)| *new_count.get_or_insert(0) += 1)
             .or_insert((None, Some(1)));
+        if self.bm25 {
+            match self.word_frequency.get_mut(word) {
+                Some((_current_count, new_count)) => *new_count.get_or_insert(0) += 1,
+                None => {
+                    self.word_frequency.insert(word.to_string(), (None, Some(1)));
+                }
+            }
+        }
         self.current_docid = Some(docid);
 
         Ok(())
@@ -124,20 +148,29 @@ impl<'extractor> WordDocidsBalancedCaches<'extractor> {
         self.word_position_docids.insert_del_u32(&buffer, docid)?;
 
         if self.current_docid.is_some_and(|id| docid != id) {
-            self.flush_fid_word_count(&mut buffer)?;
+            self.flush_document_counts(&mut buffer)?;
         }
 
         self.fid_word_count
             .entry(field_id)
             .and_modify(|(current_count, _new_count)| *current_count.get_or_insert(0) += 1)
             .or_insert((Some(1), None));
+        if self.bm25 {
+            match self.word_frequency.get_mut(word) {
+                Some((current_count, _new_count)) => *current_count.get_or_insert(0) += 1,
+                None => {
+                    self.word_frequency.insert(word.to_string(), (Some(1), None));
+                }
+            }
+        }
 
         self.current_docid = Some(docid);
 
         Ok(())
     }
 
-    fn flush_fid_word_count(&mut self, buffer: &mut BumpVec<u8>) -> Result<()> {
+    /// Writes the word counts and the word frequencies of the current document.
+    fn flush_document_counts(&mut self, buffer: &mut BumpVec<u8>) -> Result<()> {
         for (fid, (current_count, new_count)) in self.fid_word_count.drain() {
             if current_count != new_count {
                 if let Some(current_count) =
@@ -158,6 +191,44 @@ impl<'extractor> WordDocidsBalancedCaches<'extractor> {
                     self.fid_word_count_docids
                         .insert_add_u32(buffer, self.current_docid.unwrap())?;
                 }
+                if let Some(current_count) = current_count.filter(|_| self.bm25) {
+                    let length = u16::try_from(current_count).unwrap_or(u16::MAX);
+                    buffer.clear();
+                    buffer.extend_from_slice(&fid.to_be_bytes());
+                    buffer.extend_from_slice(&length.to_be_bytes());
+                    self.fid_length_docids.insert_del_u32(buffer, self.current_docid.unwrap())?;
+                }
+                if let Some(new_count) = new_count.filter(|_| self.bm25) {
+                    let length = u16::try_from(new_count).unwrap_or(u16::MAX);
+                    buffer.clear();
+                    buffer.extend_from_slice(&fid.to_be_bytes());
+                    buffer.extend_from_slice(&length.to_be_bytes());
+                    self.fid_length_docids.insert_add_u32(buffer, self.current_docid.unwrap())?;
+                }
+            }
+        }
+
+        // words appearing once are not stored, their documents are in the word docids
+        for (word, (current_count, new_count)) in self.word_frequency.drain() {
+            if current_count != new_count {
+                if let Some(current_count) = current_count.filter(|count| *count > 1) {
+                    let frequency = u16::try_from(current_count).unwrap_or(u16::MAX);
+                    buffer.clear();
+                    buffer.extend_from_slice(word.as_bytes());
+                    buffer.push(0);
+                    buffer.extend_from_slice(&frequency.to_be_bytes());
+                    self.word_frequency_docids
+                        .insert_del_u32(buffer, self.current_docid.unwrap())?;
+                }
+                if let Some(new_count) = new_count.filter(|count| *count > 1) {
+                    let frequency = u16::try_from(new_count).unwrap_or(u16::MAX);
+                    buffer.clear();
+                    buffer.extend_from_slice(word.as_bytes());
+                    buffer.push(0);
+                    buffer.extend_from_slice(&frequency.to_be_bytes());
+                    self.word_frequency_docids
+                        .insert_add_u32(buffer, self.current_docid.unwrap())?;
+                }
             }
         }
 
@@ -171,6 +242,8 @@ pub struct WordDocidsCaches<'extractor> {
     pub exact_word_docids: Vec<BalancedCaches<'extractor>>,
     pub word_position_docids: Vec<BalancedCaches<'extractor>>,
     pub fid_word_count_docids: Vec<BalancedCaches<'extractor>>,
+    pub fid_length_docids: Vec<BalancedCaches<'extractor>>,
+    pub word_frequency_docids: Vec<BalancedCaches<'extractor>>,
 }
 
 impl<'extractor> WordDocidsCaches<'extractor> {
@@ -181,6 +254,8 @@ impl<'extractor> WordDocidsCaches<'extractor> {
             exact_word_docids: Vec::new(),
             word_position_docids: Vec::new(),
             fid_word_count_docids: Vec::new(),
+            fid_length_docids: Vec::new(),
+            word_frequency_docids: Vec::new(),
         }
     }
 
@@ -191,7 +266,10 @@ impl<'extractor> WordDocidsCaches<'extractor> {
             exact_word_docids,
             word_position_docids,
             fid_word_count_docids,
+            fid_length_docids,
+            word_frequency_docids,
             fid_word_count: _,
+            word_frequency: _,
             current_docid: _,
         } = other;
 
@@ -200,6 +278,8 @@ impl<'extractor> WordDocidsCaches<'extractor> {
         self.exact_word_docids.push(exact_word_docids);
         self.word_position_docids.push(word_position_docids);
         self.fid_word_count_docids.push(fid_word_count_docids);
+        self.fid_length_docids.push(fid_length_docids);
+        self.word_frequency_docids.push(word_frequency_docids);
 
         Ok(())
     }
@@ -210,6 +290,7 @@ pub struct WordDocidsExtractorData<'a> {
     max_memory_by_thread: Option<usize>,
     buckets: usize,
     searchable_attributes: Option<Vec<&'a str>>,
+    bm25: bool,
 }
 
 impl<'extractor> Extractor<'extractor> for WordDocidsExtractorData<'_> {
@@ -219,6 +300,7 @@ impl<'extractor> Extractor<'extractor> for WordDocidsExtractorData<'_> {
         Ok(RefCell::new(Some(WordDocidsBalancedCaches::new_in(
             self.buckets,
             self.max_memory_by_thread,
+            self.bm25,
             extractor_alloc,
         ))))
     }
@@ -280,6 +362,7 @@ impl WordDocidsExtractors {
             max_memory_by_thread: indexing_context.grenad_parameters.max_memory_by_thread(),
             buckets: rayon::current_num_threads(),
             searchable_attributes: indexing_context.index.user_defined_searchable_fields(&rtxn)?,
+            bm25: indexing_context.index.criteria(&rtxn)?.contains(&Criterion::Bm25),
         };
         let datastore = ThreadLocal::new();
         {
@@ -407,6 +490,6 @@ impl WordDocidsExtractors {
 
         let buffer_size = size_of::<FieldId>();
         let mut buffer = BumpVec::with_capacity_in(buffer_size, &context.doc_alloc);
-        cached_sorter.flush_fid_word_count(&mut buffer)
+        cached_sorter.flush_document_counts(&mut buffer)
     }
 }
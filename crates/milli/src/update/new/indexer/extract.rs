@@ -116,6 +116,8 @@ where
             exact_word_docids,
             word_position_docids,
             fid_word_count_docids,
+            fid_length_docids,
+            word_frequency_docids,
         } = {
             let span = tracing::trace_span!(target: "indexing::documents::extract", "word_docids");
             let _entered = span.enter();
@@ -202,6 +204,36 @@ where
                 &indexing_context.must_stop_processing,
             )?;
         }
+
+        {
+            let span =
+                tracing::trace_span!(target: "indexing::documents::merge", "fid_length_docids");
+            let _entered = span.enter();
+            indexing_context.progress.update_progress(MergingWordCache::FieldIdLengthDocids);
+
+            merge_and_send_docids(
+                fid_length_docids,
+                index.field_id_length_docids.remap_types(),
+                index,
+                extractor_sender.docids::<FidLengthDocids>(),
+                &indexing_context.must_stop_processing,
+            )?;
+        }
+
+        {
+            let span =
+                tracing::trace_span!(target: "indexing::documents::merge", "word_frequency_docids");
+            let _entered = span.enter();
+            indexing_context.progress.update_progress(MergingWordCache::WordFrequencyDocids);
+
+            merge_and_send_docids(
+                word_frequency_docids,
+                index.word_frequency_docids.remap_types(),
+                index,
+                extractor_sender.docids::<WordFrequencyDocids>(),
+                &indexing_context.must_stop_processing,
+            )?;
+        }
     }
 
     // run the proximity extraction only if the precision is by word
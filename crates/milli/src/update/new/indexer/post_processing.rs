@@ -20,7 +20,9 @@ use crate::update::new::words_prefix_docids::{
     compute_word_prefix_position_docids,
 };
 use crate::update::new::FacetFieldIdsDelta;
-use crate::update::{FacetsUpdateBulk, GrenadParameters};
+use crate::update::{
+    update_ngram_fid_docids, update_stem_fid_docids, FacetsUpdateBulk, GrenadParameters,
+};
 use crate::{GlobalFieldsIdsMap, Index, Result};
 
 pub(super) fn post_process<MSP>(
@@ -52,6 +54,11 @@ where
             indexing_context.progress,
         )?;
     };
+    indexing_context.progress.update_progress(PostProcessingWords::NgramFieldIdDocids);
+    update_ngram_fid_docids(wtxn, index)?;
+    indexing_context.progress.update_progress(PostProcessingWords::StemFieldIdDocids);
+    update_stem_fid_docids(wtxn, index)?;
+    index.update_field_lengths(wtxn)?;
     Ok(())
 }
 
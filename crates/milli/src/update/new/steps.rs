@@ -38,5 +38,7 @@ make_enum_progress! {
         ExactWordPrefixDocids,
         WordPrefixFieldIdDocids,
         WordPrefixPositionDocids,
+        NgramFieldIdDocids,
+        StemFieldIdDocids,
     }
 }
@@ -0,0 +1,75 @@
+use std::collections::HashSet;
+
+use heed::{RoTxn, RwTxn};
+
+use crate::index::db_name;
+use crate::update::derived_fid_docids::DerivedWordFidDocids;
+use crate::update::GrenadParameters;
+use crate::{FieldId, Index, Result};
+
+/// The number of characters of the n-grams stored in the `ngram_fid_docids` database.
+pub const NGRAM_LENGTH: usize = 3;
+
+/// Returns the distinct character n-grams of a word, in order of appearance.
+///
+/// Words shorter than [`NGRAM_LENGTH`] characters do not have any n-gram.
+pub fn word_ngrams(word: &str) -> Vec<&str> {
+    let boundaries: Vec<usize> =
+        word.char_indices().map(|(i, _)| i).chain(std::iter::once(word.len())).collect();
+    let mut ngrams: Vec<&str> = boundaries
+        .windows(NGRAM_LENGTH + 1)
+        .map(|window| &word[window[0]..window[NGRAM_LENGTH]])
+        .collect();
+    let mut seen = HashSet::new();
+    ngrams.retain(|ngram| seen.insert(*ngram));
+    ngrams
+}
+
+fn ngram_fid_docids(
+    rtxn: &RoTxn<'_>,
+    index: &Index,
+) -> Result<DerivedWordFidDocids<impl Fn(FieldId, &str) -> Vec<String>>> {
+    let ngram_fids = index.ngram_attributes_ids(rtxn)?;
+    Ok(DerivedWordFidDocids::new(
+        index.ngram_fid_docids,
+        index.ngram_fid_words,
+        db_name::NGRAM_FIELD_ID_DOCIDS,
+        ngram_fids.into_iter().collect(),
+        |_, word| word_ngrams(word).into_iter().map(str::to_owned).collect(),
+    ))
+}
+
+/// Rebuilds the `ngram_fid_docids` database from the `word_fid_docids` database.
+///
+/// Only the words of the n-gram attributes are indexed, the database is emptied
+/// when there are no n-gram attributes. Must be called when the n-gram attributes change,
+/// [`update_ngram_fid_docids`] is enough after an indexation.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::ngram")]
+pub fn compute_ngram_fid_docids(
+    wtxn: &mut RwTxn<'_>,
+    index: &Index,
+    grenad_parameters: &GrenadParameters,
+) -> Result<()> {
+    ngram_fid_docids(wtxn, index)?.compute(wtxn, index, grenad_parameters)
+}
+
+/// Updates the `ngram_fid_docids` database with the changes made to the `word_fid_docids`
+/// database in this write transaction.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::ngram")]
+pub fn update_ngram_fid_docids(wtxn: &mut RwTxn<'_>, index: &Index) -> Result<()> {
+    ngram_fid_docids(wtxn, index)?.update(wtxn, index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::word_ngrams;
+
+    #[test]
+    fn ngrams_of_words() {
+        assert_eq!(word_ngrams("do"), Vec::<&str>::new());
+        assert_eq!(word_ngrams("dog"), vec!["dog"]);
+        assert_eq!(word_ngrams("hotdog"), vec!["hot", "otd", "tdo", "dog"]);
+        assert_eq!(word_ngrams("aaaa"), vec!["aaa"]);
+        assert_eq!(word_ngrams("été"), vec!["été"]);
+    }
+}
@@ -28,14 +28,17 @@ use crate::index::{
 use crate::order_by_map::OrderByMap;
 use crate::prompt::default_max_bytes;
 use crate::proximity::ProximityPrecision;
+use crate::query_rules::validate_query_rules;
 use crate::update::index_documents::IndexDocumentsMethod;
-use crate::update::{IndexDocuments, UpdateIndexingStep};
+use crate::update::{
+    compute_ngram_fid_docids, compute_stem_fid_docids, IndexDocuments, UpdateIndexingStep,
+};
 use crate::vector::settings::{
     EmbedderAction, EmbedderSource, EmbeddingSettings, NestingContext, ReindexAction,
     SubEmbeddingSettings, WriteBackToDocuments,
 };
 use crate::vector::{Embedder, EmbeddingConfig, EmbeddingConfigs};
-use crate::{FieldId, FilterableAttributesRule, Index, LocalizedAttributesRule, Result};
+use crate::{FieldId, FilterableAttributesRule, Index, LocalizedAttributesRule, QueryRule, Result};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum Setting<T> {
@@ -176,6 +179,8 @@ pub struct Settings<'a, 't, 'i> {
     exact_words: Setting<BTreeSet<String>>,
     /// Attributes on which typo tolerance is disabled.
     exact_attributes: Setting<HashSet<String>>,
+    /// Attributes whose words can be matched by their infixes.
+    ngram_attributes: Setting<HashSet<String>>,
     max_values_per_facet: Setting<usize>,
     sort_facet_values_by: Setting<OrderByMap>,
     pagination_max_total_hits: Setting<usize>,
@@ -185,6 +190,7 @@ pub struct Settings<'a, 't, 'i> {
     localized_attributes_rules: Setting<Vec<LocalizedAttributesRule>>,
     prefix_search: Setting<PrefixSearch>,
     facet_search: Setting<bool>,
+    query_rules: Setting<Vec<QueryRule>>,
 }
 
 impl<'a, 't, 'i> Settings<'a, 't, 'i> {
@@ -214,6 +220,7 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             min_word_len_two_typos: Setting::NotSet,
             min_word_len_one_typo: Setting::NotSet,
             exact_attributes: Setting::NotSet,
+            ngram_attributes: Setting::NotSet,
             max_values_per_facet: Setting::NotSet,
             sort_facet_values_by: Setting::NotSet,
             pagination_max_total_hits: Setting::NotSet,
@@ -223,6 +230,7 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             localized_attributes_rules: Setting::NotSet,
             prefix_search: Setting::NotSet,
             facet_search: Setting::NotSet,
+            query_rules: Setting::NotSet,
             indexer_config,
         }
     }
@@ -381,6 +389,14 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.exact_attributes = Setting::Reset;
     }
 
+    pub fn set_ngram_attributes(&mut self, attrs: HashSet<String>) {
+        self.ngram_attributes = Setting::Set(attrs);
+    }
+
+    pub fn reset_ngram_attributes(&mut self) {
+        self.ngram_attributes = Setting::Reset;
+    }
+
     pub fn set_max_values_per_facet(&mut self, value: usize) {
         self.max_values_per_facet = Setting::Set(value);
     }
@@ -437,6 +453,14 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.localized_attributes_rules = Setting::Reset;
     }
 
+    pub fn set_query_rules(&mut self, value: Vec<QueryRule>) {
+        self.query_rules = Setting::Set(value);
+    }
+
+    pub fn reset_query_rules(&mut self) {
+        self.query_rules = Setting::Reset;
+    }
+
     pub fn set_prefix_search(&mut self, value: PrefixSearch) {
         self.prefix_search = Setting::Set(value);
     }
@@ -755,6 +779,25 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         }
     }
 
+    fn update_ngram_attributes(&mut self) -> Result<bool> {
+        match self.ngram_attributes {
+            Setting::Set(ref attrs) => {
+                let old_attrs = self.index.ngram_attributes(self.wtxn)?;
+                let old_attrs = old_attrs.into_iter().map(String::from).collect::<HashSet<_>>();
+
+                if attrs != &old_attrs {
+                    let attrs = attrs.iter().map(String::as_str).collect::<Vec<_>>();
+                    self.index.put_ngram_attributes(self.wtxn, &attrs)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_ngram_attributes(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
     fn update_filterable(&mut self) -> Result<()> {
         match self.filterable_fields {
             Setting::Set(ref fields) => {
@@ -1222,16 +1265,14 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(changed)
     }
 
-    fn update_localized_attributes_rules(&mut self) -> Result<()> {
-        match &self.localized_attributes_rules {
+    fn update_query_rules(&mut self) -> Result<()> {
+        match &self.query_rules {
             Setting::Set(new) => {
-                let old = self.index.localized_attributes_rules(self.wtxn)?;
-                if old.as_ref() != Some(new) {
-                    self.index.put_localized_attributes_rules(self.wtxn, new.clone())?;
-                }
+                validate_query_rules(new)?;
+                self.index.put_query_rules(self.wtxn, new)?;
             }
             Setting::Reset => {
-                self.index.delete_localized_attributes_rules(self.wtxn)?;
+                self.index.delete_query_rules(self.wtxn)?;
             }
             Setting::NotSet => (),
         }
@@ -1239,6 +1280,22 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(())
     }
 
+    fn update_localized_attributes_rules(&mut self) -> Result<bool> {
+        match &self.localized_attributes_rules {
+            Setting::Set(new) => {
+                let old = self.index.localized_attributes_rules(self.wtxn)?;
+                if old.as_ref() != Some(new) {
+                    self.index.put_localized_attributes_rules(self.wtxn, new.clone())?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_localized_attributes_rules(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
     pub fn execute<FP, FA>(mut self, progress_callback: FP, should_abort: FA) -> Result<()>
     where
         FP: Fn(UpdateIndexingStep) + Sync,
@@ -1260,6 +1317,9 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_sort_facet_values_by()?;
         self.update_pagination_max_total_hits()?;
         self.update_search_cutoff()?;
+        self.update_query_rules()?;
+        // only recomputes the n-grams database from the already indexed words
+        let ngram_attributes_changed = self.update_ngram_attributes()?;
 
         // could trigger re-indexing
         self.update_filterable()?;
@@ -1274,7 +1334,7 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_proximity_precision()?;
         self.update_prefix_search()?;
         self.update_facet_search()?;
-        self.update_localized_attributes_rules()?;
+        let localized_attributes_rules_changed = self.update_localized_attributes_rules()?;
         self.update_disabled_typos_terms()?;
 
         let embedding_config_updates = self.update_embedding_configs()?;
@@ -1295,10 +1355,36 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             settings_update_only,
         );
 
+        // the stemmed fields are searchable fields
+        let searchable_fields_changed = inner_settings_diff.reindex_searchable();
+        let bm25_removed = inner_settings_diff.old.bm25 && !inner_settings_diff.new.bm25;
         if inner_settings_diff.any_reindexing_needed() {
             self.reindex(&progress_callback, &should_abort, inner_settings_diff)?;
         }
 
+        if bm25_removed {
+            self.index.field_id_length_docids.clear(self.wtxn)?;
+            self.index.word_frequency_docids.clear(self.wtxn)?;
+            self.index.delete_field_lengths(self.wtxn)?;
+        }
+
+        if ngram_attributes_changed {
+            compute_ngram_fid_docids(
+                self.wtxn,
+                self.index,
+                &self.indexer_config.grenad_parameters(),
+            )?;
+        }
+
+        // toggling the stemming of a rule doesn't change the locales and doesn't reindex
+        if localized_attributes_rules_changed || searchable_fields_changed {
+            compute_stem_fid_docids(
+                self.wtxn,
+                self.index,
+                &self.indexer_config.grenad_parameters(),
+            )?;
+        }
+
         Ok(())
     }
 }
@@ -1314,7 +1400,7 @@ pub struct InnerIndexSettingsDiff {
     pub(crate) only_additional_fields: Option<HashSet<String>>,
 
     // Cache the check to see if all the stop_words, allowed_separators, dictionary,
-    // exact_attributes, proximity_precision are different.
+    // exact_attributes, proximity_precision are different or if the bm25 rule was added.
     pub(crate) cache_reindex_searchable_without_user_defined: bool,
     // Cache the check to see if the user_defined_searchables are different.
     pub(crate) cache_user_defined_searchables: bool,
@@ -1358,6 +1444,8 @@ impl InnerIndexSettingsDiff {
                 || old_settings.localized_attributes_rules
                     != new_settings.localized_attributes_rules
                 || old_settings.disabled_typos_terms != new_settings.disabled_typos_terms
+                // the document lengths and word frequencies are only extracted for the bm25 rule
+                || (!old_settings.bm25 && new_settings.bm25)
         };
 
         let cache_exact_attributes = old_settings.exact_attributes != new_settings.exact_attributes;
@@ -1563,6 +1651,8 @@ pub(crate) struct InnerIndexSettings {
     pub geo_fields_ids: Option<(FieldId, FieldId)>,
     pub prefix_search: PrefixSearch,
     pub facet_search: bool,
+    /// Whether the document lengths and word frequencies of the bm25 ranking rule are indexed.
+    pub bm25: bool,
 }
 
 impl InnerIndexSettings {
@@ -1584,6 +1674,7 @@ impl InnerIndexSettings {
         };
         let prefix_search = index.prefix_search(rtxn)?.unwrap_or_default();
         let facet_search = index.facet_search(rtxn)?;
+        let bm25 = index.criteria(rtxn)?.contains(&Criterion::Bm25);
         let geo_fields_ids = match fields_ids_map.id(RESERVED_GEO_FIELD_NAME) {
             Some(_) if index.is_geo_enabled(rtxn)? => {
                 // if `_geo` is faceted then we get the `lat` and `lng`
@@ -1625,6 +1716,7 @@ impl InnerIndexSettings {
             prefix_search,
             facet_search,
             disabled_typos_terms,
+            bm25,
         })
     }
 
@@ -0,0 +1,48 @@
+use heed::{RoTxn, RwTxn};
+
+use crate::index::db_name;
+use crate::stemming::stem;
+use crate::update::derived_fid_docids::DerivedWordFidDocids;
+use crate::update::GrenadParameters;
+use crate::{FieldId, Index, Result};
+
+fn stem_fid_docids(
+    rtxn: &RoTxn<'_>,
+    index: &Index,
+) -> Result<DerivedWordFidDocids<impl Fn(FieldId, &str) -> Vec<String>>> {
+    let stemmed_fields = index.stemmed_fields_ids(rtxn)?;
+    Ok(DerivedWordFidDocids::new(
+        index.stem_fid_docids,
+        index.stem_fid_words,
+        db_name::STEM_FIELD_ID_DOCIDS,
+        stemmed_fields.keys().copied().collect(),
+        move |fid, word| {
+            stemmed_fields
+                .get(&fid)
+                .and_then(|&language| stem(language, word))
+                .into_iter()
+                .collect()
+        },
+    ))
+}
+
+/// Rebuilds the `stem_fid_docids` database from the `word_fid_docids` database.
+///
+/// Only the words of the stemmed attributes are indexed, the database is emptied
+/// when no localized attributes rule enables stemming. Must be called when the
+/// localized attributes rules change, [`update_stem_fid_docids`] is enough after an indexation.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::stem")]
+pub fn compute_stem_fid_docids(
+    wtxn: &mut RwTxn<'_>,
+    index: &Index,
+    grenad_parameters: &GrenadParameters,
+) -> Result<()> {
+    stem_fid_docids(wtxn, index)?.compute(wtxn, index, grenad_parameters)
+}
+
+/// Updates the `stem_fid_docids` database with the changes made to the `word_fid_docids`
+/// database in this write transaction.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::stem")]
+pub fn update_stem_fid_docids(wtxn: &mut RwTxn<'_>, index: &Index) -> Result<()> {
+    stem_fid_docids(wtxn, index)?.update(wtxn, index)
+}
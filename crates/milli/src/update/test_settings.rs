@@ -887,6 +887,7 @@ fn test_correct_settings_init() {
                 min_word_len_one_typo,
                 exact_words,
                 exact_attributes,
+                ngram_attributes,
                 max_values_per_facet,
                 sort_facet_values_by,
                 pagination_max_total_hits,
@@ -896,6 +897,7 @@ fn test_correct_settings_init() {
                 localized_attributes_rules,
                 prefix_search,
                 facet_search,
+                query_rules,
                 disable_on_numbers,
             } = settings;
             assert!(matches!(searchable_fields, Setting::NotSet));
@@ -915,6 +917,7 @@ fn test_correct_settings_init() {
             assert!(matches!(min_word_len_one_typo, Setting::NotSet));
             assert!(matches!(exact_words, Setting::NotSet));
             assert!(matches!(exact_attributes, Setting::NotSet));
+            assert!(matches!(ngram_attributes, Setting::NotSet));
             assert!(matches!(max_values_per_facet, Setting::NotSet));
             assert!(matches!(sort_facet_values_by, Setting::NotSet));
             assert!(matches!(pagination_max_total_hits, Setting::NotSet));
@@ -924,6 +927,7 @@ fn test_correct_settings_init() {
             assert!(matches!(localized_attributes_rules, Setting::NotSet));
             assert!(matches!(prefix_search, Setting::NotSet));
             assert!(matches!(facet_search, Setting::NotSet));
+            assert!(matches!(query_rules, Setting::NotSet));
             assert!(matches!(disable_on_numbers, Setting::NotSet));
         })
         .unwrap();
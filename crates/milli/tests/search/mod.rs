@@ -186,7 +186,7 @@ pub fn expected_order(
                     new_groups
                         .extend(group.linear_group_by_key(|d| d.asc_desc_rank).map(Vec::from));
                 }
-                Criterion::Asc(_) | Criterion::Desc(_) | Criterion::Sort => {
+                Criterion::Asc(_) | Criterion::Desc(_) | Criterion::Sort | Criterion::Bm25 => {
                     new_groups.push(group.clone())
                 }
             }